@@ -8,6 +8,7 @@ use ts_rs::TS;
 pub const JSONRPC_VERSION: &str = "2.0";
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Hash, Eq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum RequestId {
     String(String),
@@ -19,6 +20,7 @@ pub type Result = serde_json::Value;
 
 /// Refers to any valid JSON-RPC object that can be decoded off the wire, or encoded to be sent.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum JSONRPCMessage {
     Request(JSONRPCRequest),
@@ -29,6 +31,7 @@ pub enum JSONRPCMessage {
 
 /// A request that expects a response.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCRequest {
     pub id: RequestId,
     pub method: String,
@@ -38,6 +41,7 @@ pub struct JSONRPCRequest {
 
 /// A notification which does not expect a response.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCNotification {
     pub method: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -46,6 +50,7 @@ pub struct JSONRPCNotification {
 
 /// A successful (non-error) response to a request.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCResponse {
     pub id: RequestId,
     pub result: Result,
@@ -53,12 +58,14 @@ pub struct JSONRPCResponse {
 
 /// A response to a request that indicates an error occurred.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCError {
     pub error: JSONRPCErrorError,
     pub id: RequestId,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCErrorError {
     pub code: i64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
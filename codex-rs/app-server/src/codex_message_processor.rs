@@ -18,6 +18,8 @@ use codex_app_server_protocol::ExecOneOffCommandParams;
 use codex_app_server_protocol::ExecOneOffCommandResponse;
 use codex_app_server_protocol::FuzzyFileSearchParams;
 use codex_app_server_protocol::FuzzyFileSearchResponse;
+use codex_app_server_protocol::GetSessionDiffParams;
+use codex_app_server_protocol::GetSessionDiffResponse;
 use codex_app_server_protocol::GetUserAgentResponse;
 use codex_app_server_protocol::GetUserSavedConfigResponse;
 use codex_app_server_protocol::GitDiffToRemoteResponse;
@@ -129,6 +131,8 @@ pub(crate) struct CodexMessageProcessor {
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
+    // Session diff requests per conversation, answered on SessionDiffResponse.
+    pending_session_diffs: Arc<Mutex<PendingSessionDiffs>>,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
@@ -149,6 +153,7 @@ impl CodexMessageProcessor {
             conversation_listeners: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
+            pending_session_diffs: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -182,6 +187,9 @@ impl CodexMessageProcessor {
             ClientRequest::InterruptConversation { request_id, params } => {
                 self.interrupt_conversation(request_id, params).await;
             }
+            ClientRequest::GetSessionDiff { request_id, params } => {
+                self.get_session_diff(request_id, params).await;
+            }
             ClientRequest::AddConversationListener { request_id, params } => {
                 self.add_conversation_listener(request_id, params).await;
             }
@@ -603,6 +611,7 @@ impl CodexMessageProcessor {
             env,
             with_escalated_permissions: None,
             justification: None,
+            resource_limits: None,
         };
 
         let effective_policy = params
@@ -1088,6 +1097,44 @@ impl CodexMessageProcessor {
         let _ = conversation.submit(Op::Interrupt).await;
     }
 
+    async fn get_session_diff(&mut self, request_id: RequestId, params: GetSessionDiffParams) {
+        let GetSessionDiffParams { conversation_id } = params;
+        let Ok(conversation) = self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("conversation not found: {conversation_id}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+
+        // The listener replies when the SessionDiffResponse or Error event for
+        // this submission arrives. Holding the lock across the submit keeps it
+        // from seeing that event before the request is recorded.
+        let mut map = self.pending_session_diffs.lock().await;
+        match conversation.submit(Op::GetSessionDiff).await {
+            Ok(submission_id) => {
+                map.entry(conversation_id)
+                    .or_default()
+                    .push((submission_id, request_id));
+            }
+            Err(err) => {
+                drop(map);
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to request session diff: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn add_conversation_listener(
         &mut self,
         request_id: RequestId,
@@ -1114,6 +1161,7 @@ impl CodexMessageProcessor {
             .insert(subscription_id, cancel_tx);
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
+        let pending_session_diffs = self.pending_session_diffs.clone();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -1154,7 +1202,7 @@ impl CodexMessageProcessor {
                         })
                         .await;
 
-                        apply_bespoke_event_handling(event.clone(), conversation_id, conversation.clone(), outgoing_for_task.clone(), pending_interrupts.clone()).await;
+                        apply_bespoke_event_handling(event.clone(), conversation_id, conversation.clone(), outgoing_for_task.clone(), pending_interrupts.clone(), pending_session_diffs.clone()).await;
                     }
                 }
             }
@@ -1249,12 +1297,34 @@ impl CodexMessageProcessor {
     }
 }
 
+/// `getSessionDiff` requests awaiting a reply, as `(submission id, request id)`
+/// pairs per conversation.
+type PendingSessionDiffs = HashMap<ConversationId, Vec<(String, RequestId)>>;
+
+/// Remove and return the request waiting on the `Op::GetSessionDiff`
+/// submission `submission_id`.
+async fn take_pending_session_diff(
+    pending_session_diffs: &Mutex<PendingSessionDiffs>,
+    conversation_id: ConversationId,
+    submission_id: &str,
+) -> Option<RequestId> {
+    let mut map = pending_session_diffs.lock().await;
+    let pending = map.get_mut(&conversation_id)?;
+    let index = pending.iter().position(|(id, _)| id == submission_id)?;
+    let (_, request_id) = pending.remove(index);
+    if pending.is_empty() {
+        map.remove(&conversation_id);
+    }
+    Some(request_id)
+}
+
 async fn apply_bespoke_event_handling(
     event: Event,
     conversation_id: ConversationId,
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
+    pending_session_diffs: Arc<Mutex<PendingSessionDiffs>>,
 ) {
     let Event { id: event_id, msg } = event;
     match msg {
@@ -1316,6 +1386,30 @@ async fn apply_bespoke_event_handling(
                 }
             }
         }
+        EventMsg::SessionDiffResponse(session_diff) => {
+            if let Some(rid) =
+                take_pending_session_diff(&pending_session_diffs, conversation_id, &event_id).await
+            {
+                let response = GetSessionDiffResponse {
+                    unified_diff: session_diff.unified_diff,
+                };
+                outgoing.send_response(rid, response).await;
+            }
+        }
+        // Computing the session diff failed; fail the request instead of
+        // leaving it unanswered.
+        EventMsg::Error(error_event) => {
+            if let Some(rid) =
+                take_pending_session_diff(&pending_session_diffs, conversation_id, &event_id).await
+            {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: error_event.message,
+                    data: None,
+                };
+                outgoing.send_error(rid, error).await;
+            }
+        }
 
         _ => {}
     }
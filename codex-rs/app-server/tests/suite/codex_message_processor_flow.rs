@@ -22,6 +22,7 @@ use codex_app_server_protocol::SendUserTurnParams;
 use codex_app_server_protocol::SendUserTurnResponse;
 use codex_app_server_protocol::ServerRequest;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol_config_types::ReasoningEffort;
 use codex_core::protocol_config_types::ReasoningSummary;
@@ -477,8 +478,11 @@ async fn test_send_user_turn_updates_sandbox_and_cwd_between_turns() {
             sandbox_policy: SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![first_cwd.clone()],
                 network_access: false,
+                allow_local_network: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
             },
             model: model.clone(),
             effort: Some(ReasoningEffort::Medium),
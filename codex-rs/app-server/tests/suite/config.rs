@@ -89,8 +89,11 @@ async fn get_config_toml_parses_all_fields() {
             sandbox_settings: Some(SandboxSettings {
                 writable_roots: vec!["/tmp".into()],
                 network_access: Some(true),
+                allow_local_network: Some(false),
                 exclude_tmpdir_env_var: Some(true),
                 exclude_slash_tmp: Some(true),
+                include_submodule_roots: None,
+                env_filter: None,
             }),
             model: Some("gpt-5-codex".into()),
             model_reasoning_effort: Some(ReasoningEffort::High),
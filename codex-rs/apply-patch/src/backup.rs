@@ -0,0 +1,112 @@
+//! Copies of the files a patch is about to touch, so a failed apply can be
+//! rolled back.
+//!
+//! Each call to [`backup_files`] writes into a fresh subdirectory of the
+//! caller's backup directory. Files that do not exist yet are recorded too;
+//! restoring them means removing whatever the patch created.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// One file recorded by [`backup_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    /// The file the patch is going to write.
+    pub original: PathBuf,
+    /// Where its previous contents were copied, or `None` when it did not
+    /// exist before the patch.
+    pub backup: Option<PathBuf>,
+}
+
+/// Copy every existing file in `paths` into a new subdirectory of
+/// `backup_dir`. Duplicate paths are backed up once.
+pub fn backup_files<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    backup_dir: &Path,
+) -> std::io::Result<Vec<BackupEntry>> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let run_dir = backup_dir.join(format!("{nanos}-{}", std::process::id()));
+    std::fs::create_dir_all(&run_dir)?;
+
+    let mut entries: Vec<BackupEntry> = Vec::new();
+    for path in paths {
+        if entries.iter().any(|e| e.original == path) {
+            continue;
+        }
+        let backup = if path.is_file() {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let dest = run_dir.join(format!("{}-{name}", entries.len()));
+            std::fs::copy(path, &dest)?;
+            Some(dest)
+        } else {
+            None
+        };
+        entries.push(BackupEntry {
+            original: path.to_path_buf(),
+            backup,
+        });
+    }
+    Ok(entries)
+}
+
+/// Put every file in `entries` back the way [`backup_files`] found it. All
+/// entries are attempted; the first error is returned.
+pub fn restore(entries: &[BackupEntry]) -> std::io::Result<()> {
+    let mut first_err = None;
+    for entry in entries {
+        let result = match &entry.backup {
+            Some(backup) => std::fs::copy(backup, &entry.original).map(|_| ()),
+            None => match std::fs::remove_file(&entry.original) {
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                other => other,
+            },
+        };
+        if let Err(err) = result {
+            first_err.get_or_insert(err);
+        }
+    }
+    first_err.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn restore_undoes_a_partially_applied_patch() {
+        let work = tempdir().unwrap();
+        let backups = tempdir().unwrap();
+        let existing = work.path().join("a.txt");
+        let created = work.path().join("new.txt");
+        std::fs::write(&existing, "original\n").unwrap();
+
+        let entries = backup_files(
+            [existing.as_path(), created.as_path(), existing.as_path()],
+            backups.path(),
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 2);
+        let saved = entries[0].backup.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(&saved).unwrap(), "original\n");
+        assert_eq!(entries[1].backup, None);
+
+        // Simulate a patch that failed halfway through.
+        std::fs::write(&existing, "half-patched\n").unwrap();
+        std::fs::write(&created, "partial\n").unwrap();
+
+        restore(&entries).unwrap();
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "original\n");
+        assert!(!created.exists());
+        assert!(saved.exists());
+    }
+}
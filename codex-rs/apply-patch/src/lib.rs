@@ -1,3 +1,4 @@
+pub mod backup;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
@@ -2,6 +2,8 @@ use crate::types::CodeTaskDetailsResponse;
 use crate::types::PaginatedListTaskListItem;
 use crate::types::TurnAttemptsSiblingTurnsResponse;
 use anyhow::Result;
+use futures::Stream;
+use futures::StreamExt;
 use reqwest::header::AUTHORIZATION;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::header::HeaderMap;
@@ -9,6 +11,24 @@ use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use reqwest::header::USER_AGENT;
 use serde::de::DeserializeOwned;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Set to `1` to log every backend request and response at `debug` level.
+const HTTP_DEBUG_ENV: &str = "CODEX_CLOUD_TASKS_HTTP_DEBUG";
+/// Tracing target for the request/response debug log.
+pub const HTTP_DEBUG_TARGET: &str = "codex_backend_client::http";
+/// Maximum number of response body bytes included in a debug log line.
+const HTTP_DEBUG_BODY_LIMIT: usize = 2048;
+/// Characters of a credential kept visible in a debug log line.
+const REDACTED_PREFIX_CHARS: usize = 6;
+
+/// Whether `CODEX_CLOUD_TASKS_HTTP_DEBUG=1` asks for the request/response
+/// debug log. Callers still need to enable `debug` for [`HTTP_DEBUG_TARGET`]
+/// in their tracing subscriber.
+pub fn http_debug_enabled() -> bool {
+    std::env::var(HTTP_DEBUG_ENV).is_ok_and(|v| v == "1")
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PathStyle {
@@ -36,6 +56,7 @@ pub struct Client {
     user_agent: Option<HeaderValue>,
     chatgpt_account_id: Option<String>,
     path_style: PathStyle,
+    http_debug: bool,
 }
 
 impl Client {
@@ -61,6 +82,7 @@ impl Client {
             user_agent: None,
             chatgpt_account_id: None,
             path_style,
+            http_debug: http_debug_enabled(),
         })
     }
 
@@ -108,13 +130,36 @@ impl Client {
         h
     }
 
+    /// Send `req` and return its body and content type. Every endpoint goes
+    /// through here, so this is also where the opt-in debug log is written.
     async fn exec_request(
         &self,
         req: reqwest::RequestBuilder,
         method: &str,
         url: &str,
     ) -> Result<(String, String)> {
-        let res = req.send().await?;
+        let request = req.build()?;
+        let debug_request = self
+            .http_debug
+            .then(|| (request.url().to_string(), request.headers().clone()));
+        let started = Instant::now();
+        let res = match self.http.execute(request).await {
+            Ok(res) => res,
+            Err(e) => {
+                if let Some((full_url, headers)) = &debug_request {
+                    let line = format_http_debug_line(
+                        method,
+                        full_url,
+                        headers,
+                        &format!("error: {e}"),
+                        started.elapsed(),
+                        "",
+                    );
+                    tracing::debug!(target: HTTP_DEBUG_TARGET, "{line}");
+                }
+                return Err(e.into());
+            }
+        };
         let status = res.status();
         let ct = res
             .headers()
@@ -123,12 +168,65 @@ impl Client {
             .unwrap_or("")
             .to_string();
         let body = res.text().await.unwrap_or_default();
+        if let Some((full_url, headers)) = &debug_request {
+            let line = format_http_debug_line(
+                method,
+                full_url,
+                headers,
+                &status.to_string(),
+                started.elapsed(),
+                &body,
+            );
+            tracing::debug!(target: HTTP_DEBUG_TARGET, "{line}");
+        }
         if !status.is_success() {
             anyhow::bail!("{method} {url} failed: {status}; content-type={ct}; body={body}");
         }
         Ok((body, ct))
     }
 
+    /// Like [`Client::exec_request`], but hands back the body as it arrives.
+    /// The debug log records the status without the body.
+    async fn exec_streaming_request(
+        &self,
+        req: reqwest::RequestBuilder,
+        method: &str,
+        url: &str,
+    ) -> Result<impl Stream<Item = Result<Vec<u8>>> + Send + 'static> {
+        let request = req.build()?;
+        let debug_request = self
+            .http_debug
+            .then(|| (request.url().to_string(), request.headers().clone()));
+        let started = Instant::now();
+        let res = self.http.execute(request).await;
+        if let Some((full_url, headers)) = &debug_request {
+            let outcome = match &res {
+                Ok(res) => res.status().to_string(),
+                Err(e) => format!("error: {e}"),
+            };
+            let line =
+                format_http_debug_line(method, full_url, headers, &outcome, started.elapsed(), "");
+            tracing::debug!(target: HTTP_DEBUG_TARGET, "{line}");
+        }
+        let res = res?;
+        let status = res.status();
+        if !status.is_success() {
+            let ct = res
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("")
+                .to_string();
+            let body = res.text().await.unwrap_or_default();
+            anyhow::bail!("{method} {url} failed: {status}; content-type={ct}; body={body}");
+        }
+        Ok(res.bytes_stream().map(|chunk| {
+            chunk
+                .map(|bytes| bytes.to_vec())
+                .map_err(anyhow::Error::from)
+        }))
+    }
+
     fn decode_json<T: DeserializeOwned>(&self, url: &str, ct: &str, body: &str) -> Result<T> {
         match serde_json::from_str::<T>(body) {
             Ok(v) => Ok(v),
@@ -187,6 +285,20 @@ impl Client {
         Ok((parsed, body, ct))
     }
 
+    /// Stream the raw JSON body of the task details, chunk by chunk, so large
+    /// diffs can be shown before the whole response has arrived.
+    pub async fn stream_task_details(
+        &self,
+        task_id: &str,
+    ) -> Result<impl Stream<Item = Result<Vec<u8>>> + Send + 'static> {
+        let url = match self.path_style {
+            PathStyle::CodexApi => format!("{}/api/codex/tasks/{}", self.base_url, task_id),
+            PathStyle::ChatGptApi => format!("{}/wham/tasks/{}", self.base_url, task_id),
+        };
+        let req = self.http.get(&url).headers(self.headers());
+        self.exec_streaming_request(req, "GET", &url).await
+    }
+
     pub async fn list_sibling_turns(
         &self,
         task_id: &str,
@@ -242,3 +354,112 @@ impl Client {
         }
     }
 }
+
+/// Render one request/response exchange for the debug log, with credential
+/// headers cut down to a short prefix and the body capped at 2KB.
+fn format_http_debug_line(
+    method: &str,
+    url: &str,
+    headers: &HeaderMap,
+    status: &str,
+    latency: Duration,
+    body: &str,
+) -> String {
+    let headers = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            if is_credential_header(name) {
+                format!("{name}: {}", redact(value))
+            } else {
+                format!("{name}: {value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut end = body.len().min(HTTP_DEBUG_BODY_LIMIT);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    let truncated = if end < body.len() { "…" } else { "" };
+    format!(
+        "{method} {url} headers=[{headers}] status={status} latency={}ms body={}{truncated}",
+        latency.as_millis(),
+        &body[..end]
+    )
+}
+
+fn is_credential_header(name: &HeaderName) -> bool {
+    name == AUTHORIZATION || name.as_str().eq_ignore_ascii_case("chatgpt-account-id")
+}
+
+/// Keep the auth scheme (if any) and the first few characters of the secret.
+fn redact(value: &str) -> String {
+    let (scheme, secret) = match value.split_once(' ') {
+        Some((scheme, secret)) => (format!("{scheme} "), secret),
+        None => (String::new(), value),
+    };
+    let prefix: String = secret.chars().take(REDACTED_PREFIX_CHARS).collect();
+    format!("{scheme}{prefix}…<redacted>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn debug_line_redacts_credentials() {
+        let token = "eyJhbGciOiJSUzI1NiJ9.secret-payload.signature";
+        let account = "acct-1234567890";
+        let client = Client::new("https://chatgpt.com")
+            .expect("client")
+            .with_bearer_token(token)
+            .with_chatgpt_account_id(account);
+        let line = format_http_debug_line(
+            "GET",
+            "https://chatgpt.com/backend-api/wham/tasks/list?limit=20",
+            &client.headers(),
+            "200 OK",
+            Duration::from_millis(42),
+            "{\"items\":[]}",
+        );
+
+        assert!(!line.contains(token), "{line}");
+        assert!(!line.contains(account), "{line}");
+        assert!(
+            line.contains("authorization: Bearer eyJhbG…<redacted>"),
+            "{line}"
+        );
+        assert!(
+            line.contains("chatgpt-account-id: acct-1…<redacted>"),
+            "{line}"
+        );
+        assert!(
+            line.starts_with("GET https://chatgpt.com/backend-api/wham/tasks/list?limit=20 "),
+            "{line}"
+        );
+        assert!(
+            line.ends_with("status=200 OK latency=42ms body={\"items\":[]}"),
+            "{line}"
+        );
+    }
+
+    #[test]
+    fn debug_line_caps_body_at_2kb() {
+        let body = "é".repeat(HTTP_DEBUG_BODY_LIMIT);
+        let line = format_http_debug_line(
+            "GET",
+            "https://example.com",
+            &HeaderMap::new(),
+            "200 OK",
+            Duration::ZERO,
+            &body,
+        );
+        let logged = line.split_once("body=").map(|(_, b)| b).unwrap_or("");
+        assert_eq!(
+            logged,
+            format!("{}…", "é".repeat(HTTP_DEBUG_BODY_LIMIT / 2))
+        );
+    }
+}
@@ -2,6 +2,8 @@ mod client;
 pub mod types;
 
 pub use client::Client;
+pub use client::HTTP_DEBUG_TARGET;
+pub use client::http_debug_enabled;
 pub use types::CodeTaskDetailsResponse;
 pub use types::CodeTaskDetailsResponseExt;
 pub use types::PaginatedListTaskListItem;
@@ -4,11 +4,14 @@ use codex_common::CliConfigOverrides;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::exec_env::create_env;
+use codex_core::executor::SandboxExplanation;
+use codex_core::executor::explain_shell_command;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
 use codex_protocol::config_types::SandboxMode;
 
+use crate::ExplainCommand;
 use crate::LandlockCommand;
 use crate::SeatbeltCommand;
 use crate::exit_status::handle_exit_status;
@@ -96,6 +99,7 @@ async fn run_command_under_sandbox(
                 sandbox_policy_cwd.as_path(),
                 stdio_policy,
                 env,
+                None,
             )
             .await?
         }
@@ -112,6 +116,7 @@ async fn run_command_under_sandbox(
                 sandbox_policy_cwd.as_path(),
                 stdio_policy,
                 env,
+                None,
             )
             .await?
         }
@@ -121,6 +126,104 @@ async fn run_command_under_sandbox(
     handle_exit_status(status);
 }
 
+/// Print how `command` would be sandboxed under the effective config. The
+/// command itself is never run.
+pub async fn explain_command(
+    command: ExplainCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let ExplainCommand {
+        json,
+        config_overrides,
+        command,
+    } = command;
+    let config = Config::load_with_cli_overrides(
+        config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides {
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let explanation = explain_shell_command(
+        command,
+        config.approval_policy,
+        &config.sandbox_policy,
+        &config.cwd,
+        config.codex_linux_sandbox_exe.as_deref(),
+    );
+    if json {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+    } else {
+        print!("{}", format_explanation(&explanation));
+    }
+    Ok(())
+}
+
+fn format_explanation(explanation: &SandboxExplanation) -> String {
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let mut out = String::new();
+    out.push_str(&format!("command: {}\n", explanation.command.join(" ")));
+    out.push_str(&format!(
+        "approval policy: {}\n",
+        explanation.approval_policy
+    ));
+    out.push_str(&format!("sandbox: {}\n", explanation.sandbox));
+    let available: Vec<String> = explanation
+        .available_sandboxes
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    out.push_str(&format!("available sandboxes: {}\n", available.join(", ")));
+    if explanation.writable_roots.is_empty() {
+        out.push_str("writable roots: none\n");
+    } else {
+        out.push_str("writable roots:\n");
+        for root in &explanation.writable_roots {
+            out.push_str(&format!("  {}\n", root.root.display()));
+            for sub in &root.read_only_subpaths {
+                out.push_str(&format!("    read-only: {}\n", sub.display()));
+            }
+        }
+    }
+    out.push_str(&format!(
+        "network access: {}\n",
+        yes_no(explanation.network_access)
+    ));
+    out.push_str(&format!(
+        "matched safe command: {}\n",
+        yes_no(explanation.matched_safe_command)
+    ));
+    match &explanation.rejected {
+        Some(reason) => out.push_str(&format!("approval: rejected ({reason})\n")),
+        None => out.push_str(&format!(
+            "approval required: {}\n",
+            yes_no(explanation.approval_required)
+        )),
+    }
+    out.push_str(&format!(
+        "escalate on failure: {}\n",
+        yes_no(explanation.escalate_on_failure)
+    ));
+    if let Some(helper) = &explanation.linux_sandbox_helper {
+        let path = helper
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "not configured".to_string());
+        let status = if helper.reachable {
+            "ok"
+        } else {
+            "NOT REACHABLE"
+        };
+        out.push_str(&format!("linux sandbox helper: {path} ({status})\n"));
+    }
+    out
+}
+
 pub fn create_sandbox_mode(full_auto: bool) -> SandboxMode {
     if full_auto {
         SandboxMode::WorkspaceWrite
@@ -32,3 +32,17 @@ pub struct LandlockCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Debug, Parser)]
+pub struct ExplainCommand {
+    /// Print the explanation as JSON.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Full command args to explain. The command is not executed.
+    #[arg(trailing_var_arg = true, required = true)]
+    pub command: Vec<String>,
+}
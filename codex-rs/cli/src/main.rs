@@ -5,6 +5,7 @@ use clap_complete::generate;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
+use codex_cli::ExplainCommand;
 use codex_cli::LandlockCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::login::read_api_key_from_stdin;
@@ -24,8 +25,10 @@ use std::path::PathBuf;
 use supports_color::Stream;
 
 mod mcp_cmd;
+mod rollout_cmd;
 
 use crate::mcp_cmd::McpCli;
+use crate::rollout_cmd::RolloutCli;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 
@@ -105,6 +108,15 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Manage command approvals persisted by `persist_command_approvals`.
+    Approvals(ApprovalsCli),
+
+    /// Inspect recorded session rollouts.
+    Rollout(RolloutCli),
+
+    /// Inspect custom prompts in `$CODEX_HOME/prompts`.
+    Prompts(PromptsCli),
 }
 
 #[derive(Debug, Parser)]
@@ -144,6 +156,9 @@ enum SandboxCommand {
     /// Run a command under Landlock+seccomp (Linux only).
     #[clap(visible_alias = "landlock")]
     Linux(LandlockCommand),
+
+    /// Show how a command would be sandboxed and approved, without running it.
+    Explain(ExplainCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -202,6 +217,11 @@ struct GenerateTsCommand {
     /// Optional path to the Prettier executable to format generated files
     #[arg(short = 'p', long = "prettier", value_name = "PRETTIER_BIN")]
     prettier: Option<PathBuf>,
+
+    /// Path to the `codex-rs` workspace whose Rust doc comments are copied
+    /// into the bindings. Defaults to the workspace containing `--out`.
+    #[arg(long = "source-root", value_name = "DIR")]
+    source_root: Option<PathBuf>,
 }
 
 fn format_exit_messages(exit_info: AppExitInfo, color_enabled: bool) -> Vec<String> {
@@ -275,6 +295,46 @@ enum FeaturesSubcommand {
     List,
 }
 
+#[derive(Debug, Parser)]
+struct ApprovalsCli {
+    #[command(subcommand)]
+    sub: ApprovalsSubcommand,
+}
+
+#[derive(Debug, Parser)]
+enum ApprovalsSubcommand {
+    /// Forget every persisted command approval, for all projects.
+    Clear,
+}
+
+#[derive(Debug, Parser)]
+struct PromptsCli {
+    #[command(subcommand)]
+    sub: PromptsSubcommand,
+}
+
+#[derive(Debug, Parser)]
+enum PromptsSubcommand {
+    /// List discovered custom prompts with their summary and path.
+    List,
+}
+
+/// One-line summary for a prompt: its description, else its first non-empty line.
+fn prompt_summary(prompt: &codex_protocol::custom_prompts::CustomPrompt) -> String {
+    prompt
+        .description
+        .clone()
+        .or_else(|| {
+            prompt
+                .content
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_default()
+}
+
 fn stage_str(stage: codex_core::features::Stage) -> &'static str {
     use codex_core::features::Stage;
     match stage {
@@ -427,6 +487,14 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::Explain(mut explain_cli) => {
+                prepend_config_flags(
+                    &mut explain_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::explain_command(explain_cli, codex_linux_sandbox_exe)
+                    .await?;
+            }
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
@@ -440,7 +508,11 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 .await??;
         }
         Some(Subcommand::GenerateTs(gen_cli)) => {
-            codex_protocol_ts::generate_ts(&gen_cli.out_dir, gen_cli.prettier.as_deref())?;
+            codex_protocol_ts::generate_ts(
+                &gen_cli.out_dir,
+                gen_cli.prettier.as_deref(),
+                gen_cli.source_root.as_deref(),
+            )?;
         }
         Some(Subcommand::Features(FeaturesCli { sub })) => match sub {
             FeaturesSubcommand::List => {
@@ -466,6 +538,33 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 }
             }
         },
+        Some(Subcommand::Approvals(ApprovalsCli { sub })) => match sub {
+            ApprovalsSubcommand::Clear => {
+                let codex_home = codex_core::config::find_codex_home()?;
+                if codex_core::executor::approval_store::clear_persisted_approvals(&codex_home)? {
+                    println!("Cleared persisted command approvals");
+                } else {
+                    println!("No persisted command approvals");
+                }
+            }
+        },
+        Some(Subcommand::Rollout(rollout_cli)) => {
+            rollout_cli.run().await?;
+        }
+        Some(Subcommand::Prompts(PromptsCli { sub })) => match sub {
+            PromptsSubcommand::List => {
+                let codex_home = codex_core::config::find_codex_home()?;
+                let dir = codex_core::custom_prompts::prompts_dir(&codex_home);
+                let prompts = codex_core::custom_prompts::discover_prompts_in(&dir).await;
+                if prompts.is_empty() {
+                    println!("No custom prompts in {}", dir.display());
+                }
+                for prompt in &prompts {
+                    let summary = prompt_summary(prompt);
+                    println!("{}\t{summary}\t{}", prompt.name, prompt.path.display());
+                }
+            }
+        },
     }
 
     Ok(())
@@ -598,6 +697,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prompt_summary_prefers_description_then_first_line() {
+        let mut prompt = codex_protocol::custom_prompts::CustomPrompt {
+            name: "review".to_string(),
+            path: PathBuf::from("/tmp/prompts/review.md"),
+            content: "\n  Review the staged diff\nThen summarize.\n".to_string(),
+            description: None,
+            argument_hint: None,
+        };
+        assert_eq!(prompt_summary(&prompt), "Review the staged diff");
+        prompt.description = Some("Code review".to_string());
+        assert_eq!(prompt_summary(&prompt), "Code review");
+    }
+
     #[test]
     fn format_exit_messages_skips_zero_usage() {
         let exit_info = AppExitInfo {
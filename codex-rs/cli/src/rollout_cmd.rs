@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::find_codex_home;
+use codex_core::rollout::replay::ReplayEvent;
+use codex_core::rollout::replay::resolve_rollout_path;
+use codex_core::rollout::replay::stream_events;
+use tokio_stream::StreamExt;
+
+/// Inspect recorded session rollouts.
+#[derive(Debug, clap::Parser)]
+pub struct RolloutCli {
+    #[command(subcommand)]
+    pub subcommand: RolloutSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum RolloutSubcommand {
+    /// Print the events recorded in a rollout file in chronological order.
+    Replay(ReplayArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ReplayArgs {
+    /// Path to a rollout `.jsonl` file, or a session id to look up under
+    /// `$CODEX_HOME/sessions`.
+    #[arg(value_name = "SESSION_PATH_OR_ID")]
+    pub target: String,
+
+    /// Emit each event as a single line of raw JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Only show events of this type (e.g. `AgentMessage` or `agent_message`).
+    /// Streaming fragments have their own types, such as `AgentMessageDelta`.
+    #[arg(long, value_name = "PATTERN")]
+    pub grep: Option<String>,
+
+    /// Skip events recorded before this offset (in milliseconds) from the
+    /// start of the session.
+    #[arg(long, value_name = "OFFSET_MS")]
+    pub since: Option<u64>,
+}
+
+impl RolloutCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            RolloutSubcommand::Replay(args) => run_replay(args).await,
+        }
+    }
+}
+
+async fn run_replay(args: ReplayArgs) -> Result<()> {
+    let codex_home = find_codex_home()?;
+    let path = resolve_rollout_path(&codex_home, &args.target).await?;
+    let since = Duration::from_millis(args.since.unwrap_or(0));
+
+    let mut events = Box::pin(stream_events(&path));
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                eprintln!("warning: {err:#}");
+                continue;
+            }
+        };
+        if event.offset < since {
+            continue;
+        }
+        if let Some(pattern) = args.grep.as_deref()
+            && !event.type_matches(pattern)
+        {
+            continue;
+        }
+        if args.json {
+            println!("{}", serde_json::to_string(&event.msg)?);
+        } else {
+            print_event(&event)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_event(event: &ReplayEvent) -> Result<()> {
+    let offset = event.offset.as_secs_f64();
+    println!("[+{offset:.3}s] {} {}", event.timestamp, event.msg);
+    println!("{}", serde_json::to_string_pretty(&event.msg)?);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+const ROLLOUT: &str = r#"{"timestamp":"2025-01-01T00:00:00.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hi"}]}}
+{"timestamp":"2025-01-01T00:00:00.100Z","type":"event_msg","payload":{"type":"user_message","message":"hi","kind":"plain"}}
+{"timestamp":"2025-01-01T00:00:00.500Z","type":"event_msg","payload":{"type":"agent_message_delta","delta":"fir"}}
+{"timestamp":"2025-01-01T00:00:00.600Z","type":"event_msg","payload":{"type":"agent_message_delta","delta":"st"}}
+{"timestamp":"2025-01-01T00:00:01.000Z","type":"event_msg","payload":{"type":"agent_message","message":"first"}}
+{"timestamp":"2025-01-01T00:00:01.200Z","type":"event_msg","payload":{"type":"agent_reasoning","text":"thinking"}}
+{"timestamp":"2025-01-01T00:00:03.000Z","type":"event_msg","payload":{"type":"agent_message","message":"second"}}
+"#;
+
+#[test]
+fn replay_grep_filters_to_agent_messages() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = codex_home.path().join("rollout.jsonl");
+    std::fs::write(&rollout, ROLLOUT)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["rollout", "replay", "--json", "--grep", "AgentMessage"])
+        .arg(&rollout)
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let events = stdout
+        .lines()
+        .map(serde_json::from_str::<JsonValue>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let messages: Vec<_> = events
+        .iter()
+        .map(|event| {
+            assert_eq!(event["type"], "agent_message");
+            event["message"].as_str().unwrap_or_default()
+        })
+        .collect();
+    assert_eq!(messages, vec!["first", "second"]);
+
+    Ok(())
+}
+
+#[test]
+fn replay_since_skips_earlier_events() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = codex_home.path().join("rollout.jsonl");
+    std::fs::write(&rollout, ROLLOUT)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["rollout", "replay", "--since", "2000"])
+        .arg(&rollout)
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[+3.000s]"));
+    assert!(stdout.contains("second"));
+    assert!(!stdout.contains("first"));
+
+    Ok(())
+}
@@ -1,11 +1,23 @@
 use chrono::DateTime;
 use chrono::Utc;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use futures::Stream;
+use futures::StreamExt;
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
+use std::pin::Pin;
 
 pub type Result<T> = std::result::Result<T, CloudTaskError>;
 
-#[derive(Debug, thiserror::Error)]
+/// Assistant messages for a task, yielded in order as they become available.
+pub type TaskMessageStream<'a> = Pin<Box<dyn Stream<Item = Result<String>> + Send + 'a>>;
+
+/// Consecutive pieces of a task's unified diff; concatenated they form the
+/// whole diff. An empty stream means the task has no diff.
+pub type TaskDiffStream<'a> = Pin<Box<dyn Stream<Item = Result<String>> + Send + 'a>>;
+
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum CloudTaskError {
     #[error("unimplemented: {0}")]
     Unimplemented(&'static str),
@@ -15,6 +27,13 @@ pub enum CloudTaskError {
     Io(String),
     #[error("{0}")]
     Msg(String),
+    /// The task's current turn has not finished, so no output is available yet.
+    #[error("task is still running (status: {status})")]
+    TaskStillRunning {
+        status: String,
+        /// Text of the most recent backend event for the turn, when reported.
+        latest_event: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -87,6 +106,10 @@ pub struct ApplyOutcome {
     pub skipped_paths: Vec<String>,
     #[serde(default)]
     pub conflict_paths: Vec<String>,
+    /// Files the three-way merge fallback left with conflict markers. Empty for
+    /// preflight, which never writes to the working tree.
+    #[serde(default)]
+    pub merged_with_conflicts: Vec<PathBuf>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,6 +117,27 @@ pub struct CreatedTask {
     pub id: TaskId,
 }
 
+/// Options for [`CloudBackend::create_task`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateTaskOptions {
+    /// Number of assistant attempts to run (best-of-N).
+    pub attempts: u8,
+    /// Run the environment in QA mode.
+    pub qa_mode: bool,
+    /// Git branch the task starts from.
+    pub branch: String,
+}
+
+impl Default for CreateTaskOptions {
+    fn default() -> Self {
+        Self {
+            attempts: 1,
+            qa_mode: false,
+            branch: "main".to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct DiffSummary {
     pub files_changed: usize,
@@ -101,6 +145,72 @@ pub struct DiffSummary {
     pub lines_removed: usize,
 }
 
+impl DiffSummary {
+    /// Count files and added/removed lines in a unified diff.
+    ///
+    /// Lines are only counted inside hunks, using the line counts from each
+    /// `@@` header, so content lines that start with `++` or `--` are not
+    /// mistaken for file headers.
+    pub fn from_unified_diff(diff: &str) -> Self {
+        let mut out = Self::default();
+        // Old and new lines still expected in the current hunk.
+        let (mut old_left, mut new_left) = (0usize, 0usize);
+        // Whether the current file was already counted by its `diff --git` line.
+        let mut file_counted = false;
+        for line in diff.lines() {
+            if old_left > 0 || new_left > 0 {
+                match line.as_bytes().first() {
+                    Some(b'+') => {
+                        out.lines_added += 1;
+                        new_left = new_left.saturating_sub(1);
+                    }
+                    Some(b'-') => {
+                        out.lines_removed += 1;
+                        old_left = old_left.saturating_sub(1);
+                    }
+                    // "\ No newline at end of file"
+                    Some(b'\\') => {}
+                    _ => {
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+                continue;
+            }
+            if line.starts_with("diff --git ") {
+                out.files_changed += 1;
+                file_counted = true;
+            } else if line.starts_with("+++ ") {
+                if !file_counted {
+                    out.files_changed += 1;
+                }
+                file_counted = false;
+            } else if let Some((old, new)) = hunk_line_counts(line) {
+                old_left = old;
+                new_left = new;
+            }
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files_changed == 0 && self.lines_added == 0 && self.lines_removed == 0
+    }
+}
+
+/// Old and new line counts from a `@@ -l,s +l,s @@` hunk header. A range
+/// without a count covers one line.
+fn hunk_line_counts(line: &str) -> Option<(usize, usize)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let count = |range: &str| match range.split_once(',') {
+        Some((_, n)) => n.parse().ok(),
+        None => Some(1),
+    };
+    Some((count(old)?, count(new)?))
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TaskText {
     pub prompt: Option<String>,
@@ -128,10 +238,44 @@ impl Default for TaskText {
 pub trait CloudBackend: Send + Sync {
     async fn list_tasks(&self, env: Option<&str>) -> Result<Vec<TaskSummary>>;
     async fn get_task_diff(&self, id: TaskId) -> Result<Option<String>>;
+    /// Stream the task's diff in pieces so large diffs can be shown while
+    /// they load. The default splits the [`CloudBackend::get_task_diff`]
+    /// result into one piece per file.
+    fn stream_task_diff(&self, id: TaskId) -> TaskDiffStream<'_> {
+        Box::pin(
+            futures::stream::once(self.get_task_diff(id)).flat_map(|result| {
+                let items: Vec<Result<String>> = match result {
+                    Ok(Some(diff)) => split_diff_by_file(&diff).into_iter().map(Ok).collect(),
+                    Ok(None) => Vec::new(),
+                    Err(err) => vec![Err(err)],
+                };
+                futures::stream::iter(items)
+            }),
+        )
+    }
     /// Return assistant output messages (no diff) when available.
     async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>>;
+    /// Stream assistant output messages as they arrive. The stream ends once
+    /// the task's turn is no longer running, or after the first error. The
+    /// default yields the current [`CloudBackend::get_task_messages`] snapshot.
+    fn stream_task_messages(&self, id: TaskId) -> TaskMessageStream<'_> {
+        Box::pin(
+            futures::stream::once(self.get_task_messages(id)).flat_map(|result| {
+                let items: Vec<Result<String>> = match result {
+                    Ok(messages) => messages.into_iter().map(Ok).collect(),
+                    Err(err) => vec![Err(err)],
+                };
+                futures::stream::iter(items)
+            }),
+        )
+    }
     /// Return the creating prompt and assistant messages (when available).
     async fn get_task_text(&self, id: TaskId) -> Result<TaskText>;
+    /// Return the latest plan snapshot, or `None` when the task has no plan
+    /// or the backend does not expose one.
+    async fn get_task_plan(&self, _id: TaskId) -> Result<Option<UpdatePlanArgs>> {
+        Ok(None)
+    }
     /// Return any sibling attempts (best-of-N) for the given assistant turn.
     async fn list_sibling_attempts(
         &self,
@@ -151,8 +295,74 @@ pub trait CloudBackend: Send + Sync {
         &self,
         env_id: &str,
         prompt: &str,
-        git_ref: &str,
-        qa_mode: bool,
-        best_of_n: usize,
+        options: CreateTaskOptions,
     ) -> Result<CreatedTask>;
 }
+
+/// Split a unified diff before each `diff --git` header. Concatenating the
+/// pieces gives back `diff` exactly.
+fn split_diff_by_file(diff: &str) -> Vec<String> {
+    let mut pieces: Vec<String> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        match pieces.last_mut() {
+            Some(current) if !line.starts_with("diff --git ") => current.push_str(line),
+            _ => pieces.push(line.to_string()),
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn split_diff_by_file_keeps_every_byte() {
+        let diff =
+            "diff --git a/a b/a\n@@ -1 +1 @@\n-x\n+y\ndiff --git a/b b/b\n@@ -1 +1 @@\n-p\n+q";
+        let pieces = split_diff_by_file(diff);
+        assert_eq!(
+            pieces,
+            vec![
+                "diff --git a/a b/a\n@@ -1 +1 @@\n-x\n+y\n".to_string(),
+                "diff --git a/b b/b\n@@ -1 +1 @@\n-p\n+q".to_string(),
+            ]
+        );
+        assert_eq!(pieces.concat(), diff);
+        assert!(split_diff_by_file("").is_empty());
+    }
+
+    #[test]
+    fn diff_summary_counts_files_and_lines() {
+        let diff = "diff --git a/a b/a\nindex 1..2 100644\n--- a/a\n+++ b/a\n@@ -1,2 +1,3 @@\n keep\n-old\n+new\n+more\ndiff --git a/b b/b\nnew file mode 100644\n--- /dev/null\n+++ b/b\n@@ -0,0 +1 @@\n+hello\n\\ No newline at end of file\n";
+        assert_eq!(
+            DiffSummary::from_unified_diff(diff),
+            DiffSummary {
+                files_changed: 2,
+                lines_added: 3,
+                lines_removed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn diff_summary_counts_content_lines_that_look_like_headers() {
+        // Removing a line that reads "-- x" and adding one that reads "++ y"
+        // shows up as "--- x" and "+++ y" inside the hunk.
+        let diff = "--- a/notes.md\n+++ b/notes.md\n@@ -1,2 +1,2 @@\n--- x\n+++ y\n same\n";
+        assert_eq!(
+            DiffSummary::from_unified_diff(diff),
+            DiffSummary {
+                files_changed: 1,
+                lines_added: 1,
+                lines_removed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn diff_summary_of_empty_diff_is_empty() {
+        assert!(DiffSummary::from_unified_diff("").is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Backend setup shared by `codex cloud` and the MCP server's cloud task
+//! tools.
+
+use std::sync::Arc;
+
+use base64::Engine as _;
+use codex_core::AuthManager;
+
+use crate::CloudBackend;
+use crate::HttpClient;
+
+/// Backend used when `CODEX_CLOUD_TASKS_BASE_URL` is not set.
+pub const DEFAULT_BASE_URL: &str = "https://chatgpt.com/backend-api";
+
+/// Base URL from `CODEX_CLOUD_TASKS_BASE_URL`, defaulting to the ChatGPT backend.
+pub fn configured_base_url() -> String {
+    std::env::var("CODEX_CLOUD_TASKS_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+/// Whether `CODEX_CLOUD_TASKS_MODE=mock` asks for the in-memory mock backend.
+pub fn mock_mode_requested() -> bool {
+    matches!(
+        std::env::var("CODEX_CLOUD_TASKS_MODE").ok().as_deref(),
+        Some("mock") | Some("MOCK")
+    )
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectError {
+    #[error("not signed in")]
+    NotSignedIn,
+    #[error("failed to create cloud tasks client: {0}")]
+    Client(anyhow::Error),
+}
+
+/// A backend returned by [`connect_backend`], with what it was set up from.
+pub struct BackendConnection {
+    pub backend: Arc<dyn CloudBackend>,
+    pub base_url: String,
+    /// ChatGPT account id sent with each request, when known.
+    pub account_id: Option<String>,
+}
+
+/// The mock backend when `CODEX_CLOUD_TASKS_MODE=mock` (and the `mock` feature
+/// is on), otherwise the HTTP backend at [`configured_base_url`]
+/// authenticated with the stored ChatGPT login.
+///
+/// Set the user agent suffix before calling this; it is read here.
+pub async fn connect_backend() -> Result<BackendConnection, ConnectError> {
+    let base_url = configured_base_url();
+
+    #[cfg(feature = "mock")]
+    if mock_mode_requested() {
+        return Ok(BackendConnection {
+            backend: Arc::new(crate::MockClient::from_env()),
+            base_url,
+            account_id: None,
+        });
+    }
+
+    let auth = codex_core::config::find_codex_home()
+        .ok()
+        .map(|home| AuthManager::new(home, false))
+        .and_then(|am| am.auth())
+        .ok_or(ConnectError::NotSignedIn)?;
+    let token = match auth.get_token().await {
+        Ok(token) if !token.is_empty() => token,
+        _ => return Err(ConnectError::NotSignedIn),
+    };
+    let account_id = auth
+        .get_account_id()
+        .or_else(|| extract_chatgpt_account_id(&token));
+
+    let mut http = HttpClient::new(base_url.clone())
+        .map_err(ConnectError::Client)?
+        .with_user_agent(codex_core::default_client::get_codex_user_agent())
+        .with_bearer_token(token);
+    if let Some(account_id) = &account_id {
+        http = http.with_chatgpt_account_id(account_id.clone());
+    }
+    Ok(BackendConnection {
+        backend: Arc::new(http),
+        base_url,
+        account_id,
+    })
+}
+
+/// Extract the ChatGPT account id from a JWT token, when present.
+pub fn extract_chatgpt_account_id(token: &str) -> Option<String> {
+    let mut parts = token.split('.');
+    let (_h, payload_b64, _s) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(h), Some(p), Some(s)) if !h.is_empty() && !p.is_empty() && !s.is_empty() => (h, p, s),
+        _ => return None,
+    };
+    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .ok()?;
+    let v: serde_json::Value = serde_json::from_slice(&payload_bytes).ok()?;
+    v.get("https://api.openai.com/auth")
+        .and_then(|auth| auth.get("chatgpt_account_id"))
+        .and_then(|id| id.as_str())
+        .map(str::to_string)
+}
@@ -3,19 +3,28 @@ use crate::ApplyStatus;
 use crate::AttemptStatus;
 use crate::CloudBackend;
 use crate::CloudTaskError;
+use crate::CreateTaskOptions;
 use crate::DiffSummary;
 use crate::Result;
+use crate::TaskDiffStream;
 use crate::TaskId;
+use crate::TaskMessageStream;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
 use crate::api::TaskText;
 use chrono::DateTime;
 use chrono::Utc;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use std::collections::VecDeque;
+use std::time::Duration;
 
 use codex_backend_client as backend;
 use codex_backend_client::CodeTaskDetailsResponseExt;
 
+/// How often [`HttpClient::stream_task_messages`] re-fetches a running task.
+const MESSAGE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Clone)]
 pub struct HttpClient {
     pub base_url: String,
@@ -67,14 +76,70 @@ impl CloudBackend for HttpClient {
         self.tasks_api().diff(id).await
     }
 
+    fn stream_task_diff(&self, id: TaskId) -> TaskDiffStream<'_> {
+        self.tasks_api().diff_stream(id)
+    }
+
     async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>> {
         self.tasks_api().messages(id).await
     }
 
+    fn stream_task_messages(&self, id: TaskId) -> TaskMessageStream<'_> {
+        // The backend exposes no push channel for turn output, so poll the
+        // task details and yield messages beyond those already seen.
+        struct Poll {
+            id: TaskId,
+            seen: usize,
+            queued: VecDeque<String>,
+            polled: bool,
+            done: bool,
+        }
+        let state = Poll {
+            id,
+            seen: 0,
+            queued: VecDeque::new(),
+            polled: false,
+            done: false,
+        };
+        Box::pin(futures::stream::unfold(state, move |mut st| async move {
+            loop {
+                if let Some(message) = st.queued.pop_front() {
+                    return Some((Ok(message), st));
+                }
+                if st.done {
+                    return None;
+                }
+                if st.polled {
+                    tokio::time::sleep(MESSAGE_POLL_INTERVAL).await;
+                }
+                st.polled = true;
+                match self.tasks_api().task_text(st.id.clone()).await {
+                    Ok(text) => {
+                        let total = text.messages.len();
+                        st.queued.extend(text.messages.into_iter().skip(st.seen));
+                        st.seen = st.seen.max(total);
+                        st.done = !matches!(
+                            text.attempt_status,
+                            AttemptStatus::Pending | AttemptStatus::InProgress
+                        );
+                    }
+                    Err(err) => {
+                        st.done = true;
+                        return Some((Err(err), st));
+                    }
+                }
+            }
+        }))
+    }
+
     async fn get_task_text(&self, id: TaskId) -> Result<TaskText> {
         self.tasks_api().task_text(id).await
     }
 
+    async fn get_task_plan(&self, id: TaskId) -> Result<Option<UpdatePlanArgs>> {
+        self.tasks_api().plan(id).await
+    }
+
     async fn list_sibling_attempts(
         &self,
         task: TaskId,
@@ -99,18 +164,16 @@ impl CloudBackend for HttpClient {
         &self,
         env_id: &str,
         prompt: &str,
-        git_ref: &str,
-        qa_mode: bool,
-        best_of_n: usize,
+        options: CreateTaskOptions,
     ) -> Result<crate::CreatedTask> {
-        self.tasks_api()
-            .create(env_id, prompt, git_ref, qa_mode, best_of_n)
-            .await
+        self.tasks_api().create(env_id, prompt, &options).await
     }
 }
 
 mod api {
     use super::*;
+    use futures::Stream;
+    use futures::StreamExt;
     use serde_json::Value;
     use std::cmp::Ordering;
     use std::collections::HashMap;
@@ -157,8 +220,30 @@ mod api {
             if let Some(diff) = details.unified_diff() {
                 return Ok(Some(diff));
             }
-            let _ = (body, ct);
-            Ok(None)
+            let _ = ct;
+            match still_running_from_body(&body) {
+                Some(err) => Err(err),
+                None => Ok(None),
+            }
+        }
+
+        /// Stream the diff out of the task details body while it downloads.
+        /// Once the body is complete it is parsed like [`Tasks::diff`]; if
+        /// that picks a different diff than the one streamed, the stream ends
+        /// with an error.
+        pub(crate) fn diff_stream(&self, id: TaskId) -> TaskDiffStream<'a> {
+            let backend = self.backend;
+            Box::pin(
+                futures::stream::once(async move { backend.stream_task_details(&id.0).await })
+                    .flat_map(|started| -> TaskDiffStream<'a> {
+                        match started {
+                            Ok(body) => Box::pin(diff_from_body_stream(body)),
+                            Err(e) => Box::pin(futures::stream::iter([Err(CloudTaskError::Http(
+                                format!("get_task_details failed: {e}"),
+                            ))])),
+                        }
+                    }),
+            )
         }
 
         pub(crate) async fn messages(&self, id: TaskId) -> Result<Vec<String>> {
@@ -177,6 +262,9 @@ mod api {
             if let Some(err) = details.assistant_error_message() {
                 return Ok(vec![format!("Task failed: {err}")]);
             }
+            if let Some(err) = still_running_from_body(&body) {
+                return Err(err);
+            }
 
             let url = match details_path(self.base_url, &id.0) {
                 Some(url) => url,
@@ -216,47 +304,23 @@ mod api {
             })
         }
 
+        pub(crate) async fn plan(&self, id: TaskId) -> Result<Option<UpdatePlanArgs>> {
+            let (_details, body, _ct) = self
+                .details_with_body(&id.0)
+                .await
+                .map_err(|e| CloudTaskError::Http(format!("get_task_details failed: {e}")))?;
+            Ok(plan_from_body(&body))
+        }
+
         pub(crate) async fn create(
             &self,
             env_id: &str,
             prompt: &str,
-            git_ref: &str,
-            qa_mode: bool,
-            best_of_n: usize,
+            options: &CreateTaskOptions,
         ) -> Result<crate::CreatedTask> {
-            let mut input_items: Vec<serde_json::Value> = Vec::new();
-            input_items.push(serde_json::json!({
-                "type": "message",
-                "role": "user",
-                "content": [{ "content_type": "text", "text": prompt }]
-            }));
-
-            if let Ok(diff) = std::env::var("CODEX_STARTING_DIFF")
-                && !diff.is_empty()
-            {
-                input_items.push(serde_json::json!({
-                    "type": "pre_apply_patch",
-                    "output_diff": { "diff": diff }
-                }));
-            }
-
-            let mut request_body = serde_json::json!({
-                "new_task": {
-                    "environment_id": env_id,
-                    "branch": git_ref,
-                    "run_environment_in_qa_mode": qa_mode,
-                },
-                "input_items": input_items,
-            });
-
-            if best_of_n > 1
-                && let Some(obj) = request_body.as_object_mut()
-            {
-                obj.insert(
-                    "metadata".to_string(),
-                    serde_json::json!({ "best_of_n": best_of_n }),
-                );
-            }
+            let starting_diff = std::env::var("CODEX_STARTING_DIFF").ok();
+            let request_body =
+                create_task_request_body(env_id, prompt, options, starting_diff.as_deref());
 
             match self.backend.create_task(request_body).await {
                 Ok(id) => {
@@ -359,6 +423,7 @@ mod api {
                         .to_string(),
                     skipped_paths: Vec::new(),
                     conflict_paths: Vec::new(),
+                    merged_with_conflicts: Vec::new(),
                 });
             }
 
@@ -379,12 +444,21 @@ mod api {
                 ApplyStatus::Error
             };
             let applied = matches!(status, ApplyStatus::Success) && !preflight;
+            let merged = r.merge == codex_git_apply::MergeOutcome::Clean;
+            let markers = r.merge == codex_git_apply::MergeOutcome::Conflicts;
 
             let message = if preflight {
                 match status {
+                    ApplyStatus::Success if merged => {
+                        format!("Preflight passed for task {id} (applies with a three-way merge)")
+                    }
                     ApplyStatus::Success => {
                         format!("Preflight passed for task {id} (applies cleanly)")
                     }
+                    ApplyStatus::Partial if markers => format!(
+                        "Preflight: task {id} would leave conflict markers in {} file(s)",
+                        r.merged_with_conflicts.len()
+                    ),
                     ApplyStatus::Partial => format!(
                         "Preflight: patch does not fully apply for task {id} (applied={}, skipped={}, conflicts={})",
                         r.applied_paths.len(),
@@ -400,10 +474,18 @@ mod api {
                 }
             } else {
                 match status {
+                    ApplyStatus::Success if merged => format!(
+                        "Applied task {id} locally via three-way merge ({} files)",
+                        r.applied_paths.len()
+                    ),
                     ApplyStatus::Success => format!(
                         "Applied task {id} locally ({} files)",
                         r.applied_paths.len()
                     ),
+                    ApplyStatus::Partial if markers => format!(
+                        "Applied task {id} with conflict markers in {} file(s); resolve them before committing",
+                        r.merged_with_conflicts.len()
+                    ),
                     ApplyStatus::Partial => format!(
                         "Apply partially succeeded for task {id} (applied={}, skipped={}, conflicts={})",
                         r.applied_paths.len(),
@@ -428,10 +510,11 @@ mod api {
                 use std::fmt::Write as _;
                 let _ = writeln!(
                     &mut log,
-                    "apply_result: mode={} id={} status={:?} applied={} skipped={} conflicts={} cmd={}",
+                    "apply_result: mode={} id={} status={:?} merge={:?} applied={} skipped={} conflicts={} cmd={}",
                     mode,
                     id,
                     status,
+                    r.merge,
                     r.applied_paths.len(),
                     r.skipped_paths.len(),
                     r.conflicted_paths.len(),
@@ -457,10 +540,62 @@ mod api {
                 message,
                 skipped_paths: r.skipped_paths,
                 conflict_paths: r.conflicted_paths,
+                merged_with_conflicts: if preflight {
+                    Vec::new()
+                } else {
+                    r.merged_with_conflicts
+                        .into_iter()
+                        .map(std::path::PathBuf::from)
+                        .collect()
+                },
             })
         }
     }
 
+    /// Request body for `POST /tasks`. `starting_diff` is pre-applied before
+    /// the agent runs; best-of-N is only sent when more than one attempt is requested.
+    pub(crate) fn create_task_request_body(
+        env_id: &str,
+        prompt: &str,
+        options: &CreateTaskOptions,
+        starting_diff: Option<&str>,
+    ) -> Value {
+        let mut input_items: Vec<Value> = Vec::new();
+        input_items.push(serde_json::json!({
+            "type": "message",
+            "role": "user",
+            "content": [{ "content_type": "text", "text": prompt }]
+        }));
+
+        if let Some(diff) = starting_diff
+            && !diff.is_empty()
+        {
+            input_items.push(serde_json::json!({
+                "type": "pre_apply_patch",
+                "output_diff": { "diff": diff }
+            }));
+        }
+
+        let mut request_body = serde_json::json!({
+            "new_task": {
+                "environment_id": env_id,
+                "branch": options.branch,
+                "run_environment_in_qa_mode": options.qa_mode,
+            },
+            "input_items": input_items,
+        });
+
+        if options.attempts > 1
+            && let Some(obj) = request_body.as_object_mut()
+        {
+            obj.insert(
+                "metadata".to_string(),
+                serde_json::json!({ "best_of_n": options.attempts }),
+            );
+        }
+        request_body
+    }
+
     fn details_path(base_url: &str, id: &str) -> Option<String> {
         if base_url.contains("/backend-api") {
             Some(format!("{base_url}/wham/tasks/{id}"))
@@ -471,6 +606,305 @@ mod api {
         }
     }
 
+    /// Map a details body whose current turn has not finished to
+    /// [`CloudTaskError::TaskStillRunning`].
+    fn diff_from_body_stream(
+        body: impl Stream<Item = anyhow::Result<Vec<u8>>> + Send + 'static,
+    ) -> impl Stream<Item = Result<String>> + Send + 'static {
+        struct Download<S> {
+            body: std::pin::Pin<Box<S>>,
+            scanner: DiffFieldScanner,
+            buffered: Vec<u8>,
+        }
+        let state = Download {
+            body: Box::pin(body),
+            scanner: DiffFieldScanner::default(),
+            buffered: Vec::new(),
+        };
+        futures::stream::unfold(Some(state), |state| async move {
+            let mut st = state?;
+            loop {
+                match st.body.next().await {
+                    Some(Ok(chunk)) => {
+                        st.buffered.extend_from_slice(&chunk);
+                        let piece = st.scanner.push(&chunk);
+                        if !piece.is_empty() {
+                            return Some((Ok(piece), Some(st)));
+                        }
+                    }
+                    Some(Err(e)) => {
+                        let err = CloudTaskError::Http(format!("get_task_details failed: {e}"));
+                        return Some((Err(err), None));
+                    }
+                    None => {
+                        return finish_streamed_diff(&st.scanner, &st.buffered)
+                            .map(|item| (item, None));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Check the streamed diff against the one [`Tasks::diff`] would pick
+    /// from the complete body. Returns the last stream item, if any.
+    fn finish_streamed_diff(scanner: &DiffFieldScanner, body: &[u8]) -> Option<Result<String>> {
+        let body = String::from_utf8_lossy(body);
+        let details: backend::CodeTaskDetailsResponse = match serde_json::from_str(&body) {
+            Ok(details) => details,
+            Err(e) => {
+                return Some(Err(CloudTaskError::Http(format!(
+                    "get_task_details failed: {e}"
+                ))));
+            }
+        };
+        let streamed = scanner.streamed();
+        match details.unified_diff() {
+            Some(diff) if diff == streamed => None,
+            Some(diff) if streamed.is_empty() => Some(Ok(diff)),
+            None if streamed.is_empty() => still_running_from_body(&body).map(Err),
+            Some(_) | None => Some(Err(CloudTaskError::Msg(
+                "the streamed diff does not match the task's diff; reopen the task to reload it"
+                    .to_string(),
+            ))),
+        }
+    }
+
+    /// Pulls the first non-empty `"diff": "…"` string out of a task details
+    /// body while it downloads, decoding JSON escapes as it goes.
+    #[derive(Default)]
+    pub(crate) struct DiffFieldScanner {
+        state: ScanState,
+        /// Bytes that cannot be decided yet: a possible `"diff":"` split
+        /// across chunks while searching, or a split escape sequence inside
+        /// the value.
+        pending: Vec<u8>,
+        /// Decoded value bytes not yet handed out, e.g. half a UTF-8
+        /// character.
+        decoded: Vec<u8>,
+        /// Decoded bytes of the current value, to skip empty diffs.
+        value_len: usize,
+        streamed: String,
+    }
+
+    #[derive(Default, PartialEq)]
+    enum ScanState {
+        #[default]
+        Searching,
+        InValue,
+        Done,
+    }
+
+    impl DiffFieldScanner {
+        /// Feed the next chunk of the body; returns the newly decoded diff
+        /// text, which may be empty.
+        pub(crate) fn push(&mut self, chunk: &[u8]) -> String {
+            let mut buf = std::mem::take(&mut self.pending);
+            buf.extend_from_slice(chunk);
+            let mut pos = 0;
+            while pos < buf.len() {
+                match self.state {
+                    ScanState::Done => break,
+                    ScanState::Searching => match find_diff_value(&buf[pos..]) {
+                        Ok(start) => {
+                            pos += start;
+                            self.state = ScanState::InValue;
+                            self.value_len = 0;
+                        }
+                        Err(keep_from) => {
+                            self.pending = buf[pos + keep_from..].to_vec();
+                            break;
+                        }
+                    },
+                    ScanState::InValue => {
+                        let (next, closed) = self.decode_value(&buf, pos);
+                        pos = next;
+                        if closed {
+                            self.state = if self.value_len == 0 {
+                                ScanState::Searching
+                            } else {
+                                ScanState::Done
+                            };
+                        } else if pos < buf.len() {
+                            self.pending = buf[pos..].to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+            self.take_text()
+        }
+
+        /// Everything handed out by [`DiffFieldScanner::push`] so far.
+        pub(crate) fn streamed(&self) -> &str {
+            &self.streamed
+        }
+
+        /// Decode value bytes from `buf[pos..]`. Returns where decoding
+        /// stopped and whether the closing quote was reached; stopping early
+        /// means an escape sequence is split across chunks.
+        fn decode_value(&mut self, buf: &[u8], mut pos: usize) -> (usize, bool) {
+            while pos < buf.len() {
+                match buf[pos] {
+                    b'"' => return (pos + 1, true),
+                    b'\\' => match decode_escape(&buf[pos..]) {
+                        Some((consumed, ch)) => {
+                            let mut utf8 = [0; 4];
+                            let encoded = ch.encode_utf8(&mut utf8).as_bytes();
+                            self.decoded.extend_from_slice(encoded);
+                            self.value_len += encoded.len();
+                            pos += consumed;
+                        }
+                        None => return (pos, false),
+                    },
+                    byte => {
+                        self.decoded.push(byte);
+                        self.value_len += 1;
+                        pos += 1;
+                    }
+                }
+            }
+            (pos, false)
+        }
+
+        /// Hand out the decoded bytes that form complete UTF-8 characters.
+        fn take_text(&mut self) -> String {
+            let mut text = String::new();
+            loop {
+                match std::str::from_utf8(&self.decoded) {
+                    Ok(valid) => {
+                        text.push_str(valid);
+                        self.decoded.clear();
+                        break;
+                    }
+                    Err(e) => {
+                        let valid = e.valid_up_to();
+                        text.push_str(&String::from_utf8_lossy(&self.decoded[..valid]));
+                        match e.error_len() {
+                            Some(len) => {
+                                text.push(char::REPLACEMENT_CHARACTER);
+                                self.decoded.drain(..valid + len);
+                            }
+                            None if self.state == ScanState::InValue => {
+                                self.decoded.drain(..valid);
+                                break;
+                            }
+                            None => {
+                                text.push(char::REPLACEMENT_CHARACTER);
+                                self.decoded.clear();
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            self.streamed.push_str(&text);
+            text
+        }
+    }
+
+    /// Find the first `"diff": "` in `buf`. `Ok` is the offset just past the
+    /// value's opening quote; `Err` is the offset from which bytes must be
+    /// kept because a match may continue in the next chunk.
+    fn find_diff_value(buf: &[u8]) -> std::result::Result<usize, usize> {
+        const KEY: &[u8] = b"\"diff\"";
+        let mut from = 0;
+        while let Some(found) = buf[from..]
+            .windows(KEY.len())
+            .position(|window| window == KEY)
+        {
+            let key_at = from + found;
+            let mut at = skip_whitespace(buf, key_at + KEY.len());
+            if at == buf.len() {
+                return Err(key_at);
+            }
+            if buf[at] == b':' {
+                at = skip_whitespace(buf, at + 1);
+                if at == buf.len() {
+                    return Err(key_at);
+                }
+                if buf[at] == b'"' {
+                    return Ok(at + 1);
+                }
+            }
+            from = key_at + 1;
+        }
+        Err(buf.len().saturating_sub(KEY.len() - 1).max(from))
+    }
+
+    fn skip_whitespace(buf: &[u8], mut at: usize) -> usize {
+        while at < buf.len() && buf[at].is_ascii_whitespace() {
+            at += 1;
+        }
+        at
+    }
+
+    /// Decode the JSON escape at the start of `bytes`, returning how many
+    /// bytes it used. `None` when the escape continues past `bytes`.
+    fn decode_escape(bytes: &[u8]) -> Option<(usize, char)> {
+        let kind = *bytes.get(1)?;
+        let simple = match kind {
+            b'n' => '\n',
+            b't' => '\t',
+            b'r' => '\r',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'u' => {
+                let high = hex4(bytes.get(2..6)?)?;
+                if !(0xD800..0xDC00).contains(&high) {
+                    return Some((
+                        6,
+                        char::from_u32(high).unwrap_or(char::REPLACEMENT_CHARACTER),
+                    ));
+                }
+                // A high surrogate must be followed by `\uXXXX` low surrogate.
+                if bytes.len() < 12 {
+                    return None;
+                }
+                let low = match (&bytes[6..8], hex4(&bytes[8..12])) {
+                    (b"\\u", Some(low)) if (0xDC00..0xE000).contains(&low) => low,
+                    _ => return Some((6, char::REPLACEMENT_CHARACTER)),
+                };
+                let combined = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                return Some((
+                    12,
+                    char::from_u32(combined).unwrap_or(char::REPLACEMENT_CHARACTER),
+                ));
+            }
+            _ => char::REPLACEMENT_CHARACTER,
+        };
+        Some((2, simple))
+    }
+
+    fn hex4(bytes: &[u8]) -> Option<u32> {
+        u32::from_str_radix(std::str::from_utf8(bytes).ok()?, 16).ok()
+    }
+
+    pub(crate) fn still_running_from_body(body: &str) -> Option<CloudTaskError> {
+        let full = serde_json::from_str::<Value>(body).ok()?;
+        let turn = full
+            .get("current_assistant_turn")
+            .filter(|t| t.is_object())
+            .or_else(|| full.get("current_diff_task_turn"))?;
+        let status = turn.get("turn_status")?.as_str()?;
+        if !matches!(status, "pending" | "in_progress") {
+            return None;
+        }
+        let latest_event = turn
+            .get("latest_event")
+            .and_then(|e| e.get("text"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string);
+        Some(CloudTaskError::TaskStillRunning {
+            status: status.to_string(),
+            latest_event,
+        })
+    }
+
     fn extract_assistant_messages_from_body(body: &str) -> Vec<String> {
         let mut msgs = Vec::new();
         if let Ok(full) = serde_json::from_str::<serde_json::Value>(body)
@@ -514,6 +948,63 @@ mod api {
         msgs
     }
 
+    /// Latest plan on the assistant turn. A `plan` output item is the final
+    /// snapshot; otherwise fall back to the last `update_plan` call in the
+    /// worklog, whose text part holds the tool arguments as JSON.
+    pub(crate) fn plan_from_body(body: &str) -> Option<UpdatePlanArgs> {
+        let full = serde_json::from_str::<Value>(body).ok()?;
+        let turn = full.get("current_assistant_turn")?;
+        let from_output = turn
+            .get("output_items")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .rev()
+            .filter(|item| item.get("type").and_then(Value::as_str) == Some("plan"))
+            .find_map(plan_from_value);
+        if from_output.is_some() {
+            return from_output;
+        }
+        turn.get("worklog")
+            .and_then(|w| w.get("messages"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .rev()
+            .filter(|m| m.get("recipient").and_then(Value::as_str) == Some("update_plan"))
+            .find_map(|m| {
+                let parts = m.get("content")?.get("parts")?.as_array()?;
+                parts.iter().rev().find_map(|part| {
+                    let text = part
+                        .as_str()
+                        .or_else(|| part.get("text").and_then(Value::as_str))?;
+                    plan_from_value(&serde_json::from_str(text).ok()?)
+                })
+            })
+    }
+
+    fn plan_from_value(value: &Value) -> Option<UpdatePlanArgs> {
+        let steps: Vec<codex_protocol::plan_tool::PlanItemArg> = value
+            .get("plan")?
+            .as_array()?
+            .iter()
+            .filter_map(|step| serde_json::from_value(step.clone()).ok())
+            .collect();
+        if steps.is_empty() {
+            return None;
+        }
+        let explanation = value
+            .get("explanation")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+        Some(UpdatePlanArgs {
+            explanation,
+            plan: steps,
+        })
+    }
+
     fn turn_attempt_from_map(turn: &HashMap<String, Value>) -> Option<TurnAttempt> {
         let turn_id = turn.get("id").and_then(Value::as_str)?.to_string();
         let attempt_placement = turn.get("attempt_placement").and_then(Value::as_i64);
@@ -767,3 +1258,259 @@ fn append_error_log(message: &str) {
         let _ = writeln!(f, "[{ts}] {message}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HttpClient;
+    use super::api::DiffFieldScanner;
+    use super::api::create_task_request_body;
+    use super::api::plan_from_body;
+    use super::api::still_running_from_body;
+    use crate::CloudBackend;
+    use crate::CloudTaskError;
+    use crate::CreateTaskOptions;
+    use crate::TaskId;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use codex_protocol::plan_tool::StepStatus;
+    use codex_protocol::plan_tool::UpdatePlanArgs;
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn create_task_body_for_single_attempt_omits_metadata() {
+        let body = create_task_request_body("env-1", "fix it", &CreateTaskOptions::default(), None);
+        assert_eq!(
+            body,
+            json!({
+                "new_task": {
+                    "environment_id": "env-1",
+                    "branch": "main",
+                    "run_environment_in_qa_mode": false,
+                },
+                "input_items": [{
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "content_type": "text", "text": "fix it" }],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn create_task_body_carries_options_and_starting_diff() {
+        let options = CreateTaskOptions {
+            attempts: 4,
+            qa_mode: true,
+            branch: "dev".to_string(),
+        };
+        let body = create_task_request_body("env-1", "fix it", &options, Some("diff --git a b"));
+        assert_eq!(
+            body,
+            json!({
+                "new_task": {
+                    "environment_id": "env-1",
+                    "branch": "dev",
+                    "run_environment_in_qa_mode": true,
+                },
+                "input_items": [
+                    {
+                        "type": "message",
+                        "role": "user",
+                        "content": [{ "content_type": "text", "text": "fix it" }],
+                    },
+                    {
+                        "type": "pre_apply_patch",
+                        "output_diff": { "diff": "diff --git a b" },
+                    },
+                ],
+                "metadata": { "best_of_n": 4 },
+            })
+        );
+    }
+
+    #[test]
+    fn in_progress_turn_maps_to_task_still_running() {
+        let body = json!({
+            "current_assistant_turn": {
+                "turn_status": "in_progress",
+                "latest_event": { "text": "  Running tests  " },
+            }
+        })
+        .to_string();
+        match still_running_from_body(&body) {
+            Some(CloudTaskError::TaskStillRunning {
+                status,
+                latest_event,
+            }) => {
+                assert_eq!(status, "in_progress");
+                assert_eq!(latest_event.as_deref(), Some("Running tests"));
+            }
+            other => panic!("expected TaskStillRunning, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pending_diff_turn_without_event_maps_to_task_still_running() {
+        let body = json!({
+            "current_assistant_turn": null,
+            "current_diff_task_turn": { "turn_status": "pending" },
+        })
+        .to_string();
+        match still_running_from_body(&body) {
+            Some(CloudTaskError::TaskStillRunning {
+                status,
+                latest_event,
+            }) => {
+                assert_eq!(status, "pending");
+                assert_eq!(latest_event, None);
+            }
+            other => panic!("expected TaskStillRunning, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn finished_or_unparseable_bodies_are_not_still_running() {
+        let completed = json!({
+            "current_assistant_turn": { "turn_status": "completed" }
+        })
+        .to_string();
+        assert!(still_running_from_body(&completed).is_none());
+        assert!(still_running_from_body("{}").is_none());
+        assert!(still_running_from_body("not json").is_none());
+    }
+
+    fn step(step: &str, status: StepStatus) -> PlanItemArg {
+        PlanItemArg {
+            step: step.to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn plan_output_item_is_parsed_from_task_details() {
+        let body = include_str!("../tests/fixtures/task_details_with_plan.json");
+        assert_eq!(
+            plan_from_body(body),
+            Some(UpdatePlanArgs {
+                explanation: Some("Split the client before touching the UI.".to_string()),
+                plan: vec![
+                    step("Extract the HTTP client", StepStatus::Completed),
+                    step("Add plan parsing", StepStatus::InProgress),
+                    step("Render the plan", StepStatus::Pending),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn latest_worklog_plan_update_is_used_without_plan_output() {
+        let body = include_str!("../tests/fixtures/task_details_with_worklog_plan.json");
+        assert_eq!(
+            plan_from_body(body),
+            Some(UpdatePlanArgs {
+                explanation: None,
+                plan: vec![
+                    step("Reproduce the bug", StepStatus::Completed),
+                    step("Write the fix", StepStatus::Completed),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn tasks_without_plans_have_no_plan() {
+        let body = include_str!("../../backend-client/tests/fixtures/task_details_with_diff.json");
+        assert_eq!(plan_from_body(body), None);
+        assert_eq!(plan_from_body("not json"), None);
+    }
+
+    fn scan_in_chunks(body: &[u8], chunk_len: usize) -> String {
+        let mut scanner = DiffFieldScanner::default();
+        let text: String = body.chunks(chunk_len).map(|c| scanner.push(c)).collect();
+        assert_eq!(text, scanner.streamed());
+        text
+    }
+
+    #[test]
+    fn diff_scanner_matches_the_parsed_diff_at_any_chunk_size() {
+        let body =
+            include_bytes!("../../backend-client/tests/fixtures/task_details_with_diff.json");
+        let expected = "diff --git a/src/main.rs b/src/main.rs\n+fn main() { println!(\"hi\"); }\n";
+        for chunk_len in 1..=body.len() {
+            assert_eq!(
+                scan_in_chunks(body, chunk_len),
+                expected,
+                "chunk_len={chunk_len}"
+            );
+        }
+    }
+
+    #[test]
+    fn diff_scanner_skips_empty_values_and_decodes_escapes() {
+        let body = json!({
+            "items": [
+                { "diff": null },
+                { "diff": "" },
+                { "diff": "+caf\u{e9} \u{1f600} \"q\" \\ tab\t/\n" },
+                { "diff": "ignored" },
+            ]
+        })
+        .to_string()
+        .replace('\u{1f600}', "\\ud83d\\ude00");
+        for chunk_len in 1..=body.len() {
+            assert_eq!(
+                scan_in_chunks(body.as_bytes(), chunk_len),
+                "+caf\u{e9} \u{1f600} \"q\" \\ tab\t/\n",
+                "chunk_len={chunk_len}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn task_diff_is_yielded_before_the_response_finishes() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.expect("read request");
+                assert!(n > 0, "client closed before sending the request");
+                request.extend_from_slice(&buf[..n]);
+            }
+            let first = r#"{"current_diff_task_turn":{"output_items":[{"type":"output_diff","diff":"diff --git a/a b/a\n+one\n"#;
+            let second = r#"+two\n"}]}}"#;
+            let chunk = |s: &str| format!("{:x}\r\n{s}\r\n", s.len());
+            let head = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ntransfer-encoding: chunked\r\n\r\n";
+            socket
+                .write_all(format!("{head}{}", chunk(first)).as_bytes())
+                .await
+                .expect("write first chunk");
+            socket.flush().await.expect("flush");
+            // Hold the rest back until the client has shown the first piece.
+            release_rx.await.expect("release");
+            socket
+                .write_all(format!("{}0\r\n\r\n", chunk(second)).as_bytes())
+                .await
+                .expect("write second chunk");
+            socket.flush().await.expect("flush");
+        });
+
+        let client = HttpClient::new(format!("http://{addr}")).expect("client");
+        let mut stream = client.stream_task_diff(TaskId("task-1".to_string()));
+        let first = stream.next().await.expect("first piece").expect("ok");
+        assert_eq!(first, "diff --git a/a b/a\n+one\n");
+        release_tx.send(()).expect("server waiting");
+        let second = stream.next().await.expect("second piece").expect("ok");
+        assert_eq!(second, "+two\n");
+        assert!(stream.next().await.is_none());
+        server.await.expect("server");
+    }
+}
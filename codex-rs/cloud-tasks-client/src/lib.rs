@@ -5,10 +5,13 @@ pub use api::ApplyStatus;
 pub use api::AttemptStatus;
 pub use api::CloudBackend;
 pub use api::CloudTaskError;
+pub use api::CreateTaskOptions;
 pub use api::CreatedTask;
 pub use api::DiffSummary;
 pub use api::Result;
+pub use api::TaskDiffStream;
 pub use api::TaskId;
+pub use api::TaskMessageStream;
 pub use api::TaskStatus;
 pub use api::TaskSummary;
 pub use api::TaskText;
@@ -17,12 +20,30 @@ pub use api::TurnAttempt;
 #[cfg(feature = "mock")]
 mod mock;
 
+#[cfg(feature = "online")]
+mod bootstrap;
 #[cfg(feature = "online")]
 mod http;
 
 #[cfg(feature = "mock")]
 pub use mock::MockClient;
+#[cfg(feature = "mock")]
+pub use mock::MockOp;
 
+#[cfg(feature = "online")]
+pub use bootstrap::BackendConnection;
+#[cfg(feature = "online")]
+pub use bootstrap::ConnectError;
+#[cfg(feature = "online")]
+pub use bootstrap::DEFAULT_BASE_URL;
+#[cfg(feature = "online")]
+pub use bootstrap::configured_base_url;
+#[cfg(feature = "online")]
+pub use bootstrap::connect_backend;
+#[cfg(feature = "online")]
+pub use bootstrap::extract_chatgpt_account_id;
+#[cfg(feature = "online")]
+pub use bootstrap::mock_mode_requested;
 #[cfg(feature = "online")]
 pub use http::HttpClient;
 
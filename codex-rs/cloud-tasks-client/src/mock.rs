@@ -1,21 +1,182 @@
 use crate::ApplyOutcome;
 use crate::AttemptStatus;
 use crate::CloudBackend;
+use crate::CloudTaskError;
 use crate::DiffSummary;
 use crate::Result;
 use crate::TaskId;
+use crate::TaskMessageStream;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
 use crate::api::TaskText;
 use chrono::Utc;
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
 
+/// Backend operations, used to target forced errors on a [`MockClient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MockOp {
+    ListTasks,
+    GetTaskDiff,
+    GetTaskMessages,
+    GetTaskText,
+    GetTaskPlan,
+    ListSiblingAttempts,
+    ApplyTaskPreflight,
+    ApplyTask,
+    CreateTask,
+}
+
+impl MockOp {
+    const ALL: [MockOp; 9] = [
+        MockOp::ListTasks,
+        MockOp::GetTaskDiff,
+        MockOp::GetTaskMessages,
+        MockOp::GetTaskText,
+        MockOp::GetTaskPlan,
+        MockOp::ListSiblingAttempts,
+        MockOp::ApplyTaskPreflight,
+        MockOp::ApplyTask,
+        MockOp::CreateTask,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MockOp::ListTasks => "list_tasks",
+            MockOp::GetTaskDiff => "get_task_diff",
+            MockOp::GetTaskMessages => "get_task_messages",
+            MockOp::GetTaskText => "get_task_text",
+            MockOp::GetTaskPlan => "get_task_plan",
+            MockOp::ListSiblingAttempts => "list_sibling_attempts",
+            MockOp::ApplyTaskPreflight => "apply_task_preflight",
+            MockOp::ApplyTask => "apply_task",
+            MockOp::CreateTask => "create_task",
+        }
+    }
+}
+
+/// In-memory backend with canned data. By default every call succeeds
+/// instantly; the `with_*` knobs add latency and failures so the TUI's
+/// loading and error paths can be exercised.
 #[derive(Clone, Default)]
-pub struct MockClient;
+pub struct MockClient {
+    latency: Option<Duration>,
+    failure_rate: f64,
+    seed: Option<u64>,
+    forced_errors: HashMap<MockOp, CloudTaskError>,
+    rng: Arc<Mutex<Option<u64>>>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure from the `CODEX_CLOUD_TASKS_MOCK_*` environment variables:
+    /// `LATENCY_MS`, `FAILURE_RATE` (0.0-1.0), `SEED`, and `FAIL` (a
+    /// comma-separated list of operations such as `list_tasks` that always fail).
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(format!("CODEX_CLOUD_TASKS_MOCK_{name}")).ok();
+        let mut client = Self::new();
+        if let Some(ms) = var("LATENCY_MS").and_then(|v| v.trim().parse::<u64>().ok()) {
+            client = client.with_latency(Duration::from_millis(ms));
+        }
+        if let Some(rate) = var("FAILURE_RATE").and_then(|v| v.trim().parse::<f64>().ok()) {
+            client = client.with_failure_rate(rate);
+        }
+        if let Some(seed) = var("SEED").and_then(|v| v.trim().parse::<u64>().ok()) {
+            client = client.with_seed(seed);
+        }
+        if let Some(ops) = var("FAIL") {
+            for name in ops.split(',').map(str::trim) {
+                if let Some(op) = MockOp::ALL.into_iter().find(|op| op.as_str() == name) {
+                    client = client.with_forced_error(
+                        op,
+                        CloudTaskError::Http(format!("mock: forced {name} failure")),
+                    );
+                }
+            }
+        }
+        client
+    }
+
+    /// Delay every call by `latency` before it completes.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Fail each call with probability `rate` (clamped to 0.0-1.0).
+    pub fn with_failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Seed the failure sampling so a given failure rate fails the same
+    /// calls on every run.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = Arc::new(Mutex::new(Some(seed)));
+        self
+    }
+
+    /// Always fail `op` with `error`.
+    pub fn with_forced_error(mut self, op: MockOp, error: CloudTaskError) -> Self {
+        self.forced_errors.insert(op, error);
+        self
+    }
+
+    async fn simulate(&self, op: MockOp) -> Result<()> {
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+        if let Some(err) = self.forced_errors.get(&op) {
+            return Err(err.clone());
+        }
+        if self.failure_rate > 0.0 && self.next_unit() < self.failure_rate {
+            return Err(CloudTaskError::Http(format!(
+                "mock: simulated {} failure",
+                op.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Next sample in `[0, 1)` from a splitmix64 sequence.
+    fn next_unit(&self) -> f64 {
+        let mut state = match self.rng.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let current = state.unwrap_or_else(|| {
+            self.seed.unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or_default()
+            })
+        });
+        let next = current.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        *state = Some(next);
+        let mut z = next;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
 
 #[async_trait::async_trait]
 impl CloudBackend for MockClient {
     async fn list_tasks(&self, _env: Option<&str>) -> Result<Vec<TaskSummary>> {
+        self.simulate(MockOp::ListTasks).await?;
         // Slightly vary content by env to aid tests that rely on the mock
         let rows = match _env {
             Some("env-A") => vec![("T-2000", "A: First", TaskStatus::Ready)],
@@ -61,16 +222,38 @@ impl CloudBackend for MockClient {
     }
 
     async fn get_task_diff(&self, id: TaskId) -> Result<Option<String>> {
+        self.simulate(MockOp::GetTaskDiff).await?;
         Ok(Some(mock_diff_for(&id)))
     }
 
     async fn get_task_messages(&self, _id: TaskId) -> Result<Vec<String>> {
+        self.simulate(MockOp::GetTaskMessages).await?;
         Ok(vec![
             "Mock assistant output: this task contains no diff.".to_string(),
         ])
     }
 
+    fn stream_task_messages(&self, _id: TaskId) -> TaskMessageStream<'_> {
+        Box::pin(
+            futures::stream::once(self.simulate(MockOp::GetTaskMessages)).flat_map(|result| {
+                let items: Vec<Result<String>> = match result {
+                    Ok(()) => [
+                        "Mock: setting up environment…",
+                        "Mock: running task…",
+                        "Mock assistant output: this task contains no diff.",
+                    ]
+                    .into_iter()
+                    .map(|m| Ok(m.to_string()))
+                    .collect(),
+                    Err(err) => vec![Err(err)],
+                };
+                futures::stream::iter(items)
+            }),
+        )
+    }
+
     async fn get_task_text(&self, _id: TaskId) -> Result<TaskText> {
+        self.simulate(MockOp::GetTaskText).await?;
         Ok(TaskText {
             prompt: Some("Why is there no diff?".to_string()),
             messages: vec!["Mock assistant output: this task contains no diff.".to_string()],
@@ -81,13 +264,34 @@ impl CloudBackend for MockClient {
         })
     }
 
+    async fn get_task_plan(&self, id: TaskId) -> Result<Option<UpdatePlanArgs>> {
+        self.simulate(MockOp::GetTaskPlan).await?;
+        if id.0 != "T-1000" {
+            return Ok(None);
+        }
+        let step = |step: &str, status| PlanItemArg {
+            step: step.to_string(),
+            status,
+        };
+        Ok(Some(UpdatePlanArgs {
+            explanation: Some("Mock plan for the first task.".to_string()),
+            plan: vec![
+                step("Inspect the repository", StepStatus::Completed),
+                step("Update the README", StepStatus::InProgress),
+                step("Run the tests", StepStatus::Pending),
+            ],
+        }))
+    }
+
     async fn apply_task(&self, id: TaskId, _diff_override: Option<String>) -> Result<ApplyOutcome> {
+        self.simulate(MockOp::ApplyTask).await?;
         Ok(ApplyOutcome {
             applied: true,
             status: crate::ApplyStatus::Success,
             message: format!("Applied task {} locally (mock)", id.0),
             skipped_paths: Vec::new(),
             conflict_paths: Vec::new(),
+            merged_with_conflicts: Vec::new(),
         })
     }
 
@@ -96,12 +300,14 @@ impl CloudBackend for MockClient {
         id: TaskId,
         _diff_override: Option<String>,
     ) -> Result<ApplyOutcome> {
+        self.simulate(MockOp::ApplyTaskPreflight).await?;
         Ok(ApplyOutcome {
             applied: false,
             status: crate::ApplyStatus::Success,
             message: format!("Preflight passed for task {} (mock)", id.0),
             skipped_paths: Vec::new(),
             conflict_paths: Vec::new(),
+            merged_with_conflicts: Vec::new(),
         })
     }
 
@@ -110,6 +316,7 @@ impl CloudBackend for MockClient {
         task: TaskId,
         _turn_id: String,
     ) -> Result<Vec<TurnAttempt>> {
+        self.simulate(MockOp::ListSiblingAttempts).await?;
         if task.0 == "T-1000" {
             return Ok(vec![TurnAttempt {
                 turn_id: "T-1000-attempt-2".to_string(),
@@ -127,11 +334,10 @@ impl CloudBackend for MockClient {
         &self,
         env_id: &str,
         prompt: &str,
-        git_ref: &str,
-        qa_mode: bool,
-        best_of_n: usize,
+        options: crate::CreateTaskOptions,
     ) -> Result<crate::CreatedTask> {
-        let _ = (env_id, prompt, git_ref, qa_mode, best_of_n);
+        self.simulate(MockOp::CreateTask).await?;
+        let _ = (env_id, prompt, options);
         let id = format!("task_local_{}", chrono::Utc::now().timestamp_millis());
         Ok(crate::CreatedTask { id: TaskId(id) })
     }
@@ -178,3 +384,75 @@ fn count_from_unified(diff: &str) -> (usize, usize) {
         (a, d)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn forced_error_surfaces_from_list_tasks() {
+        let client = MockClient::new()
+            .with_forced_error(MockOp::ListTasks, CloudTaskError::Http("boom".to_string()));
+
+        let err = client.list_tasks(None).await.expect_err("forced error");
+        assert_eq!(err.to_string(), "http error: boom");
+        // Other operations are unaffected.
+        assert!(client.get_task_diff(TaskId("T-1000".into())).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn latency_delays_completion() {
+        let latency = Duration::from_millis(50);
+        let client = MockClient::new().with_latency(latency);
+
+        let start = Instant::now();
+        client.list_tasks(None).await.expect("list tasks");
+        assert!(start.elapsed() >= latency);
+    }
+
+    #[tokio::test]
+    async fn seeded_failures_are_deterministic() {
+        async fn outcomes(client: &MockClient) -> Vec<bool> {
+            let mut out = Vec::new();
+            for _ in 0..32 {
+                out.push(client.list_tasks(None).await.is_ok());
+            }
+            out
+        }
+
+        let first = outcomes(&MockClient::new().with_failure_rate(0.5).with_seed(7)).await;
+        let second = outcomes(&MockClient::new().with_failure_rate(0.5).with_seed(7)).await;
+        assert_eq!(first, second);
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[tokio::test]
+    async fn streamed_messages_arrive_in_order_then_end() {
+        let client = MockClient::new();
+
+        let items: Vec<std::result::Result<String, String>> = client
+            .stream_task_messages(TaskId("T-1000".into()))
+            .map(|item| item.map_err(|e| e.to_string()))
+            .collect()
+            .await;
+        assert_eq!(
+            items,
+            vec![
+                Ok("Mock: setting up environment…".to_string()),
+                Ok("Mock: running task…".to_string()),
+                Ok("Mock assistant output: this task contains no diff.".to_string()),
+            ]
+        );
+
+        let failing = MockClient::new()
+            .with_forced_error(MockOp::GetTaskMessages, CloudTaskError::Http("boom".into()));
+        let items: Vec<std::result::Result<String, String>> = failing
+            .stream_task_messages(TaskId("T-1000".into()))
+            .map(|item| item.map_err(|e| e.to_string()))
+            .collect()
+            .await;
+        assert_eq!(items, vec![Err("http error: boom".to_string())]);
+    }
+}
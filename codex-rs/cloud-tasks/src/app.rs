@@ -1,3 +1,6 @@
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -36,9 +39,12 @@ pub struct ApplyModalState {
     pub result_level: Option<ApplyResultLevel>,
     pub skipped_paths: Vec<String>,
     pub conflict_paths: Vec<String>,
+    /// Files an apply left with three-way merge conflict markers.
+    pub merged_with_conflicts: Vec<std::path::PathBuf>,
     pub diff_override: Option<String>,
 }
 
+use crate::help::ListAction;
 use crate::scrollable_diff::ScrollableDiff;
 use codex_cloud_tasks_client::CloudBackend;
 use codex_cloud_tasks_client::TaskId;
@@ -57,6 +63,7 @@ pub struct App {
     pub env_modal: Option<EnvModalState>,
     pub apply_modal: Option<ApplyModalState>,
     pub best_of_modal: Option<BestOfModalState>,
+    pub help_overlay: Option<crate::help::HelpOverlay>,
     pub environments: Vec<EnvironmentRow>,
     pub env_last_loaded: Option<std::time::Instant>,
     pub env_loading: bool,
@@ -64,14 +71,32 @@ pub struct App {
     // New Task page
     pub new_task: Option<crate::new_task::NewTaskPage>,
     pub best_of_n: usize,
+    // "Discard draft?" confirmation shown before closing a non-empty New Task page
+    pub confirm_discard_draft: bool,
     // Apply preflight spinner state
     pub apply_preflight_inflight: bool,
     // Apply action spinner state
     pub apply_inflight: bool,
     // Background enrichment coordination
     pub list_generation: u64,
+    // Automatic retries already scheduled for the current failing list load
+    pub load_retries: u32,
+    // Opt-in periodic list refresh; None when disabled
+    pub auto_refresh: Option<AutoRefresh>,
     pub in_flight: std::collections::HashSet<String>,
-    // Background enrichment caches were planned; currently unused.
+    // Diff stats fetched in the background for tasks listed without them, by
+    // task id, with the task's `updated_at` they were computed for
+    pub diff_summaries: std::collections::HashMap<
+        String,
+        (
+            chrono::DateTime<chrono::Utc>,
+            codex_cloud_tasks_client::DiffSummary,
+        ),
+    >,
+    // Prompts from earlier successful New Task submissions, recalled with Up/Down
+    pub prompt_history: crate::prompt_history::PromptHistory,
+    // Tasks to notify about when they finish
+    pub watch: crate::watch::WatchList,
 }
 
 impl App {
@@ -88,16 +113,23 @@ impl App {
             env_modal: None,
             apply_modal: None,
             best_of_modal: None,
+            help_overlay: None,
             environments: Vec::new(),
             env_last_loaded: None,
             env_loading: false,
             env_error: None,
             new_task: None,
             best_of_n: 1,
+            confirm_discard_draft: false,
             apply_preflight_inflight: false,
             apply_inflight: false,
             list_generation: 0,
+            load_retries: 0,
+            auto_refresh: AutoRefresh::from_env(),
             in_flight: std::collections::HashSet::new(),
+            diff_summaries: std::collections::HashMap::new(),
+            prompt_history: crate::prompt_history::PromptHistory::default(),
+            watch: crate::watch::WatchList::default(),
         }
     }
 
@@ -116,6 +148,319 @@ impl App {
             self.selected -= 1;
         }
     }
+
+    /// True when auto-refresh should hold off: a load is already running or
+    /// the user is looking at something other than the bare task list.
+    pub fn auto_refresh_blocked(&self) -> bool {
+        self.refresh_inflight || !self.is_list_view()
+    }
+
+    /// True when the bare task list has focus: no page, overlay or modal is open.
+    pub fn is_list_view(&self) -> bool {
+        self.diff_overlay.is_none()
+            && self.new_task.is_none()
+            && self.env_modal.is_none()
+            && self.apply_modal.is_none()
+            && self.best_of_modal.is_none()
+            && self.help_overlay.is_none()
+            && !self.confirm_discard_draft
+    }
+
+    /// Close the New Task page, or ask for confirmation first when the
+    /// composer holds a draft. Returns `true` if the page was closed.
+    pub fn request_close_new_task(&mut self) -> bool {
+        let Some(page) = self.new_task.as_ref() else {
+            return true;
+        };
+        if page.has_unsaved_content() {
+            self.confirm_discard_draft = true;
+            self.status = "Discard draft? y/n".to_string();
+            false
+        } else {
+            self.discard_draft();
+            true
+        }
+    }
+
+    /// Confirmed: drop the New Task page and its draft.
+    pub fn discard_draft(&mut self) {
+        self.confirm_discard_draft = false;
+        self.new_task = None;
+        self.status = "Canceled new task".to_string();
+    }
+
+    /// Declined: dismiss the confirmation and keep editing.
+    pub fn keep_draft(&mut self) {
+        self.confirm_discard_draft = false;
+        self.status = "New Task: Enter to submit; Esc to cancel".to_string();
+    }
+}
+
+/// Input to [`update`].
+#[derive(Debug)]
+pub enum Msg {
+    Key(KeyEvent),
+    /// The diff fetched for an apply started from the list; `None` when the
+    /// task has no diff or it could not be fetched.
+    ApplyDiffLoaded {
+        id: TaskId,
+        title: String,
+        diff: Option<String>,
+    },
+}
+
+/// Work that [`update`] asks the IO loop in `run_main` to carry out.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    Redraw,
+    Quit,
+    /// Reload the task list for the current `env_filter`.
+    LoadTasks,
+    /// Fetch the environment list for the selector.
+    LoadEnvironments,
+    /// Fetch the diff of the task the user asked to apply.
+    LoadApplyDiff {
+        id: TaskId,
+        title: String,
+    },
+    /// Preflight applying `diff` and open the apply modal.
+    Preflight {
+        id: TaskId,
+        title: String,
+        diff: String,
+    },
+    /// Not handled by [`update`] yet; the inline key handlers in `run_main`
+    /// process it.
+    Unhandled(KeyEvent),
+}
+
+/// State transition for the cloud tasks UI. Covers Ctrl-C, the help overlay,
+/// the discard-draft prompt and the task list's navigation, refresh,
+/// environment and apply keys. The details overlay, the modals and the
+/// composer are still handled inline in `run_main`.
+pub fn update(app: &mut App, msg: Msg) -> Vec<Effect> {
+    match msg {
+        Msg::Key(key) => update_key(app, key),
+        // The user opened another view while the diff loaded; drop it.
+        Msg::ApplyDiffLoaded { .. } if !app.is_list_view() => Vec::new(),
+        Msg::ApplyDiffLoaded { id, title, diff } => match diff {
+            Some(diff) => vec![Effect::Redraw, Effect::Preflight { id, title, diff }],
+            None => {
+                app.status = "No diff available to apply".to_string();
+                vec![Effect::Redraw]
+            }
+        },
+    }
+}
+
+fn update_key(app: &mut App, key: KeyEvent) -> Vec<Effect> {
+    // Treat Ctrl-C like pressing 'q' in the current context.
+    if key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+    {
+        if app.help_overlay.is_some() {
+            app.help_overlay = None;
+        } else if app.env_modal.is_some() {
+            // Close environment selector if open (don’t quit composer).
+            app.env_modal = None;
+        } else if app.best_of_modal.is_some() {
+            app.best_of_modal = None;
+        } else if app.apply_modal.is_some() {
+            app.apply_modal = None;
+            app.status = "Apply canceled".to_string();
+        } else if app.confirm_discard_draft {
+            app.keep_draft();
+        } else if app.new_task.is_some() {
+            app.request_close_new_task();
+        } else if app.diff_overlay.is_some() {
+            app.diff_overlay = None;
+        } else {
+            return vec![Effect::Quit];
+        }
+        return vec![Effect::Redraw];
+    }
+    // Help overlay sits above everything else while open.
+    if let Some(overlay) = app.help_overlay.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                app.help_overlay = None;
+            }
+            KeyCode::Down | KeyCode::Char('j') => overlay.sd.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => overlay.sd.scroll_by(-1),
+            KeyCode::PageDown | KeyCode::Char(' ') => {
+                let step = overlay.sd.state.viewport_h.saturating_sub(1) as i16;
+                overlay.sd.page_by(step);
+            }
+            KeyCode::PageUp => {
+                let step = overlay.sd.state.viewport_h.saturating_sub(1) as i16;
+                overlay.sd.page_by(-step);
+            }
+            KeyCode::Home => overlay.sd.to_top(),
+            KeyCode::End => overlay.sd.to_bottom(),
+            _ => {}
+        }
+        return vec![Effect::Redraw];
+    }
+    // "Discard draft?" confirmation captures keys until answered.
+    if app.confirm_discard_draft {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.discard_draft(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') | KeyCode::Esc => {
+                app.keep_draft()
+            }
+            _ => {}
+        }
+        return vec![Effect::Redraw];
+    }
+    let is_ctrl_n = key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char('n') | KeyCode::Char('N'))
+        || matches!(key.code, KeyCode::Char('\u{000E}'));
+    if is_ctrl_n && app.new_task.is_none() {
+        return Vec::new();
+    }
+    if !app.is_list_view() {
+        return vec![Effect::Unhandled(key)];
+    }
+    // Base list view keys; bindings live in `help::LIST_BINDINGS`.
+    match crate::help::list_action(key.code) {
+        Some(ListAction::Quit) => vec![Effect::Quit],
+        Some(ListAction::Next) => {
+            app.next();
+            vec![Effect::Redraw]
+        }
+        Some(ListAction::Prev) => {
+            app.prev();
+            vec![Effect::Redraw]
+        }
+        Some(ListAction::Help) => {
+            app.help_overlay = Some(crate::help::HelpOverlay::new());
+            vec![Effect::Redraw]
+        }
+        Some(ListAction::Refresh) => {
+            app.status = "Refreshing…".to_string();
+            app.refresh_inflight = true;
+            app.list_generation = app.list_generation.saturating_add(1);
+            app.load_retries = 0;
+            app.in_flight.clear();
+            vec![Effect::Redraw, Effect::LoadTasks]
+        }
+        Some(ListAction::SetEnv) => {
+            app.env_modal = Some(EnvModalState::default());
+            // Cache environments until user explicitly refreshes with 'r' inside the modal.
+            if app.environments.is_empty() {
+                app.env_loading = true;
+                app.env_error = None;
+                vec![Effect::Redraw, Effect::LoadEnvironments]
+            } else {
+                vec![Effect::Redraw]
+            }
+        }
+        Some(ListAction::Apply) => {
+            if app.apply_inflight || app.apply_preflight_inflight {
+                app.status =
+                    "Finish the current apply/preflight before starting another.".to_string();
+                return vec![Effect::Redraw];
+            }
+            match app.tasks.get(app.selected) {
+                Some(task) => vec![Effect::LoadApplyDiff {
+                    id: task.id.clone(),
+                    title: task.title.clone(),
+                }],
+                None => Vec::new(),
+            }
+        }
+        _ => vec![Effect::Unhandled(key)],
+    }
+}
+
+/// Number of automatic retries after a failed task list load.
+pub const MAX_LOAD_RETRIES: u32 = 4;
+
+/// Delay before automatic retry `attempt` (0-based): 1s, 2s, 4s, 8s.
+/// Returns `None` once the retry budget is exhausted.
+pub fn load_retry_delay(attempt: u32) -> Option<Duration> {
+    (attempt < MAX_LOAD_RETRIES).then(|| Duration::from_secs(1 << attempt))
+}
+
+/// Upper bound for the auto-refresh delay after repeated failures.
+const AUTO_REFRESH_MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// How often the details overlay re-checks a task that is still running.
+pub const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Stop following a running task after this long to bound background work.
+pub const FOLLOW_MAX_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Timer for periodic task list refreshes, enabled by setting
+/// `CODEX_CLOUD_TASKS_REFRESH_SECS` to a positive number of seconds.
+///
+/// The countdown only runs while [`App::auto_refresh_blocked`] is false; it
+/// restarts from the full delay once the list is idle again. Consecutive
+/// failures double the delay, up to [`AUTO_REFRESH_MAX_BACKOFF`].
+#[derive(Clone, Debug)]
+pub struct AutoRefresh {
+    interval: Duration,
+    failures: u32,
+    next_due: Option<Instant>,
+}
+
+impl AutoRefresh {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            failures: 0,
+            next_due: None,
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let secs: u64 = std::env::var("CODEX_CLOUD_TASKS_REFRESH_SECS")
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        (secs > 0).then(|| Self::new(Duration::from_secs(secs)))
+    }
+
+    /// Delay before the next refresh, including failure backoff.
+    pub fn delay(&self) -> Duration {
+        let cap = AUTO_REFRESH_MAX_BACKOFF.max(self.interval);
+        let factor = 1u32.checked_shl(self.failures).unwrap_or(u32::MAX);
+        self.interval.saturating_mul(factor).min(cap)
+    }
+
+    /// Advance the timer. Returns true when a refresh should start now.
+    pub fn poll(&mut self, blocked: bool, now: Instant) -> bool {
+        if blocked {
+            self.next_due = None;
+            return false;
+        }
+        match self.next_due {
+            None => {
+                self.next_due = Some(now + self.delay());
+                false
+            }
+            Some(due) if now >= due => {
+                self.next_due = None;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Record the outcome of a list load to adjust the backoff.
+    pub fn record_result(&mut self, ok: bool) {
+        self.failures = if ok {
+            0
+        } else {
+            self.failures.saturating_add(1)
+        };
+    }
+
+    /// Time until the next refresh, or None while paused.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.next_due.map(|due| due.saturating_duration_since(now))
+    }
 }
 
 pub async fn load_tasks(
@@ -129,6 +474,44 @@ pub async fn load_tasks(
     Ok(filtered)
 }
 
+/// Forward `id`'s diff to `tx` as [`AppEvent::DiffChunkLoaded`] events,
+/// followed by [`AppEvent::DiffStreamFinished`]. Returns whether the task had
+/// a diff; an error before the first piece is returned instead of sent.
+pub async fn stream_diff(
+    backend: &dyn CloudBackend,
+    id: TaskId,
+    tx: &tokio::sync::mpsc::UnboundedSender<AppEvent>,
+) -> codex_cloud_tasks_client::Result<bool> {
+    use tokio_stream::StreamExt;
+
+    let mut stream = backend.stream_task_diff(id.clone());
+    let mut received = false;
+    let mut error = None;
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(chunk) => {
+                received = true;
+                let evt = AppEvent::DiffChunkLoaded {
+                    id: id.clone(),
+                    chunk,
+                };
+                if tx.send(evt).is_err() {
+                    return Ok(true);
+                }
+            }
+            Err(e) if !received => return Err(e),
+            Err(e) => {
+                error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+    if received {
+        let _ = tx.send(AppEvent::DiffStreamFinished { id, error });
+    }
+    Ok(received)
+}
+
 pub struct DiffOverlay {
     pub title: String,
     pub task_id: TaskId,
@@ -143,6 +526,31 @@ pub struct DiffOverlay {
     pub base_turn_id: Option<String>,
     pub sibling_turn_ids: Vec<String>,
     pub attempt_total_hint: Option<usize>,
+    /// Background task following output of an in-progress base attempt.
+    pub message_stream: Option<tokio::task::JoinHandle<()>>,
+    /// True while the `/` search input in the bottom bar has focus.
+    pub search_editing: bool,
+    /// Background poll waiting for a still-running task to produce a diff.
+    pub follow_task: Option<tokio::task::JoinHandle<()>>,
+    /// Latest status (and event text) reported while following.
+    pub follow_status: Option<String>,
+    /// Agent plan shown above the details content, when the task has one.
+    pub plan: Option<codex_protocol::plan_tool::UpdatePlanArgs>,
+    /// Whether the plan section lists its steps or only its summary line.
+    pub plan_expanded: bool,
+    /// True while the base attempt's diff is still streaming in.
+    pub diff_loading: bool,
+}
+
+impl Drop for DiffOverlay {
+    fn drop(&mut self) {
+        if let Some(handle) = self.message_stream.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.follow_task.take() {
+            handle.abort();
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -153,6 +561,7 @@ pub struct AttemptView {
     pub diff_lines: Vec<String>,
     pub text_lines: Vec<String>,
     pub prompt: Option<String>,
+    pub messages: Vec<String>,
     pub diff_raw: Option<String>,
 }
 
@@ -184,6 +593,13 @@ impl DiffOverlay {
             base_turn_id: None,
             sibling_turn_ids: Vec::new(),
             attempt_total_hint,
+            message_stream: None,
+            search_editing: false,
+            follow_task: None,
+            follow_status: None,
+            plan: None,
+            plan_expanded: true,
+            diff_loading: false,
         }
     }
 
@@ -237,8 +653,60 @@ impl DiffOverlay {
         true
     }
 
-    pub fn current_can_apply(&self) -> bool {
+    /// True when the diff view is active.
+    pub fn showing_diff(&self) -> bool {
         matches!(self.current_view, DetailView::Diff)
+    }
+
+    /// Add a streamed piece of the base attempt's diff. The first piece
+    /// replaces any earlier diff and switches to the diff view.
+    pub fn append_diff_chunk(&mut self, chunk: &str) {
+        let starting = !self.diff_loading;
+        self.diff_loading = true;
+        let base = self.base_attempt_mut();
+        if starting {
+            base.diff_raw = Some(String::new());
+            base.diff_lines.clear();
+        }
+        base.diff_raw
+            .get_or_insert_with(String::new)
+            .push_str(chunk);
+        base.diff_lines.extend(chunk.lines().map(str::to_string));
+
+        if starting {
+            self.base_can_apply = false;
+            self.current_view = DetailView::Diff;
+            self.apply_selection_to_fields();
+        } else if self.selected_attempt == 0 && self.showing_diff() {
+            self.diff_lines.extend(chunk.lines().map(str::to_string));
+            self.sd.append_chunk(chunk);
+        }
+    }
+
+    /// Mark the streamed diff complete. A diff that failed part way stays on
+    /// screen but cannot be applied.
+    pub fn finish_diff_stream(&mut self, failed: bool) {
+        self.diff_loading = false;
+        self.base_can_apply = !failed;
+        if failed {
+            self.base_attempt_mut().diff_raw = None;
+        }
+    }
+
+    /// The current attempt's raw diff, once it has finished loading.
+    pub fn loaded_diff(&self) -> Option<&str> {
+        if self.diff_loading && self.selected_attempt == 0 {
+            return None;
+        }
+        self.current_attempt()
+            .and_then(|attempt| attempt.diff_raw.as_deref())
+            .filter(|diff| !diff.is_empty())
+    }
+
+    pub fn current_can_apply(&self) -> bool {
+        let loading = self.diff_loading && self.selected_attempt == 0;
+        !loading
+            && matches!(self.current_view, DetailView::Diff)
             && self
                 .current_attempt()
                 .and_then(|attempt| attempt.diff_raw.as_ref())
@@ -298,16 +766,21 @@ pub enum AppEvent {
         env: Option<String>,
         result: anyhow::Result<Vec<TaskSummary>>,
     },
+    /// Backoff timer for a failed list load elapsed; dropped if `generation`
+    /// or `env` no longer match the current list.
+    RetryTasksLoad {
+        env: Option<String>,
+        generation: u64,
+    },
     // Background diff summary events were planned; removed for now to keep code minimal.
     /// Autodetection of a likely environment id finished
     EnvironmentAutodetected(anyhow::Result<crate::env_detect::AutodetectSelection>),
     /// Background completion of environment list fetch
     EnvironmentsLoaded(anyhow::Result<Vec<EnvironmentRow>>),
-    DetailsDiffLoaded {
-        id: TaskId,
-        title: String,
-        diff: String,
-    },
+    /// The next piece of the details overlay's diff.
+    DiffChunkLoaded { id: TaskId, chunk: String },
+    /// The diff stream ended; `error` is set when it failed part way.
+    DiffStreamFinished { id: TaskId, error: Option<String> },
     DetailsMessagesLoaded {
         id: TaskId,
         title: String,
@@ -318,6 +791,22 @@ pub enum AppEvent {
         attempt_placement: Option<i64>,
         attempt_status: codex_cloud_tasks_client::AttemptStatus,
     },
+    /// A new assistant message streamed in for an in-progress task.
+    DetailsMessageStreamed { id: TaskId, message: String },
+    /// The task behind the details overlay has not finished yet.
+    DetailsStillRunning {
+        id: TaskId,
+        status: String,
+        latest_event: Option<String>,
+    },
+    /// The agent's latest plan for the task behind the details overlay.
+    DetailsPlanLoaded {
+        id: TaskId,
+        plan: codex_protocol::plan_tool::UpdatePlanArgs,
+    },
+    /// Following a running task stopped; `timed_out` when it gave up after
+    /// [`FOLLOW_MAX_DURATION`].
+    DetailsFollowEnded { id: TaskId, timed_out: bool },
     DetailsFailed {
         id: TaskId,
         title: String,
@@ -327,6 +816,24 @@ pub enum AppEvent {
         id: TaskId,
         attempts: Vec<codex_cloud_tasks_client::TurnAttempt>,
     },
+    /// Diff stats computed in the background for a listed task; None when
+    /// the diff could not be fetched.
+    DiffSummaryLoaded {
+        generation: u64,
+        id: TaskId,
+        updated_at: chrono::DateTime<chrono::Utc>,
+        summary: Option<codex_cloud_tasks_client::DiffSummary>,
+    },
+    /// Watched-task poll interval elapsed.
+    WatchPollDue,
+    /// Background list load for the watched-task poller.
+    WatchedTasksLoaded(anyhow::Result<Vec<TaskSummary>>),
+    /// Diff fetched for an apply started from the task list.
+    ApplyDiffLoaded {
+        id: TaskId,
+        title: String,
+        diff: Option<String>,
+    },
     /// Background completion of new task submission
     NewTaskSubmitted(Result<codex_cloud_tasks_client::CreatedTask, String>),
     /// Background completion of apply preflight when opening modal or on demand
@@ -354,6 +861,8 @@ mod tests {
     struct FakeBackend {
         // maps env key to titles
         by_env: std::collections::HashMap<Option<String>, Vec<&'static str>>,
+        // pieces returned by `stream_task_diff`
+        diff_chunks: Vec<&'static str>,
     }
 
     #[async_trait::async_trait]
@@ -394,6 +903,11 @@ mod tests {
             ))
         }
 
+        fn stream_task_diff(&self, _id: TaskId) -> codex_cloud_tasks_client::TaskDiffStream<'_> {
+            let chunks = self.diff_chunks.iter().map(|chunk| Ok(chunk.to_string()));
+            Box::pin(tokio_stream::iter(chunks.collect::<Vec<_>>()))
+        }
+
         async fn get_task_messages(
             &self,
             _id: TaskId,
@@ -446,9 +960,7 @@ mod tests {
             &self,
             _env_id: &str,
             _prompt: &str,
-            _git_ref: &str,
-            _qa_mode: bool,
-            _best_of_n: usize,
+            _options: codex_cloud_tasks_client::CreateTaskOptions,
         ) -> codex_cloud_tasks_client::Result<codex_cloud_tasks_client::CreatedTask> {
             Err(codex_cloud_tasks_client::CloudTaskError::Unimplemented(
                 "not used in test",
@@ -463,7 +975,10 @@ mod tests {
         by_env.insert(None, vec!["root-1", "root-2"]);
         by_env.insert(Some("env-A".to_string()), vec!["A-1"]);
         by_env.insert(Some("env-B".to_string()), vec!["B-1", "B-2", "B-3"]);
-        let backend = FakeBackend { by_env };
+        let backend = FakeBackend {
+            by_env,
+            diff_chunks: Vec::new(),
+        };
 
         // Act + Assert
         let root = load_tasks(&backend, None).await.unwrap();
@@ -478,4 +993,274 @@ mod tests {
         assert_eq!(b.len(), 3);
         assert_eq!(b[2].title, "B-3");
     }
+
+    #[tokio::test]
+    async fn streamed_diff_arrives_as_incremental_events() {
+        let backend = FakeBackend {
+            by_env: std::collections::HashMap::new(),
+            diff_chunks: vec![
+                "diff --git a/a b/a\n@@ -1 +1 @@\n-x\n+y\n",
+                "diff --git a/b b/b\n@@ -1 +1 @@\n-p\n+q\n",
+            ],
+        };
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let id = TaskId("T-1".to_string());
+        assert!(stream_diff(&backend, id.clone(), &tx).await.unwrap());
+        drop(tx);
+
+        let mut overlay = DiffOverlay::new(id.clone(), "Task".to_string(), None);
+        let mut chunks = 0;
+        while let Some(evt) = rx.recv().await {
+            match evt {
+                AppEvent::DiffChunkLoaded { id: got, chunk } => {
+                    assert_eq!(got, id);
+                    chunks += 1;
+                    overlay.append_diff_chunk(&chunk);
+                    assert!(overlay.diff_loading);
+                    assert!(!overlay.current_can_apply());
+                    assert_eq!(overlay.diff_lines.len(), 4 * chunks);
+                }
+                AppEvent::DiffStreamFinished { error, .. } => {
+                    assert_eq!(error, None);
+                    overlay.finish_diff_stream(false);
+                }
+                _ => panic!("unexpected event"),
+            }
+        }
+        assert_eq!(chunks, 2);
+        assert!(overlay.showing_diff());
+        assert!(overlay.current_can_apply());
+        overlay.sd.set_width(80);
+        assert_eq!(overlay.sd.wrapped_lines().len(), 8);
+    }
+
+    fn app_with_draft(draft: &str) -> App {
+        let mut page = crate::new_task::NewTaskPage::new(Some("env-A".to_string()), 1);
+        if !draft.is_empty() {
+            page.composer.handle_paste(draft.to_string());
+        }
+        let mut app = App::new();
+        app.new_task = Some(page);
+        app
+    }
+
+    #[test]
+    fn unsaved_content_tracks_composer_text() {
+        assert!(!app_with_draft("").new_task.unwrap().has_unsaved_content());
+        assert!(
+            app_with_draft("fix the flaky test")
+                .new_task
+                .unwrap()
+                .has_unsaved_content()
+        );
+    }
+
+    #[test]
+    fn closing_empty_new_task_does_not_confirm() {
+        let mut app = app_with_draft("");
+        assert!(app.request_close_new_task());
+        assert!(app.new_task.is_none());
+        assert!(!app.confirm_discard_draft);
+    }
+
+    #[test]
+    fn closing_draft_requires_confirmation() {
+        let mut app = app_with_draft("fix the flaky test");
+        assert!(!app.request_close_new_task());
+        assert!(app.new_task.is_some());
+        assert!(app.confirm_discard_draft);
+        assert_eq!(app.status, "Discard draft? y/n");
+
+        app.keep_draft();
+        assert!(app.new_task.is_some());
+        assert!(!app.confirm_discard_draft);
+
+        assert!(!app.request_close_new_task());
+        app.discard_draft();
+        assert!(app.new_task.is_none());
+        assert!(!app.confirm_discard_draft);
+    }
+
+    #[test]
+    fn auto_refresh_fires_after_interval_and_pauses_when_blocked() {
+        let mut auto = AutoRefresh::new(Duration::from_secs(10));
+        let start = Instant::now();
+        assert!(!auto.poll(false, start));
+        assert_eq!(auto.remaining(start), Some(Duration::from_secs(10)));
+        assert!(!auto.poll(false, start + Duration::from_secs(9)));
+
+        // Opening an overlay pauses the countdown; closing restarts it in full.
+        assert!(!auto.poll(true, start + Duration::from_secs(9)));
+        assert_eq!(auto.remaining(start + Duration::from_secs(9)), None);
+        assert!(!auto.poll(false, start + Duration::from_secs(20)));
+        assert!(!auto.poll(false, start + Duration::from_secs(29)));
+        assert!(auto.poll(false, start + Duration::from_secs(30)));
+        assert_eq!(auto.remaining(start + Duration::from_secs(30)), None);
+    }
+
+    #[test]
+    fn auto_refresh_backs_off_on_failure_up_to_cap() {
+        let mut auto = AutoRefresh::new(Duration::from_secs(60));
+        let delays: Vec<Duration> = (0..5)
+            .map(|_| {
+                auto.record_result(false);
+                auto.delay()
+            })
+            .collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_secs(120),
+                Duration::from_secs(240),
+                Duration::from_secs(300),
+                Duration::from_secs(300),
+                Duration::from_secs(300),
+            ]
+        );
+        auto.record_result(true);
+        assert_eq!(auto.delay(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn load_retry_delay_backs_off_exponentially() {
+        let schedule: Vec<Option<Duration>> =
+            (0..=MAX_LOAD_RETRIES).map(load_retry_delay).collect();
+        assert_eq!(
+            schedule,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(8)),
+                None,
+            ]
+        );
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn ctrl_c() -> Msg {
+        Msg::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+    }
+
+    #[test]
+    fn ctrl_c_in_env_modal_closes_only_the_modal() {
+        let mut app = app_with_draft("fix the flaky test");
+        app.env_modal = Some(EnvModalState::default());
+
+        assert_eq!(update(&mut app, ctrl_c()), vec![Effect::Redraw]);
+        assert!(app.env_modal.is_none());
+        assert!(app.new_task.is_some());
+        assert!(!app.confirm_discard_draft);
+
+        assert_eq!(update(&mut app, ctrl_c()), vec![Effect::Redraw]);
+        assert!(app.confirm_discard_draft);
+    }
+
+    #[test]
+    fn ctrl_c_and_q_on_the_list_quit() {
+        let mut app = App::new();
+        assert_eq!(update(&mut app, ctrl_c()), vec![Effect::Quit]);
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('q'))),
+            vec![Effect::Quit]
+        );
+    }
+
+    #[test]
+    fn refresh_starts_a_new_list_generation() {
+        let mut app = App::new();
+        app.load_retries = 3;
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('r'))),
+            vec![Effect::Redraw, Effect::LoadTasks]
+        );
+        assert!(app.refresh_inflight);
+        assert_eq!(app.list_generation, 1);
+        assert_eq!(app.load_retries, 0);
+    }
+
+    #[test]
+    fn env_selector_fetches_environments_only_once() {
+        let mut app = App::new();
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('o'))),
+            vec![Effect::Redraw, Effect::LoadEnvironments]
+        );
+        assert!(app.env_modal.is_some() && app.env_loading);
+
+        app.env_modal = None;
+        app.environments.push(EnvironmentRow::default());
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('o'))),
+            vec![Effect::Redraw]
+        );
+    }
+
+    #[test]
+    fn apply_on_a_task_without_a_diff_sets_status_and_spawns_nothing() {
+        let mut app = App::new();
+        app.tasks.push(TaskSummary {
+            id: TaskId("T-1".to_string()),
+            title: "no changes".to_string(),
+            status: codex_cloud_tasks_client::TaskStatus::Ready,
+            updated_at: Utc::now(),
+            environment_id: None,
+            environment_label: None,
+            summary: codex_cloud_tasks_client::DiffSummary::default(),
+            is_review: false,
+            attempt_total: Some(1),
+        });
+
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('a'))),
+            vec![Effect::LoadApplyDiff {
+                id: TaskId("T-1".to_string()),
+                title: "no changes".to_string(),
+            }]
+        );
+        let effects = update(
+            &mut app,
+            Msg::ApplyDiffLoaded {
+                id: TaskId("T-1".to_string()),
+                title: "no changes".to_string(),
+                diff: None,
+            },
+        );
+        assert_eq!(effects, vec![Effect::Redraw]);
+        assert_eq!(app.status, "No diff available to apply");
+        assert!(app.apply_modal.is_none());
+        assert!(!app.apply_preflight_inflight);
+    }
+
+    #[test]
+    fn apply_waits_for_the_running_preflight() {
+        let mut app = App::new();
+        app.apply_preflight_inflight = true;
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('a'))),
+            vec![Effect::Redraw]
+        );
+        assert_eq!(
+            app.status,
+            "Finish the current apply/preflight before starting another."
+        );
+    }
+
+    #[test]
+    fn keys_for_views_outside_update_are_passed_through() {
+        let mut app = App::new();
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            update(&mut app, Msg::Key(enter)),
+            vec![Effect::Unhandled(enter)]
+        );
+
+        let mut app = app_with_draft("");
+        let r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(update(&mut app, Msg::Key(r)), vec![Effect::Unhandled(r)]);
+        assert!(!app.refresh_inflight);
+    }
 }
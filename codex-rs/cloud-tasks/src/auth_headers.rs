@@ -0,0 +1,247 @@
+//! Headers for direct ChatGPT backend requests (environment listing and
+//! autodetect): `User-Agent`, plus `Authorization` and `ChatGPT-Account-Id`
+//! when signed in.
+//!
+//! Credentials are read once and reused until shortly before the token's
+//! `exp` claim, so repeated environment fetches do not re-read `codex_home`
+//! and re-parse the JWT each time.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use base64::Engine as _;
+use reqwest::header::HeaderMap;
+use tokio::sync::RwLock;
+
+use crate::util::extract_chatgpt_account_id;
+use crate::util::set_user_agent_suffix;
+
+/// Refetch this long before the token expires.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+/// How long to trust a token that carries no `exp` claim.
+const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// A bearer token plus the account id to send with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Credentials {
+    pub token: String,
+    pub account_id: Option<String>,
+}
+
+type CredentialsFuture = Pin<Box<dyn Future<Output = anyhow::Result<Option<Credentials>>> + Send>>;
+
+/// Produces fresh credentials; `Ok(None)` means not signed in.
+pub type TokenSource = Arc<dyn Fn() -> CredentialsFuture + Send + Sync>;
+
+struct CachedCredentials {
+    credentials: Credentials,
+    refresh_at: SystemTime,
+}
+
+/// Builds backend headers, caching credentials for their validity window.
+pub struct AuthHeaders {
+    user_agent: String,
+    source: TokenSource,
+    cached: Arc<RwLock<Option<CachedCredentials>>>,
+}
+
+impl AuthHeaders {
+    pub fn new(user_agent: String, source: TokenSource) -> Self {
+        Self {
+            user_agent,
+            source,
+            cached: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub async fn headers(&self) -> anyhow::Result<HeaderMap> {
+        let credentials = self.credentials().await?;
+        Ok(cloud_headers(
+            &self.user_agent,
+            credentials.as_ref().map(|c| c.token.as_str()),
+            credentials.as_ref().and_then(|c| c.account_id.as_deref()),
+        ))
+    }
+
+    async fn credentials(&self) -> anyhow::Result<Option<Credentials>> {
+        if let Some(cached) = self.cached.read().await.as_ref()
+            && SystemTime::now() < cached.refresh_at
+        {
+            return Ok(Some(cached.credentials.clone()));
+        }
+        let fresh = (self.source)().await?;
+        // Signed-out results are not cached so a later `codex login` is picked up.
+        *self.cached.write().await = fresh.clone().map(|credentials| CachedCredentials {
+            refresh_at: refresh_deadline(&credentials.token),
+            credentials,
+        });
+        Ok(fresh)
+    }
+}
+
+/// Headers for the signed-in user, shared across the whole process.
+pub async fn build_backend_headers() -> anyhow::Result<HeaderMap> {
+    static SHARED: OnceLock<AuthHeaders> = OnceLock::new();
+    SHARED
+        .get_or_init(|| {
+            set_user_agent_suffix("codex_cloud_tasks_tui");
+            AuthHeaders::new(
+                codex_core::default_client::get_codex_user_agent(),
+                Arc::new(|| Box::pin(load_credentials())),
+            )
+        })
+        .headers()
+        .await
+}
+
+/// Read the current ChatGPT login from `codex_home`.
+async fn load_credentials() -> anyhow::Result<Option<Credentials>> {
+    let home = codex_core::config::find_codex_home()?;
+    let Some(auth) = codex_login::AuthManager::new(home, false).auth() else {
+        return Ok(None);
+    };
+    let token = match auth.get_token().await {
+        Ok(token) if !token.is_empty() => token,
+        _ => return Ok(None),
+    };
+    let account_id = auth
+        .get_account_id()
+        .or_else(|| extract_chatgpt_account_id(&token));
+    Ok(Some(Credentials { token, account_id }))
+}
+
+/// When to stop reusing `token`: [`REFRESH_MARGIN`] before its `exp` claim,
+/// or [`DEFAULT_TOKEN_TTL`] from now when it has none.
+fn refresh_deadline(token: &str) -> SystemTime {
+    match token_expiry(token) {
+        Some(exp) => exp.checked_sub(REFRESH_MARGIN).unwrap_or(UNIX_EPOCH),
+        None => SystemTime::now() + DEFAULT_TOKEN_TTL,
+    }
+}
+
+fn token_expiry(token: &str) -> Option<SystemTime> {
+    let payload_b64 = token.split('.').nth(1)?;
+    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .ok()?;
+    let payload: serde_json::Value = serde_json::from_slice(&payload_bytes).ok()?;
+    let exp = payload.get("exp")?.as_u64()?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(exp))
+}
+
+fn cloud_headers(user_agent: &str, token: Option<&str>, account_id: Option<&str>) -> HeaderMap {
+    use reqwest::header::AUTHORIZATION;
+    use reqwest::header::HeaderName;
+    use reqwest::header::HeaderValue;
+    use reqwest::header::USER_AGENT;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        USER_AGENT,
+        HeaderValue::from_str(user_agent).unwrap_or(HeaderValue::from_static("codex-cli")),
+    );
+    if let Some(tok) = token
+        && let Ok(hv) = HeaderValue::from_str(&format!("Bearer {tok}"))
+    {
+        headers.insert(AUTHORIZATION, hv);
+    }
+    if let Some(acc) = account_id
+        && let Ok(name) = HeaderName::from_bytes(b"ChatGPT-Account-Id")
+        && let Ok(hv) = HeaderValue::from_str(acc)
+    {
+        headers.insert(name, hv);
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    fn jwt_expiring_in(secs: u64) -> String {
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock after epoch")
+            .as_secs()
+            + secs;
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(serde_json::json!({ "exp": exp }).to_string());
+        format!("header.{payload}.sig")
+    }
+
+    /// Token source that hands out `token` and counts how often it was asked.
+    fn counting_source(token: String) -> (TokenSource, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let source: TokenSource = Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let token = token.clone();
+            Box::pin(async move {
+                Ok(Some(Credentials {
+                    token,
+                    account_id: Some("acc-1".to_string()),
+                }))
+            })
+        });
+        (source, calls)
+    }
+
+    #[test]
+    fn cloud_headers_include_user_agent_and_auth() {
+        let headers = cloud_headers("codex-test/1.0", Some("tok"), Some("acc-1"));
+        assert_eq!(headers["user-agent"], "codex-test/1.0");
+        assert_eq!(headers["authorization"], "Bearer tok");
+        assert_eq!(headers["chatgpt-account-id"], "acc-1");
+
+        let anonymous = cloud_headers("codex-test/1.0", None, None);
+        assert_eq!(anonymous.len(), 1);
+        assert_eq!(anonymous["user-agent"], "codex-test/1.0");
+    }
+
+    #[tokio::test]
+    async fn valid_token_is_read_once() {
+        let token = jwt_expiring_in(3600);
+        let (source, calls) = counting_source(token.clone());
+        let auth = AuthHeaders::new("codex-test/1.0".to_string(), source);
+
+        let first = auth.headers().await.expect("headers");
+        let second = auth.headers().await.expect("headers");
+        assert_eq!(first["authorization"], format!("Bearer {token}").as_str());
+        assert_eq!(second["chatgpt-account-id"], "acc-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn token_inside_refresh_margin_is_refetched() {
+        let (source, calls) = counting_source(jwt_expiring_in(30));
+        let auth = AuthHeaders::new("codex-test/1.0".to_string(), source);
+
+        auth.headers().await.expect("headers");
+        auth.headers().await.expect("headers");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn signed_out_requests_are_anonymous_and_not_cached() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let source: TokenSource = Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(None) })
+        });
+        let auth = AuthHeaders::new("codex-test/1.0".to_string(), source);
+
+        let headers = auth.headers().await.expect("headers");
+        assert_eq!(headers.len(), 1);
+        auth.headers().await.expect("headers");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
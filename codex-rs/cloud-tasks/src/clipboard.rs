@@ -0,0 +1,159 @@
+//! Copying task diffs and URLs to the system clipboard.
+//!
+//! The terminal clipboard (OSC 52) is preferred because it also works over
+//! SSH. The platform clipboard is used when writing the escape sequence fails,
+//! or when the text is over [`OSC52_MAX_BYTES`] and would otherwise be cut off.
+
+use std::io::Write;
+
+use base64::Engine as _;
+
+/// Largest payload sent through OSC 52. Many terminals drop longer sequences.
+pub const OSC52_MAX_BYTES: usize = 100 * 1024;
+
+/// Encoded characters written per chunk. A multiple of 4, so every chunk is
+/// complete base64 on its own.
+const OSC52_CHUNK_LEN: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyOutcome {
+    /// True when only the first [`OSC52_MAX_BYTES`] were copied.
+    pub truncated: bool,
+}
+
+/// Base64 of `text` (capped at `max_bytes` on a char boundary) in chunks of
+/// at most [`OSC52_CHUNK_LEN`] characters. Concatenated, the chunks are the
+/// encoding of the kept text. The flag reports whether anything was cut.
+pub fn osc52_chunks(text: &str, max_bytes: usize) -> (Vec<String>, bool) {
+    let mut end = text.len().min(max_bytes);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let chunk_bytes = OSC52_CHUNK_LEN / 4 * 3;
+    let chunks = text.as_bytes()[..end]
+        .chunks(chunk_bytes)
+        .map(|chunk| base64::engine::general_purpose::STANDARD.encode(chunk))
+        .collect();
+    (chunks, end < text.len())
+}
+
+/// Write `text` to `out` as an OSC 52 "set clipboard" sequence.
+pub fn write_osc52(out: &mut impl Write, text: &str) -> std::io::Result<CopyOutcome> {
+    let (chunks, truncated) = osc52_chunks(text, OSC52_MAX_BYTES);
+    out.write_all(b"\x1b]52;c;")?;
+    for chunk in chunks {
+        out.write_all(chunk.as_bytes())?;
+    }
+    out.write_all(b"\x07")?;
+    out.flush()?;
+    Ok(CopyOutcome { truncated })
+}
+
+/// Copy `text` using OSC 52, falling back to the platform clipboard.
+pub fn copy_to_clipboard(text: &str) -> Result<CopyOutcome, String> {
+    if text.len() > OSC52_MAX_BYTES && platform_copy(text).is_ok() {
+        return Ok(CopyOutcome { truncated: false });
+    }
+    match write_osc52(&mut std::io::stdout(), text) {
+        Ok(outcome) => Ok(outcome),
+        Err(osc_err) => platform_copy(text)
+            .map(|()| CopyOutcome { truncated: false })
+            .map_err(|err| format!("{err} (terminal clipboard: {osc_err})")),
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn platform_copy(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut cb| cb.set_text(text.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "android")]
+fn platform_copy(_text: &str) -> Result<(), String> {
+    Err("platform clipboard is unsupported on Android".to_string())
+}
+
+/// Status line text for copying the details overlay's diff. Nothing is copied
+/// when no diff has been loaded.
+pub fn copy_diff(
+    diff: Option<&str>,
+    copy: impl FnOnce(&str) -> Result<CopyOutcome, String>,
+) -> String {
+    let Some(diff) = diff else {
+        return "No diff loaded to copy.".to_string();
+    };
+    let lines = diff.lines().count();
+    match copy(diff) {
+        Ok(CopyOutcome { truncated: false }) => format!("Copied {lines} lines"),
+        Ok(CopyOutcome { truncated: true }) => format!(
+            "Copied {lines} lines, truncated to {} KB by the terminal clipboard",
+            OSC52_MAX_BYTES / 1024
+        ),
+        Err(err) => format!("Copy failed: {err}"),
+    }
+}
+
+/// Status line text for copying a task URL.
+pub fn copy_url(url: &str, copy: impl FnOnce(&str) -> Result<CopyOutcome, String>) -> String {
+    match copy(url) {
+        Ok(_) => format!("Copied {url}"),
+        Err(err) => format!("Copy failed: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn decode(chunks: &[String]) -> String {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(chunks.concat())
+            .expect("valid base64");
+        String::from_utf8(bytes).expect("utf-8")
+    }
+
+    #[test]
+    fn chunks_are_independent_base64_of_the_whole_text() {
+        let text = "diff --git a/x b/x\n+é\n".repeat(800);
+        let (chunks, truncated) = osc52_chunks(&text, OSC52_MAX_BYTES);
+        assert!(!truncated);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= OSC52_CHUNK_LEN);
+            assert_eq!(chunk.len() % 4, 0);
+            base64::engine::general_purpose::STANDARD
+                .decode(chunk)
+                .expect("each chunk decodes");
+        }
+        assert_eq!(decode(&chunks), text);
+    }
+
+    #[test]
+    fn payload_over_the_cap_is_truncated_on_a_char_boundary() {
+        // Two-byte chars, so a cap of 5 bytes must back off to 4.
+        let (chunks, truncated) = osc52_chunks("ééé", 5);
+        assert!(truncated);
+        assert_eq!(decode(&chunks), "éé");
+
+        let mut out = Vec::new();
+        let outcome = write_osc52(&mut out, &"x".repeat(OSC52_MAX_BYTES + 1)).expect("write");
+        assert!(outcome.truncated);
+        let seq = String::from_utf8(out).expect("ascii");
+        assert!(seq.starts_with("\x1b]52;c;"));
+        assert!(seq.ends_with('\x07'));
+    }
+
+    #[test]
+    fn copy_diff_reports_lines_and_skips_missing_diff() {
+        let status = copy_diff(None, |_| panic!("nothing should be copied"));
+        assert_eq!(status, "No diff loaded to copy.");
+
+        let status = copy_diff(Some("a\nb\nc\n"), |_| Ok(CopyOutcome { truncated: false }));
+        assert_eq!(status, "Copied 3 lines");
+
+        let status = copy_diff(Some("a\n"), |_| Err("no clipboard".to_string()));
+        assert_eq!(status, "Copy failed: no clipboard");
+    }
+}
@@ -1,6 +1,10 @@
 use reqwest::header::CONTENT_TYPE;
 use reqwest::header::HeaderMap;
 use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 use tracing::info;
 use tracing::warn;
 
@@ -21,10 +25,102 @@ pub struct AutodetectSelection {
     pub label: Option<String>,
 }
 
+/// How long environment lookups are reused before hitting the network again.
+const ENV_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cache key: the backend base URL plus the ChatGPT account id (if any).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    base_url: String,
+    account_id: Option<String>,
+}
+
+impl CacheKey {
+    fn new(base_url: &str, headers: &HeaderMap) -> Self {
+        let account_id = headers
+            .get("ChatGPT-Account-Id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+        Self {
+            base_url: base_url.to_string(),
+            account_id,
+        }
+    }
+}
+
+/// Small in-memory cache whose entries expire after a fixed TTL.
+struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: HashMap<K, (Instant, V)>,
+}
+
+impl<K: std::hash::Hash + Eq, V: Clone> TtlCache<K, V> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&self, key: &K, now: Instant) -> Option<V> {
+        let (stored_at, value) = self.entries.get(key)?;
+        (now.saturating_duration_since(*stored_at) < self.ttl).then(|| value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V, now: Instant) {
+        self.entries.insert(key, (now, value));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+type AutodetectCache = TtlCache<(CacheKey, Option<String>), AutodetectSelection>;
+type EnvironmentsCache = TtlCache<CacheKey, Vec<crate::app::EnvironmentRow>>;
+
+static AUTODETECT_CACHE: LazyLock<Mutex<AutodetectCache>> =
+    LazyLock::new(|| Mutex::new(TtlCache::new(ENV_CACHE_TTL)));
+static ENVIRONMENTS_CACHE: LazyLock<Mutex<EnvironmentsCache>> =
+    LazyLock::new(|| Mutex::new(TtlCache::new(ENV_CACHE_TTL)));
+
+/// Drop cached environment lookups so the next call refetches from the backend.
+pub fn invalidate_cache() {
+    if let Ok(mut cache) = AUTODETECT_CACHE.lock() {
+        cache.clear();
+    }
+    if let Ok(mut cache) = ENVIRONMENTS_CACHE.lock() {
+        cache.clear();
+    }
+}
+
+/// Autodetect the environment for the current repo, reusing a recent result
+/// for the same backend and account when available.
 pub async fn autodetect_environment_id(
     base_url: &str,
     headers: &HeaderMap,
     desired_label: Option<String>,
+) -> anyhow::Result<AutodetectSelection> {
+    let key = (CacheKey::new(base_url, headers), desired_label.clone());
+    if let Some(hit) = AUTODETECT_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&key, Instant::now()))
+    {
+        crate::append_error_log(format!("env: autodetect cache hit: {}", hit.id));
+        return Ok(hit);
+    }
+    let selection = fetch_autodetect_environment_id(base_url, headers, desired_label).await?;
+    if let Ok(mut cache) = AUTODETECT_CACHE.lock() {
+        cache.insert(key, selection.clone(), Instant::now());
+    }
+    Ok(selection)
+}
+
+async fn fetch_autodetect_environment_id(
+    base_url: &str,
+    headers: &HeaderMap,
+    desired_label: Option<String>,
 ) -> anyhow::Result<AutodetectSelection> {
     // 1) Try repo-specific environments based on local git origins (GitHub only, like VSCode)
     let origins = get_git_origins();
@@ -251,10 +347,31 @@ fn parse_owner_repo(url: &str) -> Option<(String, String)> {
 }
 
 /// List environments for the current repo(s) with a fallback to the global list.
-/// Returns a de-duplicated, sorted set suitable for the TUI modal.
+/// Returns a de-duplicated, sorted set suitable for the TUI modal. Results are
+/// cached per backend and account for [`ENV_CACHE_TTL`].
 pub async fn list_environments(
     base_url: &str,
     headers: &HeaderMap,
+) -> anyhow::Result<Vec<crate::app::EnvironmentRow>> {
+    let key = CacheKey::new(base_url, headers);
+    if let Some(hit) = ENVIRONMENTS_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&key, Instant::now()))
+    {
+        info!("env_tui: using cached environments ({} envs)", hit.len());
+        return Ok(hit);
+    }
+    let rows = fetch_environments(base_url, headers).await?;
+    if let Ok(mut cache) = ENVIRONMENTS_CACHE.lock() {
+        cache.insert(key, rows.clone(), Instant::now());
+    }
+    Ok(rows)
+}
+
+async fn fetch_environments(
+    base_url: &str,
+    headers: &HeaderMap,
 ) -> anyhow::Result<Vec<crate::app::EnvironmentRow>> {
     let mut map: HashMap<String, crate::app::EnvironmentRow> = HashMap::new();
 
@@ -359,3 +476,58 @@ pub async fn list_environments(
     });
     Ok(rows)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn key(base_url: &str, account_id: Option<&str>) -> CacheKey {
+        CacheKey {
+            base_url: base_url.to_string(),
+            account_id: account_id.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn cache_returns_value_within_ttl() {
+        let mut cache = TtlCache::new(Duration::from_secs(60));
+        let start = Instant::now();
+        cache.insert(key("https://example.com", Some("acc")), 7, start);
+
+        assert_eq!(
+            cache.get(
+                &key("https://example.com", Some("acc")),
+                start + Duration::from_secs(59)
+            ),
+            Some(7)
+        );
+        assert_eq!(
+            cache.get(&key("https://example.com", Some("other")), start),
+            None
+        );
+    }
+
+    #[test]
+    fn cache_expires_after_ttl() {
+        let mut cache = TtlCache::new(Duration::from_secs(60));
+        let start = Instant::now();
+        let k = key("https://example.com", None);
+        cache.insert(k.clone(), 7, start);
+
+        assert_eq!(cache.get(&k, start + Duration::from_secs(60)), None);
+
+        cache.insert(k.clone(), 8, start + Duration::from_secs(60));
+        assert_eq!(cache.get(&k, start + Duration::from_secs(61)), Some(8));
+    }
+
+    #[test]
+    fn cache_key_includes_account_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("ChatGPT-Account-Id", "acc-1".parse().unwrap());
+        assert_eq!(
+            CacheKey::new("https://example.com", &headers),
+            key("https://example.com", Some("acc-1"))
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use crate::scrollable_diff::ScrollableDiff;
+use crossterm::event::KeyCode;
+
+/// Actions available from the base task list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListAction {
+    Next,
+    Prev,
+    Open,
+    Refresh,
+    Apply,
+    OpenInBrowser,
+    CopyUrl,
+    Watch,
+    SetEnv,
+    NewTask,
+    Help,
+    Quit,
+}
+
+/// A keybinding shown in the help overlay.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyBinding {
+    /// Key codes that trigger the binding.
+    pub codes: &'static [KeyCode],
+    /// Human-readable key label, e.g. `"↑/k"`.
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// Base list view bindings. The event loop dispatches through [`list_action`],
+/// so this table is the single source of truth for both handling and help.
+pub const LIST_BINDINGS: &[(ListAction, KeyBinding)] = &[
+    (
+        ListAction::Next,
+        KeyBinding {
+            codes: &[KeyCode::Down, KeyCode::Char('j')],
+            keys: "↓/j",
+            description: "Move down",
+        },
+    ),
+    (
+        ListAction::Prev,
+        KeyBinding {
+            codes: &[KeyCode::Up, KeyCode::Char('k')],
+            keys: "↑/k",
+            description: "Move up",
+        },
+    ),
+    (
+        ListAction::Open,
+        KeyBinding {
+            codes: &[KeyCode::Enter],
+            keys: "Enter",
+            description: "Open task details",
+        },
+    ),
+    (
+        ListAction::Refresh,
+        KeyBinding {
+            codes: &[KeyCode::Char('r'), KeyCode::Char('R')],
+            keys: "r",
+            description: "Refresh tasks",
+        },
+    ),
+    (
+        ListAction::Apply,
+        KeyBinding {
+            codes: &[KeyCode::Char('a')],
+            keys: "a",
+            description: "Apply the selected task's diff locally",
+        },
+    ),
+    (
+        ListAction::OpenInBrowser,
+        KeyBinding {
+            codes: &[KeyCode::Char('g')],
+            keys: "g",
+            description: "Open the selected task in a browser",
+        },
+    ),
+    (
+        ListAction::CopyUrl,
+        KeyBinding {
+            codes: &[KeyCode::Char('Y')],
+            keys: "Y",
+            description: "Copy the selected task's URL",
+        },
+    ),
+    (
+        ListAction::Watch,
+        KeyBinding {
+            codes: &[KeyCode::Char('w')],
+            keys: "w",
+            description: "Watch the selected task and notify when it finishes",
+        },
+    ),
+    (
+        ListAction::SetEnv,
+        KeyBinding {
+            codes: &[KeyCode::Char('o'), KeyCode::Char('O')],
+            keys: "o",
+            description: "Choose environment filter",
+        },
+    ),
+    (
+        ListAction::NewTask,
+        KeyBinding {
+            codes: &[KeyCode::Char('n')],
+            keys: "n",
+            description: "Start a new task",
+        },
+    ),
+    (
+        ListAction::Help,
+        KeyBinding {
+            codes: &[KeyCode::Char('?')],
+            keys: "?",
+            description: "Show this help",
+        },
+    ),
+    (
+        ListAction::Quit,
+        KeyBinding {
+            codes: &[KeyCode::Char('q'), KeyCode::Esc],
+            keys: "q/Esc",
+            description: "Quit",
+        },
+    ),
+];
+
+/// Task details overlay bindings (display only).
+pub const DETAILS_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        codes: &[
+            KeyCode::Down,
+            KeyCode::Char('j'),
+            KeyCode::Up,
+            KeyCode::Char('k'),
+        ],
+        keys: "↑/↓ j/k",
+        description: "Scroll",
+    },
+    KeyBinding {
+        codes: &[KeyCode::PageUp, KeyCode::PageDown, KeyCode::Char(' ')],
+        keys: "PgUp/PgDn Space",
+        description: "Scroll by page",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Home, KeyCode::End],
+        keys: "Home/End",
+        description: "Jump to top/bottom",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Left, KeyCode::Right],
+        keys: "←/→",
+        description: "Switch between prompt and diff",
+    },
+    KeyBinding {
+        codes: &[
+            KeyCode::Tab,
+            KeyCode::BackTab,
+            KeyCode::Char('{'),
+            KeyCode::Char('}'),
+        ],
+        keys: "Tab/Shift-Tab { }",
+        description: "Cycle attempts",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('['), KeyCode::Char(']')],
+        keys: "[ ]",
+        description: "Previous/next hunk (diff view; cycles attempts in prompt view)",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('p')],
+        keys: "p",
+        description: "Expand/collapse the task plan",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('L')],
+        keys: "L",
+        description: "Toggle line numbers (diff view)",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('/')],
+        keys: "/",
+        description: "Search",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Enter, KeyCode::Char('n'), KeyCode::Char('N')],
+        keys: "Enter/n N",
+        description: "Next/previous search match",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('a')],
+        keys: "a",
+        description: "Apply the current attempt's diff",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('y')],
+        keys: "y",
+        description: "Copy the current attempt's diff",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Char('o')],
+        keys: "o",
+        description: "Choose environment filter",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Esc, KeyCode::Char('q')],
+        keys: "q/Esc",
+        description: "Clear search, or close details",
+    },
+];
+
+/// New task composer bindings (display only).
+pub const NEW_TASK_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Submit task",
+    },
+    KeyBinding {
+        codes: &[],
+        keys: "Shift+Enter",
+        description: "Insert newline",
+    },
+    KeyBinding {
+        codes: &[],
+        keys: "Ctrl+O",
+        description: "Choose environment",
+    },
+    KeyBinding {
+        codes: &[],
+        keys: "Ctrl+A",
+        description: "Cycle attempts (1/2/4)",
+    },
+    KeyBinding {
+        codes: &[],
+        keys: "Ctrl+N",
+        description: "Choose number of attempts",
+    },
+    KeyBinding {
+        codes: &[],
+        keys: "↑/↓",
+        description: "Recall previous prompts",
+    },
+    KeyBinding {
+        codes: &[KeyCode::Esc],
+        keys: "Esc",
+        description: "Cancel new task",
+    },
+];
+
+/// Resolve a key pressed in the base list view to its action.
+pub fn list_action(code: KeyCode) -> Option<ListAction> {
+    LIST_BINDINGS
+        .iter()
+        .find(|(_, binding)| binding.codes.contains(&code))
+        .map(|(action, _)| *action)
+}
+
+/// Plain-text lines for the help overlay, grouped by view.
+pub fn help_lines() -> Vec<String> {
+    let sections: [(&str, Vec<&KeyBinding>); 3] = [
+        ("Task list", LIST_BINDINGS.iter().map(|(_, b)| b).collect()),
+        ("Task details", DETAILS_BINDINGS.iter().collect()),
+        ("New task", NEW_TASK_BINDINGS.iter().collect()),
+    ];
+    let width = sections
+        .iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .map(|b| b.keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for (title, bindings) in sections {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(title.to_string());
+        for b in bindings {
+            lines.push(format!("  {:<width$}  {}", b.keys, b.description));
+        }
+    }
+    lines
+}
+
+/// Scrollable keybinding reference opened with `?`.
+pub struct HelpOverlay {
+    pub sd: ScrollableDiff,
+}
+
+impl HelpOverlay {
+    pub fn new() -> Self {
+        let mut sd = ScrollableDiff::new();
+        sd.set_content(help_lines());
+        Self { sd }
+    }
+}
+
+impl Default for HelpOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_list_action_has_a_binding() {
+        let all = [
+            ListAction::Next,
+            ListAction::Prev,
+            ListAction::Open,
+            ListAction::Refresh,
+            ListAction::Apply,
+            ListAction::OpenInBrowser,
+            ListAction::CopyUrl,
+            ListAction::Watch,
+            ListAction::SetEnv,
+            ListAction::NewTask,
+            ListAction::Help,
+            ListAction::Quit,
+        ];
+        for action in all {
+            assert!(
+                LIST_BINDINGS.iter().any(|(a, _)| *a == action),
+                "missing help entry for {action:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn list_keys_resolve_to_actions() {
+        let cases = [
+            (KeyCode::Char('j'), Some(ListAction::Next)),
+            (KeyCode::Up, Some(ListAction::Prev)),
+            (KeyCode::Enter, Some(ListAction::Open)),
+            (KeyCode::Char('R'), Some(ListAction::Refresh)),
+            (KeyCode::Char('a'), Some(ListAction::Apply)),
+            (KeyCode::Char('g'), Some(ListAction::OpenInBrowser)),
+            (KeyCode::Char('Y'), Some(ListAction::CopyUrl)),
+            (KeyCode::Char('w'), Some(ListAction::Watch)),
+            (KeyCode::Char('o'), Some(ListAction::SetEnv)),
+            (KeyCode::Char('n'), Some(ListAction::NewTask)),
+            (KeyCode::Char('?'), Some(ListAction::Help)),
+            (KeyCode::Esc, Some(ListAction::Quit)),
+            (KeyCode::Char('x'), None),
+        ];
+        let resolved: Vec<_> = cases.iter().map(|(code, _)| list_action(*code)).collect();
+        let expected: Vec<_> = cases.iter().map(|(_, action)| *action).collect();
+        assert_eq!(resolved, expected);
+    }
+
+    #[test]
+    fn help_lines_list_every_binding() {
+        let lines = help_lines();
+        for (_, binding) in LIST_BINDINGS {
+            assert!(
+                lines.iter().any(|l| l.contains(binding.description)),
+                "help is missing {:?}",
+                binding.keys
+            );
+        }
+    }
+}
@@ -1,13 +1,19 @@
 mod app;
+mod auth_headers;
 mod cli;
+mod clipboard;
 pub mod env_detect;
+mod help;
 mod new_task;
+mod prompt_history;
 pub mod scrollable_diff;
 mod ui;
 pub mod util;
+mod watch;
 pub use cli::Cli;
 
 use anyhow::anyhow;
+use help::ListAction;
 use std::io::IsTerminal;
 use std::io::Read;
 use std::path::PathBuf;
@@ -31,70 +37,32 @@ struct BackendContext {
 }
 
 async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext> {
-    let use_mock = matches!(
-        std::env::var("CODEX_CLOUD_TASKS_MODE").ok().as_deref(),
-        Some("mock") | Some("MOCK")
-    );
-    let base_url = std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
-        .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string());
-
     set_user_agent_suffix(user_agent_suffix);
 
-    if use_mock {
-        return Ok(BackendContext {
-            backend: Arc::new(codex_cloud_tasks_client::MockClient),
-            base_url,
-        });
-    }
-
-    let ua = codex_core::default_client::get_codex_user_agent();
-    let mut http = codex_cloud_tasks_client::HttpClient::new(base_url.clone())?.with_user_agent(ua);
-    let style = if base_url.contains("/backend-api") {
-        "wham"
-    } else {
-        "codex-api"
-    };
-    append_error_log(format!("startup: base_url={base_url} path_style={style}"));
-
-    let auth = match codex_core::config::find_codex_home()
-        .ok()
-        .map(|home| codex_login::AuthManager::new(home, false))
-        .and_then(|am| am.auth())
-    {
-        Some(auth) => auth,
-        None => {
+    let connection = match codex_cloud_tasks_client::connect_backend().await {
+        Ok(connection) => connection,
+        Err(codex_cloud_tasks_client::ConnectError::NotSignedIn) => {
             eprintln!(
                 "Not signed in. Please run 'codex login' to sign in with ChatGPT, then re-run 'codex cloud'."
             );
             std::process::exit(1);
         }
+        Err(err) => return Err(err.into()),
     };
 
-    if let Some(acc) = auth.get_account_id() {
-        append_error_log(format!("auth: mode=ChatGPT account_id={acc}"));
-    }
-
-    let token = match auth.get_token().await {
-        Ok(t) if !t.is_empty() => t,
-        _ => {
-            eprintln!(
-                "Not signed in. Please run 'codex login' to sign in with ChatGPT, then re-run 'codex cloud'."
-            );
-            std::process::exit(1);
-        }
+    let base_url = connection.base_url;
+    let style = if base_url.contains("/backend-api") {
+        "wham"
+    } else {
+        "codex-api"
     };
-
-    http = http.with_bearer_token(token.clone());
-    if let Some(acc) = auth
-        .get_account_id()
-        .or_else(|| util::extract_chatgpt_account_id(&token))
-    {
+    append_error_log(format!("startup: base_url={base_url} path_style={style}"));
+    if let Some(acc) = &connection.account_id {
         append_error_log(format!("auth: set ChatGPT-Account-Id header: {acc}"));
-        http = http.with_chatgpt_account_id(acc);
     }
 
     Ok(BackendContext {
-        backend: Arc::new(http),
+        backend: connection.backend,
         base_url,
     })
 }
@@ -112,9 +80,7 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
         &*ctx.backend,
         &env_id,
         &prompt,
-        "main",
-        false,
-        attempts,
+        create_task_options(attempts),
     )
     .await?;
     let url = util::task_url(&ctx.base_url, &created.id.0);
@@ -122,13 +88,20 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn create_task_options(attempts: usize) -> codex_cloud_tasks_client::CreateTaskOptions {
+    codex_cloud_tasks_client::CreateTaskOptions {
+        attempts: u8::try_from(attempts).unwrap_or(u8::MAX),
+        ..Default::default()
+    }
+}
+
 async fn resolve_environment_id(ctx: &BackendContext, requested: &str) -> anyhow::Result<String> {
     let trimmed = requested.trim();
     if trimmed.is_empty() {
         return Err(anyhow!("environment id must not be empty"));
     }
     let normalized = util::normalize_base_url(&ctx.base_url);
-    let headers = util::build_chatgpt_headers().await;
+    let headers = auth_headers::build_backend_headers().await?;
     let environments = crate::env_detect::list_environments(&normalized, &headers).await?;
     if environments.is_empty() {
         return Err(anyhow!(
@@ -202,6 +175,84 @@ fn level_from_status(status: codex_cloud_tasks_client::ApplyStatus) -> app::Appl
     }
 }
 
+/// Reload the task list for `env` in the background; the result arrives as
+/// `AppEvent::TasksLoaded`.
+fn spawn_load_tasks(
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+    env: Option<String>,
+) {
+    let backend = backend.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = app::load_tasks(&*backend, env.as_deref()).await;
+        let _ = tx.send(app::AppEvent::TasksLoaded { env, result });
+    });
+}
+
+/// Fetch the environment list in the background; the result arrives as
+/// `AppEvent::EnvironmentsLoaded`.
+fn spawn_load_environments(tx: &UnboundedSender<app::AppEvent>) {
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let base_url = util::cloud_base_url();
+        let res = match auth_headers::build_backend_headers().await {
+            Ok(headers) => crate::env_detect::list_environments(&base_url, &headers).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
+    });
+}
+
+/// Fetch the diff of the task to apply in the background; the result arrives
+/// as `AppEvent::ApplyDiffLoaded`.
+fn spawn_load_apply_diff(
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+    id: codex_cloud_tasks_client::TaskId,
+    title: String,
+) {
+    let backend = backend.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let diff = codex_cloud_tasks_client::CloudBackend::get_task_diff(&*backend, id.clone())
+            .await
+            .ok()
+            .flatten();
+        let _ = tx.send(app::AppEvent::ApplyDiffLoaded { id, title, diff });
+    });
+}
+
+/// Preflight `diff` for the task and open the apply modal on it.
+fn start_apply_preflight(
+    app: &mut app::App,
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+    frame_tx: &UnboundedSender<Instant>,
+    task_id: codex_cloud_tasks_client::TaskId,
+    title: String,
+    diff: String,
+) {
+    let diff_override = Some(diff);
+    let job = ApplyJob {
+        task_id: task_id.clone(),
+        diff_override: diff_override.clone(),
+    };
+    if spawn_preflight(app, backend, tx, frame_tx, title.clone(), job) {
+        app.apply_modal = Some(app::ApplyModalState {
+            task_id,
+            title: title.clone(),
+            result_message: None,
+            result_level: None,
+            skipped_paths: Vec::new(),
+            conflict_paths: Vec::new(),
+            merged_with_conflicts: Vec::new(),
+            diff_override,
+        });
+        app.status = format!("Preflighting '{title}'...");
+    }
+}
+
 fn spawn_preflight(
     app: &mut app::App,
     backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
@@ -264,6 +315,64 @@ fn spawn_preflight(
     true
 }
 
+/// Fill in diff stats for listed tasks the backend returned without them:
+/// reuse cached results, and fetch the rest in the background. The cache is
+/// keyed by task id and `updated_at`, so a refresh only downloads diffs of
+/// tasks that are new or changed. Results are tagged with the current
+/// `list_generation` so a newer list wins.
+fn spawn_diff_summaries(
+    app: &mut app::App,
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+) {
+    let generation = app.list_generation;
+    for task in app.tasks.iter_mut() {
+        if !task.summary.is_empty()
+            || !matches!(
+                task.status,
+                codex_cloud_tasks_client::TaskStatus::Ready
+                    | codex_cloud_tasks_client::TaskStatus::Applied
+            )
+        {
+            continue;
+        }
+        if let Some((updated_at, summary)) = app.diff_summaries.get(&task.id.0)
+            && *updated_at == task.updated_at
+        {
+            task.summary = summary.clone();
+            continue;
+        }
+        if !app.in_flight.insert(task.id.0.clone()) {
+            continue;
+        }
+        let backend = backend.clone();
+        let tx = tx.clone();
+        let id = task.id.clone();
+        let updated_at = task.updated_at;
+        tokio::spawn(async move {
+            let summary =
+                match codex_cloud_tasks_client::CloudBackend::get_task_diff(&*backend, id.clone())
+                    .await
+                {
+                    Ok(Some(diff)) => Some(
+                        codex_cloud_tasks_client::DiffSummary::from_unified_diff(&diff),
+                    ),
+                    Ok(None) => Some(codex_cloud_tasks_client::DiffSummary::default()),
+                    Err(e) => {
+                        append_error_log(format!("diff summary failed for {}: {e}", id.0));
+                        None
+                    }
+                };
+            let _ = tx.send(app::AppEvent::DiffSummaryLoaded {
+                generation,
+                id,
+                updated_at,
+                summary,
+            });
+        });
+    }
+}
+
 fn spawn_apply(
     app: &mut app::App,
     backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
@@ -329,24 +438,42 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
 
     // Very minimal logging setup; mirrors other crates' pattern.
     let default_level = "error";
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env()
-                .or_else(|_| EnvFilter::try_new(default_level))
-                .unwrap_or_else(|_| EnvFilter::new(default_level)),
-        )
-        .with_ansi(std::io::stderr().is_terminal())
-        .with_writer(std::io::stderr)
-        .try_init();
+    let mut env_filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(default_level))
+        .unwrap_or_else(|_| EnvFilter::new(default_level));
+    // `CODEX_CLOUD_TASKS_HTTP_DEBUG=1` logs backend traffic at debug level.
+    // The TUI owns the terminal, so the log goes to `error.log` instead.
+    let http_debug_log = if codex_backend_client::http_debug_enabled() {
+        if let Ok(directive) = format!("{}=debug", codex_backend_client::HTTP_DEBUG_TARGET).parse()
+        {
+            env_filter = env_filter.add_directive(directive);
+        }
+        util::open_error_log().ok()
+    } else {
+        None
+    };
+    let subscriber = tracing_subscriber::fmt().with_env_filter(env_filter);
+    let _ = match http_debug_log {
+        Some(file) => subscriber
+            .with_ansi(false)
+            .with_writer(std::sync::Mutex::new(file))
+            .try_init(),
+        None => subscriber
+            .with_ansi(std::io::stderr().is_terminal())
+            .with_writer(std::io::stderr)
+            .try_init(),
+    };
 
     info!("Launching Cloud Tasks list UI");
-    let BackendContext { backend, .. } = init_backend("codex_cloud_tasks_tui").await?;
+    let BackendContext { backend, base_url } = init_backend("codex_cloud_tasks_tui").await?;
     let backend = backend;
 
     // Terminal setup
     use crossterm::ExecutableCommand;
     use crossterm::event::DisableBracketedPaste;
+    use crossterm::event::DisableMouseCapture;
     use crossterm::event::EnableBracketedPaste;
+    use crossterm::event::EnableMouseCapture;
     use crossterm::event::KeyboardEnhancementFlags;
     use crossterm::event::PopKeyboardEnhancementFlags;
     use crossterm::event::PushKeyboardEnhancementFlags;
@@ -370,12 +497,21 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                 | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
         )
     );
+    // Mouse capture breaks native text selection, so wheel scrolling is opt-in.
+    let mouse_capture = matches!(
+        std::env::var("CODEX_CLOUD_TASKS_MOUSE").ok().as_deref(),
+        Some("1") | Some("true") | Some("TRUE")
+    ) && crossterm::execute!(std::io::stdout(), EnableMouseCapture).is_ok();
     let backend_ui = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend_ui)?;
     terminal.clear()?;
 
     // App state
     let mut app = app::App::new();
+    if let Ok(home) = codex_core::config::find_codex_home() {
+        app.prompt_history = crate::prompt_history::PromptHistory::load(&home);
+        app.watch = crate::watch::WatchList::load(&home);
+    }
     // Initial load
     let force_internal = matches!(
         std::env::var("CODEX_CLOUD_TASKS_FORCE_INTERNAL")
@@ -402,6 +538,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
     use crossterm::event::KeyCode;
     use crossterm::event::KeyEventKind;
     use crossterm::event::KeyModifiers;
+    use crossterm::event::MouseEventKind;
     use tokio_stream::StreamExt;
     let mut events = EventStream::new();
 
@@ -421,37 +558,41 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         });
     }
     // Fetch environment list in parallel so the header can show friendly names quickly.
-    {
-        let tx = tx.clone();
-        tokio::spawn(async move {
-            let base_url = util::normalize_base_url(
-                &std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
-                    .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()),
-            );
-            let headers = util::build_chatgpt_headers().await;
-            let res = crate::env_detect::list_environments(&base_url, &headers).await;
-            let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-        });
-    }
+    spawn_load_environments(&tx);
 
     // Try to auto-detect a likely environment id on startup and refresh if found.
     // Do this concurrently so the initial list shows quickly; on success we refetch with filter.
     {
         let tx = tx.clone();
         tokio::spawn(async move {
-            let base_url = util::normalize_base_url(
-                &std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
-                    .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()),
-            );
-            // Build headers: UA + ChatGPT auth if available
-            let headers = util::build_chatgpt_headers().await;
-
+            let base_url = util::cloud_base_url();
             // Run autodetect. If it fails, we keep using "All".
-            let res = crate::env_detect::autodetect_environment_id(&base_url, &headers, None).await;
+            let res = match auth_headers::build_backend_headers().await {
+                Ok(headers) => {
+                    crate::env_detect::autodetect_environment_id(&base_url, &headers, None).await
+                }
+                Err(e) => Err(e),
+            };
             let _ = tx.send(app::AppEvent::EnvironmentAutodetected(res));
         });
     }
 
+    // Periodic tick for the watched-task poller; aborted when the UI exits.
+    let watch_poller = {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(watch::WATCH_POLL_INTERVAL);
+            // The first tick completes immediately; the initial list load covers it.
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                if tx.send(app::AppEvent::WatchPollDue).is_err() {
+                    break;
+                }
+            }
+        })
+    };
+
     // Event-driven redraws with a tiny coalescing scheduler (snappy UI, no fixed 250ms tick).
     let mut needs_redraw = true;
     use std::time::Instant;
@@ -529,6 +670,25 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                 } else {
                     app.spinner_start = None;
                 }
+                // Periodic list refresh; ticks once a second to keep the countdown current.
+                let blocked = app.auto_refresh_blocked();
+                if let Some(auto) = app.auto_refresh.as_mut() {
+                    let now = Instant::now();
+                    if auto.poll(blocked, now) {
+                        append_error_log(format!(
+                            "refresh.auto: env={}",
+                            app.env_filter.clone().unwrap_or_else(|| "<all>".to_string())
+                        ));
+                        app.status = "Auto-refreshing…".to_string();
+                        app.refresh_inflight = true;
+                        app.list_generation = app.list_generation.saturating_add(1);
+                        app.load_retries = 0;
+                        app.in_flight.clear();
+                        spawn_load_tasks(&backend, &tx, app.env_filter.clone());
+                    }
+                    needs_redraw = true;
+                    let _ = frame_tx.send(now + Duration::from_secs(1));
+                }
                 render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
             }
             maybe_app_event = rx.recv() => {
@@ -554,39 +714,121 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                     ));
                                     app.tasks = tasks;
                                     if app.selected >= app.tasks.len() { app.selected = app.tasks.len().saturating_sub(1); }
+                                    spawn_diff_summaries(&mut app, &backend, &tx);
                                     app.status = "Loaded tasks".to_string();
+                                    let tasks = app.tasks.clone();
+                                    notify_finished_watched(&mut app, &tasks);
+                                    app.load_retries = 0;
+                                    if let Some(auto) = app.auto_refresh.as_mut() { auto.record_result(true); }
                                 }
                                 Err(e) => {
                                     append_error_log(format!("refresh load_tasks failed: {e}"));
-                                    app.status = format!("Failed to load tasks: {e}");
+                                    if let Some(delay) = app::load_retry_delay(app.load_retries) {
+                                        app.load_retries += 1;
+                                        app.status = format!(
+                                            "Failed to load tasks: {e}. Retrying in {}s…",
+                                            delay.as_secs()
+                                        );
+                                        let tx = tx.clone();
+                                        let generation = app.list_generation;
+                                        tokio::spawn(async move {
+                                            tokio::time::sleep(delay).await;
+                                            let _ = tx.send(app::AppEvent::RetryTasksLoad { env, generation });
+                                        });
+                                    } else {
+                                        app.load_retries = 0;
+                                        app.status = format!("Failed to load tasks: {e}");
+                                        if let Some(auto) = app.auto_refresh.as_mut() { auto.record_result(false); }
+                                    }
                                 }
                             }
                             needs_redraw = true;
                             let _ = frame_tx.send(Instant::now());
                         }
+                        app::AppEvent::DiffSummaryLoaded { generation, id, updated_at, summary } => {
+                            app.in_flight.remove(&id.0);
+                            // Failed fetches are retried with the next list load.
+                            let Some(summary) = summary else { continue };
+                            // Cache even when a newer list arrived meanwhile, so
+                            // the next load does not download the diff again.
+                            app.diff_summaries.insert(id.0.clone(), (updated_at, summary.clone()));
+                            if generation != app.list_generation {
+                                continue;
+                            }
+                            if let Some(task) = app.tasks.iter_mut().find(|t| t.id == id && t.updated_at == updated_at) {
+                                task.summary = summary;
+                                needs_redraw = true;
+                            }
+                        }
+                        app::AppEvent::RetryTasksLoad { env, generation } => {
+                            if generation != app.list_generation || env.as_deref() != app.env_filter.as_deref() {
+                                append_error_log(format!(
+                                    "refresh.retry.drop: env={} generation={generation}",
+                                    env.unwrap_or_else(|| "<all>".to_string())
+                                ));
+                                continue;
+                            }
+                            append_error_log(format!(
+                                "refresh.retry: env={} attempt={}",
+                                env.clone().unwrap_or_else(|| "<all>".to_string()),
+                                app.load_retries
+                            ));
+                            app.status = "Retrying…".to_string();
+                            app.refresh_inflight = true;
+                            needs_redraw = true;
+                            spawn_load_tasks(&backend, &tx, env);
+                            let _ = frame_tx.send(Instant::now());
+                        }
+                        app::AppEvent::WatchPollDue => {
+                            if !app.watch.is_empty() {
+                                let backend = Arc::clone(&backend);
+                                let tx = tx.clone();
+                                tokio::spawn(async move {
+                                    let res = app::load_tasks(&*backend, None).await;
+                                    let _ = tx.send(app::AppEvent::WatchedTasksLoaded(res));
+                                });
+                            }
+                        }
+                        app::AppEvent::WatchedTasksLoaded(result) => match result {
+                            Ok(tasks) => {
+                                if notify_finished_watched(&mut app, &tasks) {
+                                    needs_redraw = true;
+                                }
+                            }
+                            Err(e) => append_error_log(format!("watch.poll failed: {e}")),
+                        },
                         app::AppEvent::NewTaskSubmitted(result) => {
                             match result {
                                 Ok(created) => {
                                     append_error_log(format!("new-task: created id={}", created.id.0));
-                                    app.status = format!("Submitted as {}", created.id.0);
+                                    let attempts = app.new_task.as_ref().map_or(app.best_of_n, |page| page.best_of_n);
+                                    let attempts_note = if attempts > 1 { format!(" ({attempts} attempts)") } else { String::new() };
+                                    if let Some(prompt) = app.new_task.as_mut().and_then(|page| page.submitted_prompt.take())
+                                        && let Err(e) = app.prompt_history.record(&prompt)
+                                    {
+                                        append_error_log(format!("new-task: failed to save prompt history: {e}"));
+                                    }
                                     app.new_task = None;
                                     // Refresh tasks in background for current filter
-                                    app.status = format!("Submitted as {} — refreshing…", created.id.0);
+                                    app.status = format!("Submitted as {}{attempts_note} — refreshing…", created.id.0);
                                     app.refresh_inflight = true;
                                     app.list_generation = app.list_generation.saturating_add(1);
+                                    app.load_retries = 0;
                                     needs_redraw = true;
-                                    let backend = Arc::clone(&backend);
-                                    let tx = tx.clone();
-                                    let env_sel = app.env_filter.clone();
-                                    tokio::spawn(async move {
-                                        let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
-                                        let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
-                                    });
+                                    spawn_load_tasks(&backend, &tx, app.env_filter.clone());
                                     let _ = frame_tx.send(Instant::now());
                                 }
                                 Err(msg) => {
                                     append_error_log(format!("new-task: submit failed: {msg}"));
-                                    if let Some(page) = app.new_task.as_mut() { page.submitting = false; }
+                                    if let Some(page) = app.new_task.as_mut() {
+                                        page.submitting = false;
+                                        // Put the prompt back so it can be tweaked and resent.
+                                        if let Some(prompt) = page.submitted_prompt.take()
+                                            && page.composer.is_empty()
+                                        {
+                                            page.composer.set_text(prompt);
+                                        }
+                                    }
                                     app.status = format!("Submit failed: {msg}. See error.log for details.");
                                     needs_redraw = true;
                                     let _ = frame_tx.send(Instant::now());
@@ -594,6 +836,22 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             }
                         }
                         // (removed TaskSummaryUpdated; unused in this prototype)
+                        app::AppEvent::ApplyDiffLoaded { id, title, diff } => {
+                            for effect in app::update(&mut app, app::Msg::ApplyDiffLoaded { id, title, diff }) {
+                                match effect {
+                                    app::Effect::Redraw => needs_redraw = true,
+                                    app::Effect::Preflight { id, title, diff } => {
+                                        start_apply_preflight(&mut app, &backend, &tx, &frame_tx, id, title, diff)
+                                    }
+                                    // A loaded diff only ever redraws or starts a preflight.
+                                    app::Effect::Quit
+                                    | app::Effect::LoadTasks
+                                    | app::Effect::LoadEnvironments
+                                    | app::Effect::LoadApplyDiff { .. }
+                                    | app::Effect::Unhandled(_) => {}
+                                }
+                            }
+                        }
                         app::AppEvent::ApplyPreflightFinished { id, title, message, level, skipped, conflicts } => {
                             // Only update if modal is still open and ids match
                             if let Some(m) = app.apply_modal.as_mut()
@@ -644,67 +902,38 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                     app.status = "Loading tasks…".to_string();
                                     app.refresh_inflight = true;
                                     app.list_generation = app.list_generation.saturating_add(1);
+                                    app.load_retries = 0;
                                     app.in_flight.clear();
                             // reset spinner state
                                     needs_redraw = true;
-                                    {
-                                        let backend = Arc::clone(&backend);
-                                        let tx = tx.clone();
-                                        let env_sel = app.env_filter.clone();
-                                        tokio::spawn(async move {
-                                            let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
-                                            let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
-                                        });
-                                    }
+                                    spawn_load_tasks(&backend, &tx, app.env_filter.clone());
                                     // Proactively fetch environments to resolve a friendly name for the header.
                                     app.env_loading = true;
-                                    {
-                                        let tx = tx.clone();
-                                        tokio::spawn(async move {
-                                            let base_url = crate::util::normalize_base_url(
-                                                &std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
-                                                    .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()),
-                                            );
-                                            let headers = crate::util::build_chatgpt_headers().await;
-                                            let res = crate::env_detect::list_environments(&base_url, &headers).await;
-                                            let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-                                        });
-                                    }
+                                    spawn_load_environments(&tx);
                                     let _ = frame_tx.send(Instant::now());
                                 }
                             }
                             // on Err, silently continue with All
                         }
-                        app::AppEvent::DetailsDiffLoaded { id, title, diff } => {
-                            if let Some(ov) = &app.diff_overlay
-                                && ov.task_id != id {
-                                    continue;
-                                }
-                            let diff_lines: Vec<String> = diff.lines().map(str::to_string).collect();
-                            if let Some(ov) = app.diff_overlay.as_mut() {
-                                ov.title = title;
-                                {
-                                    let base = ov.base_attempt_mut();
-                                    base.diff_lines = diff_lines.clone();
-                                    base.diff_raw = Some(diff.clone());
+                        app::AppEvent::DiffChunkLoaded { id, chunk } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id {
+                                    ov.append_diff_chunk(&chunk);
+                                    app.details_inflight = false;
+                                    app.status.clear();
+                                    needs_redraw = true;
                                 }
-                                ov.base_can_apply = true;
-                                ov.apply_selection_to_fields();
-                            } else {
-                                let mut overlay = app::DiffOverlay::new(id.clone(), title, None);
-                                {
-                                    let base = overlay.base_attempt_mut();
-                                    base.diff_lines = diff_lines.clone();
-                                    base.diff_raw = Some(diff.clone());
+                        }
+                        app::AppEvent::DiffStreamFinished { id, error } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id {
+                                    ov.finish_diff_stream(error.is_some());
+                                    if let Some(e) = error {
+                                        append_error_log(format!("diff stream failed for {}: {e}", id.0));
+                                        app.status = format!("Diff incomplete: {e}");
+                                    }
+                                    needs_redraw = true;
                                 }
-                                overlay.base_can_apply = true;
-                                overlay.current_view = app::DetailView::Diff;
-                                overlay.apply_selection_to_fields();
-                                app.diff_overlay = Some(overlay);
-                            }
-                            app.details_inflight = false;
-                            app.status.clear();
-                            needs_redraw = true;
                         }
                         app::AppEvent::DetailsMessagesLoaded {
                             id,
@@ -727,6 +956,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                     let base = ov.base_attempt_mut();
                                     base.text_lines = conv.clone();
                                     base.prompt = prompt.clone();
+                                    base.messages = messages.clone();
                                     base.turn_id = turn_id.clone();
                                     base.status = attempt_status;
                                     base.attempt_placement = attempt_placement;
@@ -769,6 +999,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                     let base = overlay.base_attempt_mut();
                                     base.text_lines = conv.clone();
                                     base.prompt = prompt.clone();
+                                    base.messages = messages.clone();
                                     base.turn_id = turn_id.clone();
                                     base.status = attempt_status;
                                     base.attempt_placement = attempt_placement;
@@ -780,10 +1011,95 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                 overlay.apply_selection_to_fields();
                                 app.diff_overlay = Some(overlay);
                             }
+                            // Follow output of a task that is still running.
+                            if matches!(
+                                attempt_status,
+                                codex_cloud_tasks_client::AttemptStatus::Pending
+                                    | codex_cloud_tasks_client::AttemptStatus::InProgress
+                            ) && let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.message_stream.is_none()
+                                && ov.follow_task.is_none()
+                            {
+                                let backend = Arc::clone(&backend);
+                                let tx = tx.clone();
+                                let task_id = id.clone();
+                                let already_shown = messages.len();
+                                ov.message_stream = Some(tokio::spawn(async move {
+                                    let mut stream = codex_cloud_tasks_client::CloudBackend::stream_task_messages(&*backend, task_id.clone())
+                                        .skip(already_shown);
+                                    while let Some(item) = stream.next().await {
+                                        match item {
+                                            Ok(message) => {
+                                                let evt = app::AppEvent::DetailsMessageStreamed { id: task_id.clone(), message };
+                                                if tx.send(evt).is_err() {
+                                                    break;
+                                                }
+                                            }
+                                            Err(e) => {
+                                                append_error_log(format!("message stream failed for {}: {e}", task_id.0));
+                                            }
+                                        }
+                                    }
+                                }));
+                            }
                             app.details_inflight = false;
                             app.status.clear();
                             needs_redraw = true;
                         }
+                        app::AppEvent::DetailsMessageStreamed { id, message } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id
+                            {
+                                let base = ov.base_attempt_mut();
+                                base.messages.push(message);
+                                base.text_lines = conversation_lines(base.prompt.clone(), &base.messages);
+                                if ov.selected_attempt == 0 {
+                                    ov.apply_selection_to_fields();
+                                }
+                                needs_redraw = true;
+                            }
+                        }
+                        app::AppEvent::DetailsStillRunning { id, status, latest_event } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id
+                            {
+                                ov.follow_status = Some(match latest_event {
+                                    Some(text) => format!("{status}: {text}"),
+                                    None => status,
+                                });
+                                if ov.follow_task.is_none() {
+                                    // The follow poll supersedes the message stream; keep one poller per overlay.
+                                    if let Some(handle) = ov.message_stream.take() {
+                                        handle.abort();
+                                    }
+                                    ov.follow_task = Some(spawn_follow(&backend, &tx, id, ov.title.clone()));
+                                }
+                                needs_redraw = true;
+                            }
+                        }
+                        app::AppEvent::DetailsPlanLoaded { id, plan } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id
+                            {
+                                ov.plan = Some(plan);
+                                needs_redraw = true;
+                            }
+                        }
+                        app::AppEvent::DetailsFollowEnded { id, timed_out } => {
+                            if let Some(ov) = app.diff_overlay.as_mut()
+                                && ov.task_id == id
+                            {
+                                ov.follow_task = None;
+                                ov.follow_status = None;
+                                if timed_out {
+                                    app.status = format!(
+                                        "Stopped following after {} min; reopen the task to check again.",
+                                        app::FOLLOW_MAX_DURATION.as_secs() / 60
+                                    );
+                                }
+                                needs_redraw = true;
+                            }
+                        }
                         app::AppEvent::AttemptsLoaded { id, attempts } => {
                             if let Some(ov) = app.diff_overlay.as_mut() {
                                 if ov.task_id != id {
@@ -810,6 +1126,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         diff_lines,
                                         text_lines,
                                         prompt: None,
+                                        messages: attempt.messages.clone(),
                                         diff_raw: attempt.diff.clone(),
                                     });
                                 }
@@ -877,13 +1194,13 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         app.apply_modal = None;
                                         app.diff_overlay = None;
                                         // Refresh tasks after successful apply
-                                        let backend = Arc::clone(&backend);
-                                        let tx = tx.clone();
-                                        let env_sel = app.env_filter.clone();
-                                        tokio::spawn(async move {
-                                            let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
-                                            let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
-                                        });
+                                        spawn_load_tasks(&backend, &tx, app.env_filter.clone());
+                                    } else if let Some(m) = app.apply_modal.as_mut() {
+                                        m.result_level = Some(level_from_status(outcome.status));
+                                        m.result_message = Some(outcome.message);
+                                        m.skipped_paths = outcome.skipped_paths;
+                                        m.conflict_paths = outcome.conflict_paths;
+                                        m.merged_with_conflicts = outcome.merged_with_conflicts;
                                     }
                                 }
                                 Err(e) => {
@@ -915,41 +1232,43 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                         } else if let Some(page) = app.new_task.as_mut()
                             && !page.submitting
                         {
-                            if page.composer.handle_paste(pasted) {
-                                needs_redraw = true;
+                            if let codex_tui::PasteOutcome::Truncated { kept, total } = page.composer.handle_paste(pasted) {
+                                app.status = format!("Paste truncated to {kept} of {total} characters (CODEX_CLOUD_TASKS_MAX_PASTE_CHARS)");
                             }
+                            needs_redraw = true;
                             let _ = frame_tx.send(Instant::now());
                         }
                     }
                     Some(Ok(Event::Key(key))) if matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) => {
-                        // Treat Ctrl-C like pressing 'q' in the current context.
-                        if key.modifiers.contains(KeyModifiers::CONTROL)
-                            && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
-                        {
-                            if app.env_modal.is_some() {
-                                // Close environment selector if open (don’t quit composer).
-                                app.env_modal = None;
-                                needs_redraw = true;
-                            } else if app.best_of_modal.is_some() {
-                                app.best_of_modal = None;
-                                needs_redraw = true;
-                            } else if app.apply_modal.is_some() {
-                                app.apply_modal = None;
-                                app.status = "Apply canceled".to_string();
-                                needs_redraw = true;
-                            } else if app.new_task.is_some() {
-                                app.new_task = None;
-                                app.status = "Canceled new task".to_string();
-                                needs_redraw = true;
-                            } else if app.diff_overlay.is_some() {
-                                app.diff_overlay = None;
-                                needs_redraw = true;
-                            } else {
-                                break 0;
+                        // Keys owned by `app::update`; anything it returns as
+                        // `Unhandled` falls through to the handlers below.
+                        let mut quit = false;
+                        let mut unhandled = false;
+                        for effect in app::update(&mut app, app::Msg::Key(key)) {
+                            match effect {
+                                app::Effect::Redraw => needs_redraw = true,
+                                app::Effect::Quit => quit = true,
+                                app::Effect::LoadTasks => {
+                                    append_error_log(format!(
+                                        "refresh.request: env={}",
+                                        app.env_filter.clone().unwrap_or_else(|| "<all>".to_string())
+                                    ));
+                                    spawn_load_tasks(&backend, &tx, app.env_filter.clone());
+                                }
+                                app::Effect::LoadEnvironments => spawn_load_environments(&tx),
+                                app::Effect::LoadApplyDiff { id, title } => {
+                                    spawn_load_apply_diff(&backend, &tx, id, title)
+                                }
+                                app::Effect::Preflight { id, title, diff } => {
+                                    start_apply_preflight(&mut app, &backend, &tx, &frame_tx, id, title, diff)
+                                }
+                                app::Effect::Unhandled(_) => unhandled = true,
                             }
-                            // Render updated state immediately before continuing to next loop iteration.
-                            render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
-                            // Render after New Task branch to reflect input changes immediately.
+                        }
+                        if quit {
+                            break 0;
+                        }
+                        if !unhandled {
                             render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
                             continue;
                         }
@@ -957,9 +1276,6 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             && matches!(key.code, KeyCode::Char('n') | KeyCode::Char('N'))
                             || matches!(key.code, KeyCode::Char('\u{000E}'));
                         if is_ctrl_n {
-                            if app.new_task.is_none() {
-                                continue;
-                            }
                             if app.best_of_modal.is_some() {
                                 app.best_of_modal = None;
                                 needs_redraw = true;
@@ -1027,6 +1343,27 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
                             continue;
                         }
+                        // New Task page: Ctrl+A cycles best-of attempts through 1/2/4.
+                        let is_ctrl_a = key.modifiers.contains(KeyModifiers::CONTROL)
+                            && matches!(key.code, KeyCode::Char('a') | KeyCode::Char('A'))
+                            || matches!(key.code, KeyCode::Char('\u{0001}'));
+                        if is_ctrl_a
+                            && app.env_modal.is_none()
+                            && let Some(page) = app.new_task.as_mut()
+                            && !page.submitting
+                        {
+                            let next = crate::new_task::next_attempts(page.best_of_n);
+                            page.best_of_n = next;
+                            app.best_of_n = next;
+                            append_error_log(format!("best-of.cycle: attempts={next}"));
+                            app.status = format!(
+                                "Best-of updated to {next} attempt{}",
+                                if next == 1 { "" } else { "s" }
+                            );
+                            needs_redraw = true;
+                            render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
+                            continue;
+                        }
                         // New Task page: Ctrl+O opens environment switcher while composing.
                         let is_ctrl_o = key.modifiers.contains(KeyModifiers::CONTROL)
                             && matches!(key.code, KeyCode::Char('o') | KeyCode::Char('O'))
@@ -1045,13 +1382,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             }
                             needs_redraw = true;
                             if should_fetch {
-                                    let tx = tx.clone();
-                                    tokio::spawn(async move {
-            let base_url = crate::util::normalize_base_url(&std::env::var("CODEX_CLOUD_TASKS_BASE_URL").unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()));
-            let headers = crate::util::build_chatgpt_headers().await;
-                                        let res = crate::env_detect::list_environments(&base_url, &headers).await;
-                                        let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-                                    });
+                                spawn_load_environments(&tx);
                             }
                             // Render after opening env modal to show it instantly.
                             render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
@@ -1065,8 +1396,26 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             } else {
                             match key.code {
                                 KeyCode::Esc => {
-                                    app.new_task = None;
-                                    app.status = "Canceled new task".to_string();
+                                    app.request_close_new_task();
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Up | KeyCode::Down
+                                    if !page.submitting
+                                        && page.recall.should_handle(
+                                            app.prompt_history.entries(),
+                                            &page.composer.text(),
+                                            page.composer.cursor_on_first_line(),
+                                        ) =>
+                                {
+                                    let entries = app.prompt_history.entries();
+                                    let recalled = if key.code == KeyCode::Up {
+                                        page.recall.up(entries)
+                                    } else {
+                                        page.recall.down(entries)
+                                    };
+                                    if let Some(text) = recalled {
+                                        page.composer.set_text(text);
+                                    }
                                     needs_redraw = true;
                                 }
                                 _ => {
@@ -1081,12 +1430,14 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                     text.chars().count()
                                                 ));
                                                 page.submitting = true;
+                                                page.submitted_prompt = Some(text.clone());
+                                                page.recall.reset();
                                                 app.status = "Submitting new task…".to_string();
                                                 let tx = tx.clone();
                                                 let backend = Arc::clone(&backend);
                                                 let best_of_n = page.best_of_n;
                                                 tokio::spawn(async move {
-                                                    let result = codex_cloud_tasks_client::CloudBackend::create_task(&*backend, &env, &text, "main", false, best_of_n).await;
+                                                    let result = codex_cloud_tasks_client::CloudBackend::create_task(&*backend, &env, &text, create_task_options(best_of_n)).await;
                                                     let evt = match result {
                                                         Ok(ok) => app::AppEvent::NewTaskSubmitted(Ok(ok)),
                                                         Err(e) => app::AppEvent::NewTaskSubmitted(Err(format!("{e}"))),
@@ -1143,6 +1494,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                 result_level: None,
                                                 skipped_paths: Vec::new(),
                                                 conflict_paths: Vec::new(),
+                                                merged_with_conflicts: Vec::new(),
                                                 diff_override: m.diff_override,
                                             });
                                             app.status = format!("Preflighting '{title}'...");
@@ -1158,7 +1510,34 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                 | KeyCode::Char('Q') => { app.apply_modal = None; app.status = "Apply canceled".to_string(); needs_redraw = true; }
                                 _ => {}
                             }
+                        } else if let Some(ov) = app.diff_overlay.as_mut()
+                            && ov.search_editing
+                        {
+                            // Search input in the details bottom bar
+                            match key.code {
+                                KeyCode::Esc => {
+                                    ov.search_editing = false;
+                                    ov.sd.clear_search();
+                                }
+                                KeyCode::Enter => {
+                                    ov.search_editing = false;
+                                    let query = ov.sd.search_query.clone();
+                                    ov.sd.set_search(query);
+                                    app.status = if ov.sd.search_matches.is_empty() {
+                                        format!("No matches for '{}'", ov.sd.search_query)
+                                    } else {
+                                        format!("{} match(es) for '{}'", ov.sd.search_matches.len(), ov.sd.search_query)
+                                    };
+                                }
+                                KeyCode::Backspace => { ov.sd.search_query.pop(); }
+                                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) && !key.modifiers.contains(KeyModifiers::ALT) => {
+                                    ov.sd.search_query.push(ch);
+                                }
+                                _ => {}
+                            }
+                            needs_redraw = true;
                         } else if app.diff_overlay.is_some() {
+                            let showing_diff = app.diff_overlay.as_ref().is_some_and(app::DiffOverlay::showing_diff);
                             let mut cycle_attempt = |delta: isize| {
                                 if let Some(ov) = app.diff_overlay.as_mut()
                                     && ov.attempt_count() > 1 {
@@ -1200,6 +1579,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                     result_level: None,
                                                     skipped_paths: Vec::new(),
                                                     conflict_paths: Vec::new(),
+                                                    merged_with_conflicts: Vec::new(),
                                                     diff_override,
                                                 });
                                                 app.status = format!("Preflighting '{title}'...");
@@ -1210,6 +1590,11 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         needs_redraw = true;
                                     }
                                 }
+                                KeyCode::Char('y') => {
+                                    let diff = app.diff_overlay.as_ref().and_then(app::DiffOverlay::loaded_diff);
+                                    app.status = clipboard::copy_diff(diff, clipboard::copy_to_clipboard);
+                                    needs_redraw = true;
+                                }
                                 KeyCode::Tab => {
                                     cycle_attempt(1);
                                 }
@@ -1224,16 +1609,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                     if app.environments.is_empty() { app.env_loading = true; app.env_error = None; }
                                     needs_redraw = true;
                                     if app.environments.is_empty() {
-                                        let tx = tx.clone();
-                                        tokio::spawn(async move {
-                                            let base_url = crate::util::normalize_base_url(
-                                                &std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
-                                                    .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()),
-                                            );
-                                            let headers = crate::util::build_chatgpt_headers().await;
-                                            let res = crate::env_detect::list_environments(&base_url, &headers).await;
-                                            let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-                                        });
+                                        spawn_load_environments(&tx);
                                     }
                                 }
                                 KeyCode::Left => {
@@ -1258,12 +1634,61 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         }
                                     }
                                 }
+                                // In the diff view, [ and ] jump between hunks instead of attempts
+                                KeyCode::Char(']') if showing_diff => {
+                                    if let Some(ov) = &mut app.diff_overlay
+                                        && !ov.sd.next_hunk()
+                                    {
+                                        app.status = "Already at the last hunk".to_string();
+                                    }
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Char('[') if showing_diff => {
+                                    if let Some(ov) = &mut app.diff_overlay
+                                        && !ov.sd.prev_hunk()
+                                    {
+                                        app.status = "Already at the first hunk".to_string();
+                                    }
+                                    needs_redraw = true;
+                                }
                                 KeyCode::Char(']') | KeyCode::Char('}') => {
                                     cycle_attempt(1);
                                 }
                                 KeyCode::Char('[') | KeyCode::Char('{') => {
                                     cycle_attempt(-1);
                                 }
+                                KeyCode::Char('p') => {
+                                    if let Some(ov) = &mut app.diff_overlay
+                                        && ov.plan.is_some()
+                                    {
+                                        ov.plan_expanded = !ov.plan_expanded;
+                                        needs_redraw = true;
+                                    }
+                                }
+                                KeyCode::Char('L') if showing_diff => {
+                                    if let Some(ov) = &mut app.diff_overlay { ov.sd.toggle_line_numbers(); }
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Char('/') => {
+                                    if let Some(ov) = &mut app.diff_overlay {
+                                        ov.sd.clear_search();
+                                        ov.search_editing = true;
+                                    }
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Enter | KeyCode::Char('n') => {
+                                    if let Some(ov) = &mut app.diff_overlay { ov.sd.next_match(); }
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Char('N') => {
+                                    if let Some(ov) = &mut app.diff_overlay { ov.sd.prev_match(); }
+                                    needs_redraw = true;
+                                }
+                                // Esc clears an active search before closing the overlay
+                                KeyCode::Esc if app.diff_overlay.as_ref().is_some_and(|ov| !ov.sd.search_query.is_empty()) => {
+                                    if let Some(ov) = &mut app.diff_overlay { ov.sd.clear_search(); }
+                                    needs_redraw = true;
+                                }
                                 KeyCode::Esc | KeyCode::Char('q') => {
                                     app.diff_overlay = None;
                                     needs_redraw = true;
@@ -1293,16 +1718,11 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             match key.code {
                                 KeyCode::Esc => { app.env_modal = None; needs_redraw = true; }
                                 KeyCode::Char('r') | KeyCode::Char('R') => {
-                                    // Trigger refresh of environments
+                                    // Trigger refresh of environments, bypassing the lookup cache
+                                    crate::env_detect::invalidate_cache();
                                     app.env_loading = true; app.env_error = None; needs_redraw = true;
                                     let _ = frame_tx.send(Instant::now() + Duration::from_millis(100));
-                                    let tx = tx.clone();
-                                    tokio::spawn(async move {
-            let base_url = crate::util::normalize_base_url(&std::env::var("CODEX_CLOUD_TASKS_BASE_URL").unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()));
-            let headers = crate::util::build_chatgpt_headers().await;
-                                        let res = crate::env_detect::list_environments(&base_url, &headers).await;
-                                        let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-                                    });
+                                    spawn_load_environments(&tx);
                                 }
                                 KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) && !key.modifiers.contains(KeyModifiers::ALT) => {
                                     if let Some(m) = app.env_modal.as_mut() { m.query.push(ch); }
@@ -1358,79 +1778,38 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         app.status = "Loading tasks…".to_string();
                                         app.refresh_inflight = true;
                                         app.list_generation = app.list_generation.saturating_add(1);
+                                        app.load_retries = 0;
                                         app.in_flight.clear();
                                         // reset spinner state
                                         needs_redraw = true;
-                                        let backend = Arc::clone(&backend);
-                                        let tx = tx.clone();
-                                        let env_sel = app.env_filter.clone();
-                                        tokio::spawn(async move {
-                                            let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
-                                            let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
-                                        });
+                                        spawn_load_tasks(&backend, &tx, app.env_filter.clone());
                                     }
                                 }
                                 _ => {}
                             }
                         } else {
-                            // Base list view keys
-                            match key.code {
-                                KeyCode::Char('q') | KeyCode::Esc => {
-                                    break 0;
-                                }
-                                KeyCode::Down | KeyCode::Char('j') => {
-                                    app.next();
-                                    needs_redraw = true;
-                                }
-                                KeyCode::Up | KeyCode::Char('k') => {
-                                    app.prev();
-                                    needs_redraw = true;
-                                }
-                                // Ensure 'r' does not refresh tasks when the env modal is open.
-                                KeyCode::Char('r') | KeyCode::Char('R') => {
-                                    if app.env_modal.is_some() { break 0; }
-                                    append_error_log(format!(
-                                        "refresh.request: env={}",
-                                        app.env_filter.clone().unwrap_or_else(|| "<all>".to_string())
-                                    ));
-                                    app.status = "Refreshing…".to_string();
-                                    app.refresh_inflight = true;
-                                    app.list_generation = app.list_generation.saturating_add(1);
-                                    app.in_flight.clear();
-                                        // reset spinner state
-                                    needs_redraw = true;
-                                    // Spawn background refresh
-                                    let backend = Arc::clone(&backend);
-                                    let tx = tx.clone();
-                                    let env_sel = app.env_filter.clone();
-                                    tokio::spawn(async move {
-                                        let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
-                                        let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
-                                    });
-                                }
-                                KeyCode::Char('o') | KeyCode::Char('O') => {
-                                    app.env_modal = Some(app::EnvModalState { query: String::new(), selected: 0 });
-                                    // Cache environments until user explicitly refreshes with 'r' inside the modal.
-                                    let should_fetch = app.environments.is_empty();
-                                    if should_fetch { app.env_loading = true; app.env_error = None; }
-                                    needs_redraw = true;
-                                    if should_fetch {
-                                    let tx = tx.clone();
-                                    tokio::spawn(async move {
-                                        let base_url = crate::util::normalize_base_url(&std::env::var("CODEX_CLOUD_TASKS_BASE_URL").unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string()));
-                                        let headers = crate::util::build_chatgpt_headers().await;
-                                        let res = crate::env_detect::list_environments(&base_url, &headers).await;
-                                        let _ = tx.send(app::AppEvent::EnvironmentsLoaded(res));
-                                    });
+                            // Base list view keys; bindings live in `help::LIST_BINDINGS`.
+                            match help::list_action(key.code) {
+                                Some(ListAction::Watch) => {
+                                    if let Some(task) = app.tasks.get(app.selected).cloned() {
+                                        match app.watch.toggle(&task) {
+                                            Ok(true) => app.status = format!("Watching '{}'; you'll be notified when it finishes", task.title),
+                                            Ok(false) => app.status = format!("Stopped watching '{}'", task.title),
+                                            Err(e) => {
+                                                append_error_log(format!("watch.save failed: {e}"));
+                                                app.status = format!("Failed to save watched tasks: {e}");
+                                            }
+                                        }
+                                        needs_redraw = true;
                                     }
                                 }
-                                KeyCode::Char('n') => {
+                                Some(ListAction::NewTask) => {
                                     let env_opt = app.env_filter.clone();
                                     app.new_task = Some(crate::new_task::NewTaskPage::new(env_opt, app.best_of_n));
                                     app.status = "New Task: Enter to submit; Esc to cancel".to_string();
                                     needs_redraw = true;
                                 }
-                                KeyCode::Enter => {
+                                Some(ListAction::Open) => {
                                     if let Some(task) = app.tasks.get(app.selected).cloned() {
                                         app.status = format!("Loading details for {title}…", title = task.title);
                                         app.details_inflight = true;
@@ -1451,11 +1830,9 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                             let diff_id = id.clone();
                                             let diff_title = title.clone();
                                             tokio::spawn(async move {
-                                                match codex_cloud_tasks_client::CloudBackend::get_task_diff(&*backend, diff_id.clone()).await {
-                                                    Ok(Some(diff)) => {
-                                                        let _ = tx.send(app::AppEvent::DetailsDiffLoaded { id: diff_id, title: diff_title, diff });
-                                                    }
-                                                    Ok(None) => {
+                                                match app::stream_diff(&*backend, diff_id.clone(), &tx).await {
+                                                    Ok(true) => {}
+                                                    Ok(false) => {
                                                         match codex_cloud_tasks_client::CloudBackend::get_task_text(&*backend, diff_id.clone()).await {
                                                             Ok(text) => {
                                                                 let evt = app::AppEvent::DetailsMessagesLoaded {
@@ -1476,6 +1853,13 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                         }
                                                     }
                                                     Err(e) => {
+                                                        if let codex_cloud_tasks_client::CloudTaskError::TaskStillRunning { status, latest_event } = &e {
+                                                            let _ = tx.send(app::AppEvent::DetailsStillRunning {
+                                                                id: diff_id.clone(),
+                                                                status: status.clone(),
+                                                                latest_event: latest_event.clone(),
+                                                            });
+                                                        }
                                                         append_error_log(format!("get_task_diff failed for {}: {e}", diff_id.0));
                                                         match codex_cloud_tasks_client::CloudBackend::get_task_text(&*backend, diff_id.clone()).await {
                                                             Ok(text) => {
@@ -1499,6 +1883,21 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                 }
                                             });
                                         }
+                                        // Fetch the agent's plan, if any, for the top of the overlay
+                                        {
+                                            let backend = Arc::clone(&backend);
+                                            let tx = tx.clone();
+                                            let plan_id = id.clone();
+                                            tokio::spawn(async move {
+                                                match codex_cloud_tasks_client::CloudBackend::get_task_plan(&*backend, plan_id.clone()).await {
+                                                    Ok(Some(plan)) => {
+                                                        let _ = tx.send(app::AppEvent::DetailsPlanLoaded { id: plan_id, plan });
+                                                    }
+                                                    Ok(None) => {}
+                                                    Err(e) => append_error_log(format!("get_task_plan failed for {}: {e}", plan_id.0)),
+                                                }
+                                            });
+                                        }
                                         // Also fetch conversation text even when diff exists
                                         {
                                             let backend = Arc::clone(&backend);
@@ -1525,56 +1924,59 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                         let _ = frame_tx.send(Instant::now() + Duration::from_millis(100));
                                     }
                                 }
-                                KeyCode::Char('a') => {
-                                    if app.apply_inflight || app.apply_preflight_inflight {
-                                        app.status = "Finish the current apply/preflight before starting another.".to_string();
+                                Some(ListAction::OpenInBrowser) => {
+                                    if let Some(task) = app.tasks.get(app.selected) {
+                                        let url = util::task_url(&base_url, &task.id.0);
+                                        app.status = match webbrowser::open(&url) {
+                                            Ok(()) => format!("Opened {url}"),
+                                            // Headless or no browser configured: surface the URL instead.
+                                            Err(_) => format!("Open in browser: {url}"),
+                                        };
                                         needs_redraw = true;
-                                        continue;
                                     }
-
-                                    if let Some(task) = app.tasks.get(app.selected).cloned() {
-                                        match codex_cloud_tasks_client::CloudBackend::get_task_diff(&*backend, task.id.clone()).await {
-                                            Ok(Some(diff)) => {
-                                                let diff_override = Some(diff.clone());
-                                                let task_id = task.id.clone();
-                                                let title = task.title.clone();
-                                                let job = ApplyJob {
-                                                    task_id: task_id.clone(),
-                                                    diff_override: diff_override.clone(),
-                                                };
-                                                if spawn_preflight(
-                                                    &mut app,
-                                                    &backend,
-                                                    &tx,
-                                                    &frame_tx,
-                                                    title.clone(),
-                                                    job,
-                                                ) {
-                                                    app.apply_modal = Some(app::ApplyModalState {
-                                                        task_id,
-                                                        title: title.clone(),
-                                                        result_message: None,
-                                                        result_level: None,
-                                                        skipped_paths: Vec::new(),
-                                                        conflict_paths: Vec::new(),
-                                                        diff_override,
-                                                    });
-                                                    app.status = format!("Preflighting '{title}'...");
-                                                }
-                                            }
-                                            Ok(None) | Err(_) => {
-                                                app.status = "No diff available to apply".to_string();
-                                            }
-                                        }
+                                }
+                                Some(ListAction::CopyUrl) => {
+                                    if let Some(task) = app.tasks.get(app.selected) {
+                                        let url = util::task_url(&base_url, &task.id.0);
+                                        app.status = clipboard::copy_url(&url, clipboard::copy_to_clipboard);
                                         needs_redraw = true;
                                     }
                                 }
-                                _ => {}
+                                // Navigation, help, refresh, env, apply and quit are handled by `app::update`.
+                                Some(
+                                    ListAction::Apply
+                                    | ListAction::Quit
+                                    | ListAction::Next
+                                    | ListAction::Prev
+                                    | ListAction::Help
+                                    | ListAction::Refresh
+                                    | ListAction::SetEnv,
+                                )
+                                | None => {}
                             }
                         }
                         // Render after handling a key event (when not quitting).
                         render_if_needed(&mut terminal, &mut app, &mut needs_redraw)?;
                     }
+                    Some(Ok(Event::Mouse(mouse))) if mouse_capture => {
+                        // Wheel scrolling only applies to the env modal and the details overlay.
+                        let notches: i16 = match mouse.kind {
+                            MouseEventKind::ScrollDown => 1,
+                            MouseEventKind::ScrollUp => -1,
+                            _ => 0,
+                        };
+                        if notches != 0 {
+                            if let Some(m) = app.env_modal.as_mut() {
+                                m.selected = if notches > 0 { m.selected.saturating_add(1) } else { m.selected.saturating_sub(1) };
+                                needs_redraw = true;
+                            } else if app.apply_modal.is_none()
+                                && let Some(ov) = app.diff_overlay.as_mut()
+                            {
+                                ov.sd.scroll_by_wheel(notches);
+                                needs_redraw = true;
+                            }
+                        }
+                    }
                     Some(Ok(Event::Resize(_, _))) => {
                         needs_redraw = true;
                         // Redraw immediately on resize for snappier UX.
@@ -1589,10 +1991,15 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         }
     };
 
+    watch_poller.abort();
+
     // Restore terminal
     disable_raw_mode().ok();
     terminal.show_cursor().ok();
     let _ = crossterm::execute!(std::io::stdout(), DisableBracketedPaste);
+    if mouse_capture {
+        let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+    }
     // Best-effort restore of keyboard enhancement flags before leaving alt screen.
     let _ = crossterm::execute!(std::io::stdout(), PopKeyboardEnhancementFlags);
     let _ = crossterm::execute!(std::io::stdout(), LeaveAlternateScreen);
@@ -1605,6 +2012,96 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
 
 // extract_chatgpt_account_id moved to util.rs
 
+/// Poll a still-running task until its diff is available, the turn finishes
+/// without one, or [`app::FOLLOW_MAX_DURATION`] elapses. The handle is stored
+/// on the overlay and aborted when it closes.
+fn spawn_follow(
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+    id: codex_cloud_tasks_client::TaskId,
+    title: String,
+) -> tokio::task::JoinHandle<()> {
+    let backend = backend.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let started = Instant::now();
+        loop {
+            tokio::time::sleep(app::FOLLOW_POLL_INTERVAL).await;
+            if started.elapsed() >= app::FOLLOW_MAX_DURATION {
+                let _ = tx.send(app::AppEvent::DetailsFollowEnded {
+                    id,
+                    timed_out: true,
+                });
+                return;
+            }
+            match app::stream_diff(&*backend, id.clone(), &tx).await {
+                Ok(true) => break,
+                Ok(false) => {
+                    // Finished without a diff; refresh the conversation in place.
+                    if let Ok(text) =
+                        codex_cloud_tasks_client::CloudBackend::get_task_text(&*backend, id.clone())
+                            .await
+                    {
+                        let _ = tx.send(app::AppEvent::DetailsMessagesLoaded {
+                            id: id.clone(),
+                            title,
+                            messages: text.messages,
+                            prompt: text.prompt,
+                            turn_id: text.turn_id,
+                            sibling_turn_ids: text.sibling_turn_ids,
+                            attempt_placement: text.attempt_placement,
+                            attempt_status: text.attempt_status,
+                        });
+                    }
+                    break;
+                }
+                Err(codex_cloud_tasks_client::CloudTaskError::TaskStillRunning {
+                    status,
+                    latest_event,
+                }) => {
+                    let evt = app::AppEvent::DetailsStillRunning {
+                        id: id.clone(),
+                        status,
+                        latest_event,
+                    };
+                    if tx.send(evt).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    append_error_log(format!("follow poll failed for {}: {e}", id.0));
+                }
+            }
+        }
+        let _ = tx.send(app::AppEvent::DetailsFollowEnded {
+            id,
+            timed_out: false,
+        });
+    })
+}
+
+/// Post a notification for each watched task in `tasks` that just became
+/// ready or failed. Returns true when any notification was sent.
+fn notify_finished_watched(
+    app: &mut app::App,
+    tasks: &[codex_cloud_tasks_client::TaskSummary],
+) -> bool {
+    let finished = app.watch.observe(tasks);
+    if finished.is_empty() {
+        return false;
+    }
+    if let Err(e) = app.watch.save() {
+        append_error_log(format!("watch.save failed: {e}"));
+    }
+    for task in &finished {
+        let text = watch::notification_text(task);
+        append_error_log(format!("watch.notify: {}", task.id.0));
+        util::post_notification(&text);
+        app.status = text;
+    }
+    true
+}
+
 /// Build plain-text conversation lines: a labeled user prompt followed by assistant messages.
 fn conversation_lines(prompt: Option<String>, messages: &[String]) -> Vec<String> {
     let mut out: Vec<String> = Vec::new();
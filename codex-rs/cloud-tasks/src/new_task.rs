@@ -1,10 +1,18 @@
+use crate::prompt_history::HistoryRecall;
 use codex_tui::ComposerInput;
 
+/// Default cap on a single paste into the composer, in characters.
+pub const DEFAULT_MAX_PASTE_CHARS: usize = 100_000;
+
 pub struct NewTaskPage {
     pub composer: ComposerInput,
     pub submitting: bool,
     pub env_id: Option<String>,
     pub best_of_n: usize,
+    /// Up/Down position in the prompt history.
+    pub recall: HistoryRecall,
+    /// Prompt of the in-flight submission; saved to history once it succeeds.
+    pub submitted_prompt: Option<String>,
 }
 
 impl NewTaskPage {
@@ -14,22 +22,74 @@ impl NewTaskPage {
             ("⏎", "send"),
             ("Shift+⏎", "newline"),
             ("Ctrl+O", "env"),
-            ("Ctrl+N", "attempts"),
+            ("Ctrl+A", "attempts"),
             ("Ctrl+C", "quit"),
         ]);
+        composer.set_max_paste_chars(max_paste_chars_from_env());
         Self {
             composer,
             submitting: false,
             env_id,
             best_of_n,
+            recall: HistoryRecall::default(),
+            submitted_prompt: None,
         }
     }
 
+    /// True when closing the page would throw away typed or pasted text.
+    pub fn has_unsaved_content(&self) -> bool {
+        !self.composer.is_empty()
+    }
+
     // Additional helpers can be added as usage evolves.
 }
 
+/// Paste cap from `CODEX_CLOUD_TASKS_MAX_PASTE_CHARS`; `0` removes the cap and
+/// an unset or unparsable value uses [`DEFAULT_MAX_PASTE_CHARS`].
+fn max_paste_chars_from_env() -> Option<usize> {
+    parse_max_paste_chars(
+        std::env::var("CODEX_CLOUD_TASKS_MAX_PASTE_CHARS")
+            .ok()
+            .as_deref(),
+    )
+}
+
+fn parse_max_paste_chars(value: Option<&str>) -> Option<usize> {
+    match value.and_then(|v| v.trim().parse::<usize>().ok()) {
+        Some(0) => None,
+        Some(max) => Some(max),
+        None => Some(DEFAULT_MAX_PASTE_CHARS),
+    }
+}
+
+/// Next best-of choice for the Ctrl+A shortcut, cycling 1 → 2 → 4 → 1.
+pub fn next_attempts(current: usize) -> usize {
+    match current {
+        0 | 1 => 2,
+        2 | 3 => 4,
+        _ => 1,
+    }
+}
+
 impl Default for NewTaskPage {
     fn default() -> Self {
         Self::new(None, 1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn paste_cap_defaults_and_zero_disables() {
+        assert_eq!(parse_max_paste_chars(None), Some(DEFAULT_MAX_PASTE_CHARS));
+        assert_eq!(
+            parse_max_paste_chars(Some("junk")),
+            Some(DEFAULT_MAX_PASTE_CHARS)
+        );
+        assert_eq!(parse_max_paste_chars(Some(" 500 ")), Some(500));
+        assert_eq!(parse_max_paste_chars(Some("0")), None);
+    }
+}
@@ -29,8 +29,26 @@ pub struct ScrollableDiff {
     wrapped_src_idx: Vec<usize>,
     wrap_cols: Option<u16>,
     pub state: ScrollViewState,
+    /// Active search query; empty when no search is in effect.
+    pub search_query: String,
+    /// Raw line indices containing `search_query`, in document order.
+    pub search_matches: Vec<usize>,
+    /// Index into `search_matches` of the current match.
+    pub search_match_idx: usize,
+    /// Render a before/after line number gutter for diff content.
+    pub show_line_numbers: bool,
+    /// Before/after line numbers per raw line, derived from `@@` hunk headers.
+    line_numbers: Vec<(Option<usize>, Option<usize>)>,
 }
 
+/// Lines moved per mouse wheel notch.
+const WHEEL_SCROLL_LINES: i16 = 3;
+
+/// Width of one right-aligned line number column.
+const LINE_NUMBER_COLS: usize = 5;
+/// Gutter width: two number columns, each followed by a space.
+const GUTTER_COLS: u16 = (LINE_NUMBER_COLS as u16 + 1) * 2;
+
 impl ScrollableDiff {
     pub fn new() -> Self {
         Self::default()
@@ -38,12 +56,34 @@ impl ScrollableDiff {
 
     /// Replace the raw content lines. Does not rewrap immediately; call `set_width` next.
     pub fn set_content(&mut self, lines: Vec<String>) {
+        self.line_numbers = diff_line_numbers(&lines);
         self.raw = lines;
         self.wrapped.clear();
         self.wrapped_src_idx.clear();
         self.state.content_h = 0;
         // Force rewrap on next set_width even if width is unchanged
         self.wrap_cols = None;
+        // Keep an active search in sync with the new content.
+        self.search_matches = self.find(&self.search_query);
+        if self.search_match_idx >= self.search_matches.len() {
+            self.search_match_idx = 0;
+        }
+    }
+
+    /// Append a streamed piece of content that ends at a line boundary. Only
+    /// the new lines are wrapped unless the gutter appears with them.
+    pub fn append_chunk(&mut self, chunk: &str) {
+        let gutter_before = self.gutter_width();
+        let first_new = self.raw.len();
+        self.raw.extend(chunk.lines().map(str::to_string));
+        self.line_numbers = diff_line_numbers(&self.raw);
+        self.search_matches = self.find(&self.search_query);
+        match self.wrap_cols {
+            Some(width) if self.gutter_width() == gutter_before => {
+                self.wrap_from(first_new, width.saturating_sub(gutter_before));
+            }
+            _ => self.wrap_cols = None,
+        }
     }
 
     /// Set the wrap width. If changed, rebuild wrapped lines and clamp scroll.
@@ -52,10 +92,52 @@ impl ScrollableDiff {
             return;
         }
         self.wrap_cols = Some(width);
-        self.rewrap(width);
+        self.rewrap(width.saturating_sub(self.gutter_width()));
         self.state.clamp();
     }
 
+    /// Toggle the line number gutter; rewraps on the next `set_width`.
+    pub fn toggle_line_numbers(&mut self) {
+        self.show_line_numbers = !self.show_line_numbers;
+        self.wrap_cols = None;
+    }
+
+    /// Columns taken by the line number gutter; zero when hidden or when the
+    /// content has no hunks to number.
+    pub fn gutter_width(&self) -> u16 {
+        let has_numbers = self
+            .line_numbers
+            .iter()
+            .any(|(before, after)| before.is_some() || after.is_some());
+        if self.show_line_numbers && has_numbers {
+            GUTTER_COLS
+        } else {
+            0
+        }
+    }
+
+    /// Gutter text for a wrapped row, or None when the gutter is hidden.
+    /// Continuation rows of a wrapped line get a blank gutter.
+    pub fn gutter_for_row(&self, row: usize) -> Option<String> {
+        if self.gutter_width() == 0 {
+            return None;
+        }
+        let raw_idx = *self.wrapped_src_idx.get(row)?;
+        let first_row = row == 0 || self.wrapped_src_idx[row - 1] != raw_idx;
+        let (before, after) = if first_row {
+            self.line_numbers.get(raw_idx).copied().unwrap_or_default()
+        } else {
+            (None, None)
+        };
+        let fmt = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+        Some(format!(
+            "{:>w$} {:>w$} ",
+            fmt(before),
+            fmt(after),
+            w = LINE_NUMBER_COLS
+        ))
+    }
+
     /// Update viewport height and clamp scroll if needed.
     pub fn set_viewport(&mut self, height: u16) {
         self.state.viewport_h = height;
@@ -81,6 +163,11 @@ impl ScrollableDiff {
         self.state.scroll = s.clamp(0, self.max_scroll() as i32) as u16;
     }
 
+    /// Scroll by a number of mouse wheel notches (negative scrolls up).
+    pub fn scroll_by_wheel(&mut self, notches: i16) {
+        self.scroll_by(notches.saturating_mul(WHEEL_SCROLL_LINES));
+    }
+
     /// Page by a signed delta; typically viewport_h - 1.
     pub fn page_by(&mut self, delta: i16) {
         self.scroll_by(delta);
@@ -94,6 +181,116 @@ impl ScrollableDiff {
         self.state.scroll = self.max_scroll();
     }
 
+    /// Raw line indices whose text contains `query`. Empty for an empty query.
+    pub fn find(&self, query: &str) -> Vec<usize> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.raw
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains(query))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Start a new search and scroll to its first match.
+    pub fn set_search(&mut self, query: String) {
+        self.search_matches = self.find(&query);
+        self.search_query = query;
+        self.search_match_idx = 0;
+        self.scroll_to_match();
+    }
+
+    /// Clear the search query, matches, and highlights.
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.search_match_idx = 0;
+    }
+
+    /// Advance to the next match, wrapping around at the end.
+    pub fn next_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_idx = (self.search_match_idx + 1) % self.search_matches.len();
+        self.scroll_to_match();
+    }
+
+    /// Step back to the previous match, wrapping around at the start.
+    pub fn prev_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let len = self.search_matches.len();
+        self.search_match_idx = (self.search_match_idx + len - 1) % len;
+        self.scroll_to_match();
+    }
+
+    /// Scroll so the first wrapped row of the current match is at the top.
+    fn scroll_to_match(&mut self) {
+        let Some(&raw_idx) = self.search_matches.get(self.search_match_idx) else {
+            return;
+        };
+        self.state.scroll = self.row_for_raw(raw_idx).min(self.max_scroll());
+    }
+
+    /// Scroll to the next `@@` hunk header below the top of the viewport.
+    /// Returns false when there is no further hunk to move to.
+    pub fn next_hunk(&mut self) -> bool {
+        let scroll = self.state.scroll;
+        let Some(row) = self.hunk_rows().into_iter().find(|&row| row > scroll) else {
+            return false;
+        };
+        let target = row.min(self.max_scroll());
+        if target == scroll {
+            return false;
+        }
+        self.state.scroll = target;
+        true
+    }
+
+    /// Scroll to the previous `@@` hunk header above the top of the viewport.
+    /// Returns false when already at or before the first hunk.
+    pub fn prev_hunk(&mut self) -> bool {
+        let scroll = self.state.scroll;
+        let Some(row) = self.hunk_rows().into_iter().rfind(|&row| row < scroll) else {
+            return false;
+        };
+        self.state.scroll = row;
+        true
+    }
+
+    /// Current hunk (1-based, 0 before the first hunk) and total hunk count.
+    /// None when the content has no hunks.
+    pub fn hunk_position(&self) -> Option<(usize, usize)> {
+        let rows = self.hunk_rows();
+        if rows.is_empty() {
+            return None;
+        }
+        let current = rows.iter().filter(|&&row| row <= self.state.scroll).count();
+        Some((current, rows.len()))
+    }
+
+    /// Wrapped rows of the raw lines that start a hunk (`@@`).
+    fn hunk_rows(&self) -> Vec<u16> {
+        self.raw
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("@@"))
+            .map(|(idx, _)| self.row_for_raw(idx))
+            .collect()
+    }
+
+    /// First wrapped row rendered for a raw line index.
+    fn row_for_raw(&self, raw_idx: usize) -> u16 {
+        self.wrapped_src_idx
+            .iter()
+            .position(|&idx| idx == raw_idx)
+            .unwrap_or(raw_idx) as u16
+    }
+
     /// Optional percent scrolled; None when not enough geometry is known.
     pub fn percent_scrolled(&self) -> Option<u8> {
         if self.state.content_h == 0 || self.state.viewport_h == 0 {
@@ -112,15 +309,26 @@ impl ScrollableDiff {
     }
 
     fn rewrap(&mut self, width: u16) {
+        self.wrapped.clear();
+        self.wrapped_src_idx.clear();
+        self.wrap_from(0, width);
+    }
+
+    /// Wrap raw lines from `first_raw` on and append them to the wrapped
+    /// lines, which must already cover every earlier raw line.
+    fn wrap_from(&mut self, first_raw: usize, width: u16) {
         if width == 0 {
-            self.wrapped = self.raw.clone();
+            for (raw_idx, raw) in self.raw.iter().enumerate().skip(first_raw) {
+                self.wrapped.push(raw.clone());
+                self.wrapped_src_idx.push(raw_idx);
+            }
             self.state.content_h = self.wrapped.len() as u16;
             return;
         }
         let max_cols = width as usize;
         let mut out: Vec<String> = Vec::new();
         let mut out_idx: Vec<usize> = Vec::new();
-        for (raw_idx, raw) in self.raw.iter().enumerate() {
+        for (raw_idx, raw) in self.raw.iter().enumerate().skip(first_raw) {
             // Normalize tabs for width accounting (MVP: 4 spaces).
             let raw = raw.replace('\t', "    ");
             if raw.is_empty() {
@@ -169,8 +377,265 @@ impl ScrollableDiff {
                 out_idx.push(raw_idx);
             }
         }
-        self.wrapped = out;
-        self.wrapped_src_idx = out_idx;
+        self.wrapped.extend(out);
+        self.wrapped_src_idx.extend(out_idx);
         self.state.content_h = self.wrapped.len() as u16;
     }
 }
+
+/// Before/after line numbers for each line of a unified diff. Lines outside
+/// hunks (file headers, `@@` lines) have no numbers.
+fn diff_line_numbers(lines: &[String]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut out = Vec::with_capacity(lines.len());
+    let mut next: Option<(usize, usize)> = None;
+    for line in lines {
+        if line.starts_with("@@") {
+            next = parse_hunk_header(line);
+            out.push((None, None));
+            continue;
+        }
+        if line.starts_with("diff ") {
+            next = None;
+        }
+        let Some((before, after)) = next.as_mut() else {
+            out.push((None, None));
+            continue;
+        };
+        match line.chars().next() {
+            Some('+') => {
+                out.push((None, Some(*after)));
+                *after += 1;
+            }
+            Some('-') => {
+                out.push((Some(*before), None));
+                *before += 1;
+            }
+            Some(' ') | None => {
+                out.push((Some(*before), Some(*after)));
+                *before += 1;
+                *after += 1;
+            }
+            // e.g. "\ No newline at end of file"
+            Some(_) => out.push((None, None)),
+        }
+    }
+    out
+}
+
+/// Parse the starting line numbers from `@@ -a,b +c,d @@`.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let mut parts = line.split_whitespace().skip(1);
+    let start = |part: Option<&str>, sign: char| -> Option<usize> {
+        part?.strip_prefix(sign)?.split(',').next()?.parse().ok()
+    };
+    let before = start(parts.next(), '-')?;
+    let after = start(parts.next(), '+')?;
+    Some((before, after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn diff_with_three_hunks() -> ScrollableDiff {
+        let mut sd = ScrollableDiff::new();
+        sd.set_content(
+            [
+                "diff --git a/src/lib.rs b/src/lib.rs",
+                "--- a/src/lib.rs",
+                "+++ b/src/lib.rs",
+                "@@ -1,2 +1,2 @@",
+                "-old one",
+                "+new one",
+                "@@ -10,2 +10,2 @@",
+                "-old two",
+                "+new two",
+                "@@ -20,2 +20,2 @@",
+                "-old three",
+                "+new three",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        );
+        sd.set_width(80);
+        sd.set_viewport(2);
+        sd
+    }
+
+    #[test]
+    fn find_returns_each_hunk_header() {
+        let sd = diff_with_three_hunks();
+        assert_eq!(sd.find("@@"), vec![3, 6, 9]);
+        assert_eq!(sd.find(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn next_and_prev_match_wrap_and_scroll() {
+        let mut sd = diff_with_three_hunks();
+        sd.set_search("@@".to_string());
+        assert_eq!(sd.state.scroll, 3);
+
+        sd.next_match();
+        assert_eq!((sd.search_match_idx, sd.state.scroll), (1, 6));
+        sd.next_match();
+        sd.next_match();
+        assert_eq!((sd.search_match_idx, sd.state.scroll), (0, 3));
+        sd.prev_match();
+        assert_eq!((sd.search_match_idx, sd.state.scroll), (2, 9));
+
+        sd.clear_search();
+        assert_eq!(sd.search_query, "");
+        assert_eq!(sd.search_matches, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn line_numbers_track_after_side_for_added_lines() {
+        let mut sd = diff_with_three_hunks();
+        assert_eq!(sd.gutter_for_row(5), None);
+
+        sd.toggle_line_numbers();
+        sd.set_width(80);
+        let first_added = sd
+            .wrapped_lines()
+            .iter()
+            .position(|l| l.starts_with('+') && !l.starts_with("+++"))
+            .expect("added line");
+        assert_eq!(sd.wrapped_lines()[first_added], "+new one");
+        assert_eq!(
+            sd.gutter_for_row(first_added),
+            Some("          1 ".to_string())
+        );
+        // "-old two" sits at line 10 of the old file.
+        assert_eq!(sd.gutter_for_row(7), Some("   10       ".to_string()));
+        assert_eq!(sd.gutter_for_row(3), Some(" ".repeat(12)));
+    }
+
+    #[test]
+    fn diff_line_numbers_follow_context_across_hunks() {
+        let lines: Vec<String> = [
+            "--- a/f",
+            "+++ b/f",
+            "@@ -3,3 +3,4 @@",
+            " keep",
+            "-drop",
+            "+add one",
+            "+add two",
+            " tail",
+            "@@ -40,2 +41,1 @@",
+            " ctx",
+            "-gone",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+        assert_eq!(
+            diff_line_numbers(&lines),
+            vec![
+                (None, None),
+                (None, None),
+                (None, None),
+                (Some(3), Some(3)),
+                (Some(4), None),
+                (None, Some(4)),
+                (None, Some(5)),
+                (Some(5), Some(6)),
+                (None, None),
+                (Some(40), Some(41)),
+                (Some(41), None),
+            ]
+        );
+        assert_eq!(parse_hunk_header("@@ -7 +9 @@ fn main()"), Some((7, 9)));
+        assert_eq!(parse_hunk_header("@@ bogus @@"), None);
+    }
+
+    #[test]
+    fn gutter_narrows_the_wrap_width() {
+        let mut sd = ScrollableDiff::new();
+        sd.set_content(vec![
+            "@@ -1 +1 @@".to_string(),
+            format!("+{}", "x".repeat(19)),
+        ]);
+        sd.set_width(20);
+        assert_eq!(sd.wrapped_lines().len(), 2);
+
+        sd.toggle_line_numbers();
+        sd.set_width(20);
+        // 20 columns less the 12-column gutter leaves 8 for content, so both
+        // the header and the added line wrap.
+        assert_eq!(sd.wrapped_lines().len(), 5);
+        assert_eq!(sd.gutter_for_row(2), Some("          1 ".to_string()));
+        assert_eq!(sd.gutter_for_row(3), Some(" ".repeat(12)));
+    }
+
+    #[test]
+    fn wheel_scroll_clamps_at_top_and_bottom() {
+        let mut sd = diff_with_three_hunks();
+        // 12 lines in a 2-line viewport: max scroll is 10.
+        sd.scroll_by_wheel(-1);
+        assert_eq!(sd.state.scroll, 0);
+
+        sd.scroll_by_wheel(1);
+        assert_eq!(sd.state.scroll, 3);
+        sd.scroll_by_wheel(5);
+        assert_eq!(sd.state.scroll, 10);
+
+        sd.scroll_by_wheel(-1);
+        assert_eq!(sd.state.scroll, 7);
+        sd.scroll_by_wheel(i16::MIN);
+        assert_eq!(sd.state.scroll, 0);
+    }
+
+    #[test]
+    fn next_hunk_stops_after_last_hunk() {
+        let mut sd = ScrollableDiff::new();
+        let mut lines = vec!["--- a/f".to_string(), "+++ b/f".to_string()];
+        for n in 1..=4 {
+            lines.push(format!("@@ -{n},1 +{n},1 @@"));
+            lines.push(format!("-old {n}"));
+            lines.push(format!("+new {n}"));
+        }
+        sd.set_content(lines);
+        sd.set_width(80);
+        sd.set_viewport(2);
+        assert_eq!(sd.hunk_position(), Some((0, 4)));
+
+        for expected in 1..=4 {
+            assert!(sd.next_hunk());
+            assert_eq!(sd.hunk_position(), Some((expected, 4)));
+        }
+        assert!(!sd.next_hunk());
+
+        assert!(sd.prev_hunk());
+        assert_eq!(sd.hunk_position(), Some((3, 4)));
+    }
+
+    #[test]
+    fn appended_chunks_wrap_like_whole_content() {
+        let first = "diff --git a/a b/a\n@@ -1 +1 @@\n-old line that wraps\n";
+        let second = "diff --git a/b b/b\n@@ -3 +3 @@\n+new line that wraps too\n";
+
+        let mut streamed = ScrollableDiff::new();
+        streamed.toggle_line_numbers();
+        streamed.set_width(24);
+        streamed.append_chunk(first);
+        streamed.set_width(24);
+        streamed.append_chunk(second);
+        streamed.set_width(24);
+
+        let mut whole = ScrollableDiff::new();
+        whole.toggle_line_numbers();
+        whole.set_content(
+            format!("{first}{second}")
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        );
+        whole.set_width(24);
+
+        assert_eq!(streamed.wrapped_lines(), whole.wrapped_lines());
+        assert_eq!(streamed.wrapped_src_indices(), whole.wrapped_src_indices());
+        assert_eq!(streamed.gutter_for_row(4), whole.gutter_for_row(4));
+    }
+}
@@ -24,6 +24,8 @@ use chrono::Local;
 use chrono::Utc;
 use codex_cloud_tasks_client::AttemptStatus;
 use codex_cloud_tasks_client::TaskStatus;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_tui::render_markdown_text;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
@@ -55,6 +57,12 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     if app.apply_modal.is_some() {
         draw_apply_modal(frame, area, app);
     }
+    if app.confirm_discard_draft {
+        draw_discard_draft_modal(frame, area);
+    }
+    if app.help_overlay.is_some() {
+        draw_help_overlay(frame, area, app);
+    }
 }
 
 // ===== Overlay helpers (geometry + styling) =====
@@ -252,10 +260,14 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &mut App) {
             help.push("a".dim());
             help.push(": Apply  ".dim());
         }
+        if ov.loaded_diff().is_some() {
+            help.push("y".dim());
+            help.push(": Copy diff  ".dim());
+        }
         if ov.attempt_count() > 1 {
             help.push("Tab".dim());
             help.push(": Next attempt  ".dim());
-            help.push("[ ]".dim());
+            help.push("{ }".dim());
             help.push(": Cycle attempts  ".dim());
         }
     } else {
@@ -264,11 +276,12 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &mut App) {
     }
     help.push("o : Set Env  ".dim());
     if app.new_task.is_some() {
-        help.push("Ctrl+N".dim());
+        help.push("Ctrl+A".dim());
         help.push(format!(": Attempts {}x  ", app.best_of_n).dim());
         help.push("(editing new task)  ".dim());
     } else {
         help.push("n : New Task  ".dim());
+        help.push("? : Help  ".dim());
     }
     help.extend(vec!["q".dim(), ": Quit  ".dim()]);
     // Split footer area into two rows: help+spinner (top) and status (bottom)
@@ -304,6 +317,13 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &mut App) {
         status_line.truncate(2000);
         status_line.push('…');
     }
+    if let Some(remaining) = app
+        .auto_refresh
+        .as_ref()
+        .and_then(|auto| auto.remaining(Instant::now()))
+    {
+        status_line.push_str(&format!("  •  auto-refresh in {}s", remaining.as_secs()));
+    }
     // Clear the status row to avoid trailing characters when the message shrinks.
     frame.render_widget(Clear, rows[1]);
     let status = Paragraph::new(status_line);
@@ -332,6 +352,10 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
         .as_ref()
         .map(|o| o.title.clone())
         .unwrap_or_default();
+    let diff_loading = app
+        .diff_overlay
+        .as_ref()
+        .is_some_and(|o| o.diff_loading && o.showing_diff() && o.selected_attempt == 0);
 
     // Title block
     let title_ref = title.as_str();
@@ -342,7 +366,7 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
             " ".into(),
             title_ref.magenta(),
         ]
-    } else if ov_can_apply {
+    } else if ov_can_apply || diff_loading {
         vec!["Diff: ".magenta(), title_ref.magenta()]
     } else {
         vec!["Details: ".magenta(), title_ref.magenta()]
@@ -355,6 +379,10 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
         title_spans.push("  • ".dim());
         title_spans.push(format!("{p}%").dim());
     }
+    if diff_loading {
+        title_spans.push("  • ".dim());
+        title_spans.push("loading…".dim());
+    }
     frame.render_widget(Clear, inner);
     frame.render_widget(
         overlay_block().title(Line::from(title_spans)).clone(),
@@ -411,18 +439,70 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
                         .bold()
                         .dim(),
                     "  ".into(),
-                    "(Tab/Shift-Tab or [ ] to cycle attempts)".dim(),
+                    "(Tab/Shift-Tab or { } to cycle attempts)".dim(),
+                ]);
+            }
+            if matches!(ov.current_view, crate::app::DetailView::Diff)
+                && let Some((current, total)) = ov.sd.hunk_position()
+            {
+                spans.extend(vec![
+                    "  ".into(),
+                    format!("Hunk {current}/{total}").bold().dim(),
+                    "  ".into(),
+                    "([ ] to jump)".dim(),
                 ]);
             }
             frame.render_widget(Paragraph::new(Line::from(spans)), rows[0]);
-            ov.sd.set_width(rows[1].width);
-            ov.sd.set_viewport(rows[1].height);
             content_area = rows[1];
         } else {
-            ov.sd.set_width(content_full.width);
-            ov.sd.set_viewport(content_full.height);
             content_area = content_full;
         }
+        // Agent plan above the content, capped at half of the remaining height
+        if let Some(plan) = ov.plan.as_ref().filter(|p| !p.plan.is_empty()) {
+            let lines = plan_lines(plan, ov.plan_expanded);
+            let height = (lines.len() as u16).min(content_area.height / 2);
+            if height > 0 {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(height), Constraint::Min(1)])
+                    .split(content_area);
+                frame.render_widget(Paragraph::new(lines), rows[0]);
+                content_area = rows[1];
+            }
+        }
+        // Search bar along the bottom while typing or while a query is active
+        if ov.search_editing || !ov.sd.search_query.is_empty() {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .split(content_area);
+            frame.render_widget(Paragraph::new(search_bar_line(ov)), rows[1]);
+            content_area = rows[0];
+        }
+        // Follow bar while polling a task that is still running
+        if let Some(status) = ov.follow_status.as_deref() {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .split(content_area);
+            let line = Line::from(vec![
+                "Following ".cyan().bold(),
+                status.to_string().into(),
+                format!(
+                    "  (checking every {}s)",
+                    crate::app::FOLLOW_POLL_INTERVAL.as_secs()
+                )
+                .dim(),
+            ]);
+            frame.render_widget(Paragraph::new(line), rows[1]);
+            content_area = rows[0];
+        }
+        ov.sd.set_width(content_area.width);
+        ov.sd.set_viewport(content_area.height);
+        // Keep the newest lines in view while the diff streams in.
+        if diff_loading {
+            ov.sd.to_bottom();
+        }
     }
 
     // Styled content render
@@ -433,17 +513,48 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
         .map(|o| matches!(o.current_view, crate::app::DetailView::Diff))
         .unwrap_or(false);
     let styled_lines: Vec<Line<'static>> = if is_diff_view {
-        let raw = app.diff_overlay.as_ref().map(|o| o.sd.wrapped_lines());
-        raw.unwrap_or(&[])
-            .iter()
-            .map(|l| style_diff_line(l))
-            .collect()
+        app.diff_overlay
+            .as_ref()
+            .map(|o| {
+                o.sd.wrapped_lines()
+                    .iter()
+                    .zip(o.sd.wrapped_src_indices())
+                    .map(|(row, &src)| style_diff_line(row, o.sd.raw_line_at(src)))
+                    .collect()
+            })
+            .unwrap_or_default()
     } else {
         app.diff_overlay
             .as_ref()
             .map(|o| style_conversation_lines(&o.sd, o.current_attempt()))
             .unwrap_or_default()
     };
+    let search_query = app
+        .diff_overlay
+        .as_ref()
+        .map(|o| o.sd.search_query.as_str())
+        .unwrap_or("");
+    let styled_lines: Vec<Line<'static>> = if search_query.is_empty() {
+        styled_lines
+    } else {
+        styled_lines
+            .into_iter()
+            .map(|line| highlight_matches(line, search_query))
+            .collect()
+    };
+    // Line number gutter goes on last so search highlighting never touches it.
+    let styled_lines: Vec<Line<'static>> = match app.diff_overlay.as_ref() {
+        Some(ov) if is_diff_view && ov.sd.gutter_width() > 0 => styled_lines
+            .into_iter()
+            .enumerate()
+            .map(|(row, mut line)| {
+                let gutter = ov.sd.gutter_for_row(row).unwrap_or_default();
+                line.spans.insert(0, gutter.dim());
+                line
+            })
+            .collect(),
+        _ => styled_lines,
+    };
     let raw_empty = app
         .diff_overlay
         .as_ref()
@@ -467,6 +578,98 @@ fn draw_diff_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
+/// Bottom bar for the details search: the input while typing, else the match position.
+fn search_bar_line(ov: &crate::app::DiffOverlay) -> Line<'static> {
+    let query = ov.sd.search_query.clone();
+    if ov.search_editing {
+        return Line::from(vec![
+            "/".magenta().bold(),
+            query.into(),
+            "█".dim(),
+            "  (Enter to search, Esc to cancel)".dim(),
+        ]);
+    }
+    let position = if ov.sd.search_matches.is_empty() {
+        "no matches".to_string()
+    } else {
+        format!(
+            "match {}/{}",
+            ov.sd.search_match_idx + 1,
+            ov.sd.search_matches.len()
+        )
+    };
+    Line::from(vec![
+        "/".magenta().bold(),
+        query.into(),
+        "  ".into(),
+        position.dim(),
+        "  (n/N next/prev, Esc to clear)".dim(),
+    ])
+}
+
+/// Split spans so every occurrence of `query` is rendered reversed.
+fn highlight_matches(line: Line<'static>, query: &str) -> Line<'static> {
+    use ratatui::text::Span;
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for span in line.spans {
+        let text = span.content.as_ref();
+        if !text.contains(query) {
+            spans.push(span);
+            continue;
+        }
+        let mut rest = text;
+        while let Some(pos) = rest.find(query) {
+            if pos > 0 {
+                spans.push(Span::styled(rest[..pos].to_string(), span.style));
+            }
+            let end = pos + query.len();
+            spans.push(Span::styled(
+                rest[pos..end].to_string(),
+                span.style.add_modifier(Modifier::REVERSED),
+            ));
+            rest = &rest[end..];
+        }
+        if !rest.is_empty() {
+            spans.push(Span::styled(rest.to_string(), span.style));
+        }
+    }
+    Line { spans, ..line }
+}
+
+fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &mut App) {
+    let inner = overlay_outer(area);
+    let Some(help) = app.help_overlay.as_mut() else {
+        return;
+    };
+    let mut title_spans: Vec<ratatui::text::Span> = vec!["Keybindings".magenta().bold()];
+    if let Some(p) = help.sd.percent_scrolled() {
+        title_spans.push("  • ".dim());
+        title_spans.push(format!("{p}%").dim());
+    }
+    title_spans.push("  (Esc/q to close)".dim());
+    frame.render_widget(Clear, inner);
+    frame.render_widget(overlay_block().title(Line::from(title_spans)), inner);
+
+    let content = overlay_content(inner);
+    help.sd.set_width(content.width);
+    help.sd.set_viewport(content.height);
+    let lines: Vec<Line<'static>> = help
+        .sd
+        .wrapped_lines()
+        .iter()
+        .map(|l| {
+            if l.starts_with(' ') || l.is_empty() {
+                Line::from(l.clone())
+            } else {
+                Line::from(l.clone().cyan().bold())
+            }
+        })
+        .collect();
+    let para = Paragraph::new(Text::from(lines)).scroll((help.sd.state.scroll, 0));
+    frame.render_widget(para, content);
+}
+
 pub fn draw_apply_modal(frame: &mut Frame, area: Rect, app: &mut App) {
     use ratatui::widgets::Wrap;
     let inner = overlay_outer(area);
@@ -518,7 +721,23 @@ pub fn draw_apply_modal(frame: &mut Frame, area: Rect, app: &mut App) {
             // On partial or error, show conflicts/skips if present
             if !matches!(m.result_level, Some(crate::app::ApplyResultLevel::Success)) {
                 use ratatui::text::Span;
-                if !m.conflict_paths.is_empty() {
+                if !m.merged_with_conflicts.is_empty() {
+                    body_lines.push(Line::from(""));
+                    body_lines.push(
+                        Line::from(format!(
+                            "Conflict markers were written to {} file(s); resolve them before committing.",
+                            m.merged_with_conflicts.len()
+                        ))
+                        .red()
+                        .bold(),
+                    );
+                    for p in &m.merged_with_conflicts {
+                        body_lines.push(Line::from(vec![
+                            "  • ".into(),
+                            Span::raw(p.display().to_string()).dim(),
+                        ]));
+                    }
+                } else if !m.conflict_paths.is_empty() {
                     body_lines.push(Line::from(""));
                     body_lines.push(
                         Line::from(format!("Conflicts ({}):", m.conflict_paths.len()))
@@ -748,42 +967,63 @@ fn attempt_status_span(status: AttemptStatus) -> Option<ratatui::text::Span<'sta
     }
 }
 
-fn style_diff_line(raw: &str) -> Line<'static> {
-    use ratatui::style::Color;
-    use ratatui::style::Modifier;
-    use ratatui::style::Style;
-    use ratatui::text::Span;
+static DIFF_COLOR: OnceLock<bool> = OnceLock::new();
 
-    if raw.starts_with("@@") {
-        return Line::from(vec![Span::styled(
-            raw.to_string(),
-            Style::default()
-                .fg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
-        )]);
-    }
-    if raw.starts_with("+++") || raw.starts_with("---") {
-        return Line::from(vec![Span::styled(
-            raw.to_string(),
-            Style::default().add_modifier(Modifier::DIM),
-        )]);
-    }
-    if raw.starts_with('+') {
-        return Line::from(vec![Span::styled(
-            raw.to_string(),
-            Style::default().fg(Color::Green),
-        )]);
+fn diff_color_enabled() -> bool {
+    *DIFF_COLOR.get_or_init(|| std::io::IsTerminal::is_terminal(&std::io::stdout()))
+}
+
+/// Semantic category of a unified diff line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLineKind {
+    FileHeader,
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+fn classify_diff_line(raw: &str) -> DiffLineKind {
+    const FILE_HEADER_PREFIXES: [&str; 8] = [
+        "diff ",
+        "index ",
+        "+++",
+        "---",
+        "new file mode",
+        "deleted file mode",
+        "rename from",
+        "rename to",
+    ];
+    if FILE_HEADER_PREFIXES.iter().any(|p| raw.starts_with(p)) {
+        DiffLineKind::FileHeader
+    } else if raw.starts_with("@@") {
+        DiffLineKind::Hunk
+    } else if raw.starts_with('+') {
+        DiffLineKind::Added
+    } else if raw.starts_with('-') {
+        DiffLineKind::Removed
+    } else {
+        DiffLineKind::Context
     }
-    if raw.starts_with('-') {
-        return Line::from(vec![Span::styled(
-            raw.to_string(),
-            Style::default().fg(Color::Red),
-        )]);
+}
+
+/// Style a wrapped diff row. `src` is the unwrapped line it came from, so
+/// continuation rows keep the color of their line.
+fn style_diff_line(row: &str, src: &str) -> Line<'static> {
+    if !diff_color_enabled() {
+        return Line::from(row.to_string());
     }
-    Line::from(vec![Span::raw(raw.to_string())])
+    let style = match classify_diff_line(src) {
+        DiffLineKind::FileHeader => Style::default().add_modifier(Modifier::BOLD),
+        DiffLineKind::Hunk => Style::default().fg(Color::Cyan),
+        DiffLineKind::Added => Style::default().fg(Color::Green),
+        DiffLineKind::Removed => Style::default().fg(Color::Red),
+        DiffLineKind::Context => Style::default(),
+    };
+    Line::from(Span::styled(row.to_string(), style))
 }
 
-fn render_task_item(_app: &App, t: &codex_cloud_tasks_client::TaskSummary) -> ListItem<'static> {
+fn render_task_item(app: &App, t: &codex_cloud_tasks_client::TaskSummary) -> ListItem<'static> {
     let status = match t.status {
         TaskStatus::Ready => "READY".green(),
         TaskStatus::Pending => "PENDING".magenta(),
@@ -791,13 +1031,17 @@ fn render_task_item(_app: &App, t: &codex_cloud_tasks_client::TaskSummary) -> Li
         TaskStatus::Error => "ERROR".red(),
     };
 
-    // Title line: [STATUS] Title
-    let title = Line::from(vec![
-        "[".into(),
-        status,
-        "] ".into(),
-        t.title.clone().into(),
-    ]);
+    // Title line: [STATUS] Title  +N/-M files:K
+    let mut title_spans = vec!["[".into(), status, "] ".into(), t.title.clone().into()];
+    if let Some(stat) = format_diff_stat(&t.summary) {
+        title_spans.push("  ".into());
+        title_spans.push(stat.dim());
+    }
+    if app.watch.is_watched(&t.id) {
+        title_spans.push("  ".into());
+        title_spans.push("watching".cyan());
+    }
+    let title = Line::from(title_spans);
 
     // Meta line: environment label and relative time (dim)
     let mut meta: Vec<ratatui::text::Span> = Vec::new();
@@ -841,6 +1085,28 @@ fn render_task_item(_app: &App, t: &codex_cloud_tasks_client::TaskSummary) -> Li
     ListItem::new(vec![title, meta_line, sub, spacer])
 }
 
+/// Compact `+N/-M files:K` change stat, or None when nothing changed.
+fn format_diff_stat(summary: &codex_cloud_tasks_client::DiffSummary) -> Option<String> {
+    if summary.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "+{}/-{} files:{}",
+        compact_count(summary.lines_added),
+        compact_count(summary.lines_removed),
+        compact_count(summary.files_changed)
+    ))
+}
+
+/// Abbreviate counts of 10,000 and up (`12k`, `3.4M`) to keep rows narrow.
+fn compact_count(n: usize) -> String {
+    match n {
+        0..10_000 => n.to_string(),
+        10_000..1_000_000 => format!("{}k", n / 1_000),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
 fn format_relative_time(ts: chrono::DateTime<Utc>) -> String {
     let now = Utc::now();
     let mut secs = (now - ts).num_seconds();
@@ -1011,6 +1277,28 @@ pub fn draw_env_modal(frame: &mut Frame, area: Rect, app: &mut App) {
     frame.render_stateful_widget(list, rows[2], &mut list_state);
 }
 
+fn draw_discard_draft_modal(frame: &mut Frame, area: Rect) {
+    let inner = overlay_outer(area);
+    let modal_width = inner.width.min(36);
+    let modal_height = inner.height.min(6);
+    let modal_x = inner.x + (inner.width.saturating_sub(modal_width)) / 2;
+    let modal_y = inner.y + (inner.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+    let block = overlay_block().title(Line::from("Discard draft?".magenta().bold()));
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(block, modal_area);
+    let hint = Line::from(vec![
+        "y".bold(),
+        " discard  ".dim(),
+        "n".bold(),
+        "/".dim(),
+        "Esc".bold(),
+        " keep editing".dim(),
+    ]);
+    frame.render_widget(Paragraph::new(hint), overlay_content(modal_area));
+}
+
 pub fn draw_best_of_modal(frame: &mut Frame, area: Rect, app: &mut App) {
     use ratatui::widgets::Wrap;
 
@@ -1065,3 +1353,121 @@ pub fn draw_best_of_modal(frame: &mut Frame, area: Rect, app: &mut App) {
         .block(Block::default().borders(Borders::NONE));
     frame.render_stateful_widget(list, rows[1], &mut list_state);
 }
+
+fn plan_lines(plan: &UpdatePlanArgs, expanded: bool) -> Vec<Line<'static>> {
+    let marker = if expanded { "▾ " } else { "▸ " };
+    let completed = plan
+        .plan
+        .iter()
+        .filter(|step| step.status == StepStatus::Completed)
+        .count();
+    let mut lines = vec![Line::from(vec![
+        marker.cyan(),
+        "Plan".cyan().bold(),
+        format!(" ({completed}/{} done)", plan.plan.len()).dim(),
+        "  (p to collapse/expand)".dim(),
+    ])];
+    if !expanded {
+        return lines;
+    }
+    if let Some(explanation) = &plan.explanation {
+        lines.push(Line::from(format!("  {explanation}").dim().italic()));
+    }
+    for step in &plan.plan {
+        let line = match step.status {
+            StepStatus::Completed => {
+                Line::from(vec!["  ✔ ".green(), step.step.clone().dim().crossed_out()])
+            }
+            StepStatus::InProgress => {
+                Line::from(vec!["  ▢ ".cyan(), step.step.clone().cyan().bold()])
+            }
+            StepStatus::Pending => Line::from(vec!["  ▢ ".dim(), step.step.clone().dim()]),
+        };
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_diff_stat_handles_zero_and_large_counts() {
+        use codex_cloud_tasks_client::DiffSummary;
+
+        assert_eq!(format_diff_stat(&DiffSummary::default()), None);
+        let small = DiffSummary {
+            files_changed: 3,
+            lines_added: 12,
+            lines_removed: 0,
+        };
+        assert_eq!(format_diff_stat(&small).as_deref(), Some("+12/-0 files:3"));
+        let large = DiffSummary {
+            files_changed: 9_999,
+            lines_added: 123_456,
+            lines_removed: 2_500_000,
+        };
+        assert_eq!(
+            format_diff_stat(&large).as_deref(),
+            Some("+123k/-2.5M files:9999")
+        );
+    }
+
+    #[test]
+    fn classify_diff_line_covers_each_kind() {
+        let cases = [
+            ("diff --git a/f b/f", DiffLineKind::FileHeader),
+            ("index 1234567..89abcde 100644", DiffLineKind::FileHeader),
+            ("--- a/f", DiffLineKind::FileHeader),
+            ("+++ b/f", DiffLineKind::FileHeader),
+            ("@@ -1,2 +1,3 @@ fn main()", DiffLineKind::Hunk),
+            ("+let x = 1;", DiffLineKind::Added),
+            ("-let x = 0;", DiffLineKind::Removed),
+            (" unchanged", DiffLineKind::Context),
+            ("", DiffLineKind::Context),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(classify_diff_line(line), expected, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn plan_lines_show_progress_and_collapse() {
+        use codex_protocol::plan_tool::PlanItemArg;
+
+        let plan = UpdatePlanArgs {
+            explanation: Some("Fix the parser first".to_string()),
+            plan: vec![
+                PlanItemArg {
+                    step: "Reproduce".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "Patch".to_string(),
+                    status: StepStatus::InProgress,
+                },
+            ],
+        };
+        let text = |lines: Vec<Line<'static>>| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        assert_eq!(
+            text(plan_lines(&plan, true)),
+            vec![
+                "▾ Plan (1/2 done)  (p to collapse/expand)",
+                "  Fix the parser first",
+                "  ✔ Reproduce",
+                "  ▢ Patch",
+            ]
+        );
+        assert_eq!(
+            text(plan_lines(&plan, false)),
+            vec!["▸ Plan (1/2 done)  (p to collapse/expand)"]
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use base64::Engine as _;
 use chrono::Utc;
-use reqwest::header::HeaderMap;
+
+pub use codex_cloud_tasks_client::configured_base_url;
+pub use codex_cloud_tasks_client::extract_chatgpt_account_id;
 
 pub fn set_user_agent_suffix(suffix: &str) {
     if let Ok(mut guard) = codex_core::default_client::USER_AGENT_SUFFIX.lock() {
@@ -8,18 +9,37 @@ pub fn set_user_agent_suffix(suffix: &str) {
     }
 }
 
-pub fn append_error_log(message: impl AsRef<str>) {
-    let ts = Utc::now().to_rfc3339();
-    if let Ok(mut f) = std::fs::OpenOptions::new()
+/// Open `error.log` in the working directory for appending.
+pub fn open_error_log() -> std::io::Result<std::fs::File> {
+    std::fs::OpenOptions::new()
         .create(true)
         .append(true)
         .open("error.log")
-    {
+}
+
+pub fn append_error_log(message: impl AsRef<str>) {
+    let ts = Utc::now().to_rfc3339();
+    if let Ok(mut f) = open_error_log() {
         use std::io::Write as _;
         let _ = writeln!(f, "[{ts}] {}", message.as_ref());
     }
 }
 
+/// Ask the terminal to show a desktop notification (OSC 9). Control
+/// characters are dropped so the message cannot end the sequence early.
+pub fn post_notification(message: &str) {
+    use std::io::Write as _;
+    let message: String = message.chars().filter(|c| !c.is_control()).collect();
+    let mut out = std::io::stdout();
+    let _ = write!(out, "\x1b]9;{message}\x07");
+    let _ = out.flush();
+}
+
+/// [`configured_base_url`] in the canonical form used for direct backend requests.
+pub fn cloud_base_url() -> String {
+    normalize_base_url(&configured_base_url())
+}
+
 /// Normalize the configured base URL to a canonical form used by the backend client.
 /// - trims trailing '/'
 /// - appends '/backend-api' for ChatGPT hosts when missing
@@ -37,61 +57,6 @@ pub fn normalize_base_url(input: &str) -> String {
     base_url
 }
 
-/// Extract the ChatGPT account id from a JWT token, when present.
-pub fn extract_chatgpt_account_id(token: &str) -> Option<String> {
-    let mut parts = token.split('.');
-    let (_h, payload_b64, _s) = match (parts.next(), parts.next(), parts.next()) {
-        (Some(h), Some(p), Some(s)) if !h.is_empty() && !p.is_empty() && !s.is_empty() => (h, p, s),
-        _ => return None,
-    };
-    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-        .decode(payload_b64)
-        .ok()?;
-    let v: serde_json::Value = serde_json::from_slice(&payload_bytes).ok()?;
-    v.get("https://api.openai.com/auth")
-        .and_then(|auth| auth.get("chatgpt_account_id"))
-        .and_then(|id| id.as_str())
-        .map(str::to_string)
-}
-
-/// Build headers for ChatGPT-backed requests: `User-Agent`, optional `Authorization`,
-/// and optional `ChatGPT-Account-Id`.
-pub async fn build_chatgpt_headers() -> HeaderMap {
-    use reqwest::header::AUTHORIZATION;
-    use reqwest::header::HeaderName;
-    use reqwest::header::HeaderValue;
-    use reqwest::header::USER_AGENT;
-
-    set_user_agent_suffix("codex_cloud_tasks_tui");
-    let ua = codex_core::default_client::get_codex_user_agent();
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_str(&ua).unwrap_or(HeaderValue::from_static("codex-cli")),
-    );
-    if let Ok(home) = codex_core::config::find_codex_home() {
-        let am = codex_login::AuthManager::new(home, false);
-        if let Some(auth) = am.auth()
-            && let Ok(tok) = auth.get_token().await
-            && !tok.is_empty()
-        {
-            let v = format!("Bearer {tok}");
-            if let Ok(hv) = HeaderValue::from_str(&v) {
-                headers.insert(AUTHORIZATION, hv);
-            }
-            if let Some(acc) = auth
-                .get_account_id()
-                .or_else(|| extract_chatgpt_account_id(&tok))
-                && let Ok(name) = HeaderName::from_bytes(b"ChatGPT-Account-Id")
-                && let Ok(hv) = HeaderValue::from_str(&acc)
-            {
-                headers.insert(name, hv);
-            }
-        }
-    }
-    headers
-}
-
 /// Construct a browser-friendly task URL for the given backend base URL.
 pub fn task_url(base_url: &str, task_id: &str) -> String {
     let normalized = normalize_base_url(base_url);
@@ -106,3 +71,59 @@ pub fn task_url(base_url: &str, task_id: &str) -> String {
     }
     format!("{normalized}/codex/tasks/{task_id}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_base_url;
+    use super::task_url;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn normalize_base_url_trims_and_appends_backend_api() {
+        assert_eq!(
+            normalize_base_url("https://chatgpt.com//"),
+            "https://chatgpt.com/backend-api"
+        );
+        assert_eq!(
+            normalize_base_url("https://chat.openai.com"),
+            "https://chat.openai.com/backend-api"
+        );
+        assert_eq!(
+            normalize_base_url("https://chatgpt.com/backend-api/"),
+            "https://chatgpt.com/backend-api"
+        );
+        assert_eq!(
+            normalize_base_url("http://localhost:8080/api/codex/"),
+            "http://localhost:8080/api/codex"
+        );
+    }
+
+    #[test]
+    fn task_url_strips_backend_api_suffix() {
+        assert_eq!(
+            task_url("https://chatgpt.com/backend-api", "task_1"),
+            "https://chatgpt.com/codex/tasks/task_1"
+        );
+        // chatgpt.com hosts get `/backend-api` appended during normalization.
+        assert_eq!(
+            task_url("https://chatgpt.com/", "task_1"),
+            "https://chatgpt.com/codex/tasks/task_1"
+        );
+    }
+
+    #[test]
+    fn task_url_for_direct_base_urls() {
+        assert_eq!(
+            task_url("http://localhost:8080/api/codex", "task_1"),
+            "http://localhost:8080/codex/tasks/task_1"
+        );
+        assert_eq!(
+            task_url("http://localhost:8080/codex", "task_1"),
+            "http://localhost:8080/codex/tasks/task_1"
+        );
+        assert_eq!(
+            task_url("http://localhost:8080", "task_1"),
+            "http://localhost:8080/codex/tasks/task_1"
+        );
+    }
+}
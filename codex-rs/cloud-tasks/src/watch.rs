@@ -0,0 +1,229 @@
+//! Watched tasks for the task list.
+//!
+//! Tasks toggled with `w` are polled in the background and trigger a desktop
+//! notification when they become ready for review or fail. Watched ids are
+//! stored in `codex_home/cloud_tasks_state.json` as `{"watched": [...]}`.
+
+use codex_cloud_tasks_client::TaskId;
+use codex_cloud_tasks_client::TaskStatus;
+use codex_cloud_tasks_client::TaskSummary;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const STATE_FILE: &str = "cloud_tasks_state.json";
+
+/// How often the background watcher re-checks watched tasks.
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(45);
+
+#[derive(Default, Serialize, Deserialize)]
+struct PersistedState {
+    #[serde(default)]
+    watched: BTreeSet<String>,
+}
+
+/// Watched task ids plus the last status seen for each of them.
+#[derive(Debug, Default)]
+pub struct WatchList {
+    path: Option<PathBuf>,
+    ids: BTreeSet<String>,
+    last_status: HashMap<String, TaskStatus>,
+}
+
+impl WatchList {
+    /// Load watched ids from `codex_home`. A missing or malformed state file
+    /// yields an empty list.
+    pub fn load(codex_home: &Path) -> Self {
+        let path = codex_home.join(STATE_FILE);
+        let state: PersistedState = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            ids: state.watched,
+            last_status: HashMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn is_watched(&self, id: &TaskId) -> bool {
+        self.ids.contains(&id.0)
+    }
+
+    /// Start or stop watching `task` and rewrite the state file. Returns
+    /// whether the task is watched afterwards.
+    pub fn toggle(&mut self, task: &TaskSummary) -> std::io::Result<bool> {
+        let watched = if self.ids.remove(&task.id.0) {
+            self.last_status.remove(&task.id.0);
+            false
+        } else {
+            self.ids.insert(task.id.0.clone());
+            self.last_status
+                .insert(task.id.0.clone(), task.status.clone());
+            true
+        };
+        self.save()?;
+        Ok(watched)
+    }
+
+    /// Record the latest statuses and return the watched tasks that just
+    /// finished. Finished tasks are dropped from the list, so each one is
+    /// reported once; call [`WatchList::save`] when any were returned. A task
+    /// loaded from the state file has no status yet, so if it is already
+    /// ready or failed it finished while the TUI was closed and is reported.
+    pub fn observe(&mut self, tasks: &[TaskSummary]) -> Vec<TaskSummary> {
+        let mut finished = Vec::new();
+        for task in tasks.iter().filter(|t| self.ids.contains(&t.id.0)) {
+            let previous = self
+                .last_status
+                .insert(task.id.0.clone(), task.status.clone());
+            if finished_transition(previous.as_ref(), &task.status) {
+                finished.push(task.clone());
+            }
+        }
+        for task in &finished {
+            self.ids.remove(&task.id.0);
+            self.last_status.remove(&task.id.0);
+        }
+        finished
+    }
+
+    /// Rewrite the state file with the current watched ids.
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let state = PersistedState {
+            watched: self.ids.clone(),
+        };
+        let json = serde_json::to_string_pretty(&state).map_err(std::io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)
+    }
+}
+
+/// True when a task is Ready or Error and was either in another status or
+/// not seen before (watched in an earlier run).
+pub fn finished_transition(previous: Option<&TaskStatus>, current: &TaskStatus) -> bool {
+    matches!(current, TaskStatus::Ready | TaskStatus::Error)
+        && previous.is_none_or(|prev| prev != current)
+}
+
+/// One-line notification text for a finished task.
+pub fn notification_text(task: &TaskSummary) -> String {
+    match task.status {
+        TaskStatus::Error => format!("Codex task failed: {}", task.title),
+        _ => format!("Codex task ready for review: {}", task.title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use codex_cloud_tasks_client::DiffSummary;
+    use pretty_assertions::assert_eq;
+
+    fn task(id: &str, status: TaskStatus) -> TaskSummary {
+        TaskSummary {
+            id: TaskId(id.to_string()),
+            title: format!("Task {id}"),
+            status,
+            updated_at: Utc::now(),
+            environment_id: None,
+            environment_label: None,
+            summary: DiffSummary::default(),
+            is_review: false,
+            attempt_total: None,
+        }
+    }
+
+    #[test]
+    fn only_moves_into_ready_or_error_count_as_finished() {
+        use TaskStatus::*;
+        let cases = [
+            (Some(Pending), Ready, true),
+            (Some(Pending), Error, true),
+            (Some(Applied), Ready, true),
+            (Some(Pending), Pending, false),
+            (Some(Ready), Ready, false),
+            (Some(Ready), Applied, false),
+            (None, Ready, true),
+            (None, Error, true),
+            (None, Pending, false),
+        ];
+        for (previous, current, expected) in cases {
+            assert_eq!(
+                finished_transition(previous.as_ref(), &current),
+                expected,
+                "{previous:?} -> {current:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn observe_reports_each_finished_task_once_and_persists() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let mut watch = WatchList::load(home.path());
+        assert!(
+            watch
+                .toggle(&task("a", TaskStatus::Pending))
+                .expect("toggle")
+        );
+        assert!(
+            watch
+                .toggle(&task("b", TaskStatus::Pending))
+                .expect("toggle")
+        );
+
+        let reloaded = WatchList::load(home.path());
+        assert!(reloaded.is_watched(&TaskId("a".to_string())));
+        assert!(reloaded.is_watched(&TaskId("b".to_string())));
+
+        let finished = watch.observe(&[
+            task("a", TaskStatus::Ready),
+            task("b", TaskStatus::Pending),
+            task("c", TaskStatus::Ready),
+        ]);
+        let ids: Vec<&str> = finished.iter().map(|t| t.id.0.as_str()).collect();
+        assert_eq!(ids, vec!["a"]);
+        assert!(!watch.is_watched(&TaskId("a".to_string())));
+
+        assert!(watch.observe(&[task("a", TaskStatus::Ready)]).is_empty());
+        watch.save().expect("save");
+        let reloaded = WatchList::load(home.path());
+        assert!(!reloaded.is_watched(&TaskId("a".to_string())));
+        assert!(reloaded.is_watched(&TaskId("b".to_string())));
+    }
+
+    #[test]
+    fn tasks_finished_while_closed_are_reported_on_first_poll() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let mut watch = WatchList::load(home.path());
+        watch
+            .toggle(&task("a", TaskStatus::Pending))
+            .expect("toggle");
+        watch
+            .toggle(&task("b", TaskStatus::Pending))
+            .expect("toggle");
+
+        // A new run starts from the state file with no statuses recorded.
+        let mut reloaded = WatchList::load(home.path());
+        let finished =
+            reloaded.observe(&[task("a", TaskStatus::Error), task("b", TaskStatus::Pending)]);
+        let ids: Vec<&str> = finished.iter().map(|t| t.id.0.as_str()).collect();
+        assert_eq!(ids, vec!["a"]);
+        assert!(!reloaded.is_watched(&TaskId("a".to_string())));
+        assert!(reloaded.is_watched(&TaskId("b".to_string())));
+    }
+}
@@ -7,8 +7,11 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
         SandboxPolicy::WorkspaceWrite {
             writable_roots,
             network_access,
+            allow_local_network,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            include_submodule_roots: _,
+            env_filter: _,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -29,6 +32,8 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             summary.push_str(&format!(" [{}]", writable_entries.join(", ")));
             if *network_access {
                 summary.push_str(" (network access enabled)");
+            } else if *allow_local_network {
+                summary.push_str(" (local network access enabled)");
             }
             summary
         }
@@ -1,17 +1,26 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::error::SandboxErr;
+use crate::executor::SandboxPlan;
+use crate::executor::plan_apply_patch_sandbox;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
-use crate::safety::SafetyCheck;
-use crate::safety::assess_patch_safety;
+use crate::protocol::SandboxPolicy;
+use crate::safety::patch_paths_outside_writable_roots;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
 
+/// When set, the files an `apply_patch` exec will write are copied into this
+/// directory first and restored if the patch fails.
+pub(crate) const CODEX_APPLY_PATCH_BACKUP_DIR_ENV_VAR: &str = "CODEX_APPLY_PATCH_BACKUP_DIR";
+
 pub(crate) enum InternalApplyPatchInvocation {
     /// The `apply_patch` call was handled programmatically, without any sort
     /// of sandbox, because the user explicitly approved it. This is the
@@ -40,20 +49,19 @@ pub(crate) async fn apply_patch(
     call_id: &str,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
-    match assess_patch_safety(
+    // Use the same plan the executor and `explain_apply_patch` use, so a patch
+    // is never refused here that the executor would have asked about.
+    match plan_apply_patch_sandbox(
         &action,
         turn_context.approval_policy,
         &turn_context.sandbox_policy,
         &turn_context.cwd,
     ) {
-        SafetyCheck::AutoApprove {
-            user_explicitly_approved,
-            ..
-        } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+        SandboxPlan::Run(_) => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
-            user_explicitly_approved_this_action: user_explicitly_approved,
+            user_explicitly_approved_this_action: false,
         }),
-        SafetyCheck::AskUser => {
+        SandboxPlan::AskUser => {
             // Compute a readable summary of path changes to include in the
             // approval request so the user can make an informed decision.
             //
@@ -78,12 +86,71 @@ pub(crate) async fn apply_patch(
                 }
             }
         }
-        SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
+        SandboxPlan::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
             FunctionCallError::RespondToModel(format!("patch rejected: {reason}")),
         )),
     }
 }
 
+/// Under a read-only sandbox policy with approvals disabled a patch is
+/// refused before anything is spawned, so no file is ever touched. With any
+/// other approval policy the user is asked instead.
+pub(crate) fn ensure_writes_allowed(
+    action: &ApplyPatchAction,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+) -> Result<(), SandboxErr> {
+    if !matches!(sandbox_policy, SandboxPolicy::ReadOnly)
+        || approval_policy != AskForApproval::Never
+    {
+        return Ok(());
+    }
+    // Empty patches are rejected with a clearer reason by `assess_patch_safety`.
+    match action.changes().keys().min() {
+        Some(path) => Err(SandboxErr::WriteViolation {
+            attempted_path: path.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check every path the patch writes against the policy's writable roots
+/// before anything is spawned, reporting the first offending path. Skipped
+/// entirely under `DangerFullAccess`.
+pub(crate) fn ensure_patch_within_writable_roots(
+    action: &ApplyPatchAction,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Result<(), SandboxErr> {
+    if sandbox_policy.has_full_disk_write_access() {
+        return Ok(());
+    }
+    match patch_paths_outside_writable_roots(action, sandbox_policy, cwd)
+        .into_iter()
+        .next()
+    {
+        Some(attempted_path) => Err(SandboxErr::WriteViolation { attempted_path }),
+        None => Ok(()),
+    }
+}
+
+/// Every path the patch writes, including move destinations.
+pub(crate) fn patch_target_paths(action: &ApplyPatchAction) -> Vec<&Path> {
+    let mut paths = Vec::new();
+    for (path, change) in action.changes() {
+        paths.push(path.as_path());
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+        {
+            paths.push(dest.as_path());
+        }
+    }
+    paths.sort();
+    paths
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -114,10 +181,89 @@ pub(crate) fn convert_apply_patch_to_protocol(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::EnvFilter;
     use pretty_assertions::assert_eq;
 
     use tempfile::tempdir;
 
+    #[test]
+    fn read_only_policy_rejects_patch_without_writing() {
+        let tmp = tempdir().expect("tmp");
+        let p = tmp.path().join("a.txt");
+        let action = ApplyPatchAction::new_add_for_test(&p, "hello".to_string());
+
+        let Err(SandboxErr::WriteViolation { attempted_path }) =
+            ensure_writes_allowed(&action, AskForApproval::Never, &SandboxPolicy::ReadOnly)
+        else {
+            panic!("expected a write violation under the read-only policy");
+        };
+        assert_eq!(attempted_path, p);
+        assert!(!p.exists());
+
+        // Any policy that can ask the user leaves the decision to approval.
+        for approval_policy in [
+            AskForApproval::OnRequest,
+            AskForApproval::OnFailure,
+            AskForApproval::UnlessTrusted,
+        ] {
+            assert!(
+                ensure_writes_allowed(&action, approval_policy, &SandboxPolicy::ReadOnly).is_ok()
+            );
+        }
+        assert!(
+            ensure_writes_allowed(
+                &action,
+                AskForApproval::Never,
+                &SandboxPolicy::new_workspace_write_policy()
+            )
+            .is_ok()
+        );
+        assert!(
+            ensure_writes_allowed(
+                &action,
+                AskForApproval::Never,
+                &SandboxPolicy::DangerFullAccess
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn workspace_write_rejects_patch_outside_writable_roots() {
+        let workspace = tempdir().expect("workspace");
+        let outside = tempdir().expect("outside");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: false,
+            env_filter: EnvFilter::default(),
+        };
+
+        let inside = workspace.path().join("ok.txt");
+        let action = ApplyPatchAction::new_add_for_test(&inside, "hello".to_string());
+        assert!(ensure_patch_within_writable_roots(&action, &policy, workspace.path()).is_ok());
+
+        let escaped = outside.path().join("a.txt");
+        let action = ApplyPatchAction::new_add_for_test(&escaped, "hello".to_string());
+        let Err(SandboxErr::WriteViolation { attempted_path }) =
+            ensure_patch_within_writable_roots(&action, &policy, workspace.path())
+        else {
+            panic!("expected a write violation outside the writable roots");
+        };
+        assert_eq!(attempted_path, escaped);
+        assert!(
+            ensure_patch_within_writable_roots(
+                &action,
+                &SandboxPolicy::DangerFullAccess,
+                workspace.path()
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn convert_apply_patch_maps_add_variant() {
         let tmp = tempdir().expect("tmp");
@@ -120,6 +120,10 @@ impl ModelClient {
         })
     }
 
+    pub fn get_compact_summary_max_tokens(&self) -> u32 {
+        self.config.compact_summary_max_tokens
+    }
+
     /// Dispatches to either the Responses or Chat implementation depending on
     /// the provider config.  Public callers always invoke `stream()` – the
     /// specialised helpers are private to avoid accidental misuse.
@@ -15,6 +15,7 @@ use async_channel::Receiver;
 use async_channel::Sender;
 use codex_apply_patch::ApplyPatchAction;
 use codex_protocol::ConversationId;
+use codex_protocol::protocol::ConversationExportedEvent;
 use codex_protocol::protocol::ConversationPathResponseEvent;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ReviewRequest;
@@ -43,6 +44,7 @@ use crate::client::ModelClient;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::config::Config;
+use crate::config_types::OtelExporterKind;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::conversation_history::ConversationHistory;
 use crate::environment_context::EnvironmentContext;
@@ -56,7 +58,10 @@ use crate::exec_command::ExecSessionManager;
 use crate::exec_command::WriteStdinParams;
 use crate::executor::Executor;
 use crate::executor::ExecutorConfig;
+use crate::executor::approval_store::ApprovalStore;
 use crate::executor::normalize_exec_result;
+use crate::git_info::collect_git_info;
+use crate::git_info::get_git_repo_root;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_family::find_family_for_model;
@@ -72,6 +77,7 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::DecisionTrace;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
@@ -88,12 +94,14 @@ use crate::protocol::ReviewDecision;
 use crate::protocol::ReviewOutputEvent;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
+use crate::protocol::SessionDiffResponseEvent;
 use crate::protocol::StreamErrorEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
-use crate::protocol::TurnDiffEvent;
+use crate::protocol::ToolOverrides;
 use crate::protocol::WebSearchBeginEvent;
+use crate::redact::OutputRedactor;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::shell;
@@ -120,6 +128,7 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::InitialHistory;
 
 pub mod compact;
@@ -132,6 +141,8 @@ pub struct Codex {
     next_id: AtomicU64,
     tx_sub: Sender<Submission>,
     rx_event: Receiver<Event>,
+    config: Arc<Config>,
+    rollout: Option<RolloutRecorder>,
 }
 
 /// Wrapper returned by [`Codex::spawn`] containing the spawned [`Codex`],
@@ -188,13 +199,21 @@ impl Codex {
             CodexErr::InternalAgentDied
         })?;
         let conversation_id = session.conversation_id;
+        let rollout = session.services.rollout.lock().await.clone();
 
         // This task will run until Op::Shutdown is received.
-        tokio::spawn(submission_loop(session, turn_context, config, rx_sub));
+        tokio::spawn(submission_loop(
+            session,
+            turn_context,
+            config.clone(),
+            rx_sub,
+        ));
         let codex = Codex {
             next_id: AtomicU64::new(0),
             tx_sub,
             rx_event,
+            config,
+            rollout,
         };
 
         Ok(CodexSpawnOk {
@@ -232,6 +251,20 @@ impl Codex {
             .map_err(|_| CodexErr::InternalAgentDied)?;
         Ok(event)
     }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Flush pending rollout writes and return the rollout path.
+    pub(crate) async fn flush_rollout(&self) -> CodexResult<PathBuf> {
+        let rollout = self
+            .rollout
+            .as_ref()
+            .ok_or_else(|| std::io::Error::other("conversation has no rollout recorder"))?;
+        rollout.flush().await?;
+        Ok(rollout.get_rollout_path())
+    }
 }
 
 use crate::state::SessionState;
@@ -262,6 +295,8 @@ pub(crate) struct TurnContext {
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) shell_environment_policy: ShellEnvironmentPolicy,
     pub(crate) tools_config: ToolsConfig,
+    /// Client-requested tool restrictions; persists until overridden again.
+    pub(crate) tool_overrides: ToolOverrides,
     pub(crate) is_review_mode: bool,
     pub(crate) final_output_json_schema: Option<Value>,
 }
@@ -361,6 +396,7 @@ impl Session {
         // - spin up MCP connection manager
         // - perform default shell discovery
         // - load history metadata
+        // - collect repo identity for the OTEL conversation_starts event
         let rollout_fut = RolloutRecorder::new(&config, rollout_params);
 
         let mcp_fut = McpConnectionManager::new(
@@ -372,10 +408,29 @@ impl Session {
         );
         let default_shell_fut = shell::default_user_shell();
         let history_meta_fut = crate::message_history::history_metadata(&config);
+        let otel_enabled = !matches!(config.otel.exporter, OtelExporterKind::None);
+        let git_info_fut = async {
+            if otel_enabled {
+                collect_git_info(&cwd).await
+            } else {
+                None
+            }
+        };
 
         // Join all independent futures.
-        let (rollout_recorder, mcp_res, default_shell, (history_log_id, history_entry_count)) =
-            tokio::join!(rollout_fut, mcp_fut, default_shell_fut, history_meta_fut);
+        let (
+            rollout_recorder,
+            mcp_res,
+            default_shell,
+            (history_log_id, history_entry_count),
+            git_info,
+        ) = tokio::join!(
+            rollout_fut,
+            mcp_fut,
+            default_shell_fut,
+            history_meta_fut,
+            git_info_fut
+        );
 
         let rollout_recorder = rollout_recorder.map_err(|e| {
             error!("failed to initialize rollout recorder: {e:#}");
@@ -432,6 +487,7 @@ impl Session {
             config.sandbox_policy.clone(),
             config.mcp_servers.keys().map(String::as_str).collect(),
             config.active_profile.clone(),
+            git_info.as_ref(),
         );
 
         // Now that the conversation id is final (may have been updated by resume),
@@ -457,22 +513,38 @@ impl Session {
             sandbox_policy,
             shell_environment_policy: config.shell_environment_policy.clone(),
             cwd,
+            tool_overrides: ToolOverrides::default(),
             is_review_mode: false,
             final_output_json_schema: None,
         };
+        let mut executor = Executor::new(ExecutorConfig::new(
+            turn_context.sandbox_policy.clone(),
+            turn_context.cwd.clone(),
+            config.codex_linux_sandbox_exe.clone(),
+        ));
+        if config.persist_command_approvals {
+            let project_root =
+                get_git_repo_root(&turn_context.cwd).unwrap_or_else(|| turn_context.cwd.clone());
+            executor = executor.with_persistent_approvals(ApprovalStore::for_project(
+                &config.codex_home,
+                &project_root,
+            ));
+        }
+        let output_redactor = config.redact_secrets_in_output.then(|| {
+            Arc::new(OutputRedactor::from_process_env(
+                &config.redact_env_var_patterns,
+            ))
+        });
         let services = SessionServices {
             mcp_connection_manager,
             session_manager: ExecSessionManager::default(),
-            unified_exec_manager: UnifiedExecSessionManager::default(),
+            unified_exec_manager: UnifiedExecSessionManager::new(output_redactor.clone()),
             notifier: notify,
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: default_shell,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
-            executor: Executor::new(ExecutorConfig::new(
-                turn_context.sandbox_policy.clone(),
-                turn_context.cwd.clone(),
-                config.codex_linux_sandbox_exe.clone(),
-            )),
+            executor,
+            output_redactor,
         };
 
         let sess = Arc::new(Session {
@@ -543,6 +615,15 @@ impl Session {
                     self.record_into_history(&reconstructed_history).await;
                 }
 
+                // Restore the latest plan so `Op::GetPlan` still answers
+                // after a resume or fork.
+                if let Some(plan) = rollout_items.iter().rev().find_map(|item| match item {
+                    RolloutItem::EventMsg(EventMsg::PlanUpdate(plan)) => Some(plan.clone()),
+                    _ => None,
+                }) {
+                    self.state.lock().await.update_plan(plan);
+                }
+
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if persist && !rollout_items.is_empty() {
                     self.persist_rollout_items(&rollout_items).await;
@@ -786,6 +867,31 @@ impl Session {
         self.send_event(event).await;
     }
 
+    /// Replace the session's plan and broadcast the result.
+    pub(crate) async fn update_plan(&self, sub_id: &str, update: UpdatePlanArgs) {
+        let plan = {
+            let mut state = self.state.lock().await;
+            state.update_plan(update)
+        };
+        self.send_plan_update_event(sub_id, plan).await;
+    }
+
+    async fn send_plan_update_event(&self, sub_id: &str, plan: UpdatePlanArgs) {
+        let event = Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::PlanUpdate(plan),
+        };
+        self.send_event(event).await;
+    }
+
+    async fn set_token_usage_after_compact(&self, sub_id: &str, estimated_tokens: u64) {
+        {
+            let mut state = self.state.lock().await;
+            state.set_token_usage_after_compact(estimated_tokens);
+        }
+        self.send_token_count_event(sub_id).await;
+    }
+
     async fn set_total_tokens_full(&self, sub_id: &str, turn_context: &TurnContext) {
         let context_window = turn_context.client.get_model_context_window();
         if let Some(context_window) = context_window {
@@ -824,6 +930,7 @@ impl Session {
         &self,
         turn_diff_tracker: SharedTurnDiffTracker,
         exec_command_context: ExecCommandContext,
+        decision_trace: Option<DecisionTrace>,
     ) {
         let ExecCommandContext {
             sub_id,
@@ -842,6 +949,11 @@ impl Session {
                     let mut tracker = turn_diff_tracker.lock().await;
                     tracker.on_patch_begin(&changes);
                 }
+                self.state
+                    .lock()
+                    .await
+                    .session_diff_tracker
+                    .on_patch_begin(&changes);
 
                 EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
                     call_id,
@@ -857,6 +969,7 @@ impl Session {
                     .into_iter()
                     .map(Into::into)
                     .collect(),
+                decision_trace,
             }),
         };
         let event = Event {
@@ -885,7 +998,8 @@ impl Session {
         // Send full stdout/stderr to clients; do not truncate.
         let stdout = stdout.text.clone();
         let stderr = stderr.text.clone();
-        let formatted_output = format_exec_output_str(output);
+        let formatted_output =
+            format_exec_output_str(output, self.services.output_redactor.as_deref());
         let aggregated_output: String = aggregated_output.text.clone();
 
         let msg = if is_apply_patch {
@@ -916,20 +1030,25 @@ impl Session {
         // If this is an apply_patch, after we emit the end patch, emit a second event
         // with the full turn diff if there is one.
         if is_apply_patch {
-            let unified_diff = {
-                let mut tracker = turn_diff_tracker.lock().await;
-                tracker.get_unified_diff()
+            self.send_turn_diff(sub_id, &turn_diff_tracker).await;
+        }
+    }
+
+    /// Emit the turn's aggregated diff and line counts, if anything changed.
+    async fn send_turn_diff(&self, sub_id: &str, turn_diff_tracker: &SharedTurnDiffTracker) {
+        let turn_diff = {
+            let mut tracker = turn_diff_tracker.lock().await;
+            tracker.get_turn_diff_event()
+        };
+        if let Ok(Some(turn_diff)) = turn_diff {
+            let event = Event {
+                id: sub_id.to_string(),
+                msg: EventMsg::TurnDiff(turn_diff),
             };
-            if let Ok(Some(unified_diff)) = unified_diff {
-                let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
-                let event = Event {
-                    id: sub_id.into(),
-                    msg,
-                };
-                self.send_event(event).await;
-            }
+            self.send_event(event).await;
         }
     }
+
     /// Runs the exec tool call and emits events for the begin and end of the
     /// command even on error.
     ///
@@ -945,15 +1064,20 @@ impl Session {
         let sub_id = context.sub_id.clone();
         let call_id = context.call_id.clone();
 
-        self.on_exec_command_begin(turn_diff_tracker.clone(), context.clone())
-            .await;
+        // Plan first so the begin event can explain the sandbox decision.
+        // Planning never prompts, so any approval request still follows the
+        // begin event.
+        let plan = self.services.executor.plan(request, self, approval_policy);
+        let decision_trace = plan.as_ref().ok().and_then(|plan| plan.trace().cloned());
 
-        let result = self
-            .services
-            .executor
-            .run(request, self, approval_policy, &context)
+        self.on_exec_command_begin(turn_diff_tracker.clone(), context.clone(), decision_trace)
             .await;
 
+        let result = match plan {
+            Ok(plan) => self.services.executor.execute(plan, self, &context).await,
+            Err(err) => Err(err),
+        };
+
         let normalized = normalize_exec_result(&result);
         let borrowed = normalized.event_output();
 
@@ -1088,6 +1212,11 @@ impl Session {
         self.abort_all_tasks(TurnAbortReason::Interrupted).await;
     }
 
+    pub async fn cancel_task(self: &Arc<Self>, sub_id: &str) {
+        info!("cancel received for submission {sub_id}");
+        self.abort_task(sub_id, TurnAbortReason::Interrupted).await;
+    }
+
     fn interrupt_task_sync(&self) {
         if let Ok(mut active) = self.active_turn.try_lock()
             && let Some(at) = active.as_mut()
@@ -1135,6 +1264,9 @@ async fn submission_loop(
             Op::Interrupt => {
                 sess.interrupt_task().await;
             }
+            Op::CancelSubmission { id } => {
+                sess.cancel_task(&id).await;
+            }
             Op::OverrideTurnContext {
                 cwd,
                 approval_policy,
@@ -1142,7 +1274,19 @@ async fn submission_loop(
                 model,
                 effort,
                 summary,
+                tools,
             } => {
+                if model.as_deref().is_some_and(|m| m.trim().is_empty()) {
+                    let event = Event {
+                        id: sub.id.clone(),
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: "model override must not be empty".to_string(),
+                        }),
+                    };
+                    sess.send_event(event).await;
+                    continue;
+                }
+
                 // Recalculate the persistent turn context with provided overrides.
                 let prev = Arc::clone(&turn_context);
                 let provider = prev.client.get_provider();
@@ -1205,6 +1349,7 @@ async fn submission_loop(
                     sandbox_policy: new_sandbox_policy.clone(),
                     shell_environment_policy: prev.shell_environment_policy.clone(),
                     cwd: new_cwd.clone(),
+                    tool_overrides: tools.unwrap_or_else(|| prev.tool_overrides.clone()),
                     is_review_mode: false,
                     final_output_json_schema: None,
                 };
@@ -1212,6 +1357,33 @@ async fn submission_loop(
                 // Install the new persistent context for subsequent tasks/turns.
                 turn_context = Arc::new(new_turn_context);
 
+                // Acknowledge a model switch so clients can update their display.
+                if model.is_some() {
+                    let (history_log_id, history_entry_count) =
+                        crate::message_history::history_metadata(&config).await;
+                    let rollout_path = sess
+                        .services
+                        .rollout
+                        .lock()
+                        .await
+                        .as_ref()
+                        .map(RolloutRecorder::get_rollout_path)
+                        .unwrap_or_default();
+                    let event = Event {
+                        id: sub.id.clone(),
+                        msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
+                            session_id: sess.conversation_id,
+                            model: effective_model,
+                            reasoning_effort: effective_effort,
+                            history_log_id,
+                            history_entry_count,
+                            initial_messages: None,
+                            rollout_path,
+                        }),
+                    };
+                    sess.send_event(event).await;
+                }
+
                 // Optionally persist changes to model / effort
                 if cwd.is_some() || approval_policy.is_some() || sandbox_policy.is_some() {
                     sess.record_conversation_items(&[ResponseItem::from(EnvironmentContext::new(
@@ -1298,6 +1470,7 @@ async fn submission_loop(
                         sandbox_policy,
                         shell_environment_policy: turn_context.shell_environment_policy.clone(),
                         cwd,
+                        tool_overrides: turn_context.tool_overrides.clone(),
                         is_review_mode: false,
                         final_output_json_schema,
                     };
@@ -1409,12 +1582,9 @@ async fn submission_loop(
             Op::ListCustomPrompts => {
                 let sub_id = sub.id.clone();
 
+                let dir = crate::custom_prompts::prompts_dir(&config.codex_home);
                 let custom_prompts: Vec<CustomPrompt> =
-                    if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                        crate::custom_prompts::discover_prompts_in(&dir).await
-                    } else {
-                        Vec::new()
-                    };
+                    crate::custom_prompts::discover_prompts_cached(&dir).await;
 
                 let event = Event {
                     id: sub_id,
@@ -1469,20 +1639,13 @@ async fn submission_loop(
             Op::GetPath => {
                 let sub_id = sub.id.clone();
                 // Flush rollout writes before returning the path so readers observe a consistent file.
-                let (path, rec_opt) = {
-                    let guard = sess.services.rollout.lock().await;
-                    match guard.as_ref() {
-                        Some(rec) => (rec.get_rollout_path(), Some(rec.clone())),
-                        None => {
-                            error!("rollout recorder not found");
-                            continue;
-                        }
-                    }
-                };
+                // Without an active recorder there is no file to point at.
+                let rec_opt = sess.services.rollout.lock().await.clone();
+                let path = rec_opt.as_ref().map(RolloutRecorder::get_rollout_path);
                 if let Some(rec) = rec_opt
                     && let Err(e) = rec.flush().await
                 {
-                    warn!("failed to flush rollout recorder before GetHistory: {e}");
+                    warn!("failed to flush rollout recorder before GetPath: {e}");
                 }
                 let event = Event {
                     id: sub_id.clone(),
@@ -1493,6 +1656,64 @@ async fn submission_loop(
                 };
                 sess.send_event(event).await;
             }
+            Op::GetPlan => {
+                let plan = sess.state.lock().await.plan_snapshot();
+                // Unlike updates, this reply is not recorded in the rollout;
+                // it would only repeat the plan recorded by the last update.
+                let event = Event {
+                    id: sub.id.clone(),
+                    msg: EventMsg::PlanUpdate(plan),
+                };
+                if let Err(e) = sess.get_tx_event().send(event).await {
+                    error!("failed to send plan: {e}");
+                }
+            }
+            Op::GetSessionDiff => {
+                let unified_diff = sess
+                    .state
+                    .lock()
+                    .await
+                    .session_diff_tracker
+                    .get_unified_diff();
+                let msg = match unified_diff {
+                    Ok(unified_diff) => EventMsg::SessionDiffResponse(SessionDiffResponseEvent {
+                        unified_diff: unified_diff.unwrap_or_default(),
+                    }),
+                    Err(e) => EventMsg::Error(ErrorEvent {
+                        message: format!("failed to compute session diff: {e:#}"),
+                    }),
+                };
+                sess.send_event(Event {
+                    id: sub.id.clone(),
+                    msg,
+                })
+                .await;
+            }
+            Op::ExportConversation { path, format } => {
+                let sub_id = sub.id.clone();
+                // Flush rollout writes so the export includes the latest turn.
+                let rec_opt = sess.services.rollout.lock().await.clone();
+                let Some(rec) = rec_opt else {
+                    error!("rollout recorder not found");
+                    continue;
+                };
+                if let Err(e) = rec.flush().await {
+                    warn!("failed to flush rollout recorder before export: {e}");
+                }
+                let msg = match crate::conversation_export::export_rollout(
+                    &rec.get_rollout_path(),
+                    &path,
+                    format,
+                )
+                .await
+                {
+                    Ok(()) => EventMsg::ConversationExported(ConversationExportedEvent { path }),
+                    Err(e) => EventMsg::Error(ErrorEvent {
+                        message: format!("failed to export conversation: {e:#}"),
+                    }),
+                };
+                sess.send_event(Event { id: sub_id, msg }).await;
+            }
             Op::Review { review_request } => {
                 spawn_review_thread(
                     sess.clone(),
@@ -1577,6 +1798,7 @@ async fn spawn_review_thread(
         sandbox_policy: parent_turn_context.sandbox_policy.clone(),
         shell_environment_policy: parent_turn_context.shell_environment_policy.clone(),
         cwd: parent_turn_context.cwd.clone(),
+        tool_overrides: parent_turn_context.tool_overrides.clone(),
         is_review_mode: true,
         final_output_json_schema: None,
     };
@@ -1882,6 +2104,9 @@ pub(crate) async fn run_task(
         }
     }
 
+    // Summarize the files this turn changed before the task reports completion.
+    sess.send_turn_diff(&sub_id, &turn_diff_tracker).await;
+
     // If this was a review thread and we have a final assistant message,
     // try to parse it as a ReviewOutput.
     //
@@ -1935,10 +2160,10 @@ async fn run_turn(
     task_kind: TaskKind,
 ) -> CodexResult<TurnRunResult> {
     let mcp_tools = sess.services.mcp_connection_manager.list_all_tools();
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(mcp_tools),
-    ));
+    let router = Arc::new(
+        ToolRouter::from_config(&turn_context.tools_config, Some(mcp_tools))
+            .with_tool_overrides(turn_context.tool_overrides.clone()),
+    );
 
     let model_supports_parallel = turn_context
         .client
@@ -1994,6 +2219,10 @@ async fn run_turn(
                     warn!(
                         "stream disconnected - retrying turn ({retries}/{max_retries} in {delay:?})...",
                     );
+                    turn_context
+                        .client
+                        .get_otel_event_manager()
+                        .stream_retry(retries, delay, &e);
 
                     // Surface retry information to any UI/front‑end so the
                     // user understands what is happening instead of staring
@@ -2228,19 +2457,6 @@ async fn try_run_turn(
 
                 let processed_items: Vec<ProcessedResponseItem> = output.try_collect().await?;
 
-                let unified_diff = {
-                    let mut tracker = turn_diff_tracker.lock().await;
-                    tracker.get_unified_diff()
-                };
-                if let Ok(Some(unified_diff)) = unified_diff {
-                    let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
-                    let event = Event {
-                        id: sub_id.to_string(),
-                        msg,
-                    };
-                    sess.send_event(event).await;
-                }
-
                 let result = TurnRunResult {
                     processed_items,
                     total_token_usage: token_usage.clone(),
@@ -2454,6 +2670,7 @@ mod tests {
     use crate::state::TaskKind;
     use crate::tasks::SessionTask;
     use crate::tasks::SessionTaskContext;
+    use crate::tools::MODEL_FORMAT_HEAD_BYTES;
     use crate::tools::MODEL_FORMAT_HEAD_LINES;
     use crate::tools::MODEL_FORMAT_MAX_BYTES;
     use crate::tools::MODEL_FORMAT_MAX_LINES;
@@ -2517,6 +2734,34 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn record_initial_history_restores_latest_plan() {
+        let (session, turn_context) = make_session_and_context();
+        let plan = |explanation: &str| UpdatePlanArgs {
+            explanation: Some(explanation.to_string()),
+            plan: vec![codex_protocol::plan_tool::PlanItemArg {
+                step: "Write test".to_string(),
+                status: codex_protocol::plan_tool::StepStatus::Completed,
+            }],
+        };
+        let rollout_items = vec![
+            RolloutItem::EventMsg(EventMsg::PlanUpdate(plan("first"))),
+            RolloutItem::EventMsg(EventMsg::PlanUpdate(plan("latest"))),
+        ];
+
+        tokio_test::block_on(session.record_initial_history(
+            &turn_context,
+            InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ConversationId::default(),
+                history: rollout_items,
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }),
+        ));
+
+        let actual = tokio_test::block_on(async { session.state.lock().await.plan_snapshot() });
+        assert_eq!(plan("latest"), actual);
+    }
+
     #[test]
     fn prefers_structured_content_when_present() {
         let ctr = CallToolResult {
@@ -2557,7 +2802,7 @@ mod tests {
             timed_out: false,
         };
 
-        let out = format_exec_output_str(&exec);
+        let out = format_exec_output_str(&exec, None);
 
         // Strip truncation header if present for subsequent assertions
         let body = out
@@ -2606,7 +2851,7 @@ mod tests {
             timed_out: false,
         };
 
-        let out = format_exec_output_str(&exec);
+        let out = format_exec_output_str(&exec, None);
         // Keep strict budget on the truncated body (excluding header)
         let body = out
             .strip_prefix("Total output lines: ")
@@ -2642,7 +2887,7 @@ mod tests {
             timed_out: true,
         };
 
-        let out = format_exec_output_str(&exec);
+        let out = format_exec_output_str(&exec, None);
 
         assert_eq!(
             out,
@@ -2650,6 +2895,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn secrets_are_redacted_before_truncation() {
+        let secret = "s3cr3t-value-that-straddles-the-cut";
+        let redactor = OutputRedactor::new(
+            [("DEPLOY_TOKEN".to_string(), secret.to_string())],
+            &crate::redact::default_sensitive_env_patterns(),
+        );
+        // Place the secret across the head byte budget so truncation alone
+        // would keep its first half.
+        let full = format!(
+            "{}{secret}\n{}",
+            "a".repeat(MODEL_FORMAT_HEAD_BYTES - 10),
+            "tail line\n".repeat(400)
+        );
+        let exec = ExecToolCallOutput {
+            exit_code: 0,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new(full),
+            duration: StdDuration::from_secs(1),
+            timed_out: false,
+        };
+
+        let unredacted = format_exec_output_str(&exec, None);
+        assert!(unredacted.contains(&secret[..10]));
+
+        let out = format_exec_output_str(&exec, Some(&redactor));
+        assert!(!out.contains(&secret[..10]), "{out}");
+    }
+
     #[test]
     fn falls_back_to_content_when_structured_is_null() {
         let ctr = CallToolResult {
@@ -2756,6 +3031,7 @@ mod tests {
             sandbox_policy: config.sandbox_policy.clone(),
             shell_environment_policy: config.shell_environment_policy.clone(),
             tools_config,
+            tool_overrides: ToolOverrides::default(),
             is_review_mode: false,
             final_output_json_schema: None,
         };
@@ -2772,6 +3048,7 @@ mod tests {
                 turn_context.cwd.clone(),
                 None,
             )),
+            output_redactor: None,
         };
         let session = Session {
             conversation_id,
@@ -2824,6 +3101,7 @@ mod tests {
             sandbox_policy: config.sandbox_policy.clone(),
             shell_environment_policy: config.shell_environment_policy.clone(),
             tools_config,
+            tool_overrides: ToolOverrides::default(),
             is_review_mode: false,
             final_output_json_schema: None,
         });
@@ -2840,6 +3118,7 @@ mod tests {
                 config.cwd.clone(),
                 None,
             )),
+            output_redactor: None,
         };
         let session = Arc::new(Session {
             conversation_id,
@@ -3126,6 +3405,7 @@ mod tests {
             env: HashMap::new(),
             with_escalated_permissions: Some(true),
             justification: Some("test".to_string()),
+            resource_limits: None,
         };
 
         let params2 = ExecParams {
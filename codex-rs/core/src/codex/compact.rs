@@ -44,7 +44,18 @@ pub(crate) async fn run_inline_auto_compact_task(
     let input = vec![InputItem::Text {
         text: SUMMARIZATION_PROMPT.to_string(),
     }];
-    run_compact_task_inner(sess, turn_context, sub_id, input).await;
+    if run_compact_task_inner(sess.clone(), turn_context, sub_id.clone(), input)
+        .await
+        .is_some()
+    {
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Compact task completed".to_string(),
+            }),
+        };
+        sess.send_event(event).await;
+    }
 }
 
 pub(crate) async fn run_compact_task(
@@ -60,16 +71,29 @@ pub(crate) async fn run_compact_task(
         }),
     };
     sess.send_event(start_event).await;
-    run_compact_task_inner(sess.clone(), turn_context, sub_id.clone(), input).await;
-    None
+    let summary = run_compact_task_inner(sess.clone(), turn_context, sub_id.clone(), input).await?;
+    let event = Event {
+        id: sub_id,
+        msg: EventMsg::AgentMessage(AgentMessageEvent {
+            message: summary.clone(),
+        }),
+    };
+    sess.send_event(event).await;
+    Some(summary)
 }
 
+/// Summarize the conversation and replace the history with the summary.
+/// Token counts are reported before and after compaction. Returns the
+/// (capped) summary, or `None` when compaction failed or was interrupted.
 async fn run_compact_task_inner(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
     sub_id: String,
     input: Vec<InputItem>,
-) {
+) -> Option<String> {
+    // Report usage for the history as it stands before compacting.
+    sess.send_token_count_event(&sub_id).await;
+
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
     let mut turn_input = sess
         .turn_input_with_history(vec![initial_input_for_turn.clone().into()])
@@ -111,7 +135,7 @@ async fn run_compact_task_inner(
                 break;
             }
             Err(CodexErr::Interrupted) => {
-                return;
+                return None;
             }
             Err(e @ CodexErr::ContextWindowExceeded) => {
                 if turn_input.len() > 1 {
@@ -129,12 +153,16 @@ async fn run_compact_task_inner(
                     }),
                 };
                 sess.send_event(event).await;
-                return;
+                return None;
             }
             Err(e) => {
                 if retries < max_retries {
                     retries += 1;
                     let delay = backoff(retries);
+                    turn_context
+                        .client
+                        .get_otel_event_manager()
+                        .stream_retry(retries, delay, &e);
                     sess.notify_stream_error(
                         &sub_id,
                         format!("Re-connecting... {retries}/{max_retries}"),
@@ -150,17 +178,21 @@ async fn run_compact_task_inner(
                         }),
                     };
                     sess.send_event(event).await;
-                    return;
+                    return None;
                 }
             }
         }
     }
 
     let history_snapshot = sess.history_snapshot().await;
-    let summary_text = get_last_assistant_message_from_turn(&history_snapshot).unwrap_or_default();
+    let summary_text = cap_summary(
+        &get_last_assistant_message_from_turn(&history_snapshot).unwrap_or_default(),
+        turn_context.client.get_compact_summary_max_tokens(),
+    );
     let user_messages = collect_user_messages(&history_snapshot);
     let initial_context = sess.build_initial_context(turn_context.as_ref());
     let new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    let estimated_tokens = approx_token_count(&new_history);
     sess.replace_history(new_history).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
@@ -168,13 +200,34 @@ async fn run_compact_task_inner(
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
-    let event = Event {
-        id: sub_id.clone(),
-        msg: EventMsg::AgentMessage(AgentMessageEvent {
-            message: "Compact task completed".to_string(),
-        }),
-    };
-    sess.send_event(event).await;
+    // Report usage for the compacted history.
+    sess.set_token_usage_after_compact(&sub_id, estimated_tokens)
+        .await;
+
+    Some(summary_text)
+}
+
+/// Keep at most `max_tokens` of the summary (approx. 4 bytes/token).
+fn cap_summary(summary: &str, max_tokens: u32) -> String {
+    let max_bytes = (max_tokens as usize).saturating_mul(4);
+    if summary.len() > max_bytes {
+        truncate_middle(summary, max_bytes).0
+    } else {
+        summary.to_string()
+    }
+}
+
+/// Rough token count of the text in `items` (approx. 4 bytes/token).
+fn approx_token_count(items: &[ResponseItem]) -> u64 {
+    let bytes: usize = items
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::Message { content, .. } => content_items_to_text(content),
+            _ => None,
+        })
+        .map(|text| text.len())
+        .sum();
+    (bytes as u64).div_ceil(4)
 }
 
 pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
@@ -386,6 +439,16 @@ mod tests {
         assert_eq!(vec!["real user message".to_string()], collected);
     }
 
+    #[test]
+    fn cap_summary_keeps_short_summaries_and_truncates_long_ones() {
+        assert_eq!(cap_summary("short summary", 500), "short summary");
+
+        let long = "word ".repeat(1_000);
+        let capped = cap_summary(&long, 100);
+        assert!(capped.len() < long.len());
+        assert!(capped.contains("tokens truncated"));
+    }
+
     #[test]
     fn build_compacted_history_truncates_overlong_user_messages() {
         // Prepare a very large prior user message so the aggregated
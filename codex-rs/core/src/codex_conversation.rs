@@ -1,6 +1,11 @@
+use std::path::Path;
+use std::path::PathBuf;
+
 use crate::codex::Codex;
+use crate::config::Config;
 use crate::error::Result as CodexResult;
 use crate::protocol::Event;
+use crate::protocol::ExportFormat;
 use crate::protocol::Op;
 use crate::protocol::Submission;
 
@@ -24,7 +29,39 @@ impl CodexConversation {
         self.codex.submit_with_id(sub).await
     }
 
+    /// Use `model` for every following turn. The session acknowledges with a
+    /// fresh `EventMsg::SessionConfigured` carrying the new model.
+    pub async fn set_model(&self, model: impl Into<String>) -> CodexResult<String> {
+        self.submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: Some(model.into()),
+            effort: None,
+            summary: None,
+            tools: None,
+        })
+        .await
+    }
+
+    /// Write the conversation so far to `path` as Markdown. The file is
+    /// written before this returns; no `EventMsg::ConversationExported` is
+    /// emitted, unlike `Op::ExportConversation`.
+    pub async fn export_markdown(&self, path: &Path) -> anyhow::Result<()> {
+        let rollout_path = self.flush_rollout().await?;
+        crate::conversation_export::export_rollout(&rollout_path, path, ExportFormat::Markdown)
+            .await
+    }
+
     pub async fn next_event(&self) -> CodexResult<Event> {
         self.codex.next_event().await
     }
+
+    pub(crate) fn config(&self) -> &Config {
+        self.codex.config()
+    }
+
+    pub(crate) async fn flush_rollout(&self) -> CodexResult<PathBuf> {
+        self.codex.flush_rollout().await
+    }
 }
@@ -65,6 +65,9 @@ pub const GPT_5_CODEX_MEDIUM_MODEL: &str = "gpt-5-codex";
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
+/// Default cap on the summary produced by `/compact`, in tokens.
+pub(crate) const DEFAULT_COMPACT_SUMMARY_MAX_TOKENS: u32 = 500;
+
 pub(crate) const CONFIG_TOML_FILE: &str = "config.toml";
 
 /// Application configuration loaded from disk and merged with overrides.
@@ -87,6 +90,10 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of tokens kept from the summary produced when the
+    /// conversation is compacted.
+    pub compact_summary_max_tokens: u32,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
@@ -109,6 +116,18 @@ pub struct Config {
     /// Defaults to `false`.
     pub show_raw_agent_reasoning: bool,
 
+    /// When `true`, commands approved for the session are remembered per
+    /// project under `CODEX_HOME/approvals` and reused by later sessions.
+    pub persist_command_approvals: bool,
+
+    /// When `true`, secrets are replaced with `[REDACTED:<NAME>]` in command
+    /// output sent to the model.
+    pub redact_secrets_in_output: bool,
+
+    /// Case-insensitive globs naming the environment variables whose values
+    /// are redacted from command output.
+    pub redact_env_var_patterns: Vec<String>,
+
     /// User-provided instructions from AGENTS.md.
     pub user_instructions: Option<String>,
 
@@ -720,6 +739,9 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of tokens kept from the compaction summary (default: 500).
+    pub compact_summary_max_tokens: Option<u32>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -787,6 +809,17 @@ pub struct ConfigToml {
     /// Defaults to `false`.
     pub show_raw_agent_reasoning: Option<bool>,
 
+    /// When set to `true`, commands approved for the session are persisted
+    /// per project and reused by later sessions. Defaults to `false`.
+    pub persist_command_approvals: Option<bool>,
+
+    /// Redact secrets from command output sent to the model. Defaults to `true`.
+    pub redact_secrets_in_output: Option<bool>,
+
+    /// Environment variable name globs whose values are redacted. Defaults to
+    /// `*KEY*`, `*SECRET*`, `*TOKEN*`, `*PASSWORD*` and `*CREDENTIAL*`.
+    pub redact_env_var_patterns: Option<Vec<String>>,
+
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
@@ -888,13 +921,19 @@ impl ConfigToml {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
+                    allow_local_network,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    include_submodule_roots,
+                    env_filter,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
+                    allow_local_network: *allow_local_network,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    include_submodule_roots: include_submodule_roots.unwrap_or(true),
+                    env_filter: env_filter.clone().unwrap_or_default(),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -1025,6 +1064,16 @@ impl Config {
         let features = Features::from_config(&cfg, &config_profile, feature_overrides);
 
         let sandbox_policy = cfg.derive_sandbox_policy(sandbox_mode);
+        // The Linux sandbox filters sockets with seccomp, which cannot tell a
+        // loopback destination from a remote one.
+        #[cfg(target_os = "linux")]
+        if sandbox_policy.has_local_network_access() && !sandbox_policy.has_full_network_access() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "`sandbox_workspace_write.allow_local_network` is not supported on Linux; \
+                 set `network_access = true` instead",
+            ));
+        }
 
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
@@ -1130,6 +1179,9 @@ impl Config {
             model_context_window,
             model_max_output_tokens,
             model_auto_compact_token_limit,
+            compact_summary_max_tokens: cfg
+                .compact_summary_max_tokens
+                .unwrap_or(DEFAULT_COMPACT_SUMMARY_MAX_TOKENS),
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
@@ -1171,6 +1223,11 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
+            persist_command_approvals: cfg.persist_command_approvals.unwrap_or(false),
+            redact_secrets_in_output: cfg.redact_secrets_in_output.unwrap_or(true),
+            redact_env_var_patterns: cfg
+                .redact_env_var_patterns
+                .unwrap_or_else(crate::redact::default_sensitive_env_patterns),
             model_reasoning_effort: config_profile
                 .model_reasoning_effort
                 .or(cfg.model_reasoning_effort),
@@ -1206,10 +1263,14 @@ impl Config {
                     .environment
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
+                let resource_attributes = t.resource_attributes.unwrap_or_default();
+                let sampler = t.sampler.unwrap_or_default();
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
+                    resource_attributes,
+                    sampler,
                 }
             },
         };
@@ -1326,6 +1387,7 @@ mod tests {
     use crate::features::Feature;
 
     use super::*;
+    use crate::protocol::EnvFilter;
     use pretty_assertions::assert_eq;
 
     use std::time::Duration;
@@ -1425,11 +1487,85 @@ exclude_slash_tmp = true
             SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![PathBuf::from("/my/workspace")],
                 network_access: false,
+                allow_local_network: false,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
             },
             sandbox_workspace_write_cfg.derive_sandbox_policy(sandbox_mode_override)
         );
+
+        let sandbox_local_network = r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+allow_local_network = true
+"#;
+
+        let sandbox_local_network_cfg = toml::from_str::<ConfigToml>(sandbox_local_network)
+            .expect("TOML deserialization should succeed");
+        let sandbox_mode_override = None;
+        assert_eq!(
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![],
+                network_access: false,
+                allow_local_network: true,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
+            },
+            sandbox_local_network_cfg.derive_sandbox_policy(sandbox_mode_override)
+        );
+
+        let sandbox_env_filter = r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+env_filter = { mode = "deny-list", names = ["SECRET"] }
+"#;
+
+        let sandbox_env_filter_cfg = toml::from_str::<ConfigToml>(sandbox_env_filter)
+            .expect("TOML deserialization should succeed");
+        let sandbox_mode_override = None;
+        assert_eq!(
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![],
+                network_access: false,
+                allow_local_network: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::DenyList(vec!["SECRET".to_string()]),
+            },
+            sandbox_env_filter_cfg.derive_sandbox_policy(sandbox_mode_override)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn allow_local_network_is_rejected_on_linux() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+allow_local_network = true
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("local-only network access should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("allow_local_network"));
+        Ok(())
     }
 
     #[test]
@@ -2191,6 +2327,7 @@ model_verbosity = "high"
                 model_context_window: Some(200_000),
                 model_max_output_tokens: Some(100_000),
                 model_auto_compact_token_limit: None,
+                compact_summary_max_tokens: DEFAULT_COMPACT_SUMMARY_MAX_TOKENS,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: AskForApproval::Never,
@@ -2210,6 +2347,9 @@ model_verbosity = "high"
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
                 show_raw_agent_reasoning: false,
+                persist_command_approvals: false,
+                redact_secrets_in_output: true,
+                redact_env_var_patterns: crate::redact::default_sensitive_env_patterns(),
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_verbosity: None,
@@ -2255,6 +2395,7 @@ model_verbosity = "high"
             model_context_window: Some(16_385),
             model_max_output_tokens: Some(4_096),
             model_auto_compact_token_limit: None,
+            compact_summary_max_tokens: DEFAULT_COMPACT_SUMMARY_MAX_TOKENS,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: AskForApproval::UnlessTrusted,
@@ -2274,6 +2415,9 @@ model_verbosity = "high"
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
+            persist_command_approvals: false,
+            redact_secrets_in_output: true,
+            redact_env_var_patterns: crate::redact::default_sensitive_env_patterns(),
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_verbosity: None,
@@ -2334,6 +2478,7 @@ model_verbosity = "high"
             model_context_window: Some(200_000),
             model_max_output_tokens: Some(100_000),
             model_auto_compact_token_limit: None,
+            compact_summary_max_tokens: DEFAULT_COMPACT_SUMMARY_MAX_TOKENS,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
@@ -2353,6 +2498,9 @@ model_verbosity = "high"
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
+            persist_command_approvals: false,
+            redact_secrets_in_output: true,
+            redact_env_var_patterns: crate::redact::default_sensitive_env_patterns(),
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_verbosity: None,
@@ -2399,6 +2547,7 @@ model_verbosity = "high"
             model_context_window: Some(272_000),
             model_max_output_tokens: Some(128_000),
             model_auto_compact_token_limit: None,
+            compact_summary_max_tokens: DEFAULT_COMPACT_SUMMARY_MAX_TOKENS,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
@@ -2418,6 +2567,9 @@ model_verbosity = "high"
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
+            persist_command_approvals: false,
+            redact_secrets_in_output: true,
+            redact_env_var_patterns: crate::redact::default_sensitive_env_patterns(),
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_verbosity: Some(Verbosity::High),
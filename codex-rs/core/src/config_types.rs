@@ -9,6 +9,8 @@ use std::path::PathBuf;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
 
+use crate::protocol::EnvFilter;
+
 use serde::Deserialize;
 use serde::Serialize;
 use serde::de::Error as SerdeError;
@@ -262,13 +264,31 @@ pub enum OtelExporterKind {
         endpoint: String,
         headers: HashMap<String, String>,
         protocol: OtelHttpProtocol,
+        /// Proxy URL for the collector; defaults to `HTTPS_PROXY`/`HTTP_PROXY`.
+        proxy: Option<String>,
     },
     OtlpGrpc {
         endpoint: String,
         headers: HashMap<String, String>,
+        /// PEM bundle used to verify the collector's certificate.
+        ca_cert: Option<PathBuf>,
+        /// PEM client certificate for mTLS. Must be set together with `client_key`.
+        client_cert: Option<PathBuf>,
+        client_key: Option<PathBuf>,
     },
 }
 
+/// Which processes export OTEL events.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OtelSampler {
+    #[default]
+    AlwaysOn,
+    AlwaysOff,
+    /// Fraction of processes (`0.0..=1.0`) that export their events.
+    Ratio(f64),
+}
+
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct OtelConfigToml {
@@ -280,6 +300,12 @@ pub struct OtelConfigToml {
 
     /// Exporter to use. Defaults to `otlp-file`.
     pub exporter: Option<OtelExporterKind>,
+
+    /// Extra resource attributes attached to every exported event.
+    pub resource_attributes: Option<HashMap<String, String>>,
+
+    /// Sampling applied to exported events. Defaults to `always-on`.
+    pub sampler: Option<OtelSampler>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -288,6 +314,8 @@ pub struct OtelConfig {
     pub log_user_prompt: bool,
     pub environment: String,
     pub exporter: OtelExporterKind,
+    pub resource_attributes: HashMap<String, String>,
+    pub sampler: OtelSampler,
 }
 
 impl Default for OtelConfig {
@@ -296,6 +324,8 @@ impl Default for OtelConfig {
             log_user_prompt: false,
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
+            resource_attributes: HashMap::new(),
+            sampler: OtelSampler::AlwaysOn,
         }
     }
 }
@@ -329,9 +359,19 @@ pub struct SandboxWorkspaceWrite {
     #[serde(default)]
     pub network_access: bool,
     #[serde(default)]
+    pub allow_local_network: bool,
+    #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Make each `.gitmodules` submodule a writable root with a read-only
+    /// `.git`. Defaults to `true` when unset.
+    #[serde(default)]
+    pub include_submodule_roots: Option<bool>,
+    /// Environment filter for sandboxed commands. Defaults to dropping
+    /// well-known credentials when unset.
+    #[serde(default)]
+    pub env_filter: Option<EnvFilter>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -339,8 +379,11 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
         Self {
             writable_roots: sandbox_workspace_write.writable_roots,
             network_access: Some(sandbox_workspace_write.network_access),
+            allow_local_network: Some(sandbox_workspace_write.allow_local_network),
             exclude_tmpdir_env_var: Some(sandbox_workspace_write.exclude_tmpdir_env_var),
             exclude_slash_tmp: Some(sandbox_workspace_write.exclude_slash_tmp),
+            include_submodule_roots: sandbox_workspace_write.include_submodule_roots,
+            env_filter: sandbox_workspace_write.env_filter,
         }
     }
 }
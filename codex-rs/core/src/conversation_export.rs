@@ -0,0 +1,281 @@
+//! Export a conversation's rollout as Markdown or JSON.
+//!
+//! A turn starts at each user message (session prefix messages such as
+//! `<user_instructions>` are skipped) and collects the assistant messages and
+//! tool calls that follow it. Per-turn token usage is the growth of the
+//! cumulative usage reported by `TokenCount` events within the turn.
+
+use std::path::Path;
+
+use anyhow::Context;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExportFormat;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+
+use crate::codex::compact::content_items_to_text;
+use crate::codex::compact::is_session_prefix_message;
+use crate::rollout::RolloutRecorder;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ExportedTurn {
+    pub turn: usize,
+    pub user: String,
+    pub entries: Vec<TurnEntry>,
+    pub tokens: Option<TurnTokens>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "text", rename_all = "snake_case")]
+pub(crate) enum TurnEntry {
+    Agent(String),
+    ToolCall(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct TurnTokens {
+    pub input: u64,
+    pub output: u64,
+}
+
+/// Read the rollout at `rollout_path` and write it to `path` as `format`.
+pub(crate) async fn export_rollout(
+    rollout_path: &Path,
+    path: &Path,
+    format: ExportFormat,
+) -> anyhow::Result<()> {
+    let history = RolloutRecorder::get_rollout_history(rollout_path)
+        .await
+        .with_context(|| format!("failed to read rollout {}", rollout_path.display()))?;
+    let turns = collect_turns(&history.get_rollout_items());
+    let contents = match format {
+        ExportFormat::Markdown => render_markdown(&turns),
+        ExportFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&turns)?;
+            json.push('\n');
+            json
+        }
+    };
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub(crate) fn collect_turns(items: &[RolloutItem]) -> Vec<ExportedTurn> {
+    let mut turns: Vec<ExportedTurn> = Vec::new();
+    let mut usage_at_turn_start: Option<TokenUsage> = None;
+    let mut latest_usage: Option<TokenUsage> = None;
+
+    for item in items {
+        match item {
+            RolloutItem::ResponseItem(item) => {
+                if let ResponseItem::Message { role, content, .. } = item
+                    && role == "user"
+                {
+                    let Some(text) = content_items_to_text(content) else {
+                        continue;
+                    };
+                    if is_session_prefix_message(&text) {
+                        continue;
+                    }
+                    finish_turn(turns.last_mut(), &usage_at_turn_start, &latest_usage);
+                    usage_at_turn_start = latest_usage.clone();
+                    turns.push(ExportedTurn {
+                        turn: turns.len() + 1,
+                        user: text,
+                        entries: Vec::new(),
+                        tokens: None,
+                    });
+                    continue;
+                }
+                let Some(turn) = turns.last_mut() else {
+                    continue;
+                };
+                if let Some(entry) = turn_entry(item) {
+                    turn.entries.push(entry);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::TokenCount(ev)) => {
+                if let Some(info) = &ev.info {
+                    latest_usage = Some(info.total_token_usage.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    finish_turn(turns.last_mut(), &usage_at_turn_start, &latest_usage);
+    turns
+}
+
+fn finish_turn(
+    turn: Option<&mut ExportedTurn>,
+    at_start: &Option<TokenUsage>,
+    latest: &Option<TokenUsage>,
+) {
+    let (Some(turn), Some(latest)) = (turn, latest) else {
+        return;
+    };
+    let start = at_start.clone().unwrap_or_default();
+    if latest.total_tokens == start.total_tokens {
+        return;
+    }
+    turn.tokens = Some(TurnTokens {
+        input: latest.input_tokens.saturating_sub(start.input_tokens),
+        output: latest.output_tokens.saturating_sub(start.output_tokens),
+    });
+}
+
+fn turn_entry(item: &ResponseItem) -> Option<TurnEntry> {
+    match item {
+        ResponseItem::Message { role, content, .. } if role == "assistant" => {
+            content_items_to_text(content).map(TurnEntry::Agent)
+        }
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => {
+            let command = serde_json::from_str::<ShellToolCallParams>(arguments)
+                .ok()
+                .filter(|_| name == "shell" || name == "container.exec")
+                .map(|params| join_command(&params.command))
+                .unwrap_or_else(|| format!("{name} {arguments}"));
+            Some(TurnEntry::ToolCall(command))
+        }
+        ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => Some(TurnEntry::ToolCall(join_command(&exec.command))),
+        ResponseItem::CustomToolCall { name, input, .. } => {
+            Some(TurnEntry::ToolCall(format!("{name}\n{input}")))
+        }
+        _ => None,
+    }
+}
+
+fn join_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+pub(crate) fn render_markdown(turns: &[ExportedTurn]) -> String {
+    let mut out = String::from("# Codex conversation\n");
+    for turn in turns {
+        out.push_str(&format!("\n## Turn {}\n\n", turn.turn));
+        out.push_str("**User**\n\n");
+        out.push_str(turn.user.trim_end());
+        out.push('\n');
+        let mut agent_heading = false;
+        for entry in &turn.entries {
+            if !agent_heading {
+                out.push_str("\n**Codex**\n");
+                agent_heading = true;
+            }
+            match entry {
+                TurnEntry::Agent(text) => {
+                    out.push('\n');
+                    out.push_str(text.trim_end());
+                    out.push('\n');
+                }
+                TurnEntry::ToolCall(command) => {
+                    out.push_str("\n```bash\n");
+                    out.push_str(command.trim_end());
+                    out.push_str("\n```\n");
+                }
+            }
+        }
+        if let Some(tokens) = turn.tokens {
+            out.push_str(&format!(
+                "\n<!-- tokens: input={} output={} -->\n",
+                tokens.input, tokens.output
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        })
+    }
+
+    fn token_count(input_tokens: u64, output_tokens: u64) -> RolloutItem {
+        let usage = TokenUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            ..TokenUsage::default()
+        };
+        RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }))
+    }
+
+    #[test]
+    fn renders_turns_tool_calls_and_token_usage() {
+        let items = vec![
+            message("user", "<user_instructions>be terse</user_instructions>"),
+            message("user", "list the files"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls","-la","my dir"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            message("assistant", "There are two files."),
+            token_count(100, 20),
+            message("user", "thanks"),
+            message("assistant", "You're welcome."),
+            token_count(250, 30),
+        ];
+
+        let turns = collect_turns(&items);
+        assert_eq!(
+            turns.iter().map(|t| t.tokens).collect::<Vec<_>>(),
+            vec![
+                Some(TurnTokens {
+                    input: 100,
+                    output: 20
+                }),
+                Some(TurnTokens {
+                    input: 150,
+                    output: 10
+                }),
+            ]
+        );
+        assert_eq!(
+            render_markdown(&turns),
+            "# Codex conversation\n\
+             \n## Turn 1\n\n**User**\n\nlist the files\n\
+             \n**Codex**\n\n```bash\nls -la 'my dir'\n```\n\nThere are two files.\n\
+             \n<!-- tokens: input=100 output=20 -->\n\
+             \n## Turn 2\n\n**User**\n\nthanks\n\
+             \n**Codex**\n\nYou're welcome.\n\
+             \n<!-- tokens: input=150 output=10 -->\n"
+        );
+    }
+}
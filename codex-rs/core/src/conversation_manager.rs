@@ -171,6 +171,21 @@ impl ConversationManager {
 
         self.finalize_spawn(codex, conversation_id).await
     }
+
+    /// Fork the live conversation `source_id` before its `from_turn`th user
+    /// message (0-based), reusing its `Config`. The fork gets a fresh id and
+    /// its own rollout, so later turns in either conversation do not affect
+    /// the other.
+    pub async fn fork_conversation_from_turn(
+        &self,
+        source_id: ConversationId,
+        from_turn: usize,
+    ) -> CodexResult<NewConversation> {
+        let source = self.get_conversation(source_id).await?;
+        let path = source.flush_rollout().await?;
+        let config = source.config().clone();
+        self.fork_conversation(from_turn, config, path).await
+    }
 }
 
 /// Return a prefix of `items` obtained by cutting strictly before the nth user message
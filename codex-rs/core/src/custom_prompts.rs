@@ -2,14 +2,47 @@ use codex_protocol::custom_prompts::CustomPrompt;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 use tokio::fs;
 
+/// How long [`discover_prompts_cached`] reuses a previous scan.
+const SCAN_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Most recent cached scan: directory, scan time, and the prompts found.
+type ScanCache = Option<(PathBuf, Instant, Vec<CustomPrompt>)>;
+
+static SCAN_CACHE: Mutex<ScanCache> = Mutex::new(None);
+
 /// Return the default prompts directory: `$CODEX_HOME/prompts`.
 /// If `CODEX_HOME` cannot be resolved, returns `None`.
 pub fn default_prompts_dir() -> Option<PathBuf> {
     crate::config::find_codex_home()
         .ok()
-        .map(|home| home.join("prompts"))
+        .map(|home| prompts_dir(&home))
+}
+
+/// Return the prompts directory under the given Codex home.
+pub fn prompts_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join("prompts")
+}
+
+/// Like [`discover_prompts_in`], but reuses the previous scan of the same
+/// directory when it is less than 30 seconds old.
+pub async fn discover_prompts_cached(dir: &Path) -> Vec<CustomPrompt> {
+    if let Ok(cache) = SCAN_CACHE.lock()
+        && let Some((cached_dir, scanned_at, prompts)) = cache.as_ref()
+        && cached_dir == dir
+        && scanned_at.elapsed() < SCAN_CACHE_TTL
+    {
+        return prompts.clone();
+    }
+    let prompts = discover_prompts_in(dir).await;
+    if let Ok(mut cache) = SCAN_CACHE.lock() {
+        *cache = Some((dir.to_path_buf(), Instant::now(), prompts.clone()));
+    }
+    prompts
 }
 
 /// Discover prompt files in the given directory, returning entries sorted by name.
@@ -215,6 +248,24 @@ mod tests {
         assert_eq!(p.content, "Actual body with $1 and $ARGUMENTS");
     }
 
+    #[tokio::test]
+    async fn cached_scan_is_reused_for_the_same_dir() {
+        let tmp = tempdir().expect("create TempDir");
+        let dir = tmp.path();
+        fs::write(dir.join("first.md"), b"1").unwrap();
+        let names = |found: Vec<CustomPrompt>| -> Vec<String> {
+            found.into_iter().map(|e| e.name).collect()
+        };
+        assert_eq!(names(discover_prompts_cached(dir).await), vec!["first"]);
+
+        fs::write(dir.join("second.md"), b"2").unwrap();
+        assert_eq!(names(discover_prompts_cached(dir).await), vec!["first"]);
+        assert_eq!(
+            names(discover_prompts_in(dir).await),
+            vec!["first", "second"]
+        );
+    }
+
     #[test]
     fn parse_frontmatter_preserves_body_newlines() {
         let content = "---\r\ndescription: \"Line endings\"\r\nargument_hint: \"[arg]\"\r\n---\r\nFirst line\r\nSecond line\r\n";
@@ -18,6 +18,8 @@ use std::path::PathBuf;
 #[strum(serialize_all = "kebab-case")]
 pub enum NetworkAccess {
     Restricted,
+    /// Only localhost and unix domain sockets are reachable.
+    LocalOnly,
     Enabled,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -50,9 +52,15 @@ impl EnvironmentContext {
             network_access: match sandbox_policy {
                 Some(SandboxPolicy::DangerFullAccess) => Some(NetworkAccess::Enabled),
                 Some(SandboxPolicy::ReadOnly) => Some(NetworkAccess::Restricted),
-                Some(SandboxPolicy::WorkspaceWrite { network_access, .. }) => {
+                Some(SandboxPolicy::WorkspaceWrite {
+                    network_access,
+                    allow_local_network,
+                    ..
+                }) => {
                     if network_access {
                         Some(NetworkAccess::Enabled)
+                    } else if allow_local_network {
+                        Some(NetworkAccess::LocalOnly)
                     } else {
                         Some(NetworkAccess::Restricted)
                     }
@@ -178,14 +186,18 @@ mod tests {
     use crate::shell::ZshShell;
 
     use super::*;
+    use crate::protocol::EnvFilter;
     use pretty_assertions::assert_eq;
 
     fn workspace_write_policy(writable_roots: Vec<&str>, network_access: bool) -> SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: writable_roots.into_iter().map(PathBuf::from).collect(),
             network_access,
+            allow_local_network: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         }
     }
 
@@ -212,6 +224,27 @@ mod tests {
         assert_eq!(context.serialize_to_xml(), expected);
     }
 
+    #[test]
+    fn serialize_local_network_environment_context() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        };
+        let context = EnvironmentContext::new(None, None, Some(policy), None);
+
+        let expected = r#"<environment_context>
+  <sandbox_mode>workspace-write</sandbox_mode>
+  <network_access>local-only</network_access>
+</environment_context>"#;
+
+        assert_eq!(context.serialize_to_xml(), expected);
+    }
+
     #[test]
     fn serialize_read_only_environment_context() {
         let context = EnvironmentContext::new(
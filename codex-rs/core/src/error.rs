@@ -7,6 +7,7 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -23,7 +24,13 @@ pub enum SandboxErr {
         "sandbox denied exec error, exit code: {}, stdout: {}, stderr: {}",
         .output.exit_code, .output.stdout.text, .output.stderr.text
     )]
-    Denied { output: Box<ExecToolCallOutput> },
+    Denied {
+        output: Box<ExecToolCallOutput>,
+        /// Path the sandbox blocked, when it could be recovered from the output.
+        path: Option<PathBuf>,
+        /// Blocked operation (e.g. `file-write-create`, `syscall`), when known.
+        operation: Option<String>,
+    },
 
     /// Error from linux seccomp filter setup
     #[cfg(target_os = "linux")]
@@ -46,6 +53,13 @@ pub enum SandboxErr {
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
+
+    /// A write was attempted outside the sandbox policy's writable roots
+    #[error(
+        "the sandbox policy forbids writing outside its writable roots (attempted {})",
+        .attempted_path.display()
+    )]
+    WriteViolation { attempted_path: PathBuf },
 }
 
 #[derive(Error, Debug)]
@@ -309,7 +323,7 @@ impl CodexErr {
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
     let message = match e {
-        CodexErr::Sandbox(SandboxErr::Denied { output }) => {
+        CodexErr::Sandbox(SandboxErr::Denied { output, .. }) => {
             let aggregated = output.aggregated_output.text.trim();
             if !aggregated.is_empty() {
                 output.aggregated_output.text.clone()
@@ -387,6 +401,8 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         });
         assert_eq!(get_error_message_ui(&err), "aggregate detail");
     }
@@ -403,6 +419,8 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         });
         assert_eq!(get_error_message_ui(&err), "stderr detail\nstdout detail");
     }
@@ -419,6 +437,8 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         });
         assert_eq!(get_error_message_ui(&err), "stdout only");
     }
@@ -435,6 +455,8 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         });
         assert_eq!(
             get_error_message_ui(&err),
@@ -6,10 +6,12 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::OnceLock;
 use std::time::Duration;
 use std::time::Instant;
 
 use async_channel::Sender;
+use regex_lite::Regex;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
@@ -18,11 +20,13 @@ use tokio::process::Child;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
+use crate::git_info::sandbox_writable_roots;
 use crate::landlock::spawn_command_under_linux_sandbox;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
+use crate::protocol::ExecSandbox;
 use crate::protocol::SandboxPolicy;
 use crate::seatbelt::spawn_command_under_seatbelt;
 use crate::spawn::StdioPolicy;
@@ -53,6 +57,7 @@ pub struct ExecParams {
     pub env: HashMap<String, String>,
     pub with_escalated_permissions: Option<bool>,
     pub justification: Option<String>,
+    pub resource_limits: Option<ExecResourceLimits>,
 }
 
 impl ExecParams {
@@ -61,6 +66,36 @@ impl ExecParams {
     }
 }
 
+/// Per-command `setrlimit(2)` caps, applied to the child before it execs.
+/// Each limit is used as both the soft and the hard limit, so a command that
+/// exhausts its CPU time is killed with `SIGKILL`. Ignored on Windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExecResourceLimits {
+    pub max_cpu_time_s: Option<u32>,
+    pub max_file_size_bytes: Option<u64>,
+    pub max_open_files: Option<u32>,
+}
+
+impl ExecResourceLimits {
+    /// Every limit that is set must be positive.
+    pub fn validate(&self) -> Result<()> {
+        let zero = [
+            ("max_cpu_time_s", self.max_cpu_time_s == Some(0)),
+            ("max_file_size_bytes", self.max_file_size_bytes == Some(0)),
+            ("max_open_files", self.max_open_files == Some(0)),
+        ]
+        .into_iter()
+        .find_map(|(name, is_zero)| is_zero.then_some(name));
+        match zero {
+            Some(name) => Err(CodexErr::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("resource limit {name} must be positive"),
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SandboxType {
     None,
@@ -72,6 +107,16 @@ pub enum SandboxType {
     LinuxSeccomp,
 }
 
+impl From<SandboxType> for ExecSandbox {
+    fn from(sandbox: SandboxType) -> Self {
+        match sandbox {
+            SandboxType::None => ExecSandbox::None,
+            SandboxType::MacosSeatbelt => ExecSandbox::MacosSeatbelt,
+            SandboxType::LinuxSeccomp => ExecSandbox::LinuxSeccomp,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct StdoutStream {
     pub sub_id: String,
@@ -80,16 +125,26 @@ pub struct StdoutStream {
 }
 
 pub async fn process_exec_tool_call(
-    params: ExecParams,
+    mut params: ExecParams,
     sandbox_type: SandboxType,
     sandbox_policy: &SandboxPolicy,
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<ExecToolCallOutput> {
+    if let Some(limits) = &params.resource_limits {
+        limits.validate()?;
+    }
+    // Commands the policy lets run outside the sandbox (known-safe commands,
+    // for one) must not see the variables it filters out either.
+    if let Some(env_filter) = sandbox_policy.env_filter() {
+        env_filter.apply(&mut params.env);
+    }
+
     let start = Instant::now();
 
     let timeout_duration = params.timeout_duration();
+    let command_cwd = params.cwd.clone();
 
     let raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr> = match sandbox_type
     {
@@ -99,6 +154,7 @@ pub async fn process_exec_tool_call(
                 command,
                 cwd: command_cwd,
                 env,
+                resource_limits,
                 ..
             } = params;
             let child = spawn_command_under_seatbelt(
@@ -108,6 +164,7 @@ pub async fn process_exec_tool_call(
                 sandbox_cwd,
                 StdioPolicy::RedirectForShellTool,
                 env,
+                resource_limits,
             )
             .await?;
             consume_truncated_output(child, timeout_duration, stdout_stream.clone()).await
@@ -117,6 +174,7 @@ pub async fn process_exec_tool_call(
                 command,
                 cwd: command_cwd,
                 env,
+                resource_limits,
                 ..
             } = params;
 
@@ -131,6 +189,7 @@ pub async fn process_exec_tool_call(
                 sandbox_cwd,
                 StdioPolicy::RedirectForShellTool,
                 env,
+                resource_limits,
             )
             .await?;
 
@@ -178,8 +237,17 @@ pub async fn process_exec_tool_call(
             }
 
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
+                let (path, operation) = sandbox_denial_details(
+                    sandbox_type,
+                    &exec_output,
+                    sandbox_policy,
+                    sandbox_cwd,
+                    &command_cwd,
+                );
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
+                    path,
+                    operation,
                 }));
             }
 
@@ -248,6 +316,108 @@ fn is_likely_sandbox_denied(sandbox_type: SandboxType, exec_output: &ExecToolCal
     false
 }
 
+fn seatbelt_deny_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+
+    // e.g. `sandbox-exec: deny(1) file-write-create /etc/hosts`
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| Regex::new(r"deny(?:\(\d+\))?\s+([\w*-]+)\s+(/\S*)").unwrap())
+}
+
+/// Stderr fragments that only show up when a write was attempted. A bare
+/// "Permission denied" may just as well come from a read or an exec.
+const WRITE_DENIAL_MARKERS: &[&str] = &[
+    "read-only file system",
+    "cannot touch",
+    "cannot create",
+    "cannot remove",
+    "cannot make directory",
+    "cannot move",
+    "cannot overwrite",
+    "for writing",
+];
+
+fn quoted_path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+
+    // e.g. `touch: cannot touch '/etc/hosts': Read-only file system`
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| Regex::new(r#"['‘"`]([^'’"`]+)['’"`]"#).unwrap())
+}
+
+/// Best-effort recovery of what a denied command tried to do, as
+/// `(path, operation)`. A path is only reported when it falls outside the
+/// policy's writable roots, so ordinary permission errors inside the
+/// workspace are not misattributed to the sandbox. The operation is left
+/// unset unless the message identifies a write.
+fn sandbox_denial_details(
+    sandbox_type: SandboxType,
+    exec_output: &ExecToolCallOutput,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_cwd: &Path,
+    command_cwd: &Path,
+) -> (Option<PathBuf>, Option<String>) {
+    let lines = exec_output
+        .stderr
+        .text
+        .lines()
+        .chain(exec_output.aggregated_output.text.lines());
+
+    let writable_roots = sandbox_writable_roots(sandbox_policy, sandbox_cwd);
+    let is_blocked = |path: &Path| {
+        !sandbox_policy.has_full_disk_write_access()
+            && !writable_roots
+                .iter()
+                .any(|root| root.is_path_writable(path))
+    };
+
+    for line in lines {
+        if sandbox_type == SandboxType::MacosSeatbelt
+            && let Some(caps) = seatbelt_deny_regex().captures(line)
+        {
+            return (Some(PathBuf::from(&caps[2])), Some(caps[1].to_string()));
+        }
+
+        let lower = line.to_lowercase();
+        if ![
+            "read-only file system",
+            "permission denied",
+            "operation not permitted",
+        ]
+        .iter()
+        .any(|needle| lower.contains(needle))
+        {
+            continue;
+        }
+        let quoted = quoted_path_regex()
+            .captures_iter(line)
+            .filter_map(|caps| caps.get(1).map(|m| m.as_str()));
+        // Unquoted form, e.g. `bash: /etc/hosts: Read-only file system`.
+        let unquoted = line.split(": ").filter(|part| part.starts_with('/'));
+        for candidate in quoted.chain(unquoted) {
+            let path = command_cwd.join(candidate.trim());
+            if is_blocked(&path) {
+                let operation = WRITE_DENIAL_MARKERS
+                    .iter()
+                    .any(|marker| lower.contains(marker))
+                    .then(|| "file-write".to_string());
+                return (Some(path), operation);
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        if sandbox_type == SandboxType::LinuxSeccomp
+            && exec_output.exit_code == EXIT_CODE_SIGNAL_BASE + libc::SIGSYS
+        {
+            return (None, Some("syscall".to_string()));
+        }
+    }
+
+    (None, None)
+}
+
 #[derive(Debug)]
 pub struct StreamOutput<T> {
     pub text: T,
@@ -302,7 +472,11 @@ async fn exec(
 ) -> Result<RawExecToolCallOutput> {
     let timeout = params.timeout_duration();
     let ExecParams {
-        command, cwd, env, ..
+        command,
+        cwd,
+        env,
+        resource_limits,
+        ..
     } = params;
 
     let (program, args) = command.split_first().ok_or_else(|| {
@@ -320,6 +494,7 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        resource_limits,
     )
     .await?;
     consume_truncated_output(child, timeout, stdout_stream).await
@@ -476,6 +651,7 @@ fn synthetic_exit_status(code: i32) -> ExitStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::EnvFilter;
     use std::time::Duration;
 
     fn make_exec_output(
@@ -545,4 +721,139 @@ mod tests {
         let output = make_exec_output(exit_code, "", "", "");
         assert!(is_likely_sandbox_denied(SandboxType::LinuxSeccomp, &output));
     }
+
+    fn workspace_policy(root: &str) -> SandboxPolicy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![PathBuf::from(root)],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        }
+    }
+
+    #[test]
+    fn denial_details_parse_seatbelt_deny_line() {
+        let output = make_exec_output(
+            1,
+            "",
+            "sandbox-exec: deny(1) file-write-create /etc/codex-test\n",
+            "",
+        );
+        let policy = workspace_policy("/workspace");
+        assert_eq!(
+            sandbox_denial_details(
+                SandboxType::MacosSeatbelt,
+                &output,
+                &policy,
+                Path::new("/workspace"),
+                Path::new("/workspace"),
+            ),
+            (
+                Some(PathBuf::from("/etc/codex-test")),
+                Some("file-write-create".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn denial_details_report_path_outside_writable_roots() {
+        let output = make_exec_output(
+            1,
+            "",
+            "touch: cannot touch '/codex-test': Read-only file system\n",
+            "",
+        );
+        let policy = workspace_policy("/workspace");
+        assert_eq!(
+            sandbox_denial_details(
+                SandboxType::LinuxSeccomp,
+                &output,
+                &policy,
+                Path::new("/workspace"),
+                Path::new("/workspace"),
+            ),
+            (
+                Some(PathBuf::from("/codex-test")),
+                Some("file-write".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn denial_details_parse_unquoted_paths() {
+        let output = make_exec_output(1, "", "bash: /etc/hosts: Permission denied\n", "");
+        let policy = workspace_policy("/workspace");
+        // A bare "Permission denied" does not say which operation failed.
+        assert_eq!(
+            sandbox_denial_details(
+                SandboxType::LinuxSeccomp,
+                &output,
+                &policy,
+                Path::new("/workspace"),
+                Path::new("/workspace"),
+            ),
+            (Some(PathBuf::from("/etc/hosts")), None)
+        );
+    }
+
+    #[test]
+    fn denial_details_ignore_paths_inside_writable_roots() {
+        let output = make_exec_output(
+            1,
+            "",
+            "touch: cannot touch 'out.txt': Permission denied\n",
+            "",
+        );
+        let policy = workspace_policy("/workspace");
+        assert_eq!(
+            sandbox_denial_details(
+                SandboxType::LinuxSeccomp,
+                &output,
+                &policy,
+                Path::new("/workspace"),
+                Path::new("/workspace/sub"),
+            ),
+            (None, None)
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unsandboxed_command_does_not_receive_deny_listed_vars() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::DenyList(vec!["SECRET".to_string()]),
+        };
+        let cwd = std::env::temp_dir();
+        let params = ExecParams {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "echo \"secret=${SECRET:-unset} kept=${KEPT:-unset}\"".to_string(),
+            ],
+            cwd: cwd.clone(),
+            timeout_ms: None,
+            env: HashMap::from([
+                ("SECRET".to_string(), "value".to_string()),
+                ("KEPT".to_string(), "yes".to_string()),
+            ]),
+            with_escalated_permissions: None,
+            justification: None,
+            resource_limits: None,
+        };
+
+        let output = process_exec_tool_call(params, SandboxType::None, &policy, &cwd, &None, None)
+            .await
+            .expect("exec");
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(output.stdout.text, "secret=unset kept=yes\n");
+    }
 }
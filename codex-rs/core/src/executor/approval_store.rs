@@ -0,0 +1,241 @@
+//! Opt-in persistence for commands approved "for this session".
+//!
+//! When `persist_command_approvals` is enabled, approvals are written to
+//! `CODEX_HOME/approvals/<project-hash>.json` so they survive restarts. Only
+//! SHA-256 digests of the command vectors are stored, never the raw
+//! arguments, so secrets passed on the command line do not end up on disk.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+/// Directory under `CODEX_HOME` that holds the per-project approval files.
+pub const APPROVALS_DIR: &str = "approvals";
+
+/// Persisted approvals older than this are dropped.
+pub const DEFAULT_APPROVAL_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Upper bound on persisted approvals per project; the oldest are evicted first.
+pub const DEFAULT_MAX_APPROVALS: usize = 500;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApprovalsFile {
+    entries: Vec<PersistedApproval>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct PersistedApproval {
+    command_sha256: String,
+    /// Unix timestamp (seconds) of the most recent approval.
+    approved_at: i64,
+}
+
+/// Approval file for a single project root.
+#[derive(Clone, Debug)]
+pub struct ApprovalStore {
+    path: PathBuf,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl ApprovalStore {
+    pub fn for_project(codex_home: &Path, project_root: &Path) -> Self {
+        let digest = Sha256::digest(project_root.to_string_lossy().as_bytes());
+        let name = format!("{}.json", &hex(&digest)[..16]);
+        Self {
+            path: codex_home.join(APPROVALS_DIR).join(name),
+            ttl: DEFAULT_APPROVAL_TTL,
+            max_entries: DEFAULT_MAX_APPROVALS,
+        }
+    }
+
+    pub fn with_limits(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.ttl = ttl;
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Digests of the approvals that have not yet expired.
+    pub fn load(&self) -> HashSet<String> {
+        self.load_at(now_unix())
+    }
+
+    /// Record `command` as approved, refreshing its timestamp if already present.
+    pub fn record(&self, command: &[String]) -> std::io::Result<()> {
+        self.record_at(command, now_unix())
+    }
+
+    fn load_at(&self, now: i64) -> HashSet<String> {
+        self.live_entries(now)
+            .into_iter()
+            .map(|entry| entry.command_sha256)
+            .collect()
+    }
+
+    fn record_at(&self, command: &[String], now: i64) -> std::io::Result<()> {
+        let hash = command_hash(command);
+        let mut entries = self.live_entries(now);
+        entries.retain(|entry| entry.command_sha256 != hash);
+        entries.push(PersistedApproval {
+            command_sha256: hash,
+            approved_at: now,
+        });
+        entries.sort_by_key(|entry| entry.approved_at);
+        let excess = entries.len().saturating_sub(self.max_entries);
+        entries.drain(..excess);
+        self.write(&ApprovalsFile { entries })
+    }
+
+    fn live_entries(&self, now: i64) -> Vec<PersistedApproval> {
+        let file: ApprovalsFile = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("ignoring malformed {}: {e}", self.path.display());
+                ApprovalsFile::default()
+            }),
+            Err(_) => ApprovalsFile::default(),
+        };
+        let ttl = i64::try_from(self.ttl.as_secs()).unwrap_or(i64::MAX);
+        file.entries
+            .into_iter()
+            .filter(|entry| now.saturating_sub(entry.approved_at) < ttl)
+            .collect()
+    }
+
+    fn write(&self, file: &ApprovalsFile) -> std::io::Result<()> {
+        let Some(dir) = self.path.parent() else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)?;
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        serde_json::to_writer_pretty(&mut tmp, file)?;
+        tmp.flush()?;
+        tmp.persist(&self.path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+/// Stable digest of a command vector. Arguments are NUL-terminated so that
+/// `["ab", "c"]` and `["a", "bc"]` hash differently.
+pub fn command_hash(command: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for arg in command {
+        hasher.update(arg.as_bytes());
+        hasher.update([0]);
+    }
+    hex(&hasher.finalize())
+}
+
+/// Remove every persisted approval under `codex_home`. Returns `false` when
+/// there was nothing to remove.
+pub fn clear_persisted_approvals(codex_home: &Path) -> std::io::Result<bool> {
+    match std::fs::remove_dir_all(codex_home.join(APPROVALS_DIR)) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn cmd(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn command_hash_is_stable_and_argument_aware() {
+        assert_eq!(
+            command_hash(&cmd(&["npm", "install"])),
+            command_hash(&cmd(&["npm", "install"]))
+        );
+        assert_ne!(
+            command_hash(&cmd(&["ab", "c"])),
+            command_hash(&cmd(&["a", "bc"]))
+        );
+        // Pinned so that a change in encoding does not silently invalidate
+        // every approval already on disk.
+        assert_eq!(
+            command_hash(&cmd(&["npm", "install"])),
+            "e2040f03fb8bbc811bd2280df09558176c5853713cf71fa7254942b551cbdf39"
+        );
+    }
+
+    #[test]
+    fn expired_entries_are_pruned() {
+        let home = TempDir::new().expect("tempdir");
+        let store = ApprovalStore::for_project(home.path(), Path::new("/repo"))
+            .with_limits(Duration::from_secs(100), DEFAULT_MAX_APPROVALS);
+        let old = cmd(&["npm", "install"]);
+        let fresh = cmd(&["cargo", "build"]);
+
+        store.record_at(&old, 1_000).expect("record");
+        store.record_at(&fresh, 1_050).expect("record");
+        assert_eq!(store.load_at(1_120), HashSet::from([command_hash(&fresh)]));
+
+        // Writing again drops the expired entry from the file as well.
+        store.record_at(&fresh, 1_120).expect("record");
+        let on_disk: ApprovalsFile =
+            serde_json::from_str(&std::fs::read_to_string(store.path()).expect("read"))
+                .expect("parse");
+        assert_eq!(
+            on_disk.entries,
+            vec![PersistedApproval {
+                command_sha256: command_hash(&fresh),
+                approved_at: 1_120,
+            }]
+        );
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted_past_the_cap() {
+        let home = TempDir::new().expect("tempdir");
+        let store = ApprovalStore::for_project(home.path(), Path::new("/repo"))
+            .with_limits(DEFAULT_APPROVAL_TTL, 2);
+        for (i, name) in ["a", "b", "c"].into_iter().enumerate() {
+            store
+                .record_at(&cmd(&[name]), 10 + i as i64)
+                .expect("record");
+        }
+        assert_eq!(
+            store.load_at(20),
+            HashSet::from([command_hash(&cmd(&["b"])), command_hash(&cmd(&["c"]))])
+        );
+    }
+
+    #[test]
+    fn projects_are_isolated_and_clearable() {
+        let home = TempDir::new().expect("tempdir");
+        let a = ApprovalStore::for_project(home.path(), Path::new("/repo-a"));
+        let b = ApprovalStore::for_project(home.path(), Path::new("/repo-b"));
+        a.record(&cmd(&["make"])).expect("record");
+
+        assert_eq!(a.load(), HashSet::from([command_hash(&cmd(&["make"]))]));
+        assert!(b.load().is_empty());
+
+        assert!(clear_persisted_approvals(home.path()).expect("clear"));
+        assert!(a.load().is_empty());
+        assert!(!clear_persisted_approvals(home.path()).expect("clear"));
+    }
+}
@@ -95,6 +95,7 @@ impl ExecutionBackend for ApplyPatchBackend {
                     env: HashMap::new(),
                     with_escalated_permissions: params.with_escalated_permissions,
                     justification: params.justification,
+                    resource_limits: params.resource_limits,
                 })
             }
             ExecutionMode::Shell => Err(FunctionCallError::RespondToModel(
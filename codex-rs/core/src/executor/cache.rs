@@ -2,18 +2,53 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use super::approval_store::ApprovalStore;
+use super::approval_store::command_hash;
+
 #[derive(Clone, Debug, Default)]
 /// Thread-safe store of user approvals so repeated commands can reuse
 /// previously granted trust.
 pub(crate) struct ApprovalCache {
     inner: Arc<Mutex<HashSet<Vec<String>>>>,
+    persisted: Option<Arc<PersistedApprovals>>,
+}
+
+/// Approvals loaded from (and written back to) an [`ApprovalStore`]. Only
+/// command digests are known for entries from earlier sessions.
+#[derive(Debug)]
+struct PersistedApprovals {
+    store: ApprovalStore,
+    hashes: Mutex<HashSet<String>>,
 }
 
 impl ApprovalCache {
+    /// Cache backed by `store`, seeded with its unexpired approvals.
+    pub(crate) fn persistent(store: ApprovalStore) -> Self {
+        let hashes = Mutex::new(store.load());
+        Self {
+            inner: Arc::default(),
+            persisted: Some(Arc::new(PersistedApprovals { store, hashes })),
+        }
+    }
+
     pub(crate) fn insert(&self, command: Vec<String>) {
         if command.is_empty() {
             return;
         }
+        if let Some(persisted) = &self.persisted {
+            let hash = command_hash(&command);
+            let newly_persisted = persisted
+                .hashes
+                .lock()
+                .map(|mut hashes| hashes.insert(hash))
+                .unwrap_or(false);
+            if newly_persisted && let Err(e) = persisted.store.record(&command) {
+                tracing::warn!(
+                    "failed to persist command approval to {}: {e}",
+                    persisted.store.path().display()
+                );
+            }
+        }
         if let Ok(mut guard) = self.inner.lock() {
             guard.insert(command);
         }
@@ -22,6 +57,23 @@ impl ApprovalCache {
     pub(crate) fn snapshot(&self) -> HashSet<Vec<String>> {
         self.inner.lock().map(|g| g.clone()).unwrap_or_default()
     }
+
+    /// Snapshot for evaluating `command`; includes `command` itself when it
+    /// was approved in an earlier session.
+    pub(crate) fn snapshot_for(&self, command: &[String]) -> HashSet<Vec<String>> {
+        let mut snapshot = self.snapshot();
+        if let Some(persisted) = &self.persisted
+            && !command.is_empty()
+            && persisted
+                .hashes
+                .lock()
+                .map(|hashes| hashes.contains(&command_hash(command)))
+                .unwrap_or(false)
+        {
+            snapshot.insert(command.to_vec());
+        }
+        snapshot
+    }
 }
 
 #[cfg(test)]
@@ -48,4 +100,40 @@ mod tests {
         let snap2 = cache.snapshot();
         assert_eq!(snap1, snap2);
     }
+
+    #[test]
+    fn persisted_approvals_survive_restart_and_others_still_prompt() {
+        use crate::safety::SafetyCheck;
+        use crate::safety::assess_command_safety;
+        use codex_protocol::protocol::AskForApproval;
+        use codex_protocol::protocol::SandboxPolicy;
+        use std::path::Path;
+
+        let home = tempfile::TempDir::new().expect("tempdir");
+        let store = || ApprovalStore::for_project(home.path(), Path::new("/repo"));
+        let approved = vec!["npm".to_string(), "install".to_string()];
+        let other = vec!["npm".to_string(), "publish".to_string()];
+
+        ApprovalCache::persistent(store()).insert(approved.clone());
+
+        // A fresh cache (new session) picks the approval up from disk.
+        let cache = ApprovalCache::persistent(store());
+        let check = |command: &[String]| {
+            assess_command_safety(
+                command,
+                AskForApproval::UnlessTrusted,
+                &SandboxPolicy::ReadOnly,
+                &cache.snapshot_for(command),
+                false,
+            )
+        };
+        assert_eq!(
+            check(&approved),
+            SafetyCheck::AutoApprove {
+                sandbox_type: crate::exec::SandboxType::None,
+                user_explicitly_approved: true,
+            }
+        );
+        assert_eq!(check(&other), SafetyCheck::AskUser);
+    }
 }
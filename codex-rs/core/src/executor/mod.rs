@@ -1,3 +1,4 @@
+pub mod approval_store;
 mod backends;
 mod cache;
 mod runner;
@@ -8,6 +9,12 @@ pub(crate) use runner::ExecutionRequest;
 pub(crate) use runner::Executor;
 pub(crate) use runner::ExecutorConfig;
 pub(crate) use runner::normalize_exec_result;
+pub use sandbox::SandboxExplanation;
+pub use sandbox::SandboxHelperStatus;
+pub(crate) use sandbox::SandboxPlan;
+pub use sandbox::explain_apply_patch;
+pub use sandbox::explain_shell_command;
+pub(crate) use sandbox::plan_apply_patch_sandbox;
 
 pub(crate) mod linkers {
     use crate::exec::ExecParams;
@@ -1,11 +1,16 @@
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::time::Duration;
 
+use super::approval_store::ApprovalStore;
 use super::backends::ExecutionMode;
 use super::backends::backend_for_mode;
 use super::cache::ApprovalCache;
+use crate::apply_patch::ApplyPatchExec;
+use crate::apply_patch::CODEX_APPLY_PATCH_BACKUP_DIR_ENV_VAR;
+use crate::apply_patch::patch_target_paths;
 use crate::codex::Session;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
@@ -17,20 +22,33 @@ use crate::exec::StdoutStream;
 use crate::exec::StreamOutput;
 use crate::exec::process_exec_tool_call;
 use crate::executor::errors::ExecError;
+use crate::executor::sandbox::SandboxPlan;
+use crate::executor::sandbox::plan_sandbox;
 use crate::executor::sandbox::select_sandbox;
 use crate::function_tool::FunctionCallError;
+use crate::git_info::sandbox_writable_roots;
 use crate::protocol::AskForApproval;
+use crate::protocol::DecisionTrace;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
+use crate::redact::OutputRedactor;
+use crate::redact::maybe_redact;
 use crate::shell;
 use crate::tools::context::ExecCommandContext;
+use codex_apply_patch::backup::BackupEntry;
+use codex_apply_patch::backup::backup_files;
+use codex_apply_patch::backup::restore;
 use codex_otel::otel_event_manager::ToolDecisionSource;
+use tracing::debug;
 
 #[derive(Clone, Debug)]
 pub(crate) struct ExecutorConfig {
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) sandbox_cwd: PathBuf,
     pub(crate) codex_exe: Option<PathBuf>,
+    /// Where apply_patch targets are copied before a patch runs; read from
+    /// `CODEX_APPLY_PATCH_BACKUP_DIR`.
+    pub(crate) apply_patch_backup_dir: Option<PathBuf>,
 }
 
 impl ExecutorConfig {
@@ -43,6 +61,8 @@ impl ExecutorConfig {
             sandbox_policy,
             sandbox_cwd,
             codex_exe,
+            apply_patch_backup_dir: std::env::var_os(CODEX_APPLY_PATCH_BACKUP_DIR_ENV_VAR)
+                .map(PathBuf::from),
         }
     }
 }
@@ -62,6 +82,13 @@ impl Executor {
         }
     }
 
+    /// Persist "approve for session" decisions to `store` and reuse the
+    /// approvals already recorded there.
+    pub(crate) fn with_persistent_approvals(mut self, store: ApprovalStore) -> Self {
+        self.approval_cache = ApprovalCache::persistent(store);
+        self
+    }
+
     /// Updates the sandbox policy and working directory used for future
     /// executions without recreating the executor.
     pub(crate) fn update_environment(&self, sandbox_policy: SandboxPolicy, sandbox_cwd: PathBuf) {
@@ -71,16 +98,17 @@ impl Executor {
         }
     }
 
-    /// Runs a prepared execution request end-to-end: prepares parameters, decides on
-    /// sandbox placement (prompting the user when necessary), launches the command,
-    /// and lets the backend post-process the final output.
-    pub(crate) async fn run(
+    /// First half of running a prepared execution request: prepares parameters
+    /// and decides on sandbox placement without prompting anyone. When no
+    /// approval is needed the plan carries a [`DecisionTrace`] explaining the
+    /// decision; pass it to [`Executor::execute`] to ask for approval if
+    /// required and launch the command.
+    pub(crate) fn plan(
         &self,
         mut request: ExecutionRequest,
         session: &Session,
         approval_policy: AskForApproval,
-        context: &ExecCommandContext,
-    ) -> Result<ExecToolCallOutput, ExecError> {
+    ) -> Result<ExecPlan, ExecError> {
         if matches!(request.mode, ExecutionMode::Shell) {
             request.params =
                 maybe_translate_shell_command(request.params, session, request.use_shell_profile);
@@ -104,12 +132,43 @@ impl Executor {
             .prepare(request.params, &request.mode, &config)
             .map_err(ExecError::from)?;
 
-        // Step 3: Decide sandbox placement, prompting for approval when needed.
-        let sandbox_decision = select_sandbox(
+        // Step 3: Decide sandbox placement; any prompt waits for `execute`.
+        let sandbox_plan = plan_sandbox(
             &request,
             approval_policy,
-            self.approval_cache.snapshot(),
+            &self.approval_cache.snapshot_for(&request.approval_command),
             &config,
+        );
+
+        Ok(ExecPlan {
+            request,
+            config,
+            stdout_stream,
+            approval_policy,
+            sandbox_plan,
+        })
+    }
+
+    /// Asks for approval when the plan requires it, then launches the command
+    /// and handles sandbox outcomes.
+    pub(crate) async fn execute(
+        &self,
+        plan: ExecPlan,
+        session: &Session,
+        context: &ExecCommandContext,
+    ) -> Result<ExecToolCallOutput, ExecError> {
+        let ExecPlan {
+            request,
+            config,
+            stdout_stream,
+            approval_policy,
+            sandbox_plan,
+        } = plan;
+
+        let sandbox_decision = select_sandbox(
+            &request,
+            sandbox_plan,
+            approval_policy,
             session,
             &context.sub_id,
             &context.call_id,
@@ -119,6 +178,19 @@ impl Executor {
         if sandbox_decision.record_session_approval {
             self.approval_cache.insert(request.approval_command.clone());
         }
+        debug!(
+            call_id = %context.call_id,
+            trace = ?sandbox_decision.trace,
+            "exec decision: {}",
+            sandbox_decision.trace.explanation()
+        );
+
+        let backup = match (&request.mode, &config.apply_patch_backup_dir) {
+            (ExecutionMode::ApplyPatch(exec), Some(backup_dir)) => {
+                backup_patch_targets(exec, backup_dir, session, context).await
+            }
+            _ => None,
+        };
 
         // Step 4: Launch the command within the chosen sandbox.
         let first_attempt = self
@@ -131,7 +203,7 @@ impl Executor {
             .await;
 
         // Step 5: Handle sandbox outcomes, optionally escalating to an unsandboxed retry.
-        match first_attempt {
+        let result = match first_attempt {
             Ok(output) => Ok(output),
             Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => {
                 Err(CodexErr::Sandbox(SandboxErr::Timeout { output }).into())
@@ -145,19 +217,34 @@ impl Executor {
                         context,
                         stdout_stream,
                         error,
+                        backup.as_deref(),
                     )
                     .await
                 } else {
-                    let message = sandbox_failure_message(error);
+                    let message = sandbox_failure_message(
+                        error,
+                        &config,
+                        session.services.output_redactor.as_deref(),
+                    );
                     Err(ExecError::rejection(message))
                 }
             }
             Err(err) => Err(err.into()),
+        };
+
+        if let Some(entries) = backup
+            && !matches!(&result, Ok(output) if output.exit_code == 0)
+        {
+            restore_patch_targets(&entries, session, context).await;
         }
+        result
     }
 
     /// Fallback path invoked when a sandboxed run is denied so the user can
-    /// approve rerunning without isolation.
+    /// approve rerunning without isolation. An approved apply_patch retry
+    /// starts from the backed-up files, not from whatever the denied attempt
+    /// left behind.
+    #[allow(clippy::too_many_arguments)]
     async fn retry_without_sandbox(
         &self,
         request: &ExecutionRequest,
@@ -166,6 +253,7 @@ impl Executor {
         context: &ExecCommandContext,
         stdout_stream: Option<StdoutStream>,
         sandbox_error: SandboxErr,
+        backup: Option<&[BackupEntry]>,
     ) -> Result<ExecToolCallOutput, ExecError> {
         session
             .notify_background_event(
@@ -197,6 +285,16 @@ impl Executor {
                 session
                     .notify_background_event(&context.sub_id, "retrying command without sandbox")
                     .await;
+                if let Some(entries) = backup
+                    && let Err(err) = restore(entries)
+                {
+                    session
+                        .notify_background_event(
+                            &context.sub_id,
+                            format!("apply_patch retry: restoring from backup failed: {err}"),
+                        )
+                        .await;
+                }
 
                 let retry_output = self
                     .spawn(
@@ -234,6 +332,58 @@ impl Executor {
     }
 }
 
+/// Copy the files an apply_patch exec will write into `backup_dir` and report
+/// where they went. A failed backup is reported and the patch goes ahead
+/// without one.
+async fn backup_patch_targets(
+    exec: &ApplyPatchExec,
+    backup_dir: &Path,
+    session: &Session,
+    context: &ExecCommandContext,
+) -> Option<Vec<BackupEntry>> {
+    match backup_files(patch_target_paths(&exec.action), backup_dir) {
+        Ok(entries) => {
+            let saved: Vec<String> = entries
+                .iter()
+                .filter_map(|entry| entry.backup.as_ref())
+                .map(|path| path.display().to_string())
+                .collect();
+            let message = if saved.is_empty() {
+                "apply_patch backup: no existing files to back up".to_string()
+            } else {
+                format!("apply_patch backup: {}", saved.join(", "))
+            };
+            session
+                .notify_background_event(&context.sub_id, message)
+                .await;
+            Some(entries)
+        }
+        Err(err) => {
+            session
+                .notify_background_event(
+                    &context.sub_id,
+                    format!("apply_patch backup failed, continuing without one: {err}"),
+                )
+                .await;
+            None
+        }
+    }
+}
+
+async fn restore_patch_targets(
+    entries: &[BackupEntry],
+    session: &Session,
+    context: &ExecCommandContext,
+) {
+    let message = match restore(entries) {
+        Ok(()) => "apply_patch failed; restored files from backup".to_string(),
+        Err(err) => format!("apply_patch failed; restoring from backup failed: {err}"),
+    };
+    session
+        .notify_background_event(&context.sub_id, message)
+        .await;
+}
+
 fn maybe_translate_shell_command(
     params: ExecParams,
     session: &Session,
@@ -253,10 +403,72 @@ fn maybe_translate_shell_command(
     params
 }
 
-fn sandbox_failure_message(error: SandboxErr) -> String {
+/// The denied command's output goes to the model as is, so it is redacted the
+/// same way regular command output is.
+fn sandbox_failure_message(
+    error: SandboxErr,
+    config: &ExecutorConfig,
+    redactor: Option<&OutputRedactor>,
+) -> String {
+    let hint = sandbox_denial_hint(&error, config);
     let codex_error = CodexErr::Sandbox(error);
     let friendly = get_error_message_ui(&codex_error);
-    format!("failed in sandbox: {friendly}")
+    let message = match hint {
+        Some(hint) => format!("failed in sandbox: {friendly}\n{hint}"),
+        None => format!("failed in sandbox: {friendly}"),
+    };
+    maybe_redact(redactor, &message).into_owned()
+}
+
+/// Tell the model exactly what was blocked and where it may write instead, so
+/// it does not retry the same denied operation.
+fn sandbox_denial_hint(error: &SandboxErr, config: &ExecutorConfig) -> Option<String> {
+    let SandboxErr::Denied {
+        path: Some(path),
+        operation,
+        ..
+    } = error
+    else {
+        return None;
+    };
+    let blocked = match operation {
+        Some(operation) => format!("{operation} on"),
+        None => "access to".to_string(),
+    };
+    let roots: Vec<String> = sandbox_writable_roots(&config.sandbox_policy, &config.sandbox_cwd)
+        .iter()
+        .map(|root| root.root.display().to_string())
+        .collect();
+    let path = path.display();
+    if roots.is_empty() {
+        Some(format!(
+            "sandbox blocked {blocked} {path}: the sandbox is read-only"
+        ))
+    } else {
+        Some(format!(
+            "sandbox blocked {blocked} {path}: writes are only allowed under {}",
+            roots.join(", ")
+        ))
+    }
+}
+
+/// An execution request whose sandbox placement has been planned.
+pub(crate) struct ExecPlan {
+    request: ExecutionRequest,
+    config: ExecutorConfig,
+    stdout_stream: Option<StdoutStream>,
+    approval_policy: AskForApproval,
+    sandbox_plan: SandboxPlan,
+}
+
+impl ExecPlan {
+    /// Why the command may run, when that was decided without asking the user.
+    pub(crate) fn trace(&self) -> Option<&DecisionTrace> {
+        match &self.sandbox_plan {
+            SandboxPlan::Run(decision) => Some(&decision.trace),
+            SandboxPlan::AskUser | SandboxPlan::Reject { .. } => None,
+        }
+    }
 }
 
 pub(crate) struct ExecutionRequest {
@@ -328,6 +540,7 @@ mod tests {
     use crate::error::EnvVarError;
     use crate::error::SandboxErr;
     use crate::exec::StreamOutput;
+    use crate::protocol::EnvFilter;
     use pretty_assertions::assert_eq;
 
     fn make_output(text: &str) -> ExecToolCallOutput {
@@ -375,8 +588,10 @@ mod tests {
         };
         let err = SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         };
-        let message = sandbox_failure_message(err);
+        let message = sandbox_failure_message(err, &test_config(), None);
         assert_eq!(message, "failed in sandbox: sandbox stderr");
     }
 
@@ -392,8 +607,10 @@ mod tests {
         };
         let err = SandboxErr::Denied {
             output: Box::new(output),
+            path: None,
+            operation: None,
         };
-        let message = sandbox_failure_message(err);
+        let message = sandbox_failure_message(err, &test_config(), None);
         assert_eq!(message, "failed in sandbox: aggregate text");
     }
 
@@ -423,4 +640,220 @@ mod tests {
             "expected synthesized user-friendly message"
         );
     }
+
+    fn test_config() -> ExecutorConfig {
+        ExecutorConfig::new(
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![],
+                network_access: false,
+                allow_local_network: false,
+                exclude_tmpdir_env_var: true,
+                exclude_slash_tmp: true,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
+            },
+            PathBuf::from("/workspace"),
+            None,
+        )
+    }
+
+    #[test]
+    fn sandbox_failure_message_names_blocked_path_and_writable_roots() {
+        let output = ExecToolCallOutput {
+            exit_code: 1,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(
+                "touch: cannot touch '/codex-test': Read-only file system".to_string(),
+            ),
+            aggregated_output: StreamOutput::new(String::new()),
+            duration: Duration::from_millis(10),
+            timed_out: false,
+        };
+        let err = SandboxErr::Denied {
+            output: Box::new(output),
+            path: Some(PathBuf::from("/codex-test")),
+            operation: Some("file-write".to_string()),
+        };
+        let message = sandbox_failure_message(err, &test_config(), None);
+        assert_eq!(
+            message,
+            "failed in sandbox: touch: cannot touch '/codex-test': Read-only file system\n\
+             sandbox blocked file-write on /codex-test: writes are only allowed under /workspace"
+        );
+    }
+
+    #[test]
+    fn sandbox_failure_message_without_operation_names_blocked_path() {
+        let output = ExecToolCallOutput {
+            exit_code: 1,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new("bash: /etc/hosts: Permission denied".to_string()),
+            aggregated_output: StreamOutput::new(String::new()),
+            duration: Duration::from_millis(10),
+            timed_out: false,
+        };
+        let err = SandboxErr::Denied {
+            output: Box::new(output),
+            path: Some(PathBuf::from("/etc/hosts")),
+            operation: None,
+        };
+        let message = sandbox_failure_message(err, &test_config(), None);
+        assert_eq!(
+            message,
+            "failed in sandbox: bash: /etc/hosts: Permission denied\n\
+             sandbox blocked access to /etc/hosts: writes are only allowed under /workspace"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn approved_patch_is_backed_up_and_restored_when_it_fails() {
+        use crate::codex::make_session_and_context;
+        use codex_apply_patch::ApplyPatchAction;
+        use std::os::unix::fs::PermissionsExt;
+
+        let (session, ctx) = make_session_and_context();
+        let workspace = tempfile::tempdir().expect("workspace");
+        let backups = tempfile::tempdir().expect("backups");
+        let target = workspace.path().join("a.txt");
+        std::fs::write(&target, "original\n").expect("write target");
+
+        // Stands in for `codex --codex-run-as-apply-patch`: it writes half of
+        // the patch and then fails.
+        let fake_codex = workspace.path().join("fake-codex");
+        std::fs::write(
+            &fake_codex,
+            format!(
+                "#!/bin/sh\nprintf 'half applied' > '{}'\nexit 1\n",
+                target.display()
+            ),
+        )
+        .expect("write fake codex");
+        std::fs::set_permissions(&fake_codex, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake codex");
+
+        let mut config = ExecutorConfig::new(
+            SandboxPolicy::DangerFullAccess,
+            workspace.path().to_path_buf(),
+            Some(fake_codex),
+        );
+        config.apply_patch_backup_dir = Some(backups.path().to_path_buf());
+        let executor = Executor::new(config);
+        let request = ExecutionRequest {
+            params: ExecParams {
+                command: vec!["apply_patch".into()],
+                cwd: workspace.path().to_path_buf(),
+                timeout_ms: None,
+                env: std::collections::HashMap::new(),
+                with_escalated_permissions: None,
+                justification: None,
+                resource_limits: None,
+            },
+            approval_command: vec!["apply_patch".into()],
+            mode: ExecutionMode::ApplyPatch(ApplyPatchExec {
+                action: ApplyPatchAction::new_add_for_test(&target, "patched".to_string()),
+                user_explicitly_approved_this_action: true,
+            }),
+            stdout_stream: None,
+            use_shell_profile: false,
+        };
+        let context = ExecCommandContext {
+            sub_id: "sub".to_string(),
+            call_id: "call".to_string(),
+            command_for_display: vec!["apply_patch".to_string()],
+            cwd: workspace.path().to_path_buf(),
+            apply_patch: None,
+            tool_name: "apply_patch".to_string(),
+            otel_event_manager: ctx.client.get_otel_event_manager(),
+        };
+
+        let plan = executor
+            .plan(request, &session, AskForApproval::Never)
+            .expect("plan");
+        let output = executor
+            .execute(plan, &session, &context)
+            .await
+            .expect("exec");
+        assert_eq!(output.exit_code, 1);
+
+        assert_eq!(
+            std::fs::read_to_string(&target).expect("read target"),
+            "original\n"
+        );
+        let backed_up: Vec<String> = std::fs::read_dir(backups.path())
+            .expect("backup dir")
+            .flat_map(|run| std::fs::read_dir(run.expect("run dir").path()).expect("run dir"))
+            .map(|file| std::fs::read_to_string(file.expect("backup").path()).expect("read"))
+            .collect();
+        assert_eq!(backed_up, vec!["original\n".to_string()]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn sandbox_denied_output_is_redacted_for_the_model() {
+        use crate::codex::make_session_and_context;
+        use crate::redact::default_sensitive_env_patterns;
+        use std::os::unix::fs::PermissionsExt;
+
+        let (mut session, ctx) = make_session_and_context();
+        session.services.output_redactor = Some(Arc::new(OutputRedactor::new(
+            [("DEPLOY_TOKEN".to_string(), "hunter2-deploy".to_string())],
+            &default_sensitive_env_patterns(),
+        )));
+        let workspace = tempfile::tempdir().expect("workspace");
+
+        // Stands in for `codex-linux-sandbox`: the sandboxed command dumps
+        // its environment and is then denied.
+        let fake_sandbox = workspace.path().join("fake-sandbox");
+        std::fs::write(
+            &fake_sandbox,
+            "#!/bin/sh\necho 'DEPLOY_TOKEN=hunter2-deploy' >&2\necho 'Permission denied' >&2\nexit 1\n",
+        )
+        .expect("write fake sandbox");
+        std::fs::set_permissions(&fake_sandbox, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake sandbox");
+
+        let executor = Executor::new(ExecutorConfig::new(
+            SandboxPolicy::ReadOnly,
+            workspace.path().to_path_buf(),
+            Some(fake_sandbox),
+        ));
+        let command = vec!["./deploy.sh".to_string()];
+        let request = ExecutionRequest {
+            params: ExecParams {
+                command: command.clone(),
+                cwd: workspace.path().to_path_buf(),
+                timeout_ms: None,
+                env: std::collections::HashMap::new(),
+                with_escalated_permissions: None,
+                justification: None,
+                resource_limits: None,
+            },
+            approval_command: command.clone(),
+            mode: ExecutionMode::Shell,
+            stdout_stream: None,
+            use_shell_profile: false,
+        };
+        let context = ExecCommandContext {
+            sub_id: "sub".to_string(),
+            call_id: "call".to_string(),
+            command_for_display: command,
+            cwd: workspace.path().to_path_buf(),
+            apply_patch: None,
+            tool_name: "shell".to_string(),
+            otel_event_manager: ctx.client.get_otel_event_manager(),
+        };
+
+        let plan = executor
+            .plan(request, &session, AskForApproval::Never)
+            .expect("plan");
+        let Err(ExecError::Function(FunctionCallError::RespondToModel(message))) =
+            executor.execute(plan, &session, &context).await
+        else {
+            panic!("expected the sandbox denial to be reported to the model");
+        };
+        assert!(message.starts_with("failed in sandbox: "), "{message}");
+        assert!(message.contains("[REDACTED:DEPLOY_TOKEN]"), "{message}");
+        assert!(!message.contains("hunter2-deploy"), "{message}");
+    }
 }
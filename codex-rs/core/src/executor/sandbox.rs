@@ -1,18 +1,31 @@
 use crate::apply_patch::ApplyPatchExec;
+use crate::apply_patch::ensure_patch_within_writable_roots;
+use crate::apply_patch::ensure_writes_allowed;
 use crate::codex::Session;
+use crate::command_safety::is_safe_command::is_known_safe_command;
 use crate::exec::SandboxType;
 use crate::executor::ExecutionMode;
 use crate::executor::ExecutionRequest;
 use crate::executor::ExecutorConfig;
 use crate::executor::errors::ExecError;
+use crate::git_info::sandbox_writable_roots;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_command_safety;
 use crate::safety::assess_patch_safety;
+use crate::safety::available_sandboxes;
+use codex_apply_patch::ApplyPatchAction;
 use codex_otel::otel_event_manager::OtelEventManager;
 use codex_otel::otel_event_manager::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::DecisionTrace;
+use codex_protocol::protocol::ExecSandbox;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::WritableRoot;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
 
 /// Sandbox placement options selected for an execution run, including whether
 /// to escalate after failures and whether approvals should persist.
@@ -20,23 +33,35 @@ pub(crate) struct SandboxDecision {
     pub(crate) initial_sandbox: SandboxType,
     pub(crate) escalate_on_failure: bool,
     pub(crate) record_session_approval: bool,
+    /// Explanation of the decision, surfaced on `ExecCommandBeginEvent`.
+    pub(crate) trace: DecisionTrace,
 }
 
 impl SandboxDecision {
-    fn auto(sandbox: SandboxType, escalate_on_failure: bool) -> Self {
+    fn auto(sandbox: SandboxType, approval_policy: AskForApproval) -> Self {
+        let escalate_on_failure = should_escalate_on_failure(approval_policy, sandbox);
         Self {
             initial_sandbox: sandbox,
             escalate_on_failure,
             record_session_approval: false,
+            trace: DecisionTrace {
+                approval_policy,
+                matched_safe_command: false,
+                from_approval_cache: false,
+                user_approved: false,
+                sandbox: sandbox.into(),
+                escalate_on_failure,
+            },
         }
     }
 
-    fn user_override(record_session_approval: bool) -> Self {
-        Self {
-            initial_sandbox: SandboxType::None,
-            escalate_on_failure: false,
-            record_session_approval,
-        }
+    fn user_override(record_session_approval: bool, approval_policy: AskForApproval) -> Self {
+        let mut decision = Self::auto(SandboxType::None, approval_policy);
+        decision.escalate_on_failure = false;
+        decision.record_session_approval = record_session_approval;
+        decision.trace.escalate_on_failure = false;
+        decision.trace.user_approved = true;
+        decision
     }
 }
 
@@ -50,14 +75,49 @@ fn should_escalate_on_failure(approval: AskForApproval, sandbox: SandboxType) ->
     )
 }
 
-/// Determines how a command should be sandboxed, prompting the user when
-/// policy requires explicit approval.
-#[allow(clippy::too_many_arguments)]
-pub async fn select_sandbox(
+/// Decides how a command should be sandboxed without prompting anyone, so the
+/// decision can be reported before [`select_sandbox`] asks for approval.
+pub(crate) fn plan_sandbox(
     request: &ExecutionRequest,
     approval_policy: AskForApproval,
-    approval_cache: HashSet<Vec<String>>,
+    approval_cache: &HashSet<Vec<String>>,
     config: &ExecutorConfig,
+) -> SandboxPlan {
+    match &request.mode {
+        ExecutionMode::Shell => {
+            let command_for_safety = if request.approval_command.is_empty() {
+                &request.params.command
+            } else {
+                &request.approval_command
+            };
+            plan_shell_sandbox(
+                command_for_safety,
+                approval_policy,
+                &config.sandbox_policy,
+                approval_cache,
+                request.params.with_escalated_permissions.unwrap_or(false),
+            )
+        }
+        ExecutionMode::ApplyPatch(exec) => {
+            if exec.user_explicitly_approved_this_action {
+                return SandboxPlan::Run(SandboxDecision::user_override(false, approval_policy));
+            }
+            plan_apply_patch_sandbox(
+                &exec.action,
+                approval_policy,
+                &config.sandbox_policy,
+                &config.sandbox_cwd,
+            )
+        }
+    }
+}
+
+/// Turns the [`plan_sandbox`] result into a decision, prompting the user when
+/// policy requires explicit approval.
+pub(crate) async fn select_sandbox(
+    request: &ExecutionRequest,
+    plan: SandboxPlan,
+    approval_policy: AskForApproval,
     session: &Session,
     sub_id: &str,
     call_id: &str,
@@ -67,9 +127,8 @@ pub async fn select_sandbox(
         ExecutionMode::Shell => {
             select_shell_sandbox(
                 request,
+                plan,
                 approval_policy,
-                approval_cache,
-                config,
                 session,
                 sub_id,
                 call_id,
@@ -77,50 +136,22 @@ pub async fn select_sandbox(
             )
             .await
         }
-        ExecutionMode::ApplyPatch(exec) => {
-            select_apply_patch_sandbox(exec, approval_policy, config)
-        }
+        ExecutionMode::ApplyPatch(_) => select_apply_patch_sandbox(plan),
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 async fn select_shell_sandbox(
     request: &ExecutionRequest,
+    plan: SandboxPlan,
     approval_policy: AskForApproval,
-    approved_snapshot: HashSet<Vec<String>>,
-    config: &ExecutorConfig,
     session: &Session,
     sub_id: &str,
     call_id: &str,
     otel_event_manager: &OtelEventManager,
 ) -> Result<SandboxDecision, ExecError> {
-    let command_for_safety = if request.approval_command.is_empty() {
-        request.params.command.clone()
-    } else {
-        request.approval_command.clone()
-    };
-
-    let safety = assess_command_safety(
-        &command_for_safety,
-        approval_policy,
-        &config.sandbox_policy,
-        &approved_snapshot,
-        request.params.with_escalated_permissions.unwrap_or(false),
-    );
-
-    match safety {
-        SafetyCheck::AutoApprove {
-            sandbox_type,
-            user_explicitly_approved,
-        } => {
-            let mut decision = SandboxDecision::auto(
-                sandbox_type,
-                should_escalate_on_failure(approval_policy, sandbox_type),
-            );
-            if user_explicitly_approved {
-                decision.record_session_approval = true;
-            }
-            let (decision_for_event, source) = if user_explicitly_approved {
+    match plan {
+        SandboxPlan::Run(decision) => {
+            let (decision_for_event, source) = if decision.record_session_approval {
                 (ReviewDecision::ApprovedForSession, ToolDecisionSource::User)
             } else {
                 (ReviewDecision::Approved, ToolDecisionSource::Config)
@@ -128,7 +159,7 @@ async fn select_shell_sandbox(
             otel_event_manager.tool_decision("local_shell", call_id, decision_for_event, source);
             Ok(decision)
         }
-        SafetyCheck::AskUser => {
+        SandboxPlan::AskUser => {
             let decision = session
                 .request_command_approval(
                     sub_id.to_string(),
@@ -146,47 +177,242 @@ async fn select_shell_sandbox(
                 ToolDecisionSource::User,
             );
             match decision {
-                ReviewDecision::Approved => Ok(SandboxDecision::user_override(false)),
-                ReviewDecision::ApprovedForSession => Ok(SandboxDecision::user_override(true)),
+                ReviewDecision::Approved => {
+                    Ok(SandboxDecision::user_override(false, approval_policy))
+                }
+                ReviewDecision::ApprovedForSession => {
+                    Ok(SandboxDecision::user_override(true, approval_policy))
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     Err(ExecError::rejection("exec command rejected by user"))
                 }
             }
         }
-        SafetyCheck::Reject { reason } => Err(ExecError::rejection(format!(
+        SandboxPlan::Reject { reason } => Err(ExecError::rejection(format!(
             "exec command rejected: {reason}"
         ))),
     }
 }
 
-fn select_apply_patch_sandbox(
-    exec: &ApplyPatchExec,
-    approval_policy: AskForApproval,
-    config: &ExecutorConfig,
-) -> Result<SandboxDecision, ExecError> {
-    if exec.user_explicitly_approved_this_action {
-        return Ok(SandboxDecision::user_override(false));
-    }
+/// Outcome of the safety assessment, before any prompt is shown.
+pub(crate) enum SandboxPlan {
+    /// Run without asking, starting in `initial_sandbox`.
+    Run(SandboxDecision),
+    /// Ask the user; an approved command runs without a sandbox.
+    AskUser,
+    Reject {
+        reason: String,
+    },
+}
 
-    match assess_patch_safety(
-        &exec.action,
+/// Shell half of [`plan_sandbox`], shared with [`explain_shell_command`].
+pub(crate) fn plan_shell_sandbox(
+    command: &[String],
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    approved: &HashSet<Vec<String>>,
+    with_escalated_permissions: bool,
+) -> SandboxPlan {
+    match assess_command_safety(
+        command,
         approval_policy,
-        &config.sandbox_policy,
-        &config.sandbox_cwd,
+        sandbox_policy,
+        approved,
+        with_escalated_permissions,
     ) {
-        SafetyCheck::AutoApprove { sandbox_type, .. } => Ok(SandboxDecision::auto(
+        SafetyCheck::AutoApprove {
             sandbox_type,
-            should_escalate_on_failure(approval_policy, sandbox_type),
-        )),
-        SafetyCheck::AskUser => Err(ExecError::rejection(
+            user_explicitly_approved,
+        } => {
+            let mut decision = SandboxDecision::auto(sandbox_type, approval_policy);
+            decision.trace.matched_safe_command = is_known_safe_command(command);
+            decision.trace.from_approval_cache = user_explicitly_approved;
+            decision.record_session_approval = user_explicitly_approved;
+            SandboxPlan::Run(decision)
+        }
+        SafetyCheck::AskUser => SandboxPlan::AskUser,
+        SafetyCheck::Reject { reason } => SandboxPlan::Reject { reason },
+    }
+}
+
+/// Dry-run description of how a shell command or patch would be sandboxed, as
+/// printed by `codex sandbox explain`. Nothing is executed to produce it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SandboxExplanation {
+    pub command: Vec<String>,
+    pub approval_policy: AskForApproval,
+    /// Sandbox the command would start in. `none` when approval is required,
+    /// since approved commands run unsandboxed.
+    pub sandbox: ExecSandbox,
+    pub writable_roots: Vec<WritableRoot>,
+    pub network_access: bool,
+    pub matched_safe_command: bool,
+    pub approval_required: bool,
+    /// Why the command would be refused outright, if it would be.
+    pub rejected: Option<String>,
+    /// Whether a sandbox failure would be retried after asking the user.
+    pub escalate_on_failure: bool,
+    /// Linux only: the helper binary sandboxed commands are launched through.
+    pub linux_sandbox_helper: Option<SandboxHelperStatus>,
+    /// Sandboxes usable on this platform.
+    pub available_sandboxes: Vec<ExecSandbox>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SandboxHelperStatus {
+    pub path: Option<PathBuf>,
+    pub reachable: bool,
+}
+
+/// Explain how `command` would be run under `sandbox_policy` without running
+/// it. No session approvals are assumed.
+pub fn explain_shell_command(
+    command: Vec<String>,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    codex_linux_sandbox_exe: Option<&Path>,
+) -> SandboxExplanation {
+    let plan = plan_shell_sandbox(
+        &command,
+        approval_policy,
+        sandbox_policy,
+        &HashSet::new(),
+        false,
+    );
+    explain_plan(
+        command,
+        plan,
+        approval_policy,
+        sandbox_policy,
+        cwd,
+        codex_linux_sandbox_exe,
+    )
+}
+
+/// Explain how `action` would be applied under `sandbox_policy` without
+/// touching any file.
+pub fn explain_apply_patch(
+    action: &ApplyPatchAction,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    codex_linux_sandbox_exe: Option<&Path>,
+) -> SandboxExplanation {
+    let plan = plan_apply_patch_sandbox(action, approval_policy, sandbox_policy, cwd);
+    let mut paths: Vec<String> = action
+        .changes()
+        .keys()
+        .map(|p| p.display().to_string())
+        .collect();
+    paths.sort();
+    let command = std::iter::once("apply_patch".to_string())
+        .chain(paths)
+        .collect();
+    explain_plan(
+        command,
+        plan,
+        approval_policy,
+        sandbox_policy,
+        cwd,
+        codex_linux_sandbox_exe,
+    )
+}
+
+fn explain_plan(
+    command: Vec<String>,
+    plan: SandboxPlan,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    codex_linux_sandbox_exe: Option<&Path>,
+) -> SandboxExplanation {
+    let (sandbox, matched_safe_command, approval_required, rejected, escalate_on_failure) =
+        match plan {
+            SandboxPlan::Run(decision) => (
+                decision.trace.sandbox,
+                decision.trace.matched_safe_command,
+                false,
+                None,
+                decision.escalate_on_failure,
+            ),
+            SandboxPlan::AskUser => (
+                ExecSandbox::None,
+                is_known_safe_command(&command),
+                true,
+                None,
+                false,
+            ),
+            SandboxPlan::Reject { reason } => (
+                ExecSandbox::None,
+                is_known_safe_command(&command),
+                false,
+                Some(reason),
+                false,
+            ),
+        };
+    let linux_sandbox_helper = cfg!(target_os = "linux").then(|| SandboxHelperStatus {
+        path: codex_linux_sandbox_exe.map(Path::to_path_buf),
+        reachable: codex_linux_sandbox_exe.is_some_and(Path::is_file),
+    });
+    SandboxExplanation {
+        command,
+        approval_policy,
+        sandbox,
+        writable_roots: sandbox_writable_roots(sandbox_policy, cwd),
+        network_access: sandbox_policy.has_full_network_access(),
+        matched_safe_command,
+        approval_required,
+        rejected,
+        escalate_on_failure,
+        linux_sandbox_helper,
+        available_sandboxes: available_sandboxes().into_iter().map(Into::into).collect(),
+    }
+}
+
+fn select_apply_patch_sandbox(plan: SandboxPlan) -> Result<SandboxDecision, ExecError> {
+    match plan {
+        SandboxPlan::Run(decision) => Ok(decision),
+        SandboxPlan::AskUser => Err(ExecError::rejection(
             "patch requires approval but none was recorded",
         )),
-        SafetyCheck::Reject { reason } => {
+        SandboxPlan::Reject { reason } => {
             Err(ExecError::rejection(format!("patch rejected: {reason}")))
         }
     }
 }
 
+/// Patch half of [`plan_sandbox`], shared with [`explain_apply_patch`].
+pub(crate) fn plan_apply_patch_sandbox(
+    action: &ApplyPatchAction,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> SandboxPlan {
+    if let Err(err) = ensure_writes_allowed(action, approval_policy, sandbox_policy) {
+        return SandboxPlan::Reject {
+            reason: err.to_string(),
+        };
+    }
+    // With approvals disabled an out-of-root write can neither be asked about
+    // nor escalated, so refuse it before a process is spawned and name the
+    // first offending path.
+    if approval_policy == AskForApproval::Never
+        && let Err(err) = ensure_patch_within_writable_roots(action, sandbox_policy, cwd)
+    {
+        return SandboxPlan::Reject {
+            reason: err.to_string(),
+        };
+    }
+    match assess_patch_safety(action, approval_policy, sandbox_policy, cwd) {
+        SafetyCheck::AutoApprove { sandbox_type, .. } => {
+            SandboxPlan::Run(SandboxDecision::auto(sandbox_type, approval_policy))
+        }
+        SafetyCheck::AskUser => SandboxPlan::AskUser,
+        SafetyCheck::Reject { reason } => SandboxPlan::Reject { reason },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +423,30 @@ mod tests {
     use codex_apply_patch::ApplyPatchAction;
     use pretty_assertions::assert_eq;
 
+    #[allow(clippy::too_many_arguments)]
+    async fn plan_and_select(
+        request: &ExecutionRequest,
+        approval_policy: AskForApproval,
+        approval_cache: HashSet<Vec<String>>,
+        config: &ExecutorConfig,
+        session: &Session,
+        sub_id: &str,
+        call_id: &str,
+        otel_event_manager: &OtelEventManager,
+    ) -> Result<SandboxDecision, ExecError> {
+        let plan = plan_sandbox(request, approval_policy, &approval_cache, config);
+        select_sandbox(
+            request,
+            plan,
+            approval_policy,
+            session,
+            sub_id,
+            call_id,
+            otel_event_manager,
+        )
+        .await
+    }
+
     #[tokio::test]
     async fn select_apply_patch_user_override_when_explicit() {
         let (session, ctx) = make_session_and_context();
@@ -216,6 +466,7 @@ mod tests {
                 env: std::collections::HashMap::new(),
                 with_escalated_permissions: None,
                 justification: None,
+                resource_limits: None,
             },
             approval_command: vec!["apply_patch".into()],
             mode: ExecutionMode::ApplyPatch(exec),
@@ -223,7 +474,7 @@ mod tests {
             use_shell_profile: false,
         };
         let otel_event_manager = ctx.client.get_otel_event_manager();
-        let decision = select_sandbox(
+        let decision = plan_and_select(
             &request,
             AskForApproval::OnRequest,
             Default::default(),
@@ -259,6 +510,7 @@ mod tests {
                 env: std::collections::HashMap::new(),
                 with_escalated_permissions: None,
                 justification: None,
+                resource_limits: None,
             },
             approval_command: vec!["apply_patch".into()],
             mode: ExecutionMode::ApplyPatch(exec),
@@ -266,7 +518,7 @@ mod tests {
             use_shell_profile: false,
         };
         let otel_event_manager = ctx.client.get_otel_event_manager();
-        let decision = select_sandbox(
+        let decision = plan_and_select(
             &request,
             AskForApproval::OnRequest,
             Default::default(),
@@ -303,6 +555,7 @@ mod tests {
                 env: std::collections::HashMap::new(),
                 with_escalated_permissions: None,
                 justification: None,
+                resource_limits: None,
             },
             approval_command: vec!["apply_patch".into()],
             mode: ExecutionMode::ApplyPatch(exec),
@@ -310,7 +563,7 @@ mod tests {
             use_shell_profile: false,
         };
         let otel_event_manager = ctx.client.get_otel_event_manager();
-        let result = select_sandbox(
+        let result = plan_and_select(
             &request,
             AskForApproval::UnlessTrusted,
             Default::default(),
@@ -330,6 +583,65 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn select_apply_patch_rejects_write_outside_writable_roots() {
+        let (session, ctx) = make_session_and_context();
+        let workspace = tempfile::tempdir().expect("workspace");
+        let outside = tempfile::tempdir().expect("outside");
+        let p = outside.path().join("a.txt");
+        let action = ApplyPatchAction::new_add_for_test(&p, "hello".to_string());
+        let exec = ApplyPatchExec {
+            action,
+            user_explicitly_approved_this_action: false,
+        };
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: false,
+            env_filter: Default::default(),
+        };
+        let cfg = ExecutorConfig::new(policy, workspace.path().to_path_buf(), None);
+        let request = ExecutionRequest {
+            params: ExecParams {
+                command: vec!["apply_patch".into()],
+                cwd: workspace.path().to_path_buf(),
+                timeout_ms: None,
+                env: std::collections::HashMap::new(),
+                with_escalated_permissions: None,
+                justification: None,
+                resource_limits: None,
+            },
+            approval_command: vec!["apply_patch".into()],
+            mode: ExecutionMode::ApplyPatch(exec),
+            stdout_stream: None,
+            use_shell_profile: false,
+        };
+        let otel_event_manager = ctx.client.get_otel_event_manager();
+        let result = plan_and_select(
+            &request,
+            AskForApproval::Never,
+            Default::default(),
+            &cfg,
+            &session,
+            "sub",
+            "call",
+            &otel_event_manager,
+        )
+        .await;
+        match result {
+            Ok(_) => panic!("expected error"),
+            Err(ExecError::Function(FunctionCallError::RespondToModel(msg))) => {
+                assert!(msg.contains("writable roots"), "{msg}");
+                assert!(msg.contains(&p.display().to_string()), "{msg}");
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+        }
+        assert!(!p.exists());
+    }
+
     #[tokio::test]
     async fn select_shell_autoapprove_in_danger_mode() {
         let (session, ctx) = make_session_and_context();
@@ -342,6 +654,7 @@ mod tests {
                 env: std::collections::HashMap::new(),
                 with_escalated_permissions: None,
                 justification: None,
+                resource_limits: None,
             },
             approval_command: vec!["some-unknown".into()],
             mode: ExecutionMode::Shell,
@@ -349,7 +662,7 @@ mod tests {
             use_shell_profile: false,
         };
         let otel_event_manager = ctx.client.get_otel_event_manager();
-        let decision = select_sandbox(
+        let decision = plan_and_select(
             &request,
             AskForApproval::OnRequest,
             Default::default(),
@@ -379,6 +692,7 @@ mod tests {
                 env: std::collections::HashMap::new(),
                 with_escalated_permissions: None,
                 justification: None,
+                resource_limits: None,
             },
             approval_command: vec!["some-unknown".into()],
             mode: ExecutionMode::Shell,
@@ -386,7 +700,7 @@ mod tests {
             use_shell_profile: false,
         };
         let otel_event_manager = ctx.client.get_otel_event_manager();
-        let decision = select_sandbox(
+        let decision = plan_and_select(
             &request,
             AskForApproval::OnFailure,
             Default::default(),
@@ -402,4 +716,265 @@ mod tests {
         assert_ne!(decision.initial_sandbox, SandboxType::None);
         assert_eq!(decision.escalate_on_failure, true);
     }
+
+    fn shell_request(command: &[&str]) -> ExecutionRequest {
+        let command: Vec<String> = command.iter().map(|s| (*s).to_string()).collect();
+        ExecutionRequest {
+            params: ExecParams {
+                command: command.clone(),
+                cwd: std::env::temp_dir(),
+                timeout_ms: None,
+                env: std::collections::HashMap::new(),
+                with_escalated_permissions: None,
+                justification: None,
+                resource_limits: None,
+            },
+            approval_command: command,
+            mode: ExecutionMode::Shell,
+            stdout_stream: None,
+            use_shell_profile: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn trace_explains_safe_command() {
+        let (session, ctx) = make_session_and_context();
+        let cfg = ExecutorConfig::new(SandboxPolicy::ReadOnly, std::env::temp_dir(), None);
+        let otel_event_manager = ctx.client.get_otel_event_manager();
+        let decision = plan_and_select(
+            &shell_request(&["ls"]),
+            AskForApproval::OnFailure,
+            Default::default(),
+            &cfg,
+            &session,
+            "sub",
+            "call",
+            &otel_event_manager,
+        )
+        .await
+        .expect("ok");
+        assert_eq!(
+            decision.trace,
+            DecisionTrace {
+                approval_policy: AskForApproval::OnFailure,
+                matched_safe_command: true,
+                from_approval_cache: false,
+                user_approved: false,
+                sandbox: codex_protocol::protocol::ExecSandbox::None,
+                escalate_on_failure: false,
+            }
+        );
+        assert_eq!(
+            decision.trace.explanation(),
+            "auto-approved: read-only command under on-failure policy"
+        );
+    }
+
+    #[tokio::test]
+    async fn dangerous_command_is_not_auto_approved() {
+        let (session, ctx) = make_session_and_context();
+        let cfg = ExecutorConfig::new(SandboxPolicy::DangerFullAccess, std::env::temp_dir(), None);
+        let otel_event_manager = ctx.client.get_otel_event_manager();
+        // Without an active turn the approval prompt resolves to a denial, so
+        // reaching the prompt shows up as a rejection rather than a trace.
+        let result = plan_and_select(
+            &shell_request(&["rm", "-rf", "build"]),
+            AskForApproval::OnFailure,
+            Default::default(),
+            &cfg,
+            &session,
+            "sub",
+            "call",
+            &otel_event_manager,
+        )
+        .await;
+        match result {
+            Ok(decision) => panic!("expected a prompt, got {:?}", decision.trace),
+            Err(ExecError::Function(FunctionCallError::RespondToModel(msg))) => {
+                assert_eq!(msg, "exec command rejected by user")
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn trace_marks_cached_approval_repeat() {
+        let (session, ctx) = make_session_and_context();
+        let cfg = ExecutorConfig::new(SandboxPolicy::DangerFullAccess, std::env::temp_dir(), None);
+        let request = shell_request(&["rm", "-rf", "build"]);
+        let approved = HashSet::from([request.approval_command.clone()]);
+        let otel_event_manager = ctx.client.get_otel_event_manager();
+        let decision = plan_and_select(
+            &request,
+            AskForApproval::OnFailure,
+            approved,
+            &cfg,
+            &session,
+            "sub",
+            "call",
+            &otel_event_manager,
+        )
+        .await
+        .expect("ok");
+        assert_eq!(
+            decision.trace,
+            DecisionTrace {
+                approval_policy: AskForApproval::OnFailure,
+                matched_safe_command: false,
+                from_approval_cache: true,
+                user_approved: false,
+                sandbox: codex_protocol::protocol::ExecSandbox::None,
+                escalate_on_failure: false,
+            }
+        );
+        assert_eq!(
+            decision.trace.explanation(),
+            "auto-approved: you chose to always allow this command"
+        );
+    }
+
+    #[test]
+    fn explain_reports_safe_commands_and_approval_without_running() {
+        let cwd = tempfile::tempdir().expect("tmp");
+        let policy = SandboxPolicy::new_workspace_write_policy();
+        let argv = |args: &[&str]| args.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+
+        let safe = explain_shell_command(
+            argv(&["ls", "-la"]),
+            AskForApproval::UnlessTrusted,
+            &policy,
+            cwd.path(),
+            None,
+        );
+        assert!(safe.matched_safe_command);
+        assert!(!safe.approval_required);
+        assert_eq!(safe.sandbox, ExecSandbox::None);
+        assert!(!safe.network_access);
+        assert!(safe.writable_roots.iter().any(|r| r.root == cwd.path()));
+
+        let untrusted = explain_shell_command(
+            argv(&["cargo", "build"]),
+            AskForApproval::UnlessTrusted,
+            &policy,
+            cwd.path(),
+            None,
+        );
+        assert!(!untrusted.matched_safe_command);
+        assert!(untrusted.approval_required);
+        assert_eq!(untrusted.rejected, None);
+
+        let rejected = explain_shell_command(
+            argv(&["rm", "-rf", "/"]),
+            AskForApproval::Never,
+            &policy,
+            cwd.path(),
+            None,
+        );
+        assert!(rejected.rejected.is_some());
+
+        if cfg!(target_os = "linux") {
+            let helper = safe.linux_sandbox_helper.expect("linux helper status");
+            assert!(!helper.reachable);
+        }
+    }
+
+    #[test]
+    fn explain_apply_patch_read_only_vs_workspace_write() {
+        let cwd = tempfile::tempdir().expect("tmp");
+        let action =
+            ApplyPatchAction::new_add_for_test(&cwd.path().join("a.txt"), "hello".to_string());
+
+        let read_only = explain_apply_patch(
+            &action,
+            AskForApproval::OnRequest,
+            &SandboxPolicy::ReadOnly,
+            cwd.path(),
+            None,
+        );
+        assert!(read_only.approval_required);
+        assert_eq!(read_only.sandbox, ExecSandbox::None);
+        assert!(read_only.writable_roots.is_empty());
+        assert_eq!(read_only.command[0], "apply_patch");
+
+        let workspace_write = explain_apply_patch(
+            &action,
+            AskForApproval::OnRequest,
+            &SandboxPolicy::new_workspace_write_policy(),
+            cwd.path(),
+            None,
+        );
+        assert!(
+            workspace_write
+                .writable_roots
+                .iter()
+                .any(|r| r.root == cwd.path())
+        );
+        match crate::safety::get_platform_sandbox() {
+            Some(sandbox_type) => {
+                assert!(!workspace_write.approval_required);
+                assert_eq!(workspace_write.sandbox, ExecSandbox::from(sandbox_type));
+            }
+            None => assert!(workspace_write.approval_required),
+        }
+        assert_eq!(workspace_write.rejected, None);
+    }
+
+    #[tokio::test]
+    async fn explain_apply_patch_matches_apply_patch_under_read_only() {
+        use crate::apply_patch::InternalApplyPatchInvocation;
+        use crate::apply_patch::apply_patch;
+
+        let (session, mut ctx) = make_session_and_context();
+        let cwd = tempfile::tempdir().expect("tmp");
+        let p = cwd.path().join("a.txt");
+        ctx.sandbox_policy = SandboxPolicy::ReadOnly;
+        ctx.cwd = cwd.path().to_path_buf();
+
+        for approval_policy in [
+            AskForApproval::Never,
+            AskForApproval::OnFailure,
+            AskForApproval::OnRequest,
+            AskForApproval::UnlessTrusted,
+        ] {
+            ctx.approval_policy = approval_policy;
+            let explanation = explain_apply_patch(
+                &ApplyPatchAction::new_add_for_test(&p, "hello".to_string()),
+                approval_policy,
+                &ctx.sandbox_policy,
+                &ctx.cwd,
+                None,
+            );
+            // Without an active turn nobody answers the approval request, so
+            // asking the user resolves to a denial.
+            let invocation = apply_patch(
+                &session,
+                &ctx,
+                "sub",
+                "call",
+                ApplyPatchAction::new_add_for_test(&p, "hello".to_string()),
+            )
+            .await;
+            match invocation {
+                InternalApplyPatchInvocation::DelegateToExec(_) => {
+                    assert!(!explanation.approval_required, "{approval_policy:?}");
+                    assert_eq!(explanation.rejected, None, "{approval_policy:?}");
+                }
+                InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                    message,
+                ))) if message == "patch rejected by user" => {
+                    assert!(explanation.approval_required, "{approval_policy:?}");
+                }
+                InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                    message,
+                ))) => {
+                    let reason = explanation.rejected.expect("explanation should reject");
+                    assert_eq!(message, format!("patch rejected: {reason}"));
+                }
+                InternalApplyPatchInvocation::Output(other) => {
+                    panic!("unexpected apply_patch output: {other:?}")
+                }
+            }
+            assert!(!p.exists());
+        }
+    }
 }
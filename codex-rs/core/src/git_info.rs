@@ -1,9 +1,15 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::time::Instant;
 
 use codex_app_server_protocol::GitSha;
 use codex_protocol::protocol::GitInfo;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::WritableRoot;
+use dashmap::DashMap;
 use futures::future::join_all;
 use serde::Deserialize;
 use serde::Serialize;
@@ -11,6 +17,41 @@ use tokio::process::Command;
 use tokio::time::Duration as TokioDuration;
 use tokio::time::timeout;
 
+/// Submodule checkouts declared in `<cwd>/.gitmodules`, as absolute paths
+/// under `cwd`. Entries that would escape `cwd` are ignored.
+pub fn find_submodule_paths(cwd: &Path) -> Vec<PathBuf> {
+    let Ok(contents) = std::fs::read_to_string(cwd.join(".gitmodules")) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "path" {
+            continue;
+        }
+        let relative = Path::new(value.trim().trim_matches('"'));
+        let contained = relative
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if contained && relative.components().next().is_some() {
+            let path = cwd.join(relative);
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Writable roots for `sandbox_policy` in `cwd`, with a root for each
+/// submodule declared in `<cwd>/.gitmodules`. Use this rather than
+/// [`SandboxPolicy::get_writable_roots_with_cwd`] when building a sandbox.
+pub fn sandbox_writable_roots(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Vec<WritableRoot> {
+    sandbox_policy.get_writable_roots_with_submodules(cwd, &find_submodule_paths(cwd))
+}
+
 /// Return `true` if the project folder specified by the `Config` is inside a
 /// Git repository.
 ///
@@ -44,6 +85,20 @@ pub fn get_git_repo_root(base_dir: &Path) -> Option<PathBuf> {
 /// Timeout for git commands to prevent freezing on large repositories
 const GIT_COMMAND_TIMEOUT: TokioDuration = TokioDuration::from_secs(5);
 
+/// How long a [`collect_blame_summary`] result is reused for the same `cwd`.
+const BLAME_CACHE_TTL: TokioDuration = TokioDuration::from_secs(60);
+
+/// Number of recently modified files included in the blame summary.
+const BLAME_MAX_FILES: usize = 10;
+
+/// Number of commits scanned when building the blame summary.
+const BLAME_LOG_DEPTH: &str = "200";
+
+type BlameSummary = Option<HashMap<String, u32>>;
+
+static BLAME_CACHE: LazyLock<DashMap<PathBuf, (Instant, BlameSummary)>> =
+    LazyLock::new(DashMap::new);
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GitDiffToRemote {
     pub sha: GitSha,
@@ -66,16 +121,18 @@ pub async fn collect_git_info(cwd: &Path) -> Option<GitInfo> {
     }
 
     // Run all git info collection commands in parallel
-    let (commit_result, branch_result, url_result) = tokio::join!(
+    let (commit_result, branch_result, url_result, blame_authors) = tokio::join!(
         run_git_command_with_timeout(&["rev-parse", "HEAD"], cwd),
         run_git_command_with_timeout(&["rev-parse", "--abbrev-ref", "HEAD"], cwd),
-        run_git_command_with_timeout(&["remote", "get-url", "origin"], cwd)
+        run_git_command_with_timeout(&["remote", "get-url", "origin"], cwd),
+        collect_blame_summary(cwd)
     );
 
     let mut git_info = GitInfo {
         commit_hash: None,
         branch: None,
         repository_url: None,
+        blame_authors,
     };
 
     // Process commit hash
@@ -108,6 +165,68 @@ pub async fn collect_git_info(cwd: &Path) -> Option<GitInfo> {
     Some(git_info)
 }
 
+/// Summarize recent ownership: for each of the most recently modified files,
+/// the number of distinct authors among recent commits that touched it.
+/// Results are cached per `cwd` for [`BLAME_CACHE_TTL`].
+pub async fn collect_blame_summary(cwd: &Path) -> Option<HashMap<String, u32>> {
+    if let Some(entry) = BLAME_CACHE.get(cwd)
+        && entry.0.elapsed() < BLAME_CACHE_TTL
+    {
+        return entry.1.clone();
+    }
+
+    let summary = match run_git_command_with_timeout(
+        &[
+            "log",
+            "-n",
+            BLAME_LOG_DEPTH,
+            "--name-only",
+            "--format=%x00%aE",
+        ],
+        cwd,
+    )
+    .await
+    {
+        Some(output) if output.status.success() => String::from_utf8(output.stdout)
+            .ok()
+            .map(|log| summarize_recent_authors(&log, BLAME_MAX_FILES))
+            .filter(|map| !map.is_empty()),
+        _ => None,
+    };
+    BLAME_CACHE.insert(cwd.to_path_buf(), (Instant::now(), summary.clone()));
+    summary
+}
+
+/// Count distinct authors per file in `git log --name-only --format=%x00%aE`
+/// output, keeping the first `max_files` files in log (most recent) order.
+fn summarize_recent_authors(log: &str, max_files: usize) -> HashMap<String, u32> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut authors: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut author = "";
+    for line in log.lines() {
+        if let Some(email) = line.strip_prefix('\0') {
+            author = email;
+            continue;
+        }
+        let file = line.trim();
+        if file.is_empty() {
+            continue;
+        }
+        authors
+            .entry(file)
+            .or_insert_with(|| {
+                order.push(file);
+                HashSet::new()
+            })
+            .insert(author);
+    }
+    order
+        .into_iter()
+        .take(max_files)
+        .map(|file| (file.to_string(), authors[file].len() as u32))
+        .collect()
+}
+
 /// A minimal commit summary entry used for pickers (subject + timestamp + sha).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CommitLogEntry {
@@ -652,6 +771,44 @@ mod tests {
         repo_path
     }
 
+    #[test]
+    fn gitmodules_submodule_becomes_writable_root() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cwd = temp_dir.path();
+        fs::write(
+            cwd.join(".gitmodules"),
+            "[submodule \"foo\"]\n\tpath = libs/foo\n\turl = https://example.com/foo.git\n[submodule \"escape\"]\n\tpath = ../outside\n",
+        )
+        .expect("write .gitmodules");
+        let submodule = cwd.join("libs/foo");
+        assert_eq!(find_submodule_paths(cwd), vec![submodule.clone()]);
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: Default::default(),
+        };
+        let roots = sandbox_writable_roots(&policy, cwd);
+        let submodule_root = roots
+            .iter()
+            .find(|root| root.root == submodule)
+            .expect("submodule root");
+        assert_eq!(
+            submodule_root.read_only_subpaths,
+            vec![submodule.join(".git")]
+        );
+    }
+
+    #[test]
+    fn no_gitmodules_means_no_submodules() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        assert!(find_submodule_paths(temp_dir.path()).is_empty());
+    }
+
     #[tokio::test]
     async fn test_recent_commits_non_git_directory_returns_empty() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -793,6 +950,51 @@ mod tests {
         // This is acceptable behavior
     }
 
+    #[tokio::test]
+    async fn test_collect_blame_summary_counts_recent_authors() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+
+        fs::write(repo_path.join("test.txt"), "updated").expect("Failed to write file");
+        fs::write(repo_path.join("other.txt"), "other").expect("Failed to write file");
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("Failed to add files");
+        Command::new("git")
+            .args([
+                "-c",
+                "user.name=Second User",
+                "-c",
+                "user.email=second@example.com",
+                "commit",
+                "-m",
+                "Second commit",
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("Failed to commit");
+
+        let summary = collect_blame_summary(&repo_path)
+            .await
+            .expect("Should summarize authors in a git repo");
+        assert_eq!(summary.get("test.txt"), Some(&2));
+        assert_eq!(summary.get("other.txt"), Some(&1));
+    }
+
+    #[test]
+    fn test_summarize_recent_authors_keeps_most_recent_files() {
+        let log = "\0a@example.com\n\nnew.rs\nshared.rs\n\0b@example.com\n\nshared.rs\nold.rs\n";
+        let summary = summarize_recent_authors(log, 2);
+        assert_eq!(
+            summary,
+            HashMap::from([("new.rs".to_string(), 1), ("shared.rs".to_string(), 2)])
+        );
+    }
+
     #[tokio::test]
     async fn test_collect_git_info_with_remote() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -1082,6 +1284,7 @@ mod tests {
             commit_hash: Some("abc123def456".to_string()),
             branch: Some("main".to_string()),
             repository_url: Some("https://github.com/example/repo.git".to_string()),
+            blame_authors: None,
         };
 
         let json = serde_json::to_string(&git_info).expect("Should serialize GitInfo");
@@ -1101,6 +1304,7 @@ mod tests {
             commit_hash: None,
             branch: None,
             repository_url: None,
+            blame_authors: None,
         };
 
         let json = serde_json::to_string(&git_info).expect("Should serialize GitInfo");
@@ -1,3 +1,4 @@
+use crate::exec::ExecResourceLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -13,6 +14,9 @@ use tokio::process::Child;
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
 /// the equivalent CLI options.
+///
+/// Variables rejected by the policy's [`EnvFilter`](crate::protocol::EnvFilter)
+/// are removed from `env` before the helper is spawned.
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
     command: Vec<String>,
@@ -20,11 +24,15 @@ pub async fn spawn_command_under_linux_sandbox<P>(
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
     stdio_policy: StdioPolicy,
-    env: HashMap<String, String>,
+    mut env: HashMap<String, String>,
+    resource_limits: Option<ExecResourceLimits>,
 ) -> std::io::Result<Child>
 where
     P: AsRef<Path>,
 {
+    if let Some(env_filter) = sandbox_policy.env_filter() {
+        env_filter.apply(&mut env);
+    }
     let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
@@ -35,6 +43,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        resource_limits,
     )
     .await
 }
@@ -21,6 +21,7 @@ pub mod config_edit;
 pub mod config_loader;
 pub mod config_profile;
 pub mod config_types;
+mod conversation_export;
 mod conversation_history;
 pub mod custom_prompts;
 mod environment_context;
@@ -39,6 +40,7 @@ mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
+mod redact;
 mod truncate;
 mod unified_exec;
 mod user_instructions;
@@ -86,6 +88,8 @@ pub mod util;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use command_safety::is_safe_command;
+pub use safety::available_sandboxes;
+pub use safety::describe_sandbox;
 pub use safety::get_platform_sandbox;
 // Re-export the protocol types from the standalone `codex-protocol` crate so existing
 // `codex_core::protocol::...` references continue to work across the workspace.
@@ -1,9 +1,11 @@
 use crate::config::Config;
 use crate::config_types::OtelExporterKind as Kind;
 use crate::config_types::OtelHttpProtocol as Protocol;
+use crate::config_types::OtelSampler as Sampler;
 use crate::default_client::originator;
 use codex_otel::config::OtelExporter;
 use codex_otel::config::OtelHttpProtocol;
+use codex_otel::config::OtelSampler;
 use codex_otel::config::OtelSettings;
 use codex_otel::otel_provider::OtelProvider;
 use std::error::Error;
@@ -21,6 +23,7 @@ pub fn build_provider(
             endpoint,
             headers,
             protocol,
+            proxy,
         } => {
             let protocol = match protocol {
                 Protocol::Json => OtelHttpProtocol::Json,
@@ -34,14 +37,24 @@ pub fn build_provider(
                     .map(|(k, v)| (k.clone(), v.clone()))
                     .collect(),
                 protocol,
+                proxy: proxy.clone(),
             }
         }
-        Kind::OtlpGrpc { endpoint, headers } => OtelExporter::OtlpGrpc {
+        Kind::OtlpGrpc {
+            endpoint,
+            headers,
+            ca_cert,
+            client_cert,
+            client_key,
+        } => OtelExporter::OtlpGrpc {
             endpoint: endpoint.clone(),
             headers: headers
                 .iter()
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect(),
+            ca_cert: ca_cert.clone(),
+            client_cert: client_cert.clone(),
+            client_key: client_key.clone(),
         },
     };
 
@@ -51,6 +64,17 @@ pub fn build_provider(
         codex_home: config.codex_home.clone(),
         environment: config.otel.environment.to_string(),
         exporter,
+        resource_attributes: config
+            .otel
+            .resource_attributes
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        sampler: match config.otel.sampler {
+            Sampler::AlwaysOn => OtelSampler::AlwaysOn,
+            Sampler::AlwaysOff => OtelSampler::AlwaysOff,
+            Sampler::Ratio(ratio) => OtelSampler::Ratio(ratio),
+        },
     })
 }
 
@@ -10,6 +10,7 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
 pub mod list;
 pub(crate) mod policy;
 pub mod recorder;
+pub mod replay;
 
 pub use codex_protocol::protocol::SessionMeta;
 pub use list::find_conversation_path_by_id_str;
@@ -0,0 +1,178 @@
+//! Read back the events recorded in a rollout file.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use futures::Stream;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+
+/// An event read from a rollout file.
+#[derive(Debug, Clone)]
+pub struct ReplayEvent {
+    /// Time since the first timestamped line of the rollout.
+    pub offset: Duration,
+    /// Timestamp recorded alongside the event.
+    pub timestamp: String,
+    pub msg: EventMsg,
+}
+
+impl ReplayEvent {
+    /// Whether the event type is `pattern`, ignoring case and underscores so
+    /// both `AgentMessage` and `agent_message` match. The whole type must
+    /// match, so `AgentMessage` does not pick up the `AgentMessageDelta`
+    /// fragments streamed before the assembled message.
+    pub fn type_matches(&self, pattern: &str) -> bool {
+        let normalize = |s: &str| s.replace('_', "").to_lowercase();
+        normalize(&self.msg.to_string()) == normalize(pattern)
+    }
+}
+
+struct ReplayState {
+    path: PathBuf,
+    lines: Option<Lines<BufReader<tokio::fs::File>>>,
+    start: Option<OffsetDateTime>,
+    line_no: usize,
+}
+
+/// Stream the [`EventMsg`] lines of the rollout at `path` in file order.
+///
+/// Other rollout items (response items, turn context, ...) are skipped. A
+/// malformed line yields an error and the stream continues with the next one.
+pub fn stream_events(path: &Path) -> impl Stream<Item = anyhow::Result<ReplayEvent>> {
+    let state = ReplayState {
+        path: path.to_path_buf(),
+        lines: None,
+        start: None,
+        line_no: 0,
+    };
+    futures::stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        if state.lines.is_none() {
+            match tokio::fs::File::open(&state.path).await {
+                Ok(file) => state.lines = Some(BufReader::new(file).lines()),
+                Err(err) => {
+                    let err = anyhow::Error::new(err)
+                        .context(format!("failed to open rollout {}", state.path.display()));
+                    return Some((Err(err), None));
+                }
+            }
+        }
+        loop {
+            let lines = state.lines.as_mut()?;
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(err) => return Some((Err(err.into()), None)),
+            };
+            state.line_no += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_line(&line, &mut state.start) {
+                Ok(Some(event)) => return Some((Ok(event), Some(state))),
+                Ok(None) => continue,
+                Err(err) => {
+                    let err = err.context(format!(
+                        "{}:{}: invalid rollout line",
+                        state.path.display(),
+                        state.line_no
+                    ));
+                    return Some((Err(err), Some(state)));
+                }
+            }
+        }
+    })
+}
+
+fn parse_line(
+    line: &str,
+    start: &mut Option<OffsetDateTime>,
+) -> anyhow::Result<Option<ReplayEvent>> {
+    let RolloutLine { timestamp, item } = serde_json::from_str(line)?;
+    let offset = match OffsetDateTime::parse(&timestamp, &Rfc3339) {
+        Ok(at) => {
+            let start = *start.get_or_insert(at);
+            (at - start).try_into().unwrap_or_default()
+        }
+        Err(_) => Duration::ZERO,
+    };
+    match item {
+        RolloutItem::EventMsg(msg) => Ok(Some(ReplayEvent {
+            offset,
+            timestamp,
+            msg,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Resolve a replay target that is either a rollout path or a session id.
+pub async fn resolve_rollout_path(codex_home: &Path, target: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(target);
+    if path.is_file() {
+        return Ok(path);
+    }
+    super::find_conversation_path_by_id_str(codex_home, target)
+        .await?
+        .with_context(|| format!("no rollout file or session id matches {target}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn streams_only_event_lines_with_offsets() {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file");
+        let lines = [
+            r#"{"timestamp":"2025-01-01T00:00:00.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hi"}]}}"#,
+            r#"{"timestamp":"2025-01-01T00:00:01.500Z","type":"event_msg","payload":{"type":"agent_message","message":"hello"}}"#,
+            "not json",
+            r#"{"timestamp":"2025-01-01T00:00:02.000Z","type":"event_msg","payload":{"type":"user_message","message":"thanks","kind":"plain"}}"#,
+        ];
+        for line in lines {
+            writeln!(file, "{line}").expect("write line");
+        }
+
+        let items: Vec<_> = stream_events(file.path()).collect().await;
+        assert_eq!(items.len(), 3);
+
+        let first = items[0].as_ref().expect("first event");
+        assert_eq!(first.offset, Duration::from_millis(1500));
+        assert!(first.type_matches("AgentMessage"));
+        assert!(!first.type_matches("user_message"));
+        assert!(items[1].is_err());
+        let last = items[2].as_ref().expect("last event");
+        assert_eq!(last.offset, Duration::from_secs(2));
+        assert!(last.type_matches("user_message"));
+    }
+
+    #[test]
+    fn type_matches_whole_event_type_only() {
+        let event = |msg| ReplayEvent {
+            offset: Duration::ZERO,
+            timestamp: String::new(),
+            msg,
+        };
+        let delta = event(EventMsg::AgentMessageDelta(
+            codex_protocol::protocol::AgentMessageDeltaEvent {
+                delta: "hel".to_string(),
+            },
+        ));
+        assert!(!delta.type_matches("AgentMessage"));
+        assert!(delta.type_matches("agent_message_delta"));
+        assert!(!delta.type_matches("message"));
+    }
+}
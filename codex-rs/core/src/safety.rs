@@ -7,11 +7,13 @@ use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 
 use crate::exec::SandboxType;
+use crate::git_info::sandbox_writable_roots;
 
 use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
 use crate::command_safety::is_safe_command::is_known_safe_command;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 
 #[derive(Debug, PartialEq)]
 pub enum SafetyCheck {
@@ -211,21 +213,89 @@ pub fn get_platform_sandbox() -> Option<SandboxType> {
     }
 }
 
+/// Every sandbox that commands can run under on this machine, starting with
+/// [`SandboxType::None`]. Unlike [`get_platform_sandbox`], this checks at
+/// runtime that the kernel supports Landlock or that `sandbox-exec` exists.
+pub fn available_sandboxes() -> Vec<SandboxType> {
+    std::iter::once(SandboxType::None)
+        .chain(get_platform_sandbox().filter(|s| sandbox_unavailable_reason(*s).is_none()))
+        .collect()
+}
+
+/// One-line description of `sandbox_type`, noting why it cannot be used on
+/// this machine.
+pub fn describe_sandbox(sandbox_type: SandboxType) -> String {
+    let (name, available) = match sandbox_type {
+        SandboxType::None => return "none: commands run without a sandbox".to_string(),
+        SandboxType::MacosSeatbelt => ("seatbelt", "sandbox-exec"),
+        SandboxType::LinuxSeccomp => ("landlock", "Landlock + seccomp via codex-linux-sandbox"),
+    };
+    match sandbox_unavailable_reason(sandbox_type) {
+        None => format!("{name}: available ({available})"),
+        Some(reason) => format!("{name}: unavailable, {reason}"),
+    }
+}
+
+fn sandbox_unavailable_reason(sandbox_type: SandboxType) -> Option<&'static str> {
+    match sandbox_type {
+        SandboxType::None => None,
+        SandboxType::MacosSeatbelt if !cfg!(target_os = "macos") => Some("macOS only"),
+        SandboxType::MacosSeatbelt => (!Path::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE).exists())
+            .then_some("/usr/bin/sandbox-exec not found"),
+        SandboxType::LinuxSeccomp if !cfg!(target_os = "linux") => Some("Linux only"),
+        SandboxType::LinuxSeccomp => {
+            (!kernel_supports_landlock()).then_some("the kernel does not support Landlock")
+        }
+    }
+}
+
+/// Ask the kernel for its Landlock ABI version; it reports none when Landlock
+/// is not built in or not enabled.
+#[cfg(target_os = "linux")]
+fn kernel_supports_landlock() -> bool {
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1 << 0;
+    // SAFETY: with this flag and no attributes the syscall only returns the
+    // ABI version; it creates no ruleset and touches no memory.
+    let abi = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    abi > 0
+}
+
+#[cfg(not(target_os = "linux"))]
+fn kernel_supports_landlock() -> bool {
+    false
+}
+
 fn is_write_patch_constrained_to_writable_paths(
     action: &ApplyPatchAction,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
 ) -> bool {
-    // Early‑exit if there are no declared writable roots.
-    let writable_roots = match sandbox_policy {
-        SandboxPolicy::ReadOnly => {
-            return false;
+    match sandbox_policy {
+        SandboxPolicy::ReadOnly => false,
+        SandboxPolicy::DangerFullAccess => true,
+        SandboxPolicy::WorkspaceWrite { .. } => {
+            patch_paths_outside_writable_roots(action, sandbox_policy, cwd).is_empty()
         }
-        SandboxPolicy::DangerFullAccess => {
-            return true;
-        }
-        SandboxPolicy::WorkspaceWrite { .. } => sandbox_policy.get_writable_roots_with_cwd(cwd),
-    };
+    }
+}
+
+/// Every path the patch would write (including move destinations) that falls
+/// outside the policy's writable roots, sorted. Pure path arithmetic:
+/// nothing is read from disk. Under `DangerFullAccess` there are no roots, so
+/// callers must skip this check for that policy.
+pub(crate) fn patch_paths_outside_writable_roots(
+    action: &ApplyPatchAction,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Vec<PathBuf> {
+    let writable_roots = sandbox_writable_roots(sandbox_policy, cwd);
 
     // Normalize a path by removing `.` and resolving `..` without touching the
     // filesystem (works even if the file does not exist).
@@ -262,32 +332,28 @@ fn is_write_patch_constrained_to_writable_paths(
             .any(|writable_root| writable_root.is_path_writable(&abs))
     };
 
+    let mut outside = Vec::new();
     for (path, change) in action.changes() {
-        match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
-                if !is_path_writable(path) {
-                    return false;
-                }
-            }
-            ApplyPatchFileChange::Update { move_path, .. } => {
-                if !is_path_writable(path) {
-                    return false;
-                }
-                if let Some(dest) = move_path
-                    && !is_path_writable(dest)
-                {
-                    return false;
-                }
-            }
+        if !is_path_writable(path) {
+            outside.push(path.clone());
+        }
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+            && !is_path_writable(dest)
+        {
+            outside.push(dest.clone());
         }
     }
-
-    true
+    outside.sort();
+    outside
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::EnvFilter;
     use tempfile::TempDir;
 
     #[test]
@@ -309,8 +375,11 @@ mod tests {
         let policy_workspace_only = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allow_local_network: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -330,8 +399,11 @@ mod tests {
         let policy_with_parent = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![parent],
             network_access: false,
+            allow_local_network: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
@@ -436,4 +508,38 @@ mod tests {
         };
         assert_eq!(safety_check, expected);
     }
+
+    #[test]
+    fn available_sandboxes_match_the_target_os_and_machine() {
+        let platform = if cfg!(target_os = "macos") {
+            vec![SandboxType::MacosSeatbelt]
+        } else if cfg!(target_os = "linux") {
+            vec![SandboxType::LinuxSeccomp]
+        } else {
+            vec![]
+        };
+        let expected: Vec<SandboxType> = std::iter::once(SandboxType::None)
+            .chain(
+                platform
+                    .into_iter()
+                    .filter(|s| sandbox_unavailable_reason(*s).is_none()),
+            )
+            .collect();
+        assert_eq!(available_sandboxes(), expected);
+        if cfg!(target_os = "linux") {
+            assert_eq!(
+                describe_sandbox(SandboxType::MacosSeatbelt),
+                "seatbelt: unavailable, macOS only"
+            );
+        }
+
+        for sandbox_type in [SandboxType::MacosSeatbelt, SandboxType::LinuxSeccomp] {
+            let available = available_sandboxes().contains(&sandbox_type);
+            assert_eq!(
+                describe_sandbox(sandbox_type).contains(": available"),
+                available,
+                "{sandbox_type:?}"
+            );
+        }
+    }
 }
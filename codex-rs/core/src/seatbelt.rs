@@ -3,6 +3,8 @@ use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+use crate::exec::ExecResourceLimits;
+use crate::git_info::sandbox_writable_roots;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
@@ -10,11 +12,18 @@ use crate::spawn::spawn_child_async;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 
+/// Allows loopback TCP/UDP and unix domain sockets while the wider network
+/// stays blocked.
+const MACOS_SEATBELT_LOCAL_NETWORK_POLICY: &str = r#"(allow network* (local ip "localhost:*"))
+(allow network* (remote ip "localhost:*"))
+(allow network* (remote unix-socket))
+(allow system-socket)"#;
+
 /// When working with `sandbox-exec`, only consider `sandbox-exec` in `/usr/bin`
 /// to defend against an attacker trying to inject a malicious version on the
 /// PATH. If /usr/bin/sandbox-exec has been tampered with, then the attacker
 /// already has root access.
-const MACOS_PATH_TO_SEATBELT_EXECUTABLE: &str = "/usr/bin/sandbox-exec";
+pub(crate) const MACOS_PATH_TO_SEATBELT_EXECUTABLE: &str = "/usr/bin/sandbox-exec";
 
 pub async fn spawn_command_under_seatbelt(
     command: Vec<String>,
@@ -23,6 +32,7 @@ pub async fn spawn_command_under_seatbelt(
     sandbox_policy_cwd: &Path,
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
+    resource_limits: Option<ExecResourceLimits>,
 ) -> std::io::Result<Child> {
     let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
     let arg0 = None;
@@ -35,6 +45,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        resource_limits,
     )
     .await
 }
@@ -52,7 +63,7 @@ fn create_seatbelt_command_args(
                 Vec::<String>::new(),
             )
         } else {
-            let writable_roots = sandbox_policy.get_writable_roots_with_cwd(sandbox_policy_cwd);
+            let writable_roots = sandbox_writable_roots(sandbox_policy, sandbox_policy_cwd);
 
             let mut writable_folder_policies: Vec<String> = Vec::new();
             let mut cli_args: Vec<String> = Vec::new();
@@ -106,6 +117,8 @@ fn create_seatbelt_command_args(
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
         "(allow network-outbound)\n(allow network-inbound)\n(allow system-socket)"
+    } else if sandbox_policy.has_local_network_access() {
+        MACOS_SEATBELT_LOCAL_NETWORK_POLICY
     } else {
         ""
     };
@@ -124,7 +137,9 @@ fn create_seatbelt_command_args(
 #[cfg(test)]
 mod tests {
     use super::MACOS_SEATBELT_BASE_POLICY;
+    use super::MACOS_SEATBELT_LOCAL_NETWORK_POLICY;
     use super::create_seatbelt_command_args;
+    use crate::protocol::EnvFilter;
     use crate::protocol::SandboxPolicy;
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -156,8 +171,11 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![root_with_git, root_without_git],
             network_access: false,
+            allow_local_network: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         };
 
         let args = create_seatbelt_command_args(
@@ -231,8 +249,11 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allow_local_network: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         };
 
         let args = create_seatbelt_command_args(
@@ -301,6 +322,91 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn create_seatbelt_args_keeps_submodule_git_read_only() {
+        if cfg!(target_os = "windows") {
+            // /tmp does not exist on Windows, so skip this test.
+            return;
+        }
+
+        let tmp = TempDir::new().expect("tempdir");
+        let PopulatedTmp {
+            root_with_git,
+            root_with_git_canon,
+            root_with_git_git_canon,
+            ..
+        } = populate_tmpdir(tmp.path());
+        fs::write(
+            root_with_git.join(".gitmodules"),
+            "[submodule \"foo\"]\n\tpath = libs/foo\n",
+        )
+        .expect("write .gitmodules");
+        fs::create_dir_all(root_with_git.join("libs/foo")).expect("create submodule");
+        fs::write(
+            root_with_git.join("libs/foo/.git"),
+            "gitdir: ../../.git/modules/foo\n",
+        )
+        .expect("write submodule .git");
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        };
+
+        let args = create_seatbelt_command_args(
+            vec!["/bin/echo".to_string(), "hello".to_string()],
+            &policy,
+            root_with_git.as_path(),
+        );
+
+        // The submodule is its own root, and its `.git` is carved out of both
+        // that root and the cwd root, since the roots are OR-ed.
+        let expected_policy = format!(
+            r#"{MACOS_SEATBELT_BASE_POLICY}
+; allow read-only file operations
+(allow file-read*)
+(allow file-write*
+(require-all (subpath (param "WRITABLE_ROOT_0")) (require-not (subpath (param "WRITABLE_ROOT_0_RO_0"))) (require-not (subpath (param "WRITABLE_ROOT_0_RO_1"))) ) (require-all (subpath (param "WRITABLE_ROOT_1")) (require-not (subpath (param "WRITABLE_ROOT_1_RO_0"))) )
+)
+"#,
+        );
+
+        let expected_args = vec![
+            "-p".to_string(),
+            expected_policy,
+            format!(
+                "-DWRITABLE_ROOT_0={}",
+                root_with_git_canon.to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_0_RO_0={}",
+                root_with_git_git_canon.to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_0_RO_1={}",
+                root_with_git_canon.join("libs/foo/.git").to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_1={}",
+                root_with_git_canon.join("libs/foo").to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_1_RO_0={}",
+                root_with_git_canon.join("libs/foo/.git").to_string_lossy()
+            ),
+            "--".to_string(),
+            "/bin/echo".to_string(),
+            "hello".to_string(),
+        ];
+
+        assert_eq!(expected_args, args);
+    }
+
     struct PopulatedTmp {
         root_with_git: PathBuf,
         root_without_git: PathBuf,
@@ -309,6 +415,34 @@ mod tests {
         root_without_git_canon: PathBuf,
     }
 
+    #[test]
+    fn create_seatbelt_args_with_local_network_only() {
+        let cwd = PathBuf::from("/does/not/exist");
+        let policy_for = |network_access, allow_local_network| SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access,
+            allow_local_network,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        };
+        let policy_text = |policy: &SandboxPolicy| {
+            create_seatbelt_command_args(vec!["/bin/true".to_string()], policy, &cwd)[1].clone()
+        };
+
+        let local_only = policy_text(&policy_for(false, true));
+        assert!(local_only.contains(MACOS_SEATBELT_LOCAL_NETWORK_POLICY));
+        assert!(!local_only.contains("(allow network-outbound)"));
+
+        let full = policy_text(&policy_for(true, true));
+        assert!(!full.contains(MACOS_SEATBELT_LOCAL_NETWORK_POLICY));
+        assert!(full.contains("(allow network-outbound)"));
+
+        let none = policy_text(&policy_for(false, false));
+        assert!(!none.contains(MACOS_SEATBELT_LOCAL_NETWORK_POLICY));
+    }
+
     fn populate_tmpdir(tmp: &Path) -> PopulatedTmp {
         let root_with_git = tmp.join("with_git");
         let root_without_git = tmp.join("no_git");
@@ -347,6 +347,7 @@ mod tests {
                     )]),
                     with_escalated_permissions: None,
                     justification: None,
+                    resource_limits: None,
                 },
                 SandboxType::None,
                 &SandboxPolicy::DangerFullAccess,
@@ -455,6 +456,7 @@ mod macos_tests {
                     )]),
                     with_escalated_permissions: None,
                     justification: None,
+                    resource_limits: None,
                 },
                 SandboxType::None,
                 &SandboxPolicy::DangerFullAccess,
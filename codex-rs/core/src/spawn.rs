@@ -5,6 +5,7 @@ use tokio::process::Child;
 use tokio::process::Command;
 use tracing::trace;
 
+use crate::exec::ExecResourceLimits;
 use crate::protocol::SandboxPolicy;
 
 /// Experimental environment variable that will be set to some non-empty value
@@ -17,6 +18,11 @@ use crate::protocol::SandboxPolicy;
 /// attributes, so this may change in the future.
 pub const CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR: &str = "CODEX_SANDBOX_NETWORK_DISABLED";
 
+/// Set to "1" alongside [`CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR`] when the
+/// sandbox still allows connections to localhost and unix domain sockets
+/// (`allow_local_network`).
+pub const CODEX_SANDBOX_LOCAL_NETWORK_ALLOWED_ENV_VAR: &str = "CODEX_SANDBOX_LOCAL_NETWORK_ALLOWED";
+
 /// Should be set when the process is spawned under a sandbox. Currently, the
 /// value is "seatbelt" for macOS, but it may change in the future to
 /// accommodate sandboxing configuration and other sandboxing mechanisms.
@@ -43,9 +49,10 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    resource_limits: Option<ExecResourceLimits>,
 ) -> std::io::Result<Child> {
     trace!(
-        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
+        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?} {resource_limits:?}"
     );
 
     let mut cmd = Command::new(&program);
@@ -59,6 +66,9 @@ pub(crate) async fn spawn_child_async(
     if !sandbox_policy.has_full_network_access() {
         cmd.env(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR, "1");
     }
+    if sandbox_policy.has_local_network_access() && !sandbox_policy.has_full_network_access() {
+        cmd.env(CODEX_SANDBOX_LOCAL_NETWORK_ALLOWED_ENV_VAR, "1");
+    }
 
     // If this Codex process dies (including being killed via SIGKILL), we want
     // any child processes that were spawned as part of a `"shell"` tool call
@@ -85,6 +95,15 @@ pub(crate) async fn spawn_child_async(
         });
     }
 
+    // Limits are inherited across exec, so they also bind commands that run
+    // under a sandbox helper.
+    #[cfg(unix)]
+    if let Some(limits) = resource_limits {
+        unsafe {
+            cmd.pre_exec(move || apply_resource_limits(&limits));
+        }
+    }
+
     match stdio_policy {
         StdioPolicy::RedirectForShellTool => {
             // Do not create a file descriptor for stdin because otherwise some
@@ -105,3 +124,29 @@ pub(crate) async fn spawn_child_async(
 
     cmd.kill_on_drop(true).spawn()
 }
+
+/// Set each configured limit as both the soft and hard `setrlimit(2)` value.
+/// Runs in the forked child, so it only makes async-signal-safe calls.
+#[cfg(unix)]
+fn apply_resource_limits(limits: &ExecResourceLimits) -> std::io::Result<()> {
+    let set = |resource, value: libc::rlim_t| {
+        let limit = libc::rlimit {
+            rlim_cur: value,
+            rlim_max: value,
+        };
+        if unsafe { libc::setrlimit(resource, &limit) } == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    };
+    if let Some(secs) = limits.max_cpu_time_s {
+        set(libc::RLIMIT_CPU, libc::rlim_t::from(secs))?;
+    }
+    if let Some(bytes) = limits.max_file_size_bytes {
+        set(libc::RLIMIT_FSIZE, bytes as libc::rlim_t)?;
+    }
+    if let Some(files) = limits.max_open_files {
+        set(libc::RLIMIT_NOFILE, libc::rlim_t::from(files))?;
+    }
+    Ok(())
+}
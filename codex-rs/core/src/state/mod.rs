@@ -1,7 +1,9 @@
+mod plan;
 mod service;
 mod session;
 mod turn;
 
+pub(crate) use plan::PlanState;
 pub(crate) use service::SessionServices;
 pub(crate) use session::SessionState;
 pub(crate) use turn::ActiveTurn;
@@ -0,0 +1,100 @@
+//! The agent's current plan, as last recorded with the `update_plan` tool.
+
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use serde::Serialize;
+
+/// Session-scoped copy of the most recent plan. Each `update_plan` call sends
+/// the whole plan, so an update replaces the previous explanation and steps.
+/// Serializes to `{"explanation": ..., "steps": [...]}`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct PlanState {
+    pub(crate) explanation: Option<String>,
+    pub(crate) steps: Vec<PlanItemArg>,
+}
+
+impl PlanState {
+    pub(crate) fn apply(&mut self, update: UpdatePlanArgs) {
+        self.explanation = update.explanation;
+        self.steps = update.plan;
+    }
+
+    /// The plan in the shape carried by `EventMsg::PlanUpdate`.
+    pub(crate) fn snapshot(&self) -> UpdatePlanArgs {
+        UpdatePlanArgs {
+            explanation: self.explanation.clone(),
+            plan: self.steps.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::plan_tool::StepStatus;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn update(explanation: Option<&str>, steps: &[(&str, StepStatus)]) -> UpdatePlanArgs {
+        UpdatePlanArgs {
+            explanation: explanation.map(str::to_string),
+            plan: steps
+                .iter()
+                .map(|(step, status)| PlanItemArg {
+                    step: (*step).to_string(),
+                    status: status.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn sequential_updates_replace_the_plan() {
+        let mut plan = PlanState::default();
+        plan.apply(update(
+            Some("Start with a failing test"),
+            &[
+                ("Write test", StepStatus::InProgress),
+                ("Fix bug", StepStatus::Pending),
+            ],
+        ));
+        plan.apply(update(
+            None,
+            &[
+                ("Write test", StepStatus::Completed),
+                ("Fix bug", StepStatus::InProgress),
+            ],
+        ));
+        plan.apply(update(
+            None,
+            &[
+                ("Write test", StepStatus::Completed),
+                ("Fix bug", StepStatus::Completed),
+                ("Update docs", StepStatus::InProgress),
+            ],
+        ));
+
+        assert_eq!(
+            serde_json::to_value(&plan).unwrap(),
+            json!({
+                "explanation": null,
+                "steps": [
+                    {"step": "Write test", "status": "completed"},
+                    {"step": "Fix bug", "status": "completed"},
+                    {"step": "Update docs", "status": "in_progress"},
+                ],
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(plan.snapshot()).unwrap(),
+            json!({
+                "explanation": null,
+                "plan": [
+                    {"step": "Write test", "status": "completed"},
+                    {"step": "Fix bug", "status": "completed"},
+                    {"step": "Update docs", "status": "in_progress"},
+                ],
+            })
+        );
+    }
+}
@@ -2,8 +2,10 @@ use crate::RolloutRecorder;
 use crate::exec_command::ExecSessionManager;
 use crate::executor::Executor;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::redact::OutputRedactor;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_notification::UserNotifier;
+use std::sync::Arc;
 use tokio::sync::Mutex;
 
 pub(crate) struct SessionServices {
@@ -15,4 +17,7 @@ pub(crate) struct SessionServices {
     pub(crate) user_shell: crate::shell::Shell,
     pub(crate) show_raw_agent_reasoning: bool,
     pub(crate) executor: Executor,
+    /// Secret redaction for command output sent to the model; `None` when
+    /// `redact_secrets_in_output` is off.
+    pub(crate) output_redactor: Option<Arc<OutputRedactor>>,
 }
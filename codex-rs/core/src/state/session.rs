@@ -6,6 +6,9 @@ use crate::conversation_history::ConversationHistory;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::state::PlanState;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 
 /// Persistent, session-scoped state previously stored directly on `Session`.
 #[derive(Default)]
@@ -13,6 +16,9 @@ pub(crate) struct SessionState {
     pub(crate) history: ConversationHistory,
     pub(crate) token_info: Option<TokenUsageInfo>,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) plan: PlanState,
+    /// Baselines for every file patched this session, for `Op::GetSessionDiff`.
+    pub(crate) session_diff_tracker: TurnDiffTracker,
 }
 
 impl SessionState {
@@ -73,5 +79,28 @@ impl SessionState {
         }
     }
 
+    /// Replace the last-turn usage with an estimate of the compacted history
+    /// so context-window reporting reflects the smaller prompt. Cumulative
+    /// totals are left untouched.
+    pub(crate) fn set_token_usage_after_compact(&mut self, estimated_tokens: u64) {
+        if let Some(info) = &mut self.token_info {
+            info.last_token_usage = TokenUsage {
+                input_tokens: estimated_tokens,
+                total_tokens: estimated_tokens,
+                ..TokenUsage::default()
+            };
+        }
+    }
+
+    // Plan helpers
+    pub(crate) fn update_plan(&mut self, update: UpdatePlanArgs) -> UpdatePlanArgs {
+        self.plan.apply(update);
+        self.plan.snapshot()
+    }
+
+    pub(crate) fn plan_snapshot(&self) -> UpdatePlanArgs {
+        self.plan.snapshot()
+    }
+
     // Pending input/approval moved to TurnState.
 }
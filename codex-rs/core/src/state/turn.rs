@@ -61,6 +61,10 @@ impl ActiveTurn {
         self.tasks.is_empty()
     }
 
+    pub(crate) fn take_task(&mut self, sub_id: &str) -> Option<RunningTask> {
+        self.tasks.swap_remove(sub_id)
+    }
+
     pub(crate) fn drain_tasks(&mut self) -> IndexMap<String, RunningTask> {
         std::mem::take(&mut self.tasks)
     }
@@ -99,6 +99,28 @@ impl Session {
         }
     }
 
+    /// Abort the task started by submission `sub_id`, leaving any other
+    /// running tasks alone.
+    pub async fn abort_task(self: &Arc<Self>, sub_id: &str, reason: TurnAbortReason) {
+        let task = {
+            let mut active = self.active_turn.lock().await;
+            let Some(at) = active.as_mut() else {
+                return;
+            };
+            let Some(task) = at.take_task(sub_id) else {
+                return;
+            };
+            if at.tasks.is_empty()
+                && let Some(at) = active.take()
+            {
+                at.clear_pending().await;
+            }
+            task
+        };
+        self.handle_task_abort(sub_id.to_string(), task, reason)
+            .await;
+    }
+
     pub async fn on_task_finished(
         self: &Arc<Self>,
         sub_id: String,
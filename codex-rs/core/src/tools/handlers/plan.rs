@@ -10,8 +10,6 @@ use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use async_trait::async_trait;
 use codex_protocol::plan_tool::UpdatePlanArgs;
-use codex_protocol::protocol::Event;
-use codex_protocol::protocol::EventMsg;
 use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
@@ -93,9 +91,8 @@ impl ToolHandler for PlanHandler {
     }
 }
 
-/// This function doesn't do anything useful. However, it gives the model a structured way to record its plan that clients can read and render.
-/// So it's the _inputs_ to this function that are useful to clients, not the outputs and neither are actually useful for the model other
-/// than forcing it to come up and document a plan (TBD how that affects performance).
+/// Records the plan on the session and broadcasts it as `EventMsg::PlanUpdate`. The output isn't useful to the model; the
+/// tool gives it a structured way to record its plan that clients can read and render (TBD how that affects performance).
 pub(crate) async fn handle_update_plan(
     session: &Session,
     arguments: String,
@@ -103,12 +100,7 @@ pub(crate) async fn handle_update_plan(
     _call_id: String,
 ) -> Result<String, FunctionCallError> {
     let args = parse_update_plan_arguments(&arguments)?;
-    session
-        .send_event(Event {
-            id: sub_id.to_string(),
-            msg: EventMsg::PlanUpdate(args),
-        })
-        .await;
+    session.update_plan(&sub_id, args).await;
     Ok("Plan updated".to_string())
 }
 
@@ -7,7 +7,9 @@ use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::unified_exec::InputJoin;
 use crate::unified_exec::UnifiedExecRequest;
+use crate::unified_exec::UnifiedExecSignal;
 
 pub struct UnifiedExecHandler;
 
@@ -18,6 +20,10 @@ struct UnifiedExecArgs {
     session_id: Option<String>,
     #[serde(default)]
     timeout_ms: Option<u64>,
+    #[serde(default)]
+    signal: Option<UnifiedExecSignal>,
+    #[serde(default)]
+    join_with_spaces: bool,
 }
 
 #[async_trait]
@@ -35,7 +41,10 @@ impl ToolHandler for UnifiedExecHandler {
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
-            session, payload, ..
+            session,
+            turn,
+            payload,
+            ..
         } = invocation;
 
         let args = match payload {
@@ -57,6 +66,8 @@ impl ToolHandler for UnifiedExecHandler {
             input,
             session_id,
             timeout_ms,
+            signal,
+            join_with_spaces,
         } = args;
 
         let parsed_session_id = if let Some(session_id) = session_id {
@@ -76,6 +87,13 @@ impl ToolHandler for UnifiedExecHandler {
             session_id: parsed_session_id,
             input_chunks: &input,
             timeout_ms,
+            signal,
+            input_join: if join_with_spaces {
+                InputJoin::Spaces
+            } else {
+                InputJoin::Verbatim
+            },
+            env_filter: turn.sandbox_policy.env_filter(),
         };
 
         let value = session
@@ -20,6 +20,8 @@ use crate::executor::ExecutionMode;
 use crate::executor::errors::ExecError;
 use crate::executor::linkers::PreparedExec;
 use crate::function_tool::FunctionCallError;
+use crate::redact::OutputRedactor;
+use crate::redact::maybe_redact;
 use crate::tools::context::ApplyPatchCommandContext;
 use crate::tools::context::ExecCommandContext;
 use crate::tools::context::SharedTurnDiffTracker;
@@ -159,7 +161,8 @@ pub(crate) async fn handle_container_exec_with_params(
     match output_result {
         Ok(output) => {
             let ExecToolCallOutput { exit_code, .. } = &output;
-            let content = format_exec_output_apply_patch(&output);
+            let content =
+                format_exec_output_apply_patch(&output, sess.services.output_redactor.as_deref());
             if *exit_code == 0 {
                 Ok(content)
             } else {
@@ -167,11 +170,14 @@ pub(crate) async fn handle_container_exec_with_params(
             }
         }
         Err(ExecError::Function(err)) => Err(truncate_function_error(err)),
-        Err(ExecError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => Err(
-            FunctionCallError::RespondToModel(format_exec_output_apply_patch(&output)),
-        ),
+        Err(ExecError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => {
+            Err(FunctionCallError::RespondToModel(
+                format_exec_output_apply_patch(&output, sess.services.output_redactor.as_deref()),
+            ))
+        }
         Err(ExecError::Codex(err)) => {
             let message = format!("execution error: {err:?}");
+            let message = maybe_redact(sess.services.output_redactor.as_deref(), &message);
             Err(FunctionCallError::RespondToModel(format_exec_output(
                 &message,
             )))
@@ -179,7 +185,10 @@ pub(crate) async fn handle_container_exec_with_params(
     }
 }
 
-pub fn format_exec_output_apply_patch(exec_output: &ExecToolCallOutput) -> String {
+pub(crate) fn format_exec_output_apply_patch(
+    exec_output: &ExecToolCallOutput,
+    redactor: Option<&OutputRedactor>,
+) -> String {
     let ExecToolCallOutput {
         exit_code,
         duration,
@@ -201,7 +210,7 @@ pub fn format_exec_output_apply_patch(exec_output: &ExecToolCallOutput) -> Strin
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output);
+    let formatted_output = format_exec_output_str(exec_output, redactor);
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -215,12 +224,17 @@ pub fn format_exec_output_apply_patch(exec_output: &ExecToolCallOutput) -> Strin
     serde_json::to_string(&payload).expect("serialize ExecOutput")
 }
 
-pub fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String {
+/// Output text for the model: secrets redacted when `redactor` is set, then
+/// truncated to the model budget.
+pub(crate) fn format_exec_output_str(
+    exec_output: &ExecToolCallOutput,
+    redactor: Option<&OutputRedactor>,
+) -> String {
     let ExecToolCallOutput {
         aggregated_output, ..
     } = exec_output;
 
-    let content = aggregated_output.text.as_str();
+    let content = maybe_redact(redactor, &aggregated_output.text);
 
     if exec_output.timed_out {
         let prefixed = format!(
@@ -230,7 +244,7 @@ pub fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String {
         return format_exec_output(&prefixed);
     }
 
-    format_exec_output(content)
+    format_exec_output(&content)
 }
 
 fn truncate_function_error(err: FunctionCallError) -> FunctionCallError {
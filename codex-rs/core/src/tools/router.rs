@@ -16,6 +16,7 @@ use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::ToolOverrides;
 
 #[derive(Clone)]
 pub struct ToolCall {
@@ -27,6 +28,7 @@ pub struct ToolCall {
 pub struct ToolRouter {
     registry: ToolRegistry,
     specs: Vec<ConfiguredToolSpec>,
+    overrides: ToolOverrides,
 }
 
 impl ToolRouter {
@@ -37,12 +39,23 @@ impl ToolRouter {
         let builder = build_specs(config, mcp_tools);
         let (specs, registry) = builder.build();
 
-        Self { registry, specs }
+        Self {
+            registry,
+            specs,
+            overrides: ToolOverrides::default(),
+        }
+    }
+
+    /// Hide tools disabled by the client and reject calls to them.
+    pub fn with_tool_overrides(mut self, overrides: ToolOverrides) -> Self {
+        self.overrides = overrides;
+        self
     }
 
     pub fn specs(&self) -> Vec<ToolSpec> {
         self.specs
             .iter()
+            .filter(|config| self.overrides.allows(config.spec.name()))
             .map(|config| config.spec.clone())
             .collect()
     }
@@ -145,6 +158,17 @@ impl ToolRouter {
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        if !self.overrides.allows(&tool_name) {
+            let err = FunctionCallError::RespondToModel(format!(
+                "tool {tool_name} is disabled for this turn by the client"
+            ));
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                err,
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
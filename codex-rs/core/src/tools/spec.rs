@@ -173,6 +173,28 @@ fn create_unified_exec_tool() -> ToolSpec {
         },
     );
 
+    properties.insert(
+        "signal".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Control character to send to the session (requires session_id) after any \
+                 input: \"interrupt\" for Ctrl-C or \"eof\" for Ctrl-D."
+                    .to_string(),
+            ),
+        },
+    );
+
+    properties.insert(
+        "join_with_spaces".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Legacy: separate the input strings with spaces instead of concatenating them \
+                 verbatim."
+                    .to_string(),
+            ),
+        },
+    );
+
     ToolSpec::Function(ResponsesApiTool {
         name: "unified_exec".to_string(),
         description:
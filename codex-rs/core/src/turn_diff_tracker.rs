@@ -11,6 +11,7 @@ use sha1::digest::Output;
 use uuid::Uuid;
 
 use crate::protocol::FileChange;
+use crate::protocol::TurnDiffEvent;
 
 const ZERO_OID: &str = "0000000000000000000000000000000000000000";
 const DEV_NULL: &str = "/dev/null";
@@ -23,6 +24,8 @@ struct BaselineFileInfo {
 }
 
 /// Tracks sets of changes to files and exposes the overall unified diff.
+/// One instance lives for each turn and another for the whole session, so
+/// the session diff compares against each file's state when first touched.
 /// Internally, the way this works is now:
 /// 1. Maintain an in-memory baseline snapshot of files when they are first seen.
 ///    For new additions, do not create a baseline so that diffs are shown as proper additions (using /dev/null).
@@ -223,29 +226,47 @@ impl TurnDiffTracker {
     /// collected before the first time they were touched by apply_patch during this turn with
     /// the current repo state.
     pub fn get_unified_diff(&mut self) -> Result<Option<String>> {
-        let mut aggregated = String::new();
+        Ok(self.get_turn_diff_event()?.map(|event| event.unified_diff))
+    }
+
+    /// Like [`Self::get_unified_diff`], with the modified files and the added
+    /// and removed line counts alongside the diff.
+    pub fn get_turn_diff_event(&mut self) -> Result<Option<TurnDiffEvent>> {
+        let mut event = TurnDiffEvent::default();
 
         // Compute diffs per tracked internal file in a stable order by external path.
-        let mut baseline_file_names: Vec<String> =
-            self.baseline_file_info.keys().cloned().collect();
+        let internal_names: Vec<String> = self.baseline_file_info.keys().cloned().collect();
+        let mut baseline_files: Vec<(String, String)> = internal_names
+            .into_iter()
+            .map(|internal| {
+                let display = self
+                    .get_path_for_internal(&internal)
+                    .map(|p| self.relative_to_git_root_str(&p))
+                    .unwrap_or_default();
+                (display, internal)
+            })
+            .collect();
         // Sort lexicographically by full repo-relative path to match git behavior.
-        baseline_file_names.sort_by_key(|internal| {
-            self.get_path_for_internal(internal)
-                .map(|p| self.relative_to_git_root_str(&p))
-                .unwrap_or_default()
-        });
-
-        for internal in baseline_file_names {
-            aggregated.push_str(self.get_file_diff(&internal).as_str());
-            if !aggregated.ends_with('\n') {
-                aggregated.push('\n');
+        baseline_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (display, internal) in baseline_files {
+            let file_diff = self.get_file_diff(&internal);
+            if !file_diff.is_empty() {
+                let (added, removed) = count_changed_lines(&file_diff);
+                event.total_lines_added += added;
+                event.total_lines_removed += removed;
+                event.files_modified.push(display);
+            }
+            event.unified_diff.push_str(&file_diff);
+            if !event.unified_diff.ends_with('\n') {
+                event.unified_diff.push('\n');
             }
         }
 
-        if aggregated.trim().is_empty() {
+        if event.unified_diff.trim().is_empty() {
             Ok(None)
         } else {
-            Ok(Some(aggregated))
+            Ok(Some(event))
         }
     }
 
@@ -368,6 +389,25 @@ impl TurnDiffTracker {
     }
 }
 
+/// Added and removed lines in one file's diff. Only hunk bodies are counted,
+/// so the `---`/`+++` headers are skipped.
+fn count_changed_lines(file_diff: &str) -> (usize, usize) {
+    let mut in_hunk = false;
+    let (mut added, mut removed) = (0, 0);
+    for line in file_diff.lines() {
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if line.starts_with("diff --git ") {
+            in_hunk = false;
+        } else if in_hunk && line.starts_with('+') {
+            added += 1;
+        } else if in_hunk && line.starts_with('-') {
+            removed += 1;
+        }
+    }
+    (added, removed)
+}
+
 /// Compute the Git SHA-1 blob object ID for the given content (bytes).
 fn git_blob_sha1_hex_bytes(data: &[u8]) -> Output<sha1::Sha1> {
     // Git blob hash is sha1 of: "blob <len>\0<data>"
@@ -571,6 +611,52 @@ index {ZERO_OID}..{right_oid}
         assert_eq!(combined, expected_combined);
     }
 
+    #[test]
+    fn turn_diff_event_counts_lines_per_modified_file() {
+        let dir = tempdir().unwrap();
+        let updated = dir.path().join("a.txt");
+        let added = dir.path().join("b.txt");
+        let untouched = dir.path().join("c.txt");
+        fs::write(&updated, "x\ny\n").unwrap();
+        fs::write(&untouched, "same\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([
+            (
+                updated.clone(),
+                FileChange::Update {
+                    unified_diff: "".to_owned(),
+                    move_path: None,
+                },
+            ),
+            (
+                untouched.clone(),
+                FileChange::Update {
+                    unified_diff: "".to_owned(),
+                    move_path: None,
+                },
+            ),
+        ]));
+        fs::write(&updated, "x\nz\n").unwrap();
+        acc.on_patch_begin(&HashMap::from([(
+            added.clone(),
+            FileChange::Add {
+                content: "+one\n-two\n".to_string(),
+            },
+        )]));
+        fs::write(&added, "+one\n-two\n").unwrap();
+
+        let event = acc.get_turn_diff_event().unwrap().unwrap();
+        let display = |p: &Path| p.display().to_string().replace('\\', "/");
+        assert_eq!(
+            event.files_modified,
+            vec![display(&updated), display(&added)]
+        );
+        assert_eq!(event.total_lines_added, 3);
+        assert_eq!(event.total_lines_removed, 1);
+        assert_eq!(Some(event.unified_diff), acc.get_unified_diff().unwrap());
+    }
+
     #[test]
     fn accumulates_delete() {
         let dir = tempdir().unwrap();
@@ -607,6 +693,49 @@ index {left_oid}..{ZERO_OID}
         assert_eq!(diff, expected);
     }
 
+    #[test]
+    fn deleted_then_recreated_file_collapses_to_update() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("c.txt");
+        fs::write(&file, "x\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([(
+            file.clone(),
+            FileChange::Delete {
+                content: "x\n".to_string(),
+            },
+        )]));
+        fs::remove_file(&file).unwrap();
+        acc.on_patch_begin(&HashMap::from([(
+            file.clone(),
+            FileChange::Add {
+                content: "x\n".to_string(),
+            },
+        )]));
+        fs::write(&file, "x\n").unwrap();
+        assert_eq!(acc.get_unified_diff().unwrap(), None);
+
+        fs::write(&file, "y\n").unwrap();
+        let diff = acc.get_unified_diff().unwrap().unwrap();
+        let diff = normalize_diff_for_test(&diff, dir.path());
+        let expected = {
+            let left_oid = git_blob_sha1_hex("x\n");
+            let right_oid = git_blob_sha1_hex("y\n");
+            format!(
+                r#"diff --git a/<TMP>/c.txt b/<TMP>/c.txt
+index {left_oid}..{right_oid}
+--- a/<TMP>/c.txt
++++ b/<TMP>/c.txt
+@@ -1 +1 @@
+-x
++y
+"#,
+            )
+        };
+        assert_eq!(diff, expected);
+    }
+
     #[test]
     fn accumulates_move_and_update() {
         let dir = tempdir().unwrap();
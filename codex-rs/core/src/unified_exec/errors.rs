@@ -7,16 +7,52 @@ pub(crate) enum UnifiedExecError {
         #[source]
         pty_error: anyhow::Error,
     },
+    #[error("program not found: {program}")]
+    ProgramNotFound { program: String },
+    #[error("permission denied running {program}: {source}")]
+    PermissionDenied {
+        program: String,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to spawn {program}: {source}")]
+    SpawnFailed {
+        program: String,
+        #[source]
+        source: anyhow::Error,
+    },
     #[error("Unknown session id {session_id}")]
     UnknownSessionId { session_id: i32 },
     #[error("failed to write to stdin")]
     WriteToStdin,
     #[error("missing command line for unified exec request")]
     MissingCommandLine,
+    #[error("a signal can only be sent to an existing session")]
+    SignalWithoutSession,
 }
 
 impl UnifiedExecError {
     pub(crate) fn create_session(error: anyhow::Error) -> Self {
         Self::CreateSession { pty_error: error }
     }
+
+    /// Classify a failure to spawn `program` inside the PTY.
+    pub(crate) fn spawn(program: &str, error: anyhow::Error) -> Self {
+        let program = program.to_string();
+        let io_kind = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<std::io::Error>())
+            .map(std::io::Error::kind);
+        match io_kind {
+            Some(std::io::ErrorKind::NotFound) => Self::ProgramNotFound { program },
+            Some(std::io::ErrorKind::PermissionDenied) => Self::PermissionDenied {
+                program,
+                source: error,
+            },
+            _ => Self::SpawnFailed {
+                program,
+                source: error,
+            },
+        }
+    }
 }
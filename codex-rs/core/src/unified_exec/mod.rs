@@ -1,6 +1,7 @@
 use portable_pty::CommandBuilder;
 use portable_pty::PtySize;
 use portable_pty::native_pty_system;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::io::ErrorKind;
@@ -18,6 +19,8 @@ use tokio::time::Duration;
 use tokio::time::Instant;
 
 use crate::exec_command::ExecCommandSession;
+use crate::protocol::EnvFilter;
+use crate::redact::OutputRedactor;
 use crate::truncate::truncate_middle;
 
 mod errors;
@@ -27,12 +30,74 @@ pub(crate) use errors::UnifiedExecError;
 const DEFAULT_TIMEOUT_MS: u64 = 1_000;
 const MAX_TIMEOUT_MS: u64 = 60_000;
 const UNIFIED_EXEC_OUTPUT_MAX_BYTES: usize = 128 * 1024; // 128 KiB
+/// Leading bytes inspected when deciding whether output is binary.
+const BINARY_SNIFF_BYTES: usize = 4 * 1024;
+/// Output whose sniffed prefix has more than this share of non-printable or
+/// invalid UTF-8 bytes is summarized instead of decoded.
+const BINARY_THRESHOLD_PERCENT: usize = 10;
+/// Number of leading bytes shown in the binary output summary.
+const BINARY_PREVIEW_BYTES: usize = 16;
+/// How often a freshly opened session is checked for exit while no output
+/// arrives, so short commands return without waiting for the timeout.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Extra wait after the child exits, for output the PTY reader is still
+/// forwarding.
+const EXIT_DRAIN_GRACE: Duration = Duration::from_millis(50);
 
 #[derive(Debug)]
 pub(crate) struct UnifiedExecRequest<'a> {
     pub session_id: Option<i32>,
     pub input_chunks: &'a [String],
     pub timeout_ms: Option<u64>,
+    /// Control character written to an existing session after any input.
+    pub signal: Option<UnifiedExecSignal>,
+    /// How `input_chunks` are combined for an existing session.
+    pub input_join: InputJoin,
+    /// Applied to the inherited environment when a new session is spawned;
+    /// `None` passes the full environment through.
+    pub env_filter: Option<&'a EnvFilter>,
+}
+
+/// How the input chunks for an existing session are combined before being
+/// written to its stdin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum InputJoin {
+    /// Concatenate the chunks exactly as given, so embedded newlines and
+    /// intentional splits survive.
+    #[default]
+    Verbatim,
+    /// Separate chunks with a single space (the original behaviour).
+    Spaces,
+}
+
+impl InputJoin {
+    fn join(self, chunks: &[String]) -> String {
+        match self {
+            InputJoin::Verbatim => chunks.concat(),
+            InputJoin::Spaces => chunks.join(" "),
+        }
+    }
+}
+
+/// Control characters the model can send to an interactive session. They are
+/// written to the PTY as a single raw byte, so the terminal's line discipline
+/// acts on them instead of treating them as typed text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum UnifiedExecSignal {
+    /// Ctrl-C: the PTY delivers SIGINT to the foreground process group.
+    Interrupt,
+    /// Ctrl-D: end of input for a process reading the terminal.
+    Eof,
+}
+
+impl UnifiedExecSignal {
+    fn control_byte(self) -> u8 {
+        match self {
+            UnifiedExecSignal::Interrupt => 0x03,
+            UnifiedExecSignal::Eof => 0x04,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,6 +110,8 @@ pub(crate) struct UnifiedExecResult {
 pub(crate) struct UnifiedExecSessionManager {
     next_session_id: AtomicI32,
     sessions: Mutex<HashMap<i32, ManagedUnifiedExecSession>>,
+    /// Applied to collected output before it is truncated and returned.
+    output_redactor: Option<Arc<OutputRedactor>>,
 }
 
 #[derive(Debug)]
@@ -160,6 +227,13 @@ impl Drop for ManagedUnifiedExecSession {
 }
 
 impl UnifiedExecSessionManager {
+    pub(crate) fn new(output_redactor: Option<Arc<OutputRedactor>>) -> Self {
+        Self {
+            output_redactor,
+            ..Self::default()
+        }
+    }
+
     pub async fn handle_request(
         &self,
         request: UnifiedExecRequest<'_>,
@@ -175,6 +249,10 @@ impl UnifiedExecSessionManager {
             None => (DEFAULT_TIMEOUT_MS, None),
         };
 
+        if request.signal.is_some() && request.session_id.is_none() {
+            return Err(UnifiedExecError::SignalWithoutSession);
+        }
+
         let mut new_session: Option<ManagedUnifiedExecSession> = None;
         let session_id;
         let writer_tx;
@@ -207,7 +285,8 @@ impl UnifiedExecSessionManager {
         } else {
             let command = request.input_chunks.to_vec();
             let new_id = self.next_session_id.fetch_add(1, Ordering::SeqCst);
-            let (session, initial_output_rx) = create_unified_exec_session(&command).await?;
+            let (session, initial_output_rx) =
+                create_unified_exec_session(&command, request.env_filter).await?;
             let managed_session = ManagedUnifiedExecSession::new(session, initial_output_rx);
             let (buffer, notify) = managed_session.output_handles();
             writer_tx = managed_session.writer_sender();
@@ -218,16 +297,22 @@ impl UnifiedExecSessionManager {
         };
 
         if request.session_id.is_some() {
-            let joined_input = request.input_chunks.join(" ");
+            let joined_input = request.input_join.join(request.input_chunks);
             if !joined_input.is_empty() && writer_tx.send(joined_input.into_bytes()).await.is_err()
             {
                 return Err(UnifiedExecError::WriteToStdin);
             }
+            if let Some(signal) = request.signal
+                && writer_tx.send(vec![signal.control_byte()]).await.is_err()
+            {
+                return Err(UnifiedExecError::WriteToStdin);
+            }
         }
 
         let mut collected: Vec<u8> = Vec::with_capacity(4096);
         let start = Instant::now();
         let deadline = start + Duration::from_millis(timeout_ms);
+        let mut drained_after_exit = false;
 
         loop {
             let drained_chunks;
@@ -246,11 +331,32 @@ impl UnifiedExecSessionManager {
                     break;
                 }
 
+                let new_session_exited = new_session
+                    .as_ref()
+                    .is_some_and(ManagedUnifiedExecSession::has_exited);
+                if new_session_exited {
+                    if drained_after_exit {
+                        break;
+                    }
+                    // Give the reader a moment to forward trailing output,
+                    // then drain once more before returning.
+                    drained_after_exit = true;
+                    tokio::time::sleep(EXIT_DRAIN_GRACE.min(remaining)).await;
+                    continue;
+                }
+
+                // Only a session opened by this request is polled for exit;
+                // existing sessions wait for output or the deadline.
+                let wait = if new_session.is_some() {
+                    remaining.min(EXIT_POLL_INTERVAL)
+                } else {
+                    remaining
+                };
                 let notified = wait_for_output.unwrap_or_else(|| output_notify.notified());
                 tokio::pin!(notified);
                 tokio::select! {
                     _ = &mut notified => {}
-                    _ = tokio::time::sleep(remaining) => break,
+                    _ = tokio::time::sleep(wait) => {}
                 }
                 continue;
             }
@@ -264,10 +370,12 @@ impl UnifiedExecSessionManager {
             }
         }
 
-        let (output, _maybe_tokens) = truncate_middle(
-            &String::from_utf8_lossy(&collected),
-            UNIFIED_EXEC_OUTPUT_MAX_BYTES,
-        );
+        let decoded = decode_output(&collected);
+        let decoded = match self.output_redactor.as_deref() {
+            Some(redactor) => redactor.redact(&decoded).into_owned(),
+            None => decoded,
+        };
+        let (output, _maybe_tokens) = truncate_middle(&decoded, UNIFIED_EXEC_OUTPUT_MAX_BYTES);
         let output = if let Some(warning) = timeout_warning {
             format!("{warning}{output}")
         } else {
@@ -309,8 +417,43 @@ impl UnifiedExecSessionManager {
     }
 }
 
+/// Look `program` up the way `execvp` would, on the PATH the child will see,
+/// so a program that cannot be run fails with an io error to classify.
+/// Relative paths are left to portable-pty, which resolves them against its
+/// own working directory.
+#[cfg(unix)]
+fn check_program(program: &str, path: Option<&std::ffi::OsStr>) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let runnable = |candidate: &std::path::Path| -> std::io::Result<()> {
+        let metadata = std::fs::metadata(candidate)?;
+        if metadata.is_file() && metadata.permissions().mode() & 0o111 != 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::from(ErrorKind::PermissionDenied))
+        }
+    };
+    let program_path = std::path::Path::new(program);
+    if program_path.is_absolute() {
+        return runnable(program_path);
+    }
+    if program_path.components().count() > 1 {
+        return Ok(());
+    }
+    let mut result = Err(std::io::Error::from(ErrorKind::NotFound));
+    for dir in std::env::split_paths(path.unwrap_or_default()) {
+        match runnable(&dir.join(program)) {
+            Ok(()) => return Ok(()),
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => result = Err(err),
+            Err(_) => {}
+        }
+    }
+    result
+}
+
 async fn create_unified_exec_session(
     command: &[String],
+    env_filter: Option<&EnvFilter>,
 ) -> Result<
     (
         ExecCommandSession,
@@ -338,11 +481,24 @@ async fn create_unified_exec_session(
     for arg in &command[1..] {
         command_builder.arg(arg);
     }
+    // The builder starts from this process's environment.
+    if let Some(env_filter) = env_filter {
+        for (name, _) in std::env::vars_os() {
+            if !env_filter.allows(&name.to_string_lossy()) {
+                command_builder.env_remove(name);
+            }
+        }
+    }
+
+    // portable-pty reports an unresolvable program without an io error.
+    #[cfg(unix)]
+    check_program(&command[0], command_builder.get_env("PATH"))
+        .map_err(|err| UnifiedExecError::spawn(&command[0], err.into()))?;
 
     let mut child = pair
         .slave
         .spawn_command(command_builder)
-        .map_err(UnifiedExecError::create_session)?;
+        .map_err(|err| UnifiedExecError::spawn(&command[0], err))?;
     let killer = child.clone_killer();
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(128);
@@ -412,12 +568,115 @@ async fn create_unified_exec_session(
     Ok((session, initial_output_rx))
 }
 
+/// Render collected PTY bytes for the model. Predominantly binary output is
+/// replaced by a one-line summary; otherwise invalid UTF-8 bytes are escaped
+/// as `\xNN` so the surrounding text stays readable.
+fn decode_output(bytes: &[u8]) -> String {
+    if looks_binary(bytes) {
+        let preview: Vec<String> = bytes
+            .iter()
+            .take(BINARY_PREVIEW_BYTES)
+            .map(|b| format!("{b:02X}"))
+            .collect();
+        let ellipsis = if bytes.len() > BINARY_PREVIEW_BYTES {
+            " ..."
+        } else {
+            ""
+        };
+        return format!(
+            "[binary output: {} bytes, first bytes: {}{ellipsis}]",
+            bytes.len(),
+            preview.join(" ")
+        );
+    }
+
+    let mut out = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        out.push_str(chunk.valid());
+        for b in chunk.invalid() {
+            out.push_str(&format!("\\x{b:02X}"));
+        }
+    }
+    out
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sample.is_empty() {
+        return false;
+    }
+    let mut suspicious = 0usize;
+    for chunk in sample.utf8_chunks() {
+        suspicious += chunk.invalid().len();
+        suspicious += chunk
+            .valid()
+            .chars()
+            // Whitespace, bell, backspace and ANSI escapes are normal terminal output.
+            .filter(|c| {
+                c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x07' | '\x08' | '\x1b')
+            })
+            .map(char::len_utf8)
+            .sum::<usize>();
+    }
+    suspicious * 100 > sample.len() * BINARY_THRESHOLD_PERCENT
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[cfg(unix)]
     use core_test_support::skip_if_sandbox;
 
+    #[test]
+    fn decode_output_summarizes_binary_data() {
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        png.extend((0u8..=255).cycle().take(13_474));
+        assert_eq!(
+            decode_output(&png),
+            "[binary output: 13482 bytes, first bytes: 89 50 4E 47 0D 0A 1A 0A 00 01 02 03 04 05 06 07 ...]"
+        );
+        assert_eq!(
+            decode_output(&[0x00, 0xFF]),
+            "[binary output: 2 bytes, first bytes: 00 FF]"
+        );
+    }
+
+    #[test]
+    fn decode_output_escapes_occasional_invalid_bytes() {
+        let mut text = b"build ok\n\x1b[32mpassed\x1b[0m caf\xC3\xA9 ".to_vec();
+        text.push(0xFF);
+        text.extend_from_slice(" done\n".repeat(20).as_bytes());
+        let decoded = decode_output(&text);
+        assert!(decoded.starts_with("build ok\n\x1b[32mpassed\x1b[0m caf\u{e9} \\xFF done\n"));
+        assert!(!decoded.contains('\u{FFFD}'));
+    }
+
+    #[test]
+    fn binary_threshold_counts_prefix_only() {
+        // Exactly 10% suspicious bytes stays text; just above it is binary.
+        let mut at_threshold = vec![b'a'; 90];
+        at_threshold.extend([0x01; 10]);
+        assert!(!looks_binary(&at_threshold));
+        let mut above = vec![b'a'; 89];
+        above.extend([0x01; 11]);
+        assert!(looks_binary(&above));
+
+        // Binary bytes beyond the sniffed prefix do not flip the decision.
+        let mut late_binary = vec![b'a'; BINARY_SNIFF_BYTES];
+        late_binary.extend([0x00; BINARY_SNIFF_BYTES]);
+        assert!(!looks_binary(&late_binary));
+        assert!(!looks_binary(&[]));
+    }
+
+    #[test]
+    fn truncation_runs_after_decoding() {
+        let mut text = vec![0xFF];
+        text.extend(vec![b'a'; 2 * UNIFIED_EXEC_OUTPUT_MAX_BYTES]);
+        let (output, _) = truncate_middle(&decode_output(&text), UNIFIED_EXEC_OUTPUT_MAX_BYTES);
+        assert!(output.starts_with("\\xFFaaa"));
+        assert!(output.contains("tokens truncated"));
+    }
+
     #[test]
     fn push_chunk_trims_only_excess_bytes() {
         let mut buffer = OutputBufferState::default();
@@ -447,6 +706,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["bash".to_string(), "-i".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         let session_id = open_shell.session_id.expect("expected session_id");
@@ -459,6 +721,10 @@ mod tests {
                     "CODEX_INTERACTIVE_SHELL_VAR=codex\n".to_string(),
                 ],
                 timeout_ms: Some(2_500),
+                signal: None,
+                // The command is split into words, so opt into space joining.
+                input_join: InputJoin::Spaces,
+                env_filter: None,
             })
             .await?;
 
@@ -467,6 +733,9 @@ mod tests {
                 session_id: Some(session_id),
                 input_chunks: &["echo $CODEX_INTERACTIVE_SHELL_VAR\n".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         assert!(out_2.output.contains("codex"));
@@ -486,6 +755,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["/bin/bash".to_string(), "-i".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         let session_a = shell_a.session_id.expect("expected session id");
@@ -495,6 +767,9 @@ mod tests {
                 session_id: Some(session_a),
                 input_chunks: &["export CODEX_INTERACTIVE_SHELL_VAR=codex\n".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
 
@@ -506,6 +781,9 @@ mod tests {
                     "$CODEX_INTERACTIVE_SHELL_VAR\n".to_string(),
                 ],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         assert!(!out_2.output.contains("codex"));
@@ -515,6 +793,9 @@ mod tests {
                 session_id: Some(session_a),
                 input_chunks: &["echo $CODEX_INTERACTIVE_SHELL_VAR\n".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         assert!(out_3.output.contains("codex"));
@@ -534,6 +815,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["bash".to_string(), "-i".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         let session_id = open_shell.session_id.expect("expected session id");
@@ -546,6 +830,10 @@ mod tests {
                     "CODEX_INTERACTIVE_SHELL_VAR=codex\n".to_string(),
                 ],
                 timeout_ms: Some(2_500),
+                signal: None,
+                // The command is split into words, so opt into space joining.
+                input_join: InputJoin::Spaces,
+                env_filter: None,
             })
             .await?;
 
@@ -554,6 +842,9 @@ mod tests {
                 session_id: Some(session_id),
                 input_chunks: &["sleep 5 && echo $CODEX_INTERACTIVE_SHELL_VAR\n".to_string()],
                 timeout_ms: Some(10),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         assert!(!out_2.output.contains("codex"));
@@ -566,6 +857,9 @@ mod tests {
                 session_id: Some(session_id),
                 input_chunks: &empty,
                 timeout_ms: Some(100),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
 
@@ -585,6 +879,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["echo".to_string(), "codex".to_string()],
                 timeout_ms: Some(120_000),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
 
@@ -606,6 +903,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["/bin/echo".to_string(), "codex".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
 
@@ -617,6 +917,97 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn short_command_returns_before_default_timeout() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+        let start = Instant::now();
+        let result = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["echo".to_string(), "hi".to_string()],
+                timeout_ms: None,
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await?;
+        let elapsed = start.elapsed();
+
+        assert!(result.output.contains("hi"), "{:?}", result.output);
+        assert!(result.session_id.is_none());
+        assert!(
+            elapsed < Duration::from_millis(DEFAULT_TIMEOUT_MS / 2),
+            "took {elapsed:?}"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn env_filter_removes_denied_variables() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+        let env_filter = EnvFilter::DenyList(vec!["HOME".to_string()]);
+        let result = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &[
+                    "bash".to_string(),
+                    "-c".to_string(),
+                    "echo \"home=[${HOME-unset}]\"".to_string(),
+                ],
+                timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: Some(&env_filter),
+            })
+            .await?;
+
+        assert!(
+            result.output.contains("home=[unset]"),
+            "{:?}",
+            result.output
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn output_is_redacted_before_it_is_returned() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let redactor = OutputRedactor::new(
+            [("DEPLOY_TOKEN".to_string(), "tok-1234567890".to_string())],
+            &crate::redact::default_sensitive_env_patterns(),
+        );
+        let manager = UnifiedExecSessionManager::new(Some(Arc::new(redactor)));
+        let result = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["echo".to_string(), "token=tok-1234567890".to_string()],
+                timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await?;
+
+        assert!(
+            result.output.contains("token=[REDACTED:DEPLOY_TOKEN]"),
+            "{:?}",
+            result.output
+        );
+        assert!(!result.output.contains("tok-1234567890"));
+
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn reusing_completed_session_returns_unknown_session() -> Result<(), UnifiedExecError> {
@@ -629,6 +1020,9 @@ mod tests {
                 session_id: None,
                 input_chunks: &["/bin/bash".to_string(), "-i".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
         let session_id = open_shell.session_id.expect("expected session id");
@@ -638,6 +1032,9 @@ mod tests {
                 session_id: Some(session_id),
                 input_chunks: &["exit\n".to_string()],
                 timeout_ms: Some(2_500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await?;
 
@@ -648,6 +1045,9 @@ mod tests {
                 session_id: Some(session_id),
                 input_chunks: &[],
                 timeout_ms: Some(100),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
             })
             .await
             .expect_err("expected unknown session error");
@@ -663,4 +1063,177 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn interrupt_signal_terminates_foreground_process() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+
+        let open_cat = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["cat".to_string()],
+                timeout_ms: Some(500),
+                signal: None,
+                input_join: InputJoin::default(),
+                env_filter: None,
+            })
+            .await?;
+        let session_id = open_cat.session_id.expect("expected session id");
+
+        let interrupted = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: Some(session_id),
+                input_chunks: &[],
+                timeout_ms: Some(2_500),
+                signal: Some(UnifiedExecSignal::Interrupt),
+                input_join: InputJoin::default(),
+                env_filter: None,
+            })
+            .await?;
+        assert_eq!(interrupted.session_id, None);
+        assert!(!manager.sessions.lock().await.contains_key(&session_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn signal_requires_existing_session() {
+        let manager = UnifiedExecSessionManager::default();
+        let err = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["cat".to_string()],
+                timeout_ms: Some(100),
+                signal: Some(UnifiedExecSignal::Eof),
+                input_join: InputJoin::default(),
+                env_filter: None,
+            })
+            .await
+            .expect_err("expected signal without session to fail");
+        assert!(matches!(err, UnifiedExecError::SignalWithoutSession));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn missing_program_maps_to_program_not_found() {
+        skip_if_sandbox!();
+
+        let manager = UnifiedExecSessionManager::default();
+        let err = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["codex-definitely-missing-program".to_string()],
+                timeout_ms: None,
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await
+            .expect_err("spawning a missing program should fail");
+
+        assert!(
+            matches!(
+                &err,
+                UnifiedExecError::ProgramNotFound { program }
+                    if program == "codex-definitely-missing-program"
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn non_executable_program_maps_to_permission_denied() {
+        use std::os::unix::fs::PermissionsExt;
+        skip_if_sandbox!();
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let program = dir.path().join("not-executable");
+        std::fs::write(&program, "#!/bin/sh\necho hi\n").expect("write program");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o644))
+            .expect("chmod program");
+        let program = program.display().to_string();
+
+        let manager = UnifiedExecSessionManager::default();
+        let err = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: std::slice::from_ref(&program),
+                timeout_ms: None,
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await
+            .expect_err("spawning a non-executable file should fail");
+
+        assert!(
+            matches!(
+                &err,
+                UnifiedExecError::PermissionDenied { program: denied, .. } if *denied == program
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_program_uses_the_given_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let program = dir.path().join("only-here");
+        std::fs::write(&program, "#!/bin/sh\n").expect("write program");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod program");
+
+        assert!(check_program("only-here", Some(dir.path().as_os_str())).is_ok());
+        let missing = check_program("only-here", None).expect_err("no PATH");
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn verbatim_join_keeps_chunks_exactly() {
+        let chunks = ["echo a".to_string(), "\n".to_string()];
+        assert_eq!(InputJoin::Verbatim.join(&chunks), "echo a\n");
+        assert_eq!(InputJoin::Spaces.join(&chunks), "echo a \n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn verbatim_input_is_written_without_separators() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+
+        let open_cat = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["cat".to_string()],
+                timeout_ms: Some(500),
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await?;
+        let session_id = open_cat.session_id.expect("expected session id");
+
+        let out = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: Some(session_id),
+                input_chunks: &["echo a".to_string(), "\n".to_string()],
+                timeout_ms: Some(1_000),
+                signal: None,
+                input_join: InputJoin::Verbatim,
+                env_filter: None,
+            })
+            .await?;
+        assert!(out.output.contains("echo a\r\n"), "{:?}", out.output);
+        assert!(!out.output.contains("echo a "), "{:?}", out.output);
+
+        Ok(())
+    }
 }
@@ -860,3 +860,110 @@ async fn auto_compact_allows_multiple_attempts_when_interleaved_with_other_turn_
         "second auto compact request should include the summarization prompt"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn manual_compact_reports_summary_and_shrinks_history() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+
+    let request_log = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_assistant_message("m1", FIRST_REPLY),
+                ev_completed_with_tokens("r1", 1_000),
+            ]),
+            sse(vec![
+                ev_assistant_message("m2", SECOND_LARGE_REPLY),
+                ev_completed_with_tokens("r2", 2_000),
+            ]),
+            sse(vec![
+                ev_assistant_message("m3", SUMMARY_TEXT),
+                ev_completed_with_tokens("r3", 2_500),
+            ]),
+            sse(vec![ev_completed("r4")]),
+        ],
+    )
+    .await;
+
+    let model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home);
+    config.model_provider = model_provider;
+    config.model_auto_compact_token_limit = Some(200_000);
+    let codex = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"))
+        .new_conversation(config)
+        .await
+        .unwrap()
+        .conversation;
+
+    for text in ["first turn", "second turn"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![InputItem::Text { text: text.into() }],
+            })
+            .await
+            .unwrap();
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    }
+
+    codex.submit(Op::Compact).await.unwrap();
+    let mut token_counts = Vec::new();
+    let mut agent_messages = Vec::new();
+    loop {
+        let event = codex.next_event().await.unwrap();
+        match event.msg {
+            EventMsg::TokenCount(ev) => token_counts.push(ev),
+            EventMsg::AgentMessage(ev) => agent_messages.push(ev.message),
+            EventMsg::TaskComplete(_) => break,
+            _ => {}
+        }
+    }
+    assert_eq!(agent_messages, vec![SUMMARY_TEXT.to_string()]);
+
+    let in_context = |ev: &codex_core::protocol::TokenCountEvent| {
+        ev.info
+            .as_ref()
+            .map(|info| info.last_token_usage.tokens_in_context_window())
+    };
+    let before = token_counts.first().and_then(in_context);
+    let after = token_counts.last().and_then(in_context);
+    assert_eq!(before, Some(2_000), "pre-compact count should be reported");
+    assert!(
+        after.is_some_and(|after| after < 2_000),
+        "post-compact count should shrink, got {after:?}"
+    );
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: THIRD_USER_MSG.into(),
+            }],
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 4, "two turns, compact and a follow-up");
+    let turns = |body: serde_json::Value| {
+        body["input"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| {
+                        item["type"].as_str() == Some("message")
+                            && item["role"].as_str() == Some("assistant")
+                    })
+                    .count()
+            })
+            .unwrap_or_default()
+    };
+    assert_eq!(turns(requests[2].body_json()), 2);
+    assert_eq!(turns(requests[3].body_json()), 0);
+}
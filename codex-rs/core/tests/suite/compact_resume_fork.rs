@@ -805,7 +805,9 @@ async fn fetch_conversation_path(
     })
     .await
     {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path,
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.expect("rollout path")
+        }
         _ => panic!("expected ConversationPath event for {context}"),
     }
 }
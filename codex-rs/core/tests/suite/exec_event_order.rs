@@ -0,0 +1,115 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::time::Duration;
+
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_local_shell_call;
+use core_test_support::responses::mount_sse;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_with_timeout;
+
+#[expect(clippy::unwrap_used)]
+async fn start_turn_with_shell_call(command: Vec<&str>) -> TestCodex {
+    let server = start_mock_server().await;
+    mount_sse(
+        &server,
+        sse(vec![
+            ev_local_shell_call("shell_call", "completed", command),
+            ev_completed("done"),
+        ]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.approval_policy = AskForApproval::UnlessTrusted;
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(0);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "run it".into(),
+            }],
+        })
+        .await
+        .unwrap();
+    test
+}
+
+/// The begin event comes before the approval request, as it always has, and
+/// carries no decision trace because the decision is still up to the user.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_begin_precedes_approval_request() {
+    let TestCodex { codex, .. } =
+        start_turn_with_shell_call(vec!["/bin/echo", "needs approval"]).await;
+
+    let first = wait_for_event_with_timeout(
+        &codex,
+        |ev| {
+            matches!(
+                ev,
+                EventMsg::ExecCommandBegin(_) | EventMsg::ExecApprovalRequest(_)
+            )
+        },
+        Duration::from_secs(5),
+    )
+    .await;
+    match first {
+        EventMsg::ExecCommandBegin(begin) => assert_eq!(begin.decision_trace, None),
+        other => panic!("expected ExecCommandBegin before the approval request, got {other:?}"),
+    }
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::ExecApprovalRequest(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+    codex
+        .submit(Op::ExecApproval {
+            id: "0".into(),
+            decision: ReviewDecision::Approved,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::ExecCommandEnd(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn auto_approved_exec_begin_carries_decision_trace() {
+    let TestCodex { codex, .. } = start_turn_with_shell_call(vec!["ls"]).await;
+
+    let begin = wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::ExecCommandBegin(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+    let EventMsg::ExecCommandBegin(begin) = begin else {
+        panic!("predicate only matches ExecCommandBegin");
+    };
+    let trace = begin
+        .decision_trace
+        .expect("auto-approved commands carry a trace");
+    assert!(trace.matched_safe_command);
+    assert!(!trace.user_approved);
+}
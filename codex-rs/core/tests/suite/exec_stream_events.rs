@@ -8,6 +8,7 @@ use async_channel::Receiver;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
 use codex_core::exec::ExecParams;
+use codex_core::exec::ExecResourceLimits;
 use codex_core::exec::SandboxType;
 use codex_core::exec::StdoutStream;
 use codex_core::exec::process_exec_tool_call;
@@ -57,6 +58,7 @@ async fn test_exec_stdout_stream_events_echo() {
         env: HashMap::new(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
@@ -109,6 +111,7 @@ async fn test_exec_stderr_stream_events_echo() {
         env: HashMap::new(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
@@ -164,6 +167,7 @@ async fn test_aggregated_output_interleaves_in_order() {
         env: HashMap::new(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
@@ -202,6 +206,7 @@ async fn test_exec_timeout_returns_partial_output() {
         env: HashMap::new(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
@@ -227,3 +232,81 @@ async fn test_exec_timeout_returns_partial_output() {
     assert!(output.duration >= Duration::from_millis(200));
     assert!(output.timed_out);
 }
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_exec_cpu_time_limit_kills_busy_loop() {
+    let cmd = vec![
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        "while :; do :; done".to_string(),
+    ];
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let params = ExecParams {
+        command: cmd,
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: HashMap::new(),
+        with_escalated_permissions: None,
+        justification: None,
+        resource_limits: Some(ExecResourceLimits {
+            max_cpu_time_s: Some(1),
+            ..Default::default()
+        }),
+    };
+
+    let policy = SandboxPolicy::new_read_only_policy();
+
+    let start = std::time::Instant::now();
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::None,
+        &policy,
+        cwd.as_path(),
+        &None,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Sandbox(SandboxErr::Signal(signal))) = result else {
+        panic!("expected the command to be killed by a signal");
+    };
+    assert_eq!(signal, libc::SIGKILL);
+    assert!(start.elapsed() < Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn test_exec_rejects_zero_resource_limit() {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let params = ExecParams {
+        command: vec!["/bin/echo".to_string(), "unreachable".to_string()],
+        cwd: cwd.clone(),
+        timeout_ms: Some(5_000),
+        env: HashMap::new(),
+        with_escalated_permissions: None,
+        justification: None,
+        resource_limits: Some(ExecResourceLimits {
+            max_open_files: Some(0),
+            ..Default::default()
+        }),
+    };
+
+    let policy = SandboxPolicy::new_read_only_policy();
+
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::None,
+        &policy,
+        cwd.as_path(),
+        &None,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Io(err)) = result else {
+        panic!("expected invalid input error");
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("max_open_files"));
+}
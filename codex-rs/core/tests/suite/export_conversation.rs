@@ -0,0 +1,106 @@
+use codex_core::CodexAuth;
+use codex_core::ConversationManager;
+use codex_core::ModelProviderInfo;
+use codex_core::built_in_model_providers;
+use codex_core::protocol::ConversationExportedEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExportFormat;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::wait_for_event;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn export_writes_markdown_and_json() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    mount_sse_sequence(
+        &server,
+        vec![sse(vec![
+            ev_assistant_message("m1", "Here is the answer."),
+            ev_completed_with_tokens("r1", 42),
+        ])],
+    )
+    .await;
+
+    let model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home);
+    config.model_provider = model_provider;
+    let codex = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"))
+        .new_conversation(config)
+        .await
+        .unwrap()
+        .conversation;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "what is the answer?".into(),
+            }],
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let markdown_path = home.path().join("exports/conversation.md");
+    codex.export_markdown(&markdown_path).await.unwrap();
+    let markdown = std::fs::read_to_string(&markdown_path).unwrap();
+    assert!(markdown.contains("## Turn 1"), "markdown: {markdown}");
+    assert!(markdown.contains("what is the answer?"));
+    assert!(markdown.contains("Here is the answer."));
+    assert!(markdown.contains("<!-- tokens: input=42 output=0 -->"));
+
+    let json_path = home.path().join("exports/conversation.json");
+    codex
+        .submit(Op::ExportConversation {
+            path: json_path.clone(),
+            format: ExportFormat::Json,
+        })
+        .await
+        .unwrap();
+    let EventMsg::ConversationExported(ConversationExportedEvent { path }) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationExported(_))).await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(path, json_path);
+    let turns: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(turns[0]["turn"], 1);
+    assert_eq!(turns[0]["user"], "what is the answer?");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn export_markdown_reports_write_errors() {
+    let home = TempDir::new().unwrap();
+    let config = load_default_config_for_test(&home);
+    let codex = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"))
+        .new_conversation(config)
+        .await
+        .unwrap()
+        .conversation;
+
+    // An existing directory cannot be overwritten with the export.
+    let target = home.path().join("exports");
+    std::fs::create_dir(&target).unwrap();
+    let err = codex
+        .export_markdown(&target)
+        .await
+        .expect_err("exporting over a directory should fail");
+    assert!(
+        format!("{err:#}").contains("failed to write"),
+        "error: {err:#}"
+    );
+}
@@ -85,7 +85,9 @@ async fn fork_conversation_twice_drops_to_first_message() {
     let base_history =
         wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationPath(_))).await;
     let base_path = match &base_history {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path.clone(),
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.clone().expect("rollout path")
+        }
         _ => panic!("expected ConversationHistory event"),
     };
 
@@ -155,7 +157,9 @@ async fn fork_conversation_twice_drops_to_first_message() {
     })
     .await;
     let fork1_path = match &fork1_history {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path.clone(),
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.clone().expect("rollout path")
+        }
         _ => panic!("expected ConversationHistory event after first fork"),
     };
 
@@ -181,7 +185,9 @@ async fn fork_conversation_twice_drops_to_first_message() {
     })
     .await;
     let fork2_path = match &fork2_history {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path.clone(),
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.clone().expect("rollout path")
+        }
         _ => panic!("expected ConversationHistory event after second fork"),
     };
     // GetHistory on fork2 flushed; the file is ready.
@@ -198,3 +204,106 @@ async fn fork_conversation_twice_drops_to_first_message() {
         serde_json::to_value(&expected_after_second).unwrap()
     );
 }
+
+/// Count the user inputs recorded in the rollout at `path`.
+fn count_user_turns(path: &std::path::Path) -> usize {
+    let text = std::fs::read_to_string(path).expect("read rollout file");
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str::<RolloutLine>(line).expect("rollout line"))
+        .filter(|line| match &line.item {
+            RolloutItem::ResponseItem(ResponseItem::Message { role, content, .. }) => {
+                role == "user"
+                    && content_items_to_text(content)
+                        .is_some_and(|text| !is_session_prefix_message(&text))
+            }
+            _ => false,
+        })
+        .count()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fork_conversation_from_turn_copies_earlier_turns_only() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let completed = ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(sse_completed("resp"), "text/event-stream");
+    // Four turns on the source plus one on the fork.
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(completed)
+        .expect(5)
+        .mount(&server)
+        .await;
+
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home);
+    config.model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+
+    let conversation_manager = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"));
+    let NewConversation {
+        conversation_id,
+        conversation: codex,
+        ..
+    } = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation");
+
+    let send = |codex: &std::sync::Arc<codex_core::CodexConversation>, text: &str| {
+        let codex = codex.clone();
+        let text = text.to_string();
+        async move {
+            codex
+                .submit(Op::UserInput {
+                    items: vec![InputItem::Text { text }],
+                })
+                .await
+                .unwrap();
+            wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+        }
+    };
+    let rollout_path = |codex: &std::sync::Arc<codex_core::CodexConversation>| {
+        let codex = codex.clone();
+        async move {
+            codex.submit(Op::GetPath).await.unwrap();
+            match wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationPath(_))).await {
+                EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+                    path.expect("rollout path")
+                }
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    for text in ["one", "two", "three", "four"] {
+        send(&codex, text).await;
+    }
+
+    let NewConversation {
+        conversation_id: fork_id,
+        conversation: fork,
+        session_configured,
+    } = conversation_manager
+        .fork_conversation_from_turn(conversation_id, 2)
+        .await
+        .expect("fork conversation");
+    assert_ne!(fork_id, conversation_id);
+    assert_eq!(session_configured.session_id, fork_id);
+
+    let fork_path = rollout_path(&fork).await;
+    assert_eq!(count_user_turns(&fork_path), 2);
+
+    // A new turn on the fork leaves the source untouched.
+    send(&fork, "five").await;
+    let fork_path = rollout_path(&fork).await;
+    assert_eq!(count_user_turns(&fork_path), 3);
+    let source_path = rollout_path(&codex).await;
+    assert_ne!(source_path, fork_path);
+    assert_eq!(count_user_turns(&source_path), 4);
+}
@@ -0,0 +1,43 @@
+use codex_core::CodexAuth;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::protocol::ConversationPathResponseEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use core_test_support::load_default_config_for_test;
+use core_test_support::wait_for_event;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_path_returns_rollout_file() {
+    let home = TempDir::new().unwrap();
+    let config = load_default_config_for_test(&home);
+    let conversation_manager = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"));
+    let NewConversation {
+        conversation_id,
+        conversation: codex,
+        ..
+    } = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation");
+
+    codex.submit(Op::GetPath).await.unwrap();
+    let event = wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationPath(_))).await;
+    let EventMsg::ConversationPath(ConversationPathResponseEvent {
+        conversation_id: id,
+        path,
+    }) = event
+    else {
+        panic!("expected ConversationPath event, got {event:?}");
+    };
+    assert_eq!(id, conversation_id);
+    let path = path.expect("rollout path");
+    assert!(
+        path.extension().is_some_and(|ext| ext == "jsonl"),
+        "unexpected rollout path: {}",
+        path.display()
+    );
+    assert!(path.starts_with(home.path()), "{}", path.display());
+    assert!(path.exists(), "{} was not written", path.display());
+}
@@ -0,0 +1,57 @@
+use codex_core::CodexAuth;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::Op;
+use core_test_support::load_default_config_for_test;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn list_custom_prompts_scans_codex_home_prompts_dir() {
+    let home = TempDir::new().unwrap();
+    let prompts_dir = home.path().join("prompts");
+    std::fs::create_dir_all(&prompts_dir).unwrap();
+    std::fs::write(prompts_dir.join("review.md"), "Review the staged diff\n").unwrap();
+    std::fs::write(
+        prompts_dir.join("explain.md"),
+        "---\ndescription: Explain a file\n---\nExplain $1\n",
+    )
+    .unwrap();
+    std::fs::write(prompts_dir.join("notes.json"), "{}").unwrap();
+
+    let config = load_default_config_for_test(&home);
+    let conversation_manager = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"));
+    let NewConversation {
+        conversation: codex,
+        ..
+    } = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation");
+
+    codex.submit(Op::ListCustomPrompts).await.unwrap();
+    let event = wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::ListCustomPromptsResponse(_))
+    })
+    .await;
+    let EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent { custom_prompts }) =
+        event
+    else {
+        panic!("expected ListCustomPromptsResponse event, got {event:?}");
+    };
+
+    let listed: Vec<(String, Option<String>)> = custom_prompts
+        .into_iter()
+        .map(|p| (p.name, p.description))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("explain".to_string(), Some("Explain a file".to_string())),
+            ("review".to_string(), None),
+        ]
+    );
+}
@@ -7,10 +7,14 @@ mod client;
 mod compact;
 mod compact_resume_fork;
 mod exec;
+mod exec_event_order;
 mod exec_stream_events;
+mod export_conversation;
 mod fork_conversation;
+mod get_path;
 mod grep_files;
 mod json_result;
+mod list_custom_prompts;
 mod list_dir;
 mod live_cli;
 mod model_overrides;
@@ -22,12 +26,14 @@ mod review;
 mod rmcp_client;
 mod rollout_list_find;
 mod seatbelt;
+mod session_diff;
 mod shell_serialization;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
 mod tool_harness;
 mod tool_parallelism;
 mod tools;
+mod turn_diff;
 mod unified_exec;
 mod user_notification;
 mod view_image;
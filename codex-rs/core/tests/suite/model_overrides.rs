@@ -1,9 +1,18 @@
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
 use codex_core::protocol_config_types::ReasoningEffort;
 use core_test_support::load_default_config_for_test;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
 use pretty_assertions::assert_eq;
 use tempfile::TempDir;
@@ -38,6 +47,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
             summary: None,
+            tools: None,
         })
         .await
         .expect("submit override");
@@ -78,6 +88,7 @@ async fn override_turn_context_does_not_create_config_file() {
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::Medium)),
             summary: None,
+            tools: None,
         })
         .await
         .expect("submit override");
@@ -90,3 +101,46 @@ async fn override_turn_context_does_not_create_config_file() {
         "override should not create config.toml"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn set_model_applies_to_the_next_request() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex().build(&server).await?;
+    assert_ne!(session_configured.model, "gpt-4o-mini");
+
+    codex.set_model("gpt-4o-mini").await?;
+    let ack = wait_for_event(&codex, |ev| matches!(ev, EventMsg::SessionConfigured(_))).await;
+    let EventMsg::SessionConfigured(ack) = ack else {
+        panic!("expected SessionConfigured, got {ack:?}");
+    };
+    assert_eq!(ack.model, "gpt-4o-mini");
+    assert_eq!(ack.session_id, session_configured.session_id);
+
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let body = response.single_request().body_json();
+    assert_eq!(body["model"], "gpt-4o-mini");
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use codex_core::config::OPENAI_DEFAULT_MODEL;
 use codex_core::features::Feature;
 use codex_core::model_family::find_family_for_model;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
@@ -437,12 +438,16 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() {
             sandbox_policy: Some(SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![writable.path().to_path_buf()],
                 network_access: true,
+                allow_local_network: false,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
             }),
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
             summary: Some(ReasoningSummary::Detailed),
+            tools: None,
         })
         .await
         .unwrap();
@@ -570,8 +575,11 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() {
             sandbox_policy: SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![writable.path().to_path_buf()],
                 network_access: true,
+                allow_local_network: false,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                include_submodule_roots: true,
+                env_filter: EnvFilter::default(),
             },
             model: "o3".to_string(),
             effort: Some(ReasoningEffort::High),
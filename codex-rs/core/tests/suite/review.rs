@@ -124,7 +124,9 @@ async fn review_op_emits_lifecycle_and_review_output() {
     let history_event =
         wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationPath(_))).await;
     let path = match history_event {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path,
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.expect("rollout path")
+        }
         other => panic!("expected ConversationPath event, got {other:?}"),
     };
     let text = std::fs::read_to_string(&path).expect("read rollout file");
@@ -486,7 +488,9 @@ async fn review_input_isolated_from_parent_history() {
     let history_event =
         wait_for_event(&codex, |ev| matches!(ev, EventMsg::ConversationPath(_))).await;
     let path = match history_event {
-        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => path,
+        EventMsg::ConversationPath(ConversationPathResponseEvent { path, .. }) => {
+            path.expect("rollout path")
+        }
         other => panic!("expected ConversationPath event, got {other:?}"),
     };
     let text = std::fs::read_to_string(&path).expect("read rollout file");
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::CODEX_SANDBOX_ENV_VAR;
@@ -78,8 +79,11 @@ async fn if_parent_of_repo_is_writable_then_dot_git_folder_is_writable() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![test_scenario.repo_parent.clone()],
         network_access: false,
+        allow_local_network: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
 
     test_scenario
@@ -104,8 +108,11 @@ async fn if_git_repo_is_writable_root_then_dot_git_folder_is_read_only() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![test_scenario.repo_root.clone()],
         network_access: false,
+        allow_local_network: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
 
     test_scenario
@@ -192,6 +199,7 @@ async fn python_getpwuid_works_under_seatbelt() {
         sandbox_cwd.as_path(),
         StdioPolicy::RedirectForShellTool,
         HashMap::new(),
+        None,
     )
     .await
     .expect("should be able to spawn python under seatbelt");
@@ -237,6 +245,7 @@ async fn touch(path: &Path, policy: &SandboxPolicy) -> bool {
         sandbox_cwd.as_path(),
         StdioPolicy::RedirectForShellTool,
         HashMap::new(),
+        None,
     )
     .await
     .expect("should be able to spawn command under seatbelt");
@@ -0,0 +1,119 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::fs;
+
+use codex_core::features::Feature;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionDiffResponseEvent;
+use codex_protocol::config_types::ReasoningSummary;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+fn update_patch(file_name: &str, old: &str, new: &str) -> String {
+    format!(
+        r#"*** Begin Patch
+*** Update File: {file_name}
+@@
+-{old}
++{new}
+*** End Patch"#
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_diff_spans_every_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::ApplyPatchFreeform);
+    });
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = builder.build(&server).await?;
+
+    let file_name = "notes.txt";
+    let file_path = cwd.path().join(file_name);
+    fs::write(&file_path, "one\n")?;
+
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_function_call("patch-1", &update_patch(file_name, "one", "two")),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "first patch done"),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_apply_patch_function_call("patch-2", &update_patch(file_name, "two", "three")),
+                ev_completed("resp-3"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-2", "second patch done"),
+                ev_completed("resp-4"),
+            ]),
+        ],
+    )
+    .await;
+
+    for text in ["first turn", "second turn"] {
+        codex
+            .submit(Op::UserTurn {
+                items: vec![InputItem::Text { text: text.into() }],
+                final_output_json_schema: None,
+                cwd: cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: session_configured.model.clone(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    }
+    assert_eq!(fs::read_to_string(&file_path)?, "three\n");
+
+    codex.submit(Op::GetSessionDiff).await?;
+    let event = wait_for_event(&codex, |ev| matches!(ev, EventMsg::SessionDiffResponse(_))).await;
+    let EventMsg::SessionDiffResponse(SessionDiffResponseEvent { unified_diff }) = event else {
+        panic!("expected SessionDiffResponse event, got {event:?}");
+    };
+
+    // The combined diff goes straight from the session's starting contents
+    // to the final ones; the intermediate "two" never appears.
+    let changed_lines: Vec<&str> = unified_diff
+        .lines()
+        .filter(|line| {
+            (line.starts_with('-') || line.starts_with('+'))
+                && !line.starts_with("---")
+                && !line.starts_with("+++")
+        })
+        .collect();
+    assert_eq!(changed_lines, vec!["-one", "+three"]);
+    assert!(unified_diff.contains(file_name), "{unified_diff}");
+
+    Ok(())
+}
@@ -184,6 +184,21 @@ async fn update_plan_tool_emits_plan_update_event() -> anyhow::Result<()> {
     let output_text = extract_output_text(&output_item).expect("output text present");
     assert_eq!(output_text, "Plan updated");
 
+    codex.submit(Op::GetPlan).await?;
+    let event = wait_for_event(&codex, |event| matches!(event, EventMsg::PlanUpdate(_))).await;
+    let EventMsg::PlanUpdate(current) = event else {
+        unreachable!("wait_for_event returned non-PlanUpdate event");
+    };
+    assert_eq!(current.explanation.as_deref(), Some("Tool harness check"));
+    assert_eq!(
+        current
+            .plan
+            .iter()
+            .map(|item| item.step.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Inspect workspace", "Report results"]
+    );
+
     Ok(())
 }
 
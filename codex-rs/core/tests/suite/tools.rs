@@ -9,6 +9,7 @@ use codex_core::protocol::EventMsg;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::ToolOverrides;
 use codex_protocol::config_types::ReasoningSummary;
 use core_test_support::assert_regex_match;
 use core_test_support::responses::ev_assistant_message;
@@ -283,6 +284,72 @@ async fn local_shell_missing_ids_maps_to_function_output_error() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn override_turn_context_disables_tool() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex();
+    let test = builder.build(&server).await?;
+
+    test.codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: None,
+            effort: None,
+            summary: None,
+            tools: Some(ToolOverrides {
+                enable: Vec::new(),
+                disable: vec!["shell".to_string()],
+            }),
+        })
+        .await?;
+
+    let call_id = "shell-disabled";
+    let args = json!({ "command": ["/bin/echo", "hi"], "timeout_ms": 1_000 });
+    let first_mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, "shell", &serde_json::to_string(&args)?),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let second_mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    submit_turn(
+        &test,
+        "run a command",
+        AskForApproval::Never,
+        SandboxPolicy::DangerFullAccess,
+    )
+    .await?;
+
+    let advertised = tool_names(&first_mock.single_request().body_json());
+    assert!(
+        !advertised.iter().any(|name| name == "shell"),
+        "disabled tool should not be advertised: {advertised:?}"
+    );
+
+    let output = second_mock.single_request().function_call_output(call_id);
+    assert_eq!(
+        output.get("output").and_then(Value::as_str),
+        Some("tool shell is disabled for this turn by the client"),
+    );
+
+    Ok(())
+}
+
 async fn collect_tools(use_unified_exec: bool) -> Result<Vec<String>> {
     let server = start_mock_server().await;
 
@@ -0,0 +1,114 @@
+#![cfg(not(target_os = "windows"))]
+
+use codex_core::features::Feature;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+fn add_file_patch(file_name: &str, line: &str) -> String {
+    format!(
+        r#"*** Begin Patch
+*** Add File: {file_name}
++{line}
+*** End Patch"#
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_diff_summarizes_every_patch_in_the_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::ApplyPatchFreeform);
+    });
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = builder.build(&server).await?;
+
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_function_call("patch-1", &add_file_patch("alpha.txt", "alpha")),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_apply_patch_function_call("patch-2", &add_file_patch("beta.txt", "beta")),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "both files added"),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![InputItem::Text {
+                text: "add two files".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    // The last TurnDiff before TaskComplete is the turn's summary.
+    let mut summary = None;
+    loop {
+        let event = wait_for_event(&codex, |ev| {
+            matches!(ev, EventMsg::TurnDiff(_) | EventMsg::TaskComplete(_))
+        })
+        .await;
+        match event {
+            EventMsg::TurnDiff(turn_diff) => summary = Some(turn_diff),
+            EventMsg::TaskComplete(_) => break,
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+    let summary = summary.expect("a TurnDiff event before TaskComplete");
+
+    assert_eq!(summary.files_modified.len(), 2, "{summary:?}");
+    for file_name in ["alpha.txt", "beta.txt"] {
+        assert!(
+            summary
+                .files_modified
+                .iter()
+                .any(|f| f.ends_with(file_name)),
+            "{file_name} missing from {:?}",
+            summary.files_modified
+        );
+    }
+    assert_eq!(summary.total_lines_added, 2);
+    assert_eq!(summary.total_lines_removed, 0);
+
+    Ok(())
+}
@@ -403,7 +403,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("{}", line.style(self.dimmed));
                 }
             }
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+            EventMsg::TurnDiff(TurnDiffEvent { unified_diff, .. }) => {
                 ts_msg!(
                     self,
                     "{}",
@@ -511,6 +511,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             },
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ConversationPath(_) => {}
+            EventMsg::SessionDiffResponse(_) => {}
+            EventMsg::ConversationExported(ev) => {
+                ts_msg!(self, "conversation exported to {}", ev.path.display());
+            }
             EventMsg::UserMessage(_) => {}
             EventMsg::EnteredReviewMode(_) => {}
             EventMsg::ExitedReviewMode(_) => {}
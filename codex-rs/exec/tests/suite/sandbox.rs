@@ -1,4 +1,5 @@
 #![cfg(unix)]
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::spawn::StdioPolicy;
 use std::collections::HashMap;
@@ -27,6 +28,7 @@ async fn spawn_command_under_sandbox(
         sandbox_cwd,
         stdio_policy,
         env,
+        None,
     )
     .await
 }
@@ -50,6 +52,7 @@ async fn spawn_command_under_sandbox(
         sandbox_cwd,
         stdio_policy,
         env,
+        None,
     )
     .await
 }
@@ -70,8 +73,11 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots,
         network_access: false,
+        allow_local_network: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
 
     let python_code = r#"import multiprocessing
@@ -130,8 +136,11 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![],
         network_access: false,
+        allow_local_network: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
@@ -19,30 +19,43 @@ pub struct ApplyGitResult {
     pub applied_paths: Vec<String>,
     pub skipped_paths: Vec<String>,
     pub conflicted_paths: Vec<String>,
+    /// How the three-way fallback resolved a patch that did not apply directly.
+    pub merge: MergeOutcome,
+    /// Paths that were merged but contain conflict markers. For a preflight
+    /// these are the paths that *would* get markers; nothing is written.
+    pub merged_with_conflicts: Vec<String>,
     pub stdout: String,
     pub stderr: String,
     pub cmd_for_log: String,
 }
 
+/// Result of the `git apply --3way` fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeOutcome {
+    /// The patch applied directly; no merge was attempted.
+    #[default]
+    NotNeeded,
+    /// The three-way merge resolved every hunk.
+    Clean,
+    /// The merge completed but left conflict markers behind.
+    Conflicts,
+    /// The three-way merge could not be performed either.
+    Failed,
+}
+
 pub fn apply_git_patch(req: &ApplyGitRequest) -> io::Result<ApplyGitResult> {
     let git_root = resolve_git_root(&req.cwd)?;
 
     // Write unified diff into a temporary file
+    // `tmpdir` stays alive until function end so the patch (and any scratch
+    // index) exists while git runs.
     let (tmpdir, patch_path) = write_temp_patch(&req.diff)?;
-    // Keep tmpdir alive until function end to ensure the file exists
-    let _guard = tmpdir;
 
     if req.revert && !req.preflight {
         // Stage WT paths first to avoid index mismatch on revert.
         stage_paths(&git_root, &req.diff)?;
     }
 
-    // Build git args
-    let mut args: Vec<String> = vec!["apply".into(), "--3way".into()];
-    if req.revert {
-        args.push("-R".into());
-    }
-
     // Optional: additional git config via env knob (defaults OFF)
     let mut cfg_parts: Vec<String> = Vec::new();
     if let Ok(cfg) = std::env::var("CODEX_APPLY_GIT_CFG") {
@@ -56,39 +69,79 @@ pub fn apply_git_patch(req: &ApplyGitRequest) -> io::Result<ApplyGitResult> {
         }
     }
 
-    args.push(patch_path.to_string_lossy().to_string());
-
-    // Optional preflight: dry-run only; do not modify working tree
-    if req.preflight {
-        let mut check_args = vec!["apply".to_string(), "--check".to_string()];
+    let apply_args = |extra: &[&str]| -> Vec<String> {
+        let mut args: Vec<String> = vec!["apply".into()];
+        args.extend(extra.iter().map(ToString::to_string));
         if req.revert {
-            check_args.push("-R".to_string());
+            args.push("-R".into());
         }
-        check_args.push(patch_path.to_string_lossy().to_string());
-        let rendered = render_command_for_log(&git_root, &cfg_parts, &check_args);
-        let (c_code, c_out, c_err) = run_git(&git_root, &cfg_parts, &check_args)?;
-        let (mut applied_paths, mut skipped_paths, mut conflicted_paths) =
-            parse_git_apply_output(&c_out, &c_err);
-        applied_paths.sort();
-        applied_paths.dedup();
-        skipped_paths.sort();
-        skipped_paths.dedup();
-        conflicted_paths.sort();
-        conflicted_paths.dedup();
-        return Ok(ApplyGitResult {
-            exit_code: c_code,
-            applied_paths,
-            skipped_paths,
-            conflicted_paths,
-            stdout: c_out,
-            stderr: c_err,
-            cmd_for_log: rendered,
-        });
-    }
-
-    let cmd_for_log = render_command_for_log(&git_root, &cfg_parts, &args);
-    let (code, stdout, stderr) = run_git(&git_root, &cfg_parts, &args)?;
+        args.push(patch_path.to_string_lossy().to_string());
+        args
+    };
+
+    // Straight application first (a dry run for preflight). Only fall back to
+    // a three-way merge when the context no longer matches.
+    let direct_args = if req.preflight {
+        apply_args(&["--check", "-v"])
+    } else {
+        apply_args(&["-v"])
+    };
+    let direct_cmd = render_command_for_log(&git_root, &cfg_parts, &direct_args);
+    let (code, stdout, stderr) = run_git(&git_root, &cfg_parts, &direct_args, None)?;
+    if code == 0 {
+        return Ok(build_result(
+            code,
+            stdout,
+            stderr,
+            direct_cmd,
+            MergeOutcome::NotNeeded,
+        ));
+    }
 
+    let (merge_code, merge_out, merge_err, merge_cmd) = if req.preflight {
+        // Merge into a scratch copy of the index seeded with the working tree
+        // versions of the touched paths, so no markers land on disk.
+        let index_path = tmpdir.path().join("index");
+        seed_scratch_index(&git_root, &req.diff, &index_path)?;
+        let args = apply_args(&["--3way", "--cached"]);
+        let cmd = render_command_for_log(&git_root, &cfg_parts, &args);
+        let (c, o, e) = run_git(&git_root, &cfg_parts, &args, Some(&index_path))?;
+        (c, o, e, cmd)
+    } else {
+        let args = apply_args(&["--3way"]);
+        let cmd = render_command_for_log(&git_root, &cfg_parts, &args);
+        let (c, o, e) = run_git(&git_root, &cfg_parts, &args, None)?;
+        (c, o, e, cmd)
+    };
+
+    let mut result = build_result(
+        merge_code,
+        merge_out,
+        merge_err,
+        format!("{direct_cmd} || {merge_cmd}"),
+        MergeOutcome::Failed,
+    );
+    result.merge = if merge_code == 0 {
+        MergeOutcome::Clean
+    } else if !result.conflicted_paths.is_empty() {
+        result.merged_with_conflicts = result.conflicted_paths.clone();
+        MergeOutcome::Conflicts
+    } else {
+        MergeOutcome::Failed
+    };
+    // Keep the direct attempt's output around for diagnostics.
+    result.stdout = format!("{stdout}{}", result.stdout);
+    result.stderr = format!("{stderr}{}", result.stderr);
+    Ok(result)
+}
+
+fn build_result(
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+    cmd_for_log: String,
+    merge: MergeOutcome,
+) -> ApplyGitResult {
     let (mut applied_paths, mut skipped_paths, mut conflicted_paths) =
         parse_git_apply_output(&stdout, &stderr);
     applied_paths.sort();
@@ -97,16 +150,52 @@ pub fn apply_git_patch(req: &ApplyGitRequest) -> io::Result<ApplyGitResult> {
     skipped_paths.dedup();
     conflicted_paths.sort();
     conflicted_paths.dedup();
-
-    Ok(ApplyGitResult {
-        exit_code: code,
+    ApplyGitResult {
+        exit_code,
         applied_paths,
         skipped_paths,
         conflicted_paths,
+        merge,
+        merged_with_conflicts: Vec::new(),
         stdout,
         stderr,
         cmd_for_log,
-    })
+    }
+}
+
+/// Copy the repository index to `index_path` and stage the working tree
+/// contents of every path the patch touches into the copy.
+fn seed_scratch_index(git_root: &Path, diff: &str, index_path: &Path) -> io::Result<()> {
+    let (code, out, _) = run_git(
+        git_root,
+        &[],
+        &[
+            "rev-parse".to_string(),
+            "--git-path".to_string(),
+            "index".to_string(),
+        ],
+        None,
+    )?;
+    if code == 0 {
+        let source = git_root.join(out.trim());
+        match std::fs::copy(&source, index_path) {
+            Ok(_) => {}
+            // A repository without commits may not have an index yet.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let mut args = vec!["add".to_string(), "--".to_string()];
+    args.extend(
+        extract_paths_from_patch(diff)
+            .into_iter()
+            .filter(|p| std::fs::symlink_metadata(git_root.join(p)).is_ok()),
+    );
+    if args.len() > 2 {
+        // Best-effort, like `stage_paths`.
+        let _ = run_git(git_root, &[], &args, Some(index_path))?;
+    }
+    Ok(())
 }
 
 fn resolve_git_root(cwd: &Path) -> io::Result<PathBuf> {
@@ -134,8 +223,16 @@ fn write_temp_patch(diff: &str) -> io::Result<(tempfile::TempDir, PathBuf)> {
     Ok((dir, path))
 }
 
-fn run_git(cwd: &Path, git_cfg: &[String], args: &[String]) -> io::Result<(i32, String, String)> {
+fn run_git(
+    cwd: &Path,
+    git_cfg: &[String],
+    args: &[String],
+    index_file: Option<&Path>,
+) -> io::Result<(i32, String, String)> {
     let mut cmd = std::process::Command::new("git");
+    if let Some(index_file) = index_file {
+        cmd.env("GIT_INDEX_FILE", index_file);
+    }
     for p in git_cfg {
         cmd.arg(p);
     }
@@ -654,6 +751,97 @@ mod tests {
         assert_eq!(after_preflight, "ORIG\n");
     }
 
+    /// Seed `file.txt` with `a`..`g`, commit `drifted` on top, and return a
+    /// patch against the seed that changes `d` to `D`.
+    fn drifted_repo(drifted: &str) -> (tempfile::TempDir, String) {
+        let repo = init_repo();
+        let root = repo.path();
+        std::fs::write(root.join("file.txt"), "a\nb\nc\nd\ne\nf\ng\n").unwrap();
+        let _ = run(root, &["git", "add", "file.txt"]);
+        let _ = run(root, &["git", "commit", "-m", "seed"]);
+        let (_, blob, _) = run(root, &["git", "rev-parse", "HEAD:file.txt"]);
+        std::fs::write(root.join("file.txt"), drifted).unwrap();
+        let _ = run(root, &["git", "commit", "-am", "drift"]);
+        let diff = format!(
+            "diff --git a/file.txt b/file.txt\nindex {}..1111111 100644\n--- a/file.txt\n+++ b/file.txt\n@@ -1,7 +1,7 @@\n a\n b\n c\n-d\n+D\n e\n f\n g\n",
+            &blob.trim()[..7]
+        );
+        (repo, diff)
+    }
+
+    #[test]
+    fn three_way_fallback_merges_drifted_context() {
+        let _g = env_lock().lock().unwrap();
+        let (repo, diff) = drifted_repo("A\nb\nc\nd\ne\nf\ng\n");
+        let root = repo.path();
+
+        let preflight = apply_git_patch(&ApplyGitRequest {
+            cwd: root.to_path_buf(),
+            diff: diff.clone(),
+            revert: false,
+            preflight: true,
+        })
+        .expect("preflight");
+        assert_eq!(preflight.exit_code, 0, "preflight: {}", preflight.stderr);
+        assert_eq!(preflight.merge, MergeOutcome::Clean);
+        let (_, status, _) = run(root, &["git", "status", "--porcelain"]);
+        assert_eq!(status.trim(), "", "preflight leaves the tree untouched");
+
+        let applied = apply_git_patch(&ApplyGitRequest {
+            cwd: root.to_path_buf(),
+            diff,
+            revert: false,
+            preflight: false,
+        })
+        .expect("apply");
+        assert_eq!(applied.exit_code, 0, "apply: {}", applied.stderr);
+        assert_eq!(applied.merge, MergeOutcome::Clean);
+        assert!(applied.merged_with_conflicts.is_empty());
+        assert_eq!(
+            read_file_normalized(&root.join("file.txt")),
+            "A\nb\nc\nD\ne\nf\ng\n"
+        );
+    }
+
+    #[test]
+    fn three_way_fallback_reports_conflict_markers() {
+        let _g = env_lock().lock().unwrap();
+        let (repo, diff) = drifted_repo("a\nb\nc\nlocal\ne\nf\ng\n");
+        let root = repo.path();
+
+        let preflight = apply_git_patch(&ApplyGitRequest {
+            cwd: root.to_path_buf(),
+            diff: diff.clone(),
+            revert: false,
+            preflight: true,
+        })
+        .expect("preflight");
+        assert_ne!(preflight.exit_code, 0);
+        assert_eq!(preflight.merge, MergeOutcome::Conflicts);
+        assert_eq!(
+            preflight.merged_with_conflicts,
+            vec!["file.txt".to_string()]
+        );
+        assert!(
+            !read_file_normalized(&root.join("file.txt")).contains("<<<<<<<"),
+            "preflight must not write conflict markers"
+        );
+        let (_, status, _) = run(root, &["git", "status", "--porcelain"]);
+        assert_eq!(status.trim(), "", "preflight leaves the index untouched");
+
+        let applied = apply_git_patch(&ApplyGitRequest {
+            cwd: root.to_path_buf(),
+            diff,
+            revert: false,
+            preflight: false,
+        })
+        .expect("apply");
+        assert_ne!(applied.exit_code, 0);
+        assert_eq!(applied.merge, MergeOutcome::Conflicts);
+        assert_eq!(applied.merged_with_conflicts, vec!["file.txt".to_string()]);
+        assert!(read_file_normalized(&root.join("file.txt")).contains("<<<<<<<"));
+    }
+
     #[test]
     fn preflight_blocks_partial_changes() {
         let _g = env_lock().lock().unwrap();
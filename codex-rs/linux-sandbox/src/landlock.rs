@@ -31,13 +31,24 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
 ) -> Result<()> {
+    // `allow_local_network` cannot be enforced here: seccomp cannot inspect
+    // the sockaddr behind `connect`/`bind`, Landlock network rules match
+    // ports rather than addresses, and a private network namespace would hide
+    // the host's loopback services the option exists to reach. Config loading
+    // already rejects it on Linux; policies that arrive some other way fail
+    // instead of silently running with a different network policy.
+    if sandbox_policy.has_local_network_access() && !sandbox_policy.has_full_network_access() {
+        return Err(CodexErr::UnsupportedOperation(
+            "allow_local_network is not supported by the Linux sandbox".to_string(),
+        ));
+    }
+
     if !sandbox_policy.has_full_network_access() {
         install_network_seccomp_filter_on_current_thread()?;
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
-        let writable_roots = sandbox_policy
-            .get_writable_roots_with_cwd(cwd)
+        let writable_roots = codex_core::git_info::sandbox_writable_roots(sandbox_policy, cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
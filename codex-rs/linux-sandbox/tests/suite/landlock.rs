@@ -3,9 +3,11 @@ use codex_core::config_types::ShellEnvironmentPolicy;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
 use codex_core::exec::ExecParams;
+use codex_core::exec::ExecToolCallOutput;
 use codex_core::exec::SandboxType;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::SandboxPolicy;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -33,8 +35,25 @@ fn create_env_from_core_vars() -> HashMap<String, String> {
     create_env(&policy)
 }
 
-#[expect(clippy::print_stdout, clippy::expect_used, clippy::unwrap_used)]
+#[expect(clippy::print_stdout, clippy::unwrap_used)]
 async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
+    let res = run_cmd_result(cmd, writable_roots, timeout_ms)
+        .await
+        .unwrap();
+
+    if res.exit_code != 0 {
+        println!("stdout:\n{}", res.stdout.text);
+        println!("stderr:\n{}", res.stderr.text);
+        panic!("exit code: {}", res.exit_code);
+    }
+}
+
+#[expect(clippy::expect_used)]
+async fn run_cmd_result(
+    cmd: &[&str],
+    writable_roots: &[PathBuf],
+    timeout_ms: u64,
+) -> Result<ExecToolCallOutput, CodexErr> {
     let cwd = std::env::current_dir().expect("cwd should exist");
     let sandbox_cwd = cwd.clone();
     let params = ExecParams {
@@ -44,20 +63,24 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         env: create_env_from_core_vars(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: writable_roots.to_vec(),
         network_access: false,
+        allow_local_network: false,
         // Exclude tmp-related folders from writable roots because we need a
         // folder that is writable by tests but that we intentionally disallow
         // writing to in the sandbox.
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
-    let res = process_exec_tool_call(
+    process_exec_tool_call(
         params,
         SandboxType::LinuxSeccomp,
         &sandbox_policy,
@@ -66,13 +89,47 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         None,
     )
     .await
-    .unwrap();
+}
 
-    if res.exit_code != 0 {
-        println!("stdout:\n{}", res.stdout.text);
-        println!("stderr:\n{}", res.stderr.text);
-        panic!("exit code: {}", res.exit_code);
-    }
+#[tokio::test]
+async fn deny_listed_env_vars_do_not_reach_the_child() {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let mut env = create_env_from_core_vars();
+    env.insert("SECRET".to_string(), "value".to_string());
+    let params = ExecParams {
+        command: vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            "echo \"[${SECRET-unset}]\"".to_string(),
+        ],
+        cwd: cwd.clone(),
+        timeout_ms: Some(LONG_TIMEOUT_MS),
+        env,
+        with_escalated_permissions: None,
+        justification: None,
+        resource_limits: None,
+    };
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        allow_local_network: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::DenyList(vec!["SECRET".to_string()]),
+    };
+    let codex_linux_sandbox_exe = Some(PathBuf::from(env!("CARGO_BIN_EXE_codex-linux-sandbox")));
+    let output = process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &sandbox_policy,
+        cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        None,
+    )
+    .await
+    .expect("command should run");
+    assert_eq!(output.stdout.text.trim(), "[unset]");
 }
 
 #[tokio::test]
@@ -93,6 +150,33 @@ async fn test_root_write() {
     .await;
 }
 
+#[tokio::test]
+async fn test_root_write_reports_denied_path() {
+    let tmpfile = NamedTempFile::new().unwrap();
+    let tmpfile_path = tmpfile.path().to_path_buf();
+    let result = run_cmd_result(
+        &[
+            "bash",
+            "-c",
+            &format!("echo blah > {}", tmpfile_path.to_string_lossy()),
+        ],
+        &[],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+
+    match result {
+        Err(CodexErr::Sandbox(SandboxErr::Denied {
+            path, operation, ..
+        })) => {
+            assert_eq!(path, Some(tmpfile_path));
+            // Landlock reports EACCES, which does not identify the operation.
+            assert_eq!(operation, None);
+        }
+        other => panic!("expected structured sandbox denial, got: {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn test_dev_null_write() {
     run_cmd(
@@ -146,6 +230,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         env: create_env_from_core_vars(),
         with_escalated_permissions: None,
         justification: None,
+        resource_limits: None,
     };
 
     let sandbox_policy = SandboxPolicy::new_read_only_policy();
@@ -163,7 +248,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
 
     let output = match result {
         Ok(output) => output,
-        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output, .. })) => *output,
         _ => {
             panic!("expected sandbox denied error, got: {result:?}");
         }
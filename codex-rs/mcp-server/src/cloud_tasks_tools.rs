@@ -0,0 +1,353 @@
+//! `cloud_tasks_*` tools: list, create, diff and apply Codex cloud tasks from
+//! an MCP client, signed in with the same ChatGPT login `codex cloud` uses.
+//!
+//! Only compiled with the `cloud-tasks` feature.
+
+use std::sync::Arc;
+
+use codex_cloud_tasks_client::ApplyOutcome;
+use codex_cloud_tasks_client::CloudBackend;
+use codex_cloud_tasks_client::ConnectError;
+use codex_cloud_tasks_client::CreateTaskOptions;
+use codex_cloud_tasks_client::CreatedTask;
+use codex_cloud_tasks_client::DiffSummary;
+use codex_cloud_tasks_client::TaskId;
+use codex_cloud_tasks_client::TaskSummary;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
+use mcp_types::Tool;
+use mcp_types::ToolInputSchema;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub(crate) const CLOUD_TASKS_LIST: &str = "cloud_tasks_list";
+pub(crate) const CLOUD_TASKS_CREATE: &str = "cloud_tasks_create";
+pub(crate) const CLOUD_TASKS_GET_DIFF: &str = "cloud_tasks_get_diff";
+pub(crate) const CLOUD_TASKS_APPLY: &str = "cloud_tasks_apply";
+
+const NOT_SIGNED_IN: &str =
+    "Not signed in. Run `codex login` to sign in with ChatGPT, then call the tool again.";
+
+/// Arguments for `cloud_tasks_list`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CloudTasksListParam {
+    /// Only list tasks in this environment. All environments when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment_id: Option<String>,
+}
+
+/// Arguments for `cloud_tasks_create`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CloudTasksCreateParam {
+    /// Environment the task runs in.
+    pub environment_id: String,
+
+    /// The prompt for the new task.
+    pub prompt: String,
+
+    /// Git branch the task starts from. Defaults to `main`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Number of attempts to run (best-of-N). Defaults to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u8>,
+}
+
+/// Arguments for `cloud_tasks_get_diff`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CloudTasksGetDiffParam {
+    /// The task whose diff to fetch.
+    pub task_id: String,
+}
+
+/// Arguments for `cloud_tasks_apply`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CloudTasksApplyParam {
+    /// The task whose diff to apply to the server's working directory.
+    pub task_id: String,
+
+    /// Only check whether the diff applies cleanly; leave the tree untouched.
+    #[serde(default)]
+    pub preflight: bool,
+}
+
+/// Structured result of `cloud_tasks_list`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CloudTasksListResult {
+    pub tasks: Vec<TaskSummary>,
+}
+
+/// Structured result of `cloud_tasks_get_diff`. `diff` is `None` when the
+/// task has not produced one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CloudTasksGetDiffResult {
+    pub task_id: TaskId,
+    pub diff: Option<String>,
+    pub summary: DiffSummary,
+}
+
+pub(crate) fn is_cloud_tasks_tool(name: &str) -> bool {
+    matches!(
+        name,
+        CLOUD_TASKS_LIST | CLOUD_TASKS_CREATE | CLOUD_TASKS_GET_DIFF | CLOUD_TASKS_APPLY
+    )
+}
+
+pub(crate) fn create_cloud_tasks_tools() -> Vec<Tool> {
+    vec![
+        tool_for::<CloudTasksListParam>(
+            CLOUD_TASKS_LIST,
+            "List Codex cloud tasks",
+            "List Codex cloud tasks, optionally limited to one environment.",
+        ),
+        tool_for::<CloudTasksCreateParam>(
+            CLOUD_TASKS_CREATE,
+            "Create a Codex cloud task",
+            "Start a new Codex cloud task in an environment and return its id.",
+        ),
+        tool_for::<CloudTasksGetDiffParam>(
+            CLOUD_TASKS_GET_DIFF,
+            "Get a Codex cloud task diff",
+            "Fetch the unified diff produced by a Codex cloud task.",
+        ),
+        tool_for::<CloudTasksApplyParam>(
+            CLOUD_TASKS_APPLY,
+            "Apply a Codex cloud task",
+            "Apply a Codex cloud task's diff to the server's working directory. Set `preflight` to only check that it applies.",
+        ),
+    ]
+}
+
+fn tool_for<T: JsonSchema>(name: &str, title: &str, description: &str) -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("cloud tasks tool schema should serialise to JSON");
+
+    let input_schema =
+        serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+            panic!("failed to create Tool from schema: {e}");
+        });
+
+    Tool {
+        name: name.to_string(),
+        title: Some(title.to_string()),
+        input_schema,
+        output_schema: None,
+        description: Some(description.to_string()),
+        annotations: None,
+    }
+}
+
+/// Run the `cloud_tasks_*` tool `name`. Failures, including a missing login,
+/// are reported as a tool error rather than a JSON-RPC error.
+pub(crate) async fn handle_cloud_tasks_tool_call(
+    name: &str,
+    arguments: Option<serde_json::Value>,
+) -> CallToolResult {
+    let result = match connect_backend().await {
+        Ok(backend) => run_tool(backend.as_ref(), name, arguments).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(value) => CallToolResult {
+            content: vec![text_content(value.to_string())],
+            is_error: None,
+            structured_content: Some(value),
+        },
+        Err(err) => CallToolResult {
+            content: vec![text_content(err)],
+            is_error: Some(true),
+            structured_content: None,
+        },
+    }
+}
+
+async fn run_tool(
+    backend: &dyn CloudBackend,
+    name: &str,
+    arguments: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    let value = match name {
+        CLOUD_TASKS_LIST => {
+            let params: CloudTasksListParam = parse_arguments(name, arguments)?;
+            let tasks = backend
+                .list_tasks(params.environment_id.as_deref())
+                .await
+                .map_err(|e| format!("Failed to list cloud tasks: {e}"))?;
+            serde_json::to_value(CloudTasksListResult { tasks })
+        }
+        CLOUD_TASKS_CREATE => {
+            let params: CloudTasksCreateParam = parse_arguments(name, arguments)?;
+            let mut options = CreateTaskOptions::default();
+            if let Some(branch) = params.branch {
+                options.branch = branch;
+            }
+            if let Some(attempts) = params.attempts {
+                options.attempts = attempts;
+            }
+            let created: CreatedTask = backend
+                .create_task(&params.environment_id, &params.prompt, options)
+                .await
+                .map_err(|e| format!("Failed to create cloud task: {e}"))?;
+            serde_json::to_value(created)
+        }
+        CLOUD_TASKS_GET_DIFF => {
+            let params: CloudTasksGetDiffParam = parse_arguments(name, arguments)?;
+            let task_id = TaskId(params.task_id);
+            let diff = backend
+                .get_task_diff(task_id.clone())
+                .await
+                .map_err(|e| format!("Failed to fetch diff for {}: {e}", task_id.0))?;
+            let summary = diff
+                .as_deref()
+                .map(DiffSummary::from_unified_diff)
+                .unwrap_or_default();
+            serde_json::to_value(CloudTasksGetDiffResult {
+                task_id,
+                diff,
+                summary,
+            })
+        }
+        CLOUD_TASKS_APPLY => {
+            let params: CloudTasksApplyParam = parse_arguments(name, arguments)?;
+            let task_id = TaskId(params.task_id);
+            let outcome: ApplyOutcome = if params.preflight {
+                backend.apply_task_preflight(task_id.clone(), None).await
+            } else {
+                backend.apply_task(task_id.clone(), None).await
+            }
+            .map_err(|e| format!("Failed to apply {}: {e}", task_id.0))?;
+            serde_json::to_value(outcome)
+        }
+        _ => return Err(format!("Unknown tool '{name}'")),
+    };
+    value.map_err(|e| format!("Failed to serialize {name} result: {e}"))
+}
+
+fn parse_arguments<T: DeserializeOwned>(
+    name: &str,
+    arguments: Option<serde_json::Value>,
+) -> Result<T, String> {
+    let value = arguments.unwrap_or_else(|| serde_json::json!({}));
+    serde_json::from_value(value).map_err(|e| format!("Failed to parse arguments for {name}: {e}"))
+}
+
+/// Same bootstrap as `codex cloud`; see
+/// [`codex_cloud_tasks_client::connect_backend`].
+async fn connect_backend() -> Result<Arc<dyn CloudBackend>, String> {
+    match codex_cloud_tasks_client::connect_backend().await {
+        Ok(connection) => Ok(connection.backend),
+        Err(ConnectError::NotSignedIn) => Err(NOT_SIGNED_IN.to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn text_content(text: String) -> ContentBlock {
+    ContentBlock::TextContent(TextContent {
+        r#type: "text".to_string(),
+        text,
+        annotations: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_cloud_tasks_client::MockClient;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn params_use_kebab_case_and_defaults() {
+        let create: CloudTasksCreateParam = serde_json::from_value(json!({
+            "environment-id": "env-A",
+            "prompt": "Fix the build",
+            "branch": "release",
+        }))
+        .expect("create params");
+        assert_eq!(
+            create,
+            CloudTasksCreateParam {
+                environment_id: "env-A".to_string(),
+                prompt: "Fix the build".to_string(),
+                branch: Some("release".to_string()),
+                attempts: None,
+            }
+        );
+
+        let apply: CloudTasksApplyParam =
+            serde_json::from_value(json!({ "task-id": "T-1000" })).expect("apply params");
+        assert!(!apply.preflight);
+
+        let list: CloudTasksListParam = parse_arguments(CLOUD_TASKS_LIST, None).expect("list");
+        assert_eq!(list, CloudTasksListParam::default());
+    }
+
+    #[test]
+    fn tools_require_their_mandatory_arguments() {
+        let tools = create_cloud_tasks_tools();
+        let required = |name: &str| {
+            tools
+                .iter()
+                .find(|t| t.name == name)
+                .and_then(|t| t.input_schema.required.clone())
+                .unwrap_or_default()
+        };
+        assert_eq!(required(CLOUD_TASKS_LIST), Vec::<String>::new());
+        assert_eq!(
+            required(CLOUD_TASKS_CREATE),
+            vec!["environment-id", "prompt"]
+        );
+        assert_eq!(required(CLOUD_TASKS_GET_DIFF), vec!["task-id"]);
+        assert_eq!(required(CLOUD_TASKS_APPLY), vec!["task-id"]);
+        assert!(tools.iter().all(|t| is_cloud_tasks_tool(&t.name)));
+    }
+
+    #[tokio::test]
+    async fn results_mirror_client_types() {
+        let backend = MockClient::new();
+        let value = run_tool(
+            &backend,
+            CLOUD_TASKS_LIST,
+            Some(json!({ "environment-id": "env-B" })),
+        )
+        .await
+        .expect("list");
+        let list: CloudTasksListResult = serde_json::from_value(value).expect("list result");
+        let ids: Vec<&str> = list.tasks.iter().map(|t| t.id.0.as_str()).collect();
+        assert_eq!(ids, vec!["T-3000", "T-3001"]);
+
+        let value = run_tool(
+            &backend,
+            CLOUD_TASKS_GET_DIFF,
+            Some(json!({ "task-id": "T-3000" })),
+        )
+        .await
+        .expect("diff");
+        let diff: CloudTasksGetDiffResult = serde_json::from_value(value).expect("diff result");
+        assert!(diff.diff.is_some());
+        assert_eq!(diff.summary.files_changed, 1);
+
+        let err = run_tool(&backend, CLOUD_TASKS_APPLY, Some(json!({})))
+            .await
+            .expect_err("missing task id");
+        assert!(err.starts_with("Failed to parse arguments for cloud_tasks_apply"));
+    }
+}
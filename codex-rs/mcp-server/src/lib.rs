@@ -20,6 +20,8 @@ use tracing::error;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+#[cfg(feature = "cloud-tasks")]
+mod cloud_tasks_tools;
 mod codex_tool_config;
 mod codex_tool_runner;
 mod error_code;
@@ -32,6 +34,18 @@ use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
 
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksApplyParam;
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksCreateParam;
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksGetDiffParam;
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksGetDiffResult;
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksListParam;
+#[cfg(feature = "cloud-tasks")]
+pub use crate::cloud_tasks_tools::CloudTasksListResult;
 pub use crate::codex_tool_config::CodexToolCallParam;
 pub use crate::codex_tool_config::CodexToolCallReplyParam;
 pub use crate::exec_approval::ExecApprovalElicitRequestParams;
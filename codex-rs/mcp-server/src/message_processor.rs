@@ -298,11 +298,15 @@ impl MessageProcessor {
         params: <mcp_types::ListToolsRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::trace!("tools/list -> {params:?}");
+        #[cfg_attr(not(feature = "cloud-tasks"), allow(unused_mut))]
+        let mut tools = vec![
+            create_tool_for_codex_tool_call_param(),
+            create_tool_for_codex_tool_call_reply_param(),
+        ];
+        #[cfg(feature = "cloud-tasks")]
+        tools.extend(crate::cloud_tasks_tools::create_cloud_tasks_tools());
         let result = ListToolsResult {
-            tools: vec![
-                create_tool_for_codex_tool_call_param(),
-                create_tool_for_codex_tool_call_reply_param(),
-            ],
+            tools,
             next_cursor: None,
         };
 
@@ -324,6 +328,18 @@ impl MessageProcessor {
                 self.handle_tool_call_codex_session_reply(id, arguments)
                     .await
             }
+            #[cfg(feature = "cloud-tasks")]
+            tool if crate::cloud_tasks_tools::is_cloud_tasks_tool(tool) => {
+                // Backend calls can be slow; keep serving other requests.
+                let tool = tool.to_string();
+                let outgoing = self.outgoing.clone();
+                tokio::spawn(async move {
+                    let result =
+                        crate::cloud_tasks_tools::handle_cloud_tasks_tool_call(&tool, arguments)
+                            .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -200,6 +200,23 @@ impl McpProcess {
         .await
     }
 
+    /// Calls the tool `name` with `arguments`. Returns the request id.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        let params = CallToolRequestParams {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        };
+        self.send_request(
+            mcp_types::CallToolRequest::METHOD,
+            Some(serde_json::to_value(params)?),
+        )
+        .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -0,0 +1,67 @@
+#![cfg(feature = "cloud-tasks")]
+
+use codex_mcp_server::CloudTasksListResult;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+use mcp_test_support::McpProcess;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+async fn call_tool(
+    env: &[(&str, Option<&str>)],
+    name: &str,
+    arguments: serde_json::Value,
+) -> anyhow::Result<CallToolResult> {
+    let codex_home = TempDir::new()?;
+    let mut mcp_process = McpProcess::new_with_env(codex_home.path(), env).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp_process.initialize()).await??;
+
+    let request_id = mcp_process.send_tool_call(name, arguments).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    Ok(serde_json::from_value(response.result)?)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cloud_tasks_list_returns_mock_tasks() -> anyhow::Result<()> {
+    let result = call_tool(
+        &[("CODEX_CLOUD_TASKS_MODE", Some("mock"))],
+        "cloud_tasks_list",
+        json!({ "environment-id": "env-A" }),
+    )
+    .await?;
+
+    assert_eq!(result.is_error, None);
+    let list: CloudTasksListResult =
+        serde_json::from_value(result.structured_content.expect("structured content"))?;
+    assert_eq!(list.tasks.len(), 1);
+    assert_eq!(list.tasks[0].id.0, "T-2000");
+    assert_eq!(list.tasks[0].environment_id.as_deref(), Some("env-A"));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cloud_tasks_without_login_ask_to_sign_in() -> anyhow::Result<()> {
+    let result = call_tool(
+        &[("CODEX_CLOUD_TASKS_MODE", None)],
+        "cloud_tasks_list",
+        json!({}),
+    )
+    .await?;
+
+    assert_eq!(result.is_error, Some(true));
+    let [ContentBlock::TextContent(text)] = result.content.as_slice() else {
+        panic!("expected one text block, got {:?}", result.content);
+    };
+    assert!(text.text.contains("codex login"), "{}", text.text);
+    Ok(())
+}
@@ -1 +1,2 @@
+mod cloud_tasks;
 mod codex_tool;
@@ -21,9 +21,14 @@ from typing import Any, Literal
 SCHEMA_VERSION = "2025-06-18"
 JSONRPC_VERSION = "2.0"
 
-STANDARD_DERIVE = "#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]\n"
+JSON_SCHEMA_DERIVE = '#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]\n'
+STANDARD_DERIVE = (
+    "#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]\n"
+    + JSON_SCHEMA_DERIVE
+)
 STANDARD_HASHABLE_DERIVE = (
     "#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Hash, Eq, TS)]\n"
+    + JSON_SCHEMA_DERIVE
 )
 
 # Will be populated with the schema's `definitions` map in `main()` so that
@@ -34,6 +34,7 @@ fn default_jsonrpc() -> String {
 
 /// Optional annotations for the client. The client can use annotations to inform how objects are used or displayed
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Annotations {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub audience: Option<Vec<Role>>,
@@ -49,6 +50,7 @@ pub struct Annotations {
 
 /// Audio provided to or from an LLM.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AudioContent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -60,6 +62,7 @@ pub struct AudioContent {
 
 /// Base interface for metadata with name (identifier) and title (display name) properties.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BaseMetadata {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -67,6 +70,7 @@ pub struct BaseMetadata {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BlobResourceContents {
     pub blob: String,
     #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
@@ -75,6 +79,7 @@ pub struct BlobResourceContents {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BooleanSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<bool>,
@@ -86,6 +91,7 @@ pub struct BooleanSchema {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum CallToolRequest {}
 
 impl ModelContextProtocolRequest for CallToolRequest {
@@ -95,6 +101,7 @@ impl ModelContextProtocolRequest for CallToolRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CallToolRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arguments: Option<serde_json::Value>,
@@ -103,6 +110,7 @@ pub struct CallToolRequestParams {
 
 /// The server's response to a tool call.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CallToolResult {
     pub content: Vec<ContentBlock>,
     #[serde(rename = "isError", default, skip_serializing_if = "Option::is_none")]
@@ -124,6 +132,7 @@ impl From<CallToolResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum CancelledNotification {}
 
 impl ModelContextProtocolNotification for CancelledNotification {
@@ -132,6 +141,7 @@ impl ModelContextProtocolNotification for CancelledNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CancelledNotificationParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
@@ -141,6 +151,7 @@ pub struct CancelledNotificationParams {
 
 /// Capabilities a client may support. Known capabilities are defined here, in this schema, but this is not a closed set: any client can define its own, additional capabilities.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ClientCapabilities {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub elicitation: Option<serde_json::Value>,
@@ -154,6 +165,7 @@ pub struct ClientCapabilities {
 
 /// Present if the client supports listing roots.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ClientCapabilitiesRoots {
     #[serde(
         rename = "listChanged",
@@ -164,6 +176,7 @@ pub struct ClientCapabilitiesRoots {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ClientNotification {
     CancelledNotification(CancelledNotification),
@@ -173,6 +186,7 @@ pub enum ClientNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "method", content = "params")]
 pub enum ClientRequest {
     #[serde(rename = "initialize")]
@@ -206,6 +220,7 @@ pub enum ClientRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ClientResult {
     Result(Result),
@@ -215,6 +230,7 @@ pub enum ClientResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum CompleteRequest {}
 
 impl ModelContextProtocolRequest for CompleteRequest {
@@ -224,6 +240,7 @@ impl ModelContextProtocolRequest for CompleteRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompleteRequestParams {
     pub argument: CompleteRequestParamsArgument,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -233,6 +250,7 @@ pub struct CompleteRequestParams {
 
 /// Additional, optional context for completions
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompleteRequestParamsContext {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arguments: Option<serde_json::Value>,
@@ -240,12 +258,14 @@ pub struct CompleteRequestParamsContext {
 
 /// The argument's information
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompleteRequestParamsArgument {
     pub name: String,
     pub value: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum CompleteRequestParamsRef {
     PromptReference(PromptReference),
@@ -254,11 +274,13 @@ pub enum CompleteRequestParamsRef {
 
 /// The server's response to a completion/complete request
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompleteResult {
     pub completion: CompleteResultCompletion,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompleteResultCompletion {
     #[serde(rename = "hasMore", default, skip_serializing_if = "Option::is_none")]
     pub has_more: Option<bool>,
@@ -276,6 +298,7 @@ impl From<CompleteResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ContentBlock {
     TextContent(TextContent),
@@ -286,6 +309,7 @@ pub enum ContentBlock {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum CreateMessageRequest {}
 
 impl ModelContextProtocolRequest for CreateMessageRequest {
@@ -295,6 +319,7 @@ impl ModelContextProtocolRequest for CreateMessageRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateMessageRequestParams {
     #[serde(
         rename = "includeContext",
@@ -331,6 +356,7 @@ pub struct CreateMessageRequestParams {
 
 /// The client's response to a sampling/create_message request from the server. The client should inform the user before returning the sampled message, to allow them to inspect the response (human in the loop) and decide whether to allow the server to see it.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateMessageResult {
     pub content: CreateMessageResultContent,
     pub model: String,
@@ -344,6 +370,7 @@ pub struct CreateMessageResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum CreateMessageResultContent {
     TextContent(TextContent),
@@ -360,9 +387,11 @@ impl From<CreateMessageResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Cursor(String);
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ElicitRequest {}
 
 impl ModelContextProtocolRequest for ElicitRequest {
@@ -372,6 +401,7 @@ impl ModelContextProtocolRequest for ElicitRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ElicitRequestParams {
     pub message: String,
     #[serde(rename = "requestedSchema")]
@@ -381,6 +411,7 @@ pub struct ElicitRequestParams {
 /// A restricted subset of JSON Schema.
 /// Only top-level properties are allowed, without nesting.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ElicitRequestParamsRequestedSchema {
     pub properties: serde_json::Value,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -390,6 +421,7 @@ pub struct ElicitRequestParamsRequestedSchema {
 
 /// The client's response to an elicitation request.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ElicitResult {
     pub action: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -409,6 +441,7 @@ impl From<ElicitResult> for serde_json::Value {
 /// It is up to the client how best to render embedded resources for the benefit
 /// of the LLM and/or the user.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EmbeddedResource {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -417,6 +450,7 @@ pub struct EmbeddedResource {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum EmbeddedResourceResource {
     TextResourceContents(TextResourceContents),
@@ -426,6 +460,7 @@ pub enum EmbeddedResourceResource {
 pub type EmptyResult = Result;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EnumSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -438,6 +473,7 @@ pub struct EnumSchema {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum GetPromptRequest {}
 
 impl ModelContextProtocolRequest for GetPromptRequest {
@@ -447,6 +483,7 @@ impl ModelContextProtocolRequest for GetPromptRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct GetPromptRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arguments: Option<serde_json::Value>,
@@ -455,6 +492,7 @@ pub struct GetPromptRequestParams {
 
 /// The server's response to a prompts/get request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct GetPromptResult {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -471,6 +509,7 @@ impl From<GetPromptResult> for serde_json::Value {
 
 /// An image provided to or from an LLM.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ImageContent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -482,6 +521,7 @@ pub struct ImageContent {
 
 /// Describes the name and version of an MCP implementation, with an optional title for UI representation.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Implementation {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -493,6 +533,7 @@ pub struct Implementation {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum InitializeRequest {}
 
 impl ModelContextProtocolRequest for InitializeRequest {
@@ -502,6 +543,7 @@ impl ModelContextProtocolRequest for InitializeRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InitializeRequestParams {
     pub capabilities: ClientCapabilities,
     #[serde(rename = "clientInfo")]
@@ -512,6 +554,7 @@ pub struct InitializeRequestParams {
 
 /// After receiving an initialize request from the client, the server sends this response.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InitializeResult {
     pub capabilities: ServerCapabilities,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -531,6 +574,7 @@ impl From<InitializeResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum InitializedNotification {}
 
 impl ModelContextProtocolNotification for InitializedNotification {
@@ -540,6 +584,7 @@ impl ModelContextProtocolNotification for InitializedNotification {
 
 /// A response to a request that indicates an error occurred.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCError {
     pub error: JSONRPCErrorError,
     pub id: RequestId,
@@ -548,6 +593,7 @@ pub struct JSONRPCError {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCErrorError {
     pub code: i64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -557,6 +603,7 @@ pub struct JSONRPCErrorError {
 
 /// Refers to any valid JSON-RPC object that can be decoded off the wire, or encoded to be sent.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum JSONRPCMessage {
     Request(JSONRPCRequest),
@@ -567,6 +614,7 @@ pub enum JSONRPCMessage {
 
 /// A notification which does not expect a response.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCNotification {
     #[serde(rename = "jsonrpc", default = "default_jsonrpc")]
     pub jsonrpc: String,
@@ -577,6 +625,7 @@ pub struct JSONRPCNotification {
 
 /// A request that expects a response.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCRequest {
     pub id: RequestId,
     #[serde(rename = "jsonrpc", default = "default_jsonrpc")]
@@ -588,6 +637,7 @@ pub struct JSONRPCRequest {
 
 /// A successful (non-error) response to a request.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct JSONRPCResponse {
     pub id: RequestId,
     #[serde(rename = "jsonrpc", default = "default_jsonrpc")]
@@ -596,6 +646,7 @@ pub struct JSONRPCResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ListPromptsRequest {}
 
 impl ModelContextProtocolRequest for ListPromptsRequest {
@@ -605,6 +656,7 @@ impl ModelContextProtocolRequest for ListPromptsRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListPromptsRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
@@ -612,6 +664,7 @@ pub struct ListPromptsRequestParams {
 
 /// The server's response to a prompts/list request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListPromptsResult {
     #[serde(
         rename = "nextCursor",
@@ -631,6 +684,7 @@ impl From<ListPromptsResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ListResourceTemplatesRequest {}
 
 impl ModelContextProtocolRequest for ListResourceTemplatesRequest {
@@ -640,6 +694,7 @@ impl ModelContextProtocolRequest for ListResourceTemplatesRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListResourceTemplatesRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
@@ -647,6 +702,7 @@ pub struct ListResourceTemplatesRequestParams {
 
 /// The server's response to a resources/templates/list request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListResourceTemplatesResult {
     #[serde(
         rename = "nextCursor",
@@ -667,6 +723,7 @@ impl From<ListResourceTemplatesResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ListResourcesRequest {}
 
 impl ModelContextProtocolRequest for ListResourcesRequest {
@@ -676,6 +733,7 @@ impl ModelContextProtocolRequest for ListResourcesRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListResourcesRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
@@ -683,6 +741,7 @@ pub struct ListResourcesRequestParams {
 
 /// The server's response to a resources/list request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListResourcesResult {
     #[serde(
         rename = "nextCursor",
@@ -702,6 +761,7 @@ impl From<ListResourcesResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ListRootsRequest {}
 
 impl ModelContextProtocolRequest for ListRootsRequest {
@@ -714,6 +774,7 @@ impl ModelContextProtocolRequest for ListRootsRequest {
 /// This result contains an array of Root objects, each representing a root directory
 /// or file that the server can operate on.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListRootsResult {
     pub roots: Vec<Root>,
 }
@@ -727,6 +788,7 @@ impl From<ListRootsResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ListToolsRequest {}
 
 impl ModelContextProtocolRequest for ListToolsRequest {
@@ -736,6 +798,7 @@ impl ModelContextProtocolRequest for ListToolsRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListToolsRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
@@ -743,6 +806,7 @@ pub struct ListToolsRequestParams {
 
 /// The server's response to a tools/list request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListToolsResult {
     #[serde(
         rename = "nextCursor",
@@ -766,6 +830,7 @@ impl From<ListToolsResult> for serde_json::Value {
 /// These map to syslog message severities, as specified in RFC-5424:
 /// https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum LoggingLevel {
     #[serde(rename = "alert")]
     Alert,
@@ -786,6 +851,7 @@ pub enum LoggingLevel {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum LoggingMessageNotification {}
 
 impl ModelContextProtocolNotification for LoggingMessageNotification {
@@ -794,6 +860,7 @@ impl ModelContextProtocolNotification for LoggingMessageNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LoggingMessageNotificationParams {
     pub data: serde_json::Value,
     pub level: LoggingLevel,
@@ -806,6 +873,7 @@ pub struct LoggingMessageNotificationParams {
 /// Keys not declared here are currently left unspecified by the spec and are up
 /// to the client to interpret.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ModelHint {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -823,6 +891,7 @@ pub struct ModelHint {
 /// up to the client to decide how to interpret these preferences and how to
 /// balance them against other considerations.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ModelPreferences {
     #[serde(
         rename = "costPriority",
@@ -847,6 +916,7 @@ pub struct ModelPreferences {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Notification {
     pub method: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -854,6 +924,7 @@ pub struct Notification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NumberSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -867,6 +938,7 @@ pub struct NumberSchema {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PaginatedRequest {
     pub method: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -874,12 +946,14 @@ pub struct PaginatedRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PaginatedRequestParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PaginatedResult {
     #[serde(
         rename = "nextCursor",
@@ -898,6 +972,7 @@ impl From<PaginatedResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum PingRequest {}
 
 impl ModelContextProtocolRequest for PingRequest {
@@ -909,6 +984,7 @@ impl ModelContextProtocolRequest for PingRequest {
 /// Restricted schema definitions that only allow primitive types
 /// without nested objects or arrays.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum PrimitiveSchemaDefinition {
     StringSchema(StringSchema),
@@ -918,6 +994,7 @@ pub enum PrimitiveSchemaDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ProgressNotification {}
 
 impl ModelContextProtocolNotification for ProgressNotification {
@@ -926,6 +1003,7 @@ impl ModelContextProtocolNotification for ProgressNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ProgressNotificationParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
@@ -937,6 +1015,7 @@ pub struct ProgressNotificationParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Hash, Eq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ProgressToken {
     String(String),
@@ -945,6 +1024,7 @@ pub enum ProgressToken {
 
 /// A prompt or prompt template that the server offers.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Prompt {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Vec<PromptArgument>>,
@@ -957,6 +1037,7 @@ pub struct Prompt {
 
 /// Describes an argument that a prompt can accept.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PromptArgument {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -968,6 +1049,7 @@ pub struct PromptArgument {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum PromptListChangedNotification {}
 
 impl ModelContextProtocolNotification for PromptListChangedNotification {
@@ -980,6 +1062,7 @@ impl ModelContextProtocolNotification for PromptListChangedNotification {
 /// This is similar to `SamplingMessage`, but also supports the embedding of
 /// resources from the MCP server.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PromptMessage {
     pub content: ContentBlock,
     pub role: Role,
@@ -987,6 +1070,7 @@ pub struct PromptMessage {
 
 /// Identifies a prompt.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PromptReference {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -995,6 +1079,7 @@ pub struct PromptReference {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ReadResourceRequest {}
 
 impl ModelContextProtocolRequest for ReadResourceRequest {
@@ -1004,17 +1089,20 @@ impl ModelContextProtocolRequest for ReadResourceRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReadResourceRequestParams {
     pub uri: String,
 }
 
 /// The server's response to a resources/read request from the client.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReadResourceResult {
     pub contents: Vec<ReadResourceResultContents>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ReadResourceResultContents {
     TextResourceContents(TextResourceContents),
@@ -1030,6 +1118,7 @@ impl From<ReadResourceResult> for serde_json::Value {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Request {
     pub method: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1037,6 +1126,7 @@ pub struct Request {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Hash, Eq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum RequestId {
     String(String),
@@ -1045,6 +1135,7 @@ pub enum RequestId {
 
 /// A known resource that the server is capable of reading.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Resource {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -1062,6 +1153,7 @@ pub struct Resource {
 
 /// The contents of a specific resource or sub-resource.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResourceContents {
     #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
@@ -1072,6 +1164,7 @@ pub struct ResourceContents {
 ///
 /// Note: resource links returned by tools are not guaranteed to appear in the results of `resources/list` requests.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResourceLink {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -1089,6 +1182,7 @@ pub struct ResourceLink {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ResourceListChangedNotification {}
 
 impl ModelContextProtocolNotification for ResourceListChangedNotification {
@@ -1098,6 +1192,7 @@ impl ModelContextProtocolNotification for ResourceListChangedNotification {
 
 /// A template description for resources available on the server.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResourceTemplate {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -1114,12 +1209,14 @@ pub struct ResourceTemplate {
 
 /// A reference to a resource or resource template definition.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResourceTemplateReference {
     pub r#type: String, // &'static str = "ref/resource"
     pub uri: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ResourceUpdatedNotification {}
 
 impl ModelContextProtocolNotification for ResourceUpdatedNotification {
@@ -1128,6 +1225,7 @@ impl ModelContextProtocolNotification for ResourceUpdatedNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResourceUpdatedNotificationParams {
     pub uri: String,
 }
@@ -1136,6 +1234,7 @@ pub type Result = serde_json::Value;
 
 /// The sender or recipient of messages and data in a conversation.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum Role {
     #[serde(rename = "assistant")]
     Assistant,
@@ -1145,6 +1244,7 @@ pub enum Role {
 
 /// Represents a root directory or file that the server can operate on.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Root {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -1152,6 +1252,7 @@ pub struct Root {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum RootsListChangedNotification {}
 
 impl ModelContextProtocolNotification for RootsListChangedNotification {
@@ -1161,12 +1262,14 @@ impl ModelContextProtocolNotification for RootsListChangedNotification {
 
 /// Describes a message issued to or received from an LLM API.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SamplingMessage {
     pub content: SamplingMessageContent,
     pub role: Role,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum SamplingMessageContent {
     TextContent(TextContent),
@@ -1176,6 +1279,7 @@ pub enum SamplingMessageContent {
 
 /// Capabilities that a server may support. Known capabilities are defined here, in this schema, but this is not a closed set: any server can define its own, additional capabilities.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ServerCapabilities {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub completions: Option<serde_json::Value>,
@@ -1193,6 +1297,7 @@ pub struct ServerCapabilities {
 
 /// Present if the server offers any tools to call.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ServerCapabilitiesTools {
     #[serde(
         rename = "listChanged",
@@ -1204,6 +1309,7 @@ pub struct ServerCapabilitiesTools {
 
 /// Present if the server offers any resources to read.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ServerCapabilitiesResources {
     #[serde(
         rename = "listChanged",
@@ -1217,6 +1323,7 @@ pub struct ServerCapabilitiesResources {
 
 /// Present if the server offers any prompt templates.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ServerCapabilitiesPrompts {
     #[serde(
         rename = "listChanged",
@@ -1227,6 +1334,7 @@ pub struct ServerCapabilitiesPrompts {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "method", content = "params")]
 pub enum ServerNotification {
     #[serde(rename = "notifications/cancelled")]
@@ -1256,6 +1364,7 @@ pub enum ServerNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ServerRequest {
     PingRequest(PingRequest),
@@ -1265,6 +1374,7 @@ pub enum ServerRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
 pub enum ServerResult {
@@ -1281,6 +1391,7 @@ pub enum ServerResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum SetLevelRequest {}
 
 impl ModelContextProtocolRequest for SetLevelRequest {
@@ -1290,11 +1401,13 @@ impl ModelContextProtocolRequest for SetLevelRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SetLevelRequestParams {
     pub level: LoggingLevel,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StringSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -1310,6 +1423,7 @@ pub struct StringSchema {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum SubscribeRequest {}
 
 impl ModelContextProtocolRequest for SubscribeRequest {
@@ -1319,12 +1433,14 @@ impl ModelContextProtocolRequest for SubscribeRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SubscribeRequestParams {
     pub uri: String,
 }
 
 /// Text provided to or from an LLM.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TextContent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
@@ -1333,6 +1449,7 @@ pub struct TextContent {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TextResourceContents {
     #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
@@ -1342,6 +1459,7 @@ pub struct TextResourceContents {
 
 /// Definition for a tool the client can call.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Tool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<ToolAnnotations>,
@@ -1363,6 +1481,7 @@ pub struct Tool {
 /// An optional JSON Schema object defining the structure of the tool's output returned in
 /// the structuredContent field of a CallToolResult.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ToolOutputSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub properties: Option<serde_json::Value>,
@@ -1373,6 +1492,7 @@ pub struct ToolOutputSchema {
 
 /// A JSON Schema object defining the expected parameters for the tool.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ToolInputSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub properties: Option<serde_json::Value>,
@@ -1390,6 +1510,7 @@ pub struct ToolInputSchema {
 /// Clients should never make tool use decisions based on ToolAnnotations
 /// received from untrusted servers.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ToolAnnotations {
     #[serde(
         rename = "destructiveHint",
@@ -1420,6 +1541,7 @@ pub struct ToolAnnotations {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ToolListChangedNotification {}
 
 impl ModelContextProtocolNotification for ToolListChangedNotification {
@@ -1428,6 +1550,7 @@ impl ModelContextProtocolNotification for ToolListChangedNotification {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum UnsubscribeRequest {}
 
 impl ModelContextProtocolRequest for UnsubscribeRequest {
@@ -1437,6 +1560,7 @@ impl ModelContextProtocolRequest for UnsubscribeRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UnsubscribeRequestParams {
     pub uri: String,
 }
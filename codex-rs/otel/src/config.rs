@@ -8,6 +8,22 @@ pub struct OtelSettings {
     pub service_version: String,
     pub codex_home: PathBuf,
     pub exporter: OtelExporter,
+    /// Extra attributes merged into the exported `Resource`. A
+    /// `service.instance.id` entry here overrides the generated one.
+    pub resource_attributes: Vec<(String, String)>,
+    pub sampler: OtelSampler,
+}
+
+/// Decides whether this process exports telemetry at all. The decision is
+/// made once per process so a sampled session keeps all of its events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum OtelSampler {
+    #[default]
+    AlwaysOn,
+    AlwaysOff,
+    /// Export from this fraction (`0.0..=1.0`) of processes, keyed on the
+    /// process's `service.instance.id`.
+    Ratio(f64),
 }
 
 #[derive(Clone, Debug)]
@@ -24,10 +40,19 @@ pub enum OtelExporter {
     OtlpGrpc {
         endpoint: String,
         headers: HashMap<String, String>,
+        /// PEM bundle used to verify the collector's certificate.
+        ca_cert: Option<PathBuf>,
+        /// PEM client certificate presented for mTLS; requires `client_key`.
+        client_cert: Option<PathBuf>,
+        /// PEM private key for `client_cert`.
+        client_key: Option<PathBuf>,
     },
     OtlpHttp {
         endpoint: String,
         headers: HashMap<String, String>,
         protocol: OtelHttpProtocol,
+        /// Proxy URL for the collector. When unset, `HTTPS_PROXY`/`HTTP_PROXY`
+        /// from the environment apply; `NO_PROXY` is honored either way.
+        proxy: Option<String>,
     },
 }
@@ -4,22 +4,58 @@ pub mod otel_event_manager;
 #[cfg(feature = "otel")]
 pub mod otel_provider;
 
+/// Inert stand-in for `otel_provider::OtelProvider` so call sites compile
+/// unchanged without the `otel` feature; keep its signatures in sync.
 #[cfg(not(feature = "otel"))]
 mod imp {
     use reqwest::header::HeaderMap;
+    use std::error::Error;
     use tracing::Span;
 
     pub struct OtelProvider;
 
     impl OtelProvider {
-        pub fn from(_settings: &crate::config::OtelSettings) -> Option<Self> {
-            None
+        pub fn from(
+            _settings: &crate::config::OtelSettings,
+        ) -> Result<Option<Self>, Box<dyn Error>> {
+            Ok(None)
         }
 
+        pub fn shutdown(&self) {}
+
+        pub fn force_flush(&self) {}
+
         pub fn headers(_span: &Span) -> HeaderMap {
             HeaderMap::new()
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::config::OtelExporter;
+        use crate::config::OtelSettings;
+        use std::path::PathBuf;
+
+        #[test]
+        fn stub_provider_is_inert() {
+            let settings = OtelSettings {
+                environment: "test".to_string(),
+                service_name: "codex-test".to_string(),
+                service_version: "0.0.0".to_string(),
+                codex_home: PathBuf::from("."),
+                exporter: OtelExporter::None,
+                sampler: Default::default(),
+                resource_attributes: Vec::new(),
+            };
+            assert!(matches!(OtelProvider::from(&settings), Ok(None)));
+
+            let provider = OtelProvider;
+            provider.force_flush();
+            provider.shutdown();
+            assert!(OtelProvider::headers(&Span::none()).is_empty());
+        }
+    }
 }
 
 #[cfg(not(feature = "otel"))]
@@ -1,5 +1,6 @@
 use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
+use crate::config::OtelSampler;
 use crate::config::OtelSettings;
 use opentelemetry::KeyValue;
 use opentelemetry_otlp::LogExporter;
@@ -10,14 +11,24 @@ use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
 use opentelemetry_semantic_conventions as semconv;
+use reqwest::NoProxy;
+use reqwest::Proxy;
+use reqwest::Url;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use std::error::Error;
+use std::path::Path;
+use std::sync::OnceLock;
 use tonic::metadata::MetadataMap;
+use tonic::transport::Certificate;
+use tonic::transport::ClientTlsConfig;
+use tonic::transport::Identity;
 use tracing::debug;
+use uuid::Uuid;
 
 const ENV_ATTRIBUTE: &str = "env";
+const SERVICE_INSTANCE_ID_ATTRIBUTE: &str = "service.instance.id";
 
 pub struct OtelProvider {
     pub logger: SdkLoggerProvider,
@@ -28,17 +39,18 @@ impl OtelProvider {
         let _ = self.logger.shutdown();
     }
 
+    /// Export any buffered log records now rather than on the next batch.
+    pub fn force_flush(&self) {
+        let _ = self.logger.force_flush();
+    }
+
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
-        let resource = Resource::builder()
-            .with_service_name(settings.service_name.clone())
-            .with_attributes(vec![
-                KeyValue::new(
-                    semconv::attribute::SERVICE_VERSION,
-                    settings.service_version.clone(),
-                ),
-                KeyValue::new(ENV_ATTRIBUTE, settings.environment.clone()),
-            ])
-            .build();
+        if !should_sample(&settings.sampler)? {
+            debug!("OTLP export sampled out for this process.");
+            return Ok(None);
+        }
+
+        let resource = build_resource(settings);
 
         let mut builder = SdkLoggerProvider::builder().with_resource(resource);
 
@@ -47,9 +59,21 @@ impl OtelProvider {
                 debug!("No exporter enabled in OTLP settings.");
                 return Ok(None);
             }
-            OtelExporter::OtlpGrpc { endpoint, headers } => {
+            OtelExporter::OtlpGrpc {
+                endpoint,
+                headers,
+                ca_cert,
+                client_cert,
+                client_key,
+            } => {
                 debug!("Using OTLP Grpc exporter: {}", endpoint);
 
+                let tls_config = build_tls_config(
+                    ca_cert.as_deref(),
+                    client_cert.as_deref(),
+                    client_key.as_deref(),
+                )?;
+
                 let mut header_map = HeaderMap::new();
                 for (key, value) in headers {
                     if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
@@ -59,11 +83,14 @@ impl OtelProvider {
                     }
                 }
 
-                let exporter = LogExporter::builder()
+                let mut exporter = LogExporter::builder()
                     .with_tonic()
                     .with_endpoint(endpoint)
-                    .with_metadata(MetadataMap::from_headers(header_map))
-                    .build()?;
+                    .with_metadata(MetadataMap::from_headers(header_map));
+                if let Some(tls_config) = tls_config {
+                    exporter = exporter.with_tls_config(tls_config);
+                }
+                let exporter = exporter.build()?;
 
                 builder = builder.with_batch_exporter(exporter);
             }
@@ -71,6 +98,7 @@ impl OtelProvider {
                 endpoint,
                 headers,
                 protocol,
+                proxy,
             } => {
                 debug!("Using OTLP Http exporter: {}", endpoint);
 
@@ -79,8 +107,12 @@ impl OtelProvider {
                     OtelHttpProtocol::Json => Protocol::HttpJson,
                 };
 
+                let proxy = proxy.as_deref().map(parse_proxy).transpose()?;
+                let client = build_http_client(proxy)?;
+
                 let exporter = LogExporter::builder()
                     .with_http()
+                    .with_http_client(client)
                     .with_endpoint(endpoint)
                     .with_protocol(protocol)
                     .with_headers(headers.clone())
@@ -96,8 +128,306 @@ impl OtelProvider {
     }
 }
 
+fn build_resource(settings: &OtelSettings) -> Resource {
+    Resource::builder()
+        .with_service_name(settings.service_name.clone())
+        .with_attributes(resource_attributes(settings))
+        .build()
+}
+
+/// Resource attributes for `settings`: the service version, environment and a
+/// `service.instance.id` that is stable for the life of the process, followed
+/// by the user-configured attributes (which win on duplicate keys).
+fn resource_attributes(settings: &OtelSettings) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new(
+            semconv::attribute::SERVICE_VERSION,
+            settings.service_version.clone(),
+        ),
+        KeyValue::new(ENV_ATTRIBUTE, settings.environment.clone()),
+    ];
+    let has_instance_id = settings
+        .resource_attributes
+        .iter()
+        .any(|(key, _)| key == SERVICE_INSTANCE_ID_ATTRIBUTE);
+    if !has_instance_id {
+        attributes.push(KeyValue::new(
+            SERVICE_INSTANCE_ID_ATTRIBUTE,
+            process_instance_id().to_string(),
+        ));
+    }
+    attributes.extend(
+        settings
+            .resource_attributes
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+    );
+    attributes
+}
+
+fn process_instance_id() -> &'static Uuid {
+    static INSTANCE_ID: OnceLock<Uuid> = OnceLock::new();
+    INSTANCE_ID.get_or_init(Uuid::new_v4)
+}
+
+fn should_sample(sampler: &OtelSampler) -> Result<bool, Box<dyn Error>> {
+    match sampler {
+        OtelSampler::AlwaysOn => Ok(true),
+        OtelSampler::AlwaysOff => Ok(false),
+        OtelSampler::Ratio(ratio) => {
+            if !(0.0..=1.0).contains(ratio) {
+                return Err(
+                    format!("OTEL sampler ratio must be within 0.0..=1.0, got {ratio}").into(),
+                );
+            }
+            Ok(ratio_admits(*ratio, process_instance_id()))
+        }
+    }
+}
+
+/// Map the random bits of `id` onto `[0, 1)` and admit it when below `ratio`.
+fn ratio_admits(ratio: f64, id: &Uuid) -> bool {
+    let (high, _) = id.as_u64_pair();
+    (high >> 11) as f64 / ((1u64 << 53) as f64) < ratio
+}
+
+/// Parse an explicit proxy URL for the HTTP exporter. Hosts listed in
+/// `NO_PROXY` still bypass it.
+fn parse_proxy(proxy: &str) -> Result<Proxy, Box<dyn Error>> {
+    let url =
+        Url::parse(proxy).map_err(|e| format!("invalid OTLP HTTP proxy URL `{proxy}`: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "invalid OTLP HTTP proxy URL `{proxy}`: unsupported scheme `{}`",
+            url.scheme()
+        )
+        .into());
+    }
+    let proxy = Proxy::all(url)
+        .map_err(|e| format!("invalid OTLP HTTP proxy URL `{proxy}`: {e}"))?
+        .no_proxy(NoProxy::from_env());
+    Ok(proxy)
+}
+
+/// Blocking client for the HTTP exporter, which runs on the batch worker
+/// thread. Without an explicit proxy, reqwest picks up `HTTPS_PROXY`,
+/// `HTTP_PROXY` and `NO_PROXY` from the environment.
+fn build_http_client(proxy: Option<Proxy>) -> Result<reqwest::blocking::Client, Box<dyn Error>> {
+    // The blocking client owns a runtime, so it must not be built on a tokio
+    // worker thread.
+    let client = std::thread::spawn(move || {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy);
+        }
+        builder.build()
+    })
+    .join()
+    .map_err(|_| "failed to build the OTLP HTTP client")?
+    .map_err(|e| format!("failed to build the OTLP HTTP client: {e}"))?;
+    Ok(client)
+}
+
+/// TLS settings for the gRPC channel, or `None` when no certificate paths are
+/// configured. A client certificate and key must be provided together.
+fn build_tls_config(
+    ca_cert: Option<&Path>,
+    client_cert: Option<&Path>,
+    client_key: Option<&Path>,
+) -> Result<Option<ClientTlsConfig>, Box<dyn Error>> {
+    if ca_cert.is_none() && client_cert.is_none() && client_key.is_none() {
+        return Ok(None);
+    }
+
+    let read_pem = |kind: &str, path: &Path| {
+        std::fs::read(path)
+            .map_err(|e| format!("failed to read OTLP {kind} {}: {e}", path.display()))
+    };
+
+    let mut config = ClientTlsConfig::new();
+    if let Some(path) = ca_cert {
+        config = config.ca_certificate(Certificate::from_pem(read_pem("CA certificate", path)?));
+    }
+    match (client_cert, client_key) {
+        (Some(cert), Some(key)) => {
+            let cert = read_pem("client certificate", cert)?;
+            let key = read_pem("client key", key)?;
+            config = config.identity(Identity::from_pem(cert, key));
+        }
+        (Some(_), None) => {
+            return Err(
+                "OTLP gRPC exporter: `client_cert` is set but `client_key` is missing".into(),
+            );
+        }
+        (None, Some(_)) => {
+            return Err(
+                "OTLP gRPC exporter: `client_key` is set but `client_cert` is missing".into(),
+            );
+        }
+        (None, None) => {}
+    }
+    Ok(Some(config))
+}
+
 impl Drop for OtelProvider {
     fn drop(&mut self) {
         let _ = self.logger.shutdown();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn grpc_settings(
+        ca_cert: Option<&str>,
+        client_cert: Option<&str>,
+        client_key: Option<&str>,
+    ) -> OtelSettings {
+        OtelSettings {
+            environment: "test".to_string(),
+            service_name: "codex-test".to_string(),
+            service_version: "0.0.0".to_string(),
+            codex_home: PathBuf::from("/tmp"),
+            exporter: OtelExporter::OtlpGrpc {
+                endpoint: "https://localhost:4317".to_string(),
+                headers: HashMap::new(),
+                ca_cert: ca_cert.map(PathBuf::from),
+                client_cert: client_cert.map(PathBuf::from),
+                client_key: client_key.map(PathBuf::from),
+            },
+            resource_attributes: Vec::new(),
+            sampler: OtelSampler::AlwaysOn,
+        }
+    }
+
+    fn init_error(settings: &OtelSettings) -> String {
+        match OtelProvider::from(settings) {
+            Ok(_) => panic!("expected OtelProvider::from to fail"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn missing_certificate_files_are_reported() {
+        let err = init_error(&grpc_settings(
+            None,
+            Some("/nonexistent/client.pem"),
+            Some("/nonexistent/client.key"),
+        ));
+        assert!(
+            err.contains("client certificate /nonexistent/client.pem"),
+            "unexpected error: {err}"
+        );
+
+        let err = init_error(&grpc_settings(Some("/nonexistent/ca.pem"), None, None));
+        assert!(
+            err.contains("CA certificate /nonexistent/ca.pem"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn proxy_url_is_parsed_or_rejected() {
+        assert!(parse_proxy("http://proxy.corp.example:3128").is_ok());
+
+        let err = match parse_proxy("proxy.corp.example:3128") {
+            Ok(_) => panic!("expected a malformed proxy URL to be rejected"),
+            Err(err) => err.to_string(),
+        };
+        assert!(
+            err.contains("invalid OTLP HTTP proxy URL `proxy.corp.example:3128`"),
+            "unexpected error: {err}"
+        );
+
+        let settings = OtelSettings {
+            environment: "test".to_string(),
+            service_name: "codex-test".to_string(),
+            service_version: "0.0.0".to_string(),
+            codex_home: PathBuf::from("/tmp"),
+            exporter: OtelExporter::OtlpHttp {
+                endpoint: "https://localhost:4318/v1/logs".to_string(),
+                headers: HashMap::new(),
+                protocol: OtelHttpProtocol::Binary,
+                proxy: Some("not a url".to_string()),
+            },
+            resource_attributes: Vec::new(),
+            sampler: OtelSampler::AlwaysOn,
+        };
+        let err = init_error(&settings);
+        assert!(
+            err.contains("invalid OTLP HTTP proxy URL `not a url`"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn resource_merges_configured_attributes() {
+        let attribute = |resource: &Resource, key: &'static str| {
+            resource
+                .get(&opentelemetry::Key::from_static_str(key))
+                .map(|value| value.to_string())
+        };
+
+        let mut settings = grpc_settings(None, None, None);
+        settings.resource_attributes = vec![
+            ("deployment.region".to_string(), "eu-west-1".to_string()),
+            ("team".to_string(), "infra".to_string()),
+        ];
+        let resource = build_resource(&settings);
+        assert_eq!(
+            attribute(&resource, "deployment.region").as_deref(),
+            Some("eu-west-1")
+        );
+        assert_eq!(attribute(&resource, "team").as_deref(), Some("infra"));
+        assert_eq!(attribute(&resource, ENV_ATTRIBUTE).as_deref(), Some("test"));
+        assert_eq!(
+            attribute(&resource, SERVICE_INSTANCE_ID_ATTRIBUTE).as_deref(),
+            Some(process_instance_id().to_string().as_str())
+        );
+
+        settings.resource_attributes.push((
+            SERVICE_INSTANCE_ID_ATTRIBUTE.to_string(),
+            "pod-7".to_string(),
+        ));
+        let resource = build_resource(&settings);
+        assert_eq!(
+            attribute(&resource, SERVICE_INSTANCE_ID_ATTRIBUTE).as_deref(),
+            Some("pod-7")
+        );
+    }
+
+    #[test]
+    fn sampler_gates_export() {
+        let settings_with = |sampler| {
+            let mut settings = grpc_settings(None, None, None);
+            settings.sampler = sampler;
+            settings
+        };
+
+        for sampler in [OtelSampler::AlwaysOff, OtelSampler::Ratio(0.0)] {
+            let provider = OtelProvider::from(&settings_with(sampler.clone()))
+                .unwrap_or_else(|err| panic!("{sampler:?} failed: {err}"));
+            assert!(provider.is_none(), "{sampler:?} should export nothing");
+        }
+
+        let err = init_error(&settings_with(OtelSampler::Ratio(1.5)));
+        assert!(err.contains("within 0.0..=1.0"), "unexpected error: {err}");
+
+        let id = Uuid::from_u64_pair(u64::MAX / 4, 0);
+        assert!(ratio_admits(0.5, &id));
+        assert!(!ratio_admits(0.2, &id));
+        assert!(ratio_admits(1.0, &Uuid::from_u64_pair(u64::MAX, u64::MAX)));
+    }
+
+    #[test]
+    fn client_cert_requires_key() {
+        let err = init_error(&grpc_settings(None, Some("/nonexistent/client.pem"), None));
+        assert!(
+            err.contains("`client_key` is missing"),
+            "unexpected error: {err}"
+        );
+    }
+}
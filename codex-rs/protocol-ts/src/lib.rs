@@ -7,6 +7,9 @@ use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::export_client_responses;
 use codex_app_server_protocol::export_server_responses;
+use codex_protocol::protocol::EventMsgType;
+use codex_protocol::protocol::OpType;
+use regex_lite::Regex;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::Read;
@@ -14,11 +17,66 @@ use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
+use strum::IntoEnumIterator;
 use ts_rs::TS;
 
 const HEADER: &str = "// GENERATED CODE! DO NOT MODIFY BY HAND!\n\n";
 
-pub fn generate_ts(out_dir: &Path, prettier: Option<&Path>) -> Result<()> {
+/// JSON Schema covering every exported type, written when the `json-schema`
+/// feature is enabled.
+#[cfg(feature = "json-schema")]
+const JSON_SCHEMA_FILE: &str = "codex-protocol.schema.json";
+
+/// Companion file holding the runtime type guards for tagged unions.
+const GUARDS_FILE: &str = "index.guards.ts";
+
+/// Rust sources, relative to the `codex-rs` workspace root, whose `///` doc
+/// comments are copied onto the generated types and their fields.
+const DOC_SOURCE_DIRS: &[&str] = &["protocol/src", "app-server-protocol/src"];
+
+/// A serde-tagged union that gets a companion module listing its `type`
+/// tags, plus `is<Variant>` type guards when ts-rs exports the union itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedUnion {
+    pub name: &'static str,
+    /// Stem of the companion module, e.g. `event_msg`.
+    pub module: &'static str,
+    /// The `type` tags in declaration order.
+    pub tags: Vec<&'static str>,
+}
+
+/// The tagged unions, with tags taken from the discriminant enums in
+/// codex-protocol so new variants are picked up without editing this list.
+pub fn tagged_unions() -> Vec<TaggedUnion> {
+    vec![
+        TaggedUnion {
+            name: "EventMsg",
+            module: "event_msg",
+            tags: EventMsgType::iter().map(Into::into).collect(),
+        },
+        TaggedUnion {
+            name: "Op",
+            module: "op",
+            tags: OpType::iter().map(Into::into).collect(),
+        },
+    ]
+}
+
+/// A type written by ts-rs, read back from its generated `.ts` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsExportedType {
+    pub name: String,
+    pub source: String,
+}
+
+/// Write the TypeScript bindings to `out_dir`. Doc comments are read from the
+/// Rust sources under `source_root` (the `codex-rs` workspace); when it is not
+/// given, it is looked up from the ancestors of `out_dir`.
+pub fn generate_ts(
+    out_dir: &Path,
+    prettier: Option<&Path>,
+    source_root: Option<&Path>,
+) -> Result<()> {
     ensure_dir(out_dir)?;
 
     // Generate the TS bindings client -> server messages.
@@ -31,15 +89,54 @@ pub fn generate_ts(out_dir: &Path, prettier: Option<&Path>) -> Result<()> {
     export_server_responses(out_dir)?;
     ServerNotification::export_all_to(out_dir)?;
 
+    // Generate the tag list and discriminated union for each tagged union.
+    let exported = exported_types_in(out_dir)?;
+    for union in tagged_unions() {
+        let is_exported = exported.iter().any(|ty| ty.name == union.name);
+        let module_path = out_dir.join(format!("{}.ts", union.module));
+        fs::write(&module_path, generate_union_module(&union, is_exported))
+            .with_context(|| format!("Failed to write {}", module_path.display()))?;
+    }
+
     // Generate index.ts that re-exports all types.
     generate_index_ts(out_dir)?;
 
+    // Generate runtime type guards for the tagged unions.
+    let guards = generate_type_guards(&exported);
+    let guards_path = out_dir.join(GUARDS_FILE);
+    fs::write(&guards_path, guards)
+        .with_context(|| format!("Failed to write {}", guards_path.display()))?;
+
+    #[cfg(feature = "json-schema")]
+    {
+        let schema = generate_json_schema(&exported_types_in(out_dir)?)?;
+        let schema_path = out_dir.join(JSON_SCHEMA_FILE);
+        let mut contents = serde_json::to_string_pretty(&schema)?;
+        contents.push('\n');
+        fs::write(&schema_path, contents)
+            .with_context(|| format!("Failed to write {}", schema_path.display()))?;
+    }
+
     // Prepend header to each generated .ts file
     let ts_files = ts_files_in(out_dir)?;
     for file in &ts_files {
         prepend_header_if_missing(file)?;
     }
 
+    // Fill in JSDoc blocks that ts-rs did not carry over from the Rust docs.
+    let source_root = match source_root {
+        Some(root) => Some(root.to_path_buf()),
+        None => find_source_root(out_dir),
+    };
+    let source_dirs: Vec<PathBuf> = source_root
+        .iter()
+        .flat_map(|root| DOC_SOURCE_DIRS.iter().map(move |dir| root.join(dir)))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    for file in &ts_files {
+        inject_doc_comments(file, &source_dirs)?;
+    }
+
     // Format with Prettier by passing individual files (no shell globbing)
     if let Some(prettier_bin) = prettier
         && !ts_files.is_empty()
@@ -57,6 +154,16 @@ pub fn generate_ts(out_dir: &Path, prettier: Option<&Path>) -> Result<()> {
     Ok(())
 }
 
+/// Find the `codex-rs` workspace containing `out_dir`, checking each ancestor
+/// and its `codex-rs` child.
+fn find_source_root(out_dir: &Path) -> Option<PathBuf> {
+    let out_dir = out_dir.canonicalize().ok()?;
+    out_dir
+        .ancestors()
+        .flat_map(|dir| [dir.to_path_buf(), dir.join("codex-rs")])
+        .find(|dir| DOC_SOURCE_DIRS.iter().all(|src| dir.join(src).is_dir()))
+}
+
 fn ensure_dir(dir: &Path) -> Result<()> {
     fs::create_dir_all(dir)
         .with_context(|| format!("Failed to create output directory {}", dir.display()))
@@ -99,7 +206,16 @@ fn ts_files_in(dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-/// Generate an index.ts file that re-exports all generated types.
+/// Whether the `.ts` file with this stem is written by the generator itself
+/// rather than by ts-rs.
+fn is_generated_companion(stem: &str) -> bool {
+    stem == "index"
+        || stem.ends_with(".guards")
+        || tagged_unions().iter().any(|union| union.module == stem)
+}
+
+/// Generate an index.ts file that re-exports all generated types, followed
+/// by the tagged union modules present in `out_dir`.
 /// This allows consumers to import all types from a single file.
 fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
     let mut entries: Vec<String> = Vec::new();
@@ -107,7 +223,11 @@ fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
         .into_iter()
         .filter_map(|p| {
             let stem = p.file_stem()?.to_string_lossy().into_owned();
-            if stem == "index" { None } else { Some(stem) }
+            if is_generated_companion(&stem) {
+                None
+            } else {
+                Some(stem)
+            }
         })
         .collect();
     stems.sort();
@@ -116,6 +236,11 @@ fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
     for name in stems {
         entries.push(format!("export type {{ {name} }} from \"./{name}\";\n"));
     }
+    for union in tagged_unions() {
+        if out_dir.join(format!("{}.ts", union.module)).is_file() {
+            entries.push(format!("export * from \"./{}\";\n", union.module));
+        }
+    }
 
     let mut content =
         String::with_capacity(HEADER.len() + entries.iter().map(String::len).sum::<usize>());
@@ -131,3 +256,645 @@ fn generate_index_ts(out_dir: &Path) -> Result<PathBuf> {
         .with_context(|| format!("Failed to write {}", index_path.display()))?;
     Ok(index_path)
 }
+
+/// Insert JSDoc blocks before the `export type` declaration in `path` and
+/// before each of its fields when ts-rs did not emit one and the Rust item is
+/// documented.
+fn inject_doc_comments(path: &Path, source_dirs: &[PathBuf]) -> Result<()> {
+    let Some(type_name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+        return Ok(());
+    };
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let decl_prefixes = [
+        format!("export type {type_name} "),
+        format!("export type {type_name}<"),
+        format!("export interface {type_name} "),
+    ];
+    let Some(decl_idx) = lines
+        .iter()
+        .position(|line| decl_prefixes.iter().any(|p| line.starts_with(p.as_str())))
+    else {
+        return Ok(());
+    };
+    let already_documented = lines[..decl_idx]
+        .iter()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim_end().ends_with("*/"));
+
+    let docs = source_dirs
+        .iter()
+        .map(|dir| extract_doc_comments(dir, &type_name))
+        .find(|docs| !docs.is_empty())
+        .unwrap_or_default();
+    let field_docs = source_dirs
+        .iter()
+        .map(|dir| extract_field_doc_comments(dir, &type_name))
+        .find(|fields| !fields.is_empty())
+        .unwrap_or_default();
+
+    let mut out = String::with_capacity(content.len());
+    for line in &lines[..decl_idx] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if !already_documented && !docs.is_empty() {
+        out.push_str(&jsdoc_block(&docs));
+    }
+    let mut decl = String::new();
+    for line in &lines[decl_idx..] {
+        decl.push_str(line);
+        decl.push('\n');
+    }
+    for (field, docs) in &field_docs {
+        decl = inject_field_doc(&decl, field, docs);
+    }
+    out.push_str(&decl);
+
+    if out == content {
+        return Ok(());
+    }
+    fs::write(path, out).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn jsdoc_block(docs: &[String]) -> String {
+    let mut block = String::from("/**\n");
+    for doc in docs {
+        if doc.is_empty() {
+            block.push_str(" *\n");
+        } else {
+            block.push_str(&format!(" * {doc}\n"));
+        }
+    }
+    block.push_str(" */\n");
+    block
+}
+
+/// Insert a JSDoc block before the first `field:` (or `field?:`) in `decl`
+/// unless one is already there.
+fn inject_field_doc(decl: &str, field: &str, docs: &[String]) -> String {
+    let Ok(field_re) = Regex::new(&format!(
+        r"(?:^|[{{,\n])\s*({}\??: )",
+        regex_lite::escape(field)
+    )) else {
+        return decl.to_string();
+    };
+    let Some(start) = field_re
+        .captures(decl)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.start())
+    else {
+        return decl.to_string();
+    };
+    if decl[..start].trim_end().ends_with("*/") {
+        return decl.to_string();
+    }
+    let mut out = String::with_capacity(decl.len());
+    out.push_str(decl[..start].trim_end_matches(' '));
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&jsdoc_block(docs));
+    out.push_str(&decl[start..]);
+    out
+}
+
+/// Collect the `///` doc comment lines on the Rust type named `type_name` in
+/// the `.rs` files directly under `source_dir`. Attributes between the doc
+/// comment and the declaration are skipped.
+pub fn extract_doc_comments(source_dir: &Path, type_name: &str) -> Vec<String> {
+    let Some((lines, decl_idx)) = find_type_decl(source_dir, type_name) else {
+        return Vec::new();
+    };
+    let (docs, _) = leading_docs_and_attributes(&lines[..decl_idx]);
+    docs
+}
+
+/// Collect the `///` doc comments on the named fields of the Rust struct
+/// `type_name`, keyed by the field name as serialized (honoring
+/// `#[serde(rename = "...")]` and `rename_all = "camelCase"`). Undocumented
+/// fields are omitted.
+pub fn extract_field_doc_comments(
+    source_dir: &Path,
+    type_name: &str,
+) -> Vec<(String, Vec<String>)> {
+    let Some((lines, decl_idx)) = find_type_decl(source_dir, type_name) else {
+        return Vec::new();
+    };
+    if !lines[decl_idx].trim_end().ends_with('{') {
+        return Vec::new();
+    }
+    let (_, attributes) = leading_docs_and_attributes(&lines[..decl_idx]);
+    let camel_case = attributes.contains("rename_all = \"camelCase\"");
+    let Ok(field_re) = Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?(?:r#)?([A-Za-z_][A-Za-z0-9_]*)\s*:")
+    else {
+        return Vec::new();
+    };
+    let Ok(rename_re) = Regex::new(r#"rename\s*=\s*"([^"]+)""#) else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    let mut docs: Vec<String> = Vec::new();
+    let mut rename = None;
+    let mut attribute = String::new();
+    for line in &lines[decl_idx + 1..] {
+        let line = line.trim();
+        if !attribute.is_empty() || line.starts_with("#[") {
+            attribute.push_str(line);
+            if line.ends_with(']') {
+                if (attribute.starts_with("#[serde(") || attribute.starts_with("#[ts("))
+                    && let Some(name) = rename_re.captures(&attribute).and_then(|caps| caps.get(1))
+                {
+                    rename = Some(name.as_str().to_string());
+                }
+                attribute.clear();
+            }
+            continue;
+        }
+        if line.starts_with('}') {
+            break;
+        }
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.strip_prefix(' ').unwrap_or(doc).trim_end().to_string());
+            continue;
+        }
+        if let Some(name) = field_re.captures(line).and_then(|caps| caps.get(1)) {
+            let name = match rename.take() {
+                Some(rename) => rename,
+                None if camel_case => snake_to_camel(name.as_str()),
+                None => name.as_str().to_string(),
+            };
+            if !docs.is_empty() {
+                fields.push((name, std::mem::take(&mut docs)));
+            }
+        }
+        docs.clear();
+    }
+    fields
+}
+
+/// Find the first `.rs` file directly under `source_dir` declaring
+/// `type_name`, returning its lines and the index of the declaration.
+fn find_type_decl(source_dir: &Path, type_name: &str) -> Option<(Vec<String>, usize)> {
+    let decl_re = Regex::new(&format!(
+        r"^\s*pub(?:\([^)]*\))?\s+(?:struct|enum|type)\s+{}\b",
+        regex_lite::escape(type_name)
+    ))
+    .ok()?;
+    let mut sources: Vec<PathBuf> = fs::read_dir(source_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("rs")))
+        .collect();
+    sources.sort();
+
+    sources.into_iter().find_map(|source| {
+        let content = fs::read_to_string(&source).ok()?;
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let decl_idx = lines.iter().position(|line| decl_re.is_match(line))?;
+        Some((lines, decl_idx))
+    })
+}
+
+/// Walk upward from the end of `lines`, returning the doc comment lines and
+/// the concatenated attributes that precede a declaration.
+fn leading_docs_and_attributes(lines: &[String]) -> (Vec<String>, String) {
+    let mut docs = Vec::new();
+    let mut attributes = String::new();
+    let mut in_attribute = false;
+    for line in lines.iter().rev() {
+        let line = line.trim();
+        if in_attribute {
+            attributes.push_str(line);
+            in_attribute = !line.starts_with("#[");
+            continue;
+        }
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.strip_prefix(' ').unwrap_or(doc).trim_end().to_string());
+        } else if line.starts_with("#[") {
+            attributes.push_str(line);
+            in_attribute = !line.ends_with(']');
+        } else if line.ends_with(")]") {
+            // Last line of a multi-line attribute such as `#[derive(\n...\n)]`.
+            attributes.push_str(line);
+            in_attribute = true;
+        } else {
+            break;
+        }
+    }
+    docs.reverse();
+    (docs, attributes)
+}
+
+fn snake_to_camel(name: &str) -> String {
+    let mut parts = name.split('_');
+    let first = parts.next().unwrap_or_default().to_string();
+    first + &pascal_case(&parts.collect::<Vec<_>>().join("_"))
+}
+
+fn exported_types_in(dir: &Path) -> Result<Vec<TsExportedType>> {
+    let mut types = Vec::new();
+    for path in ts_files_in(dir)? {
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        if is_generated_companion(&name) {
+            continue;
+        }
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        types.push(TsExportedType { name, source });
+    }
+    Ok(types)
+}
+
+/// Generate `is<Variant>` type guards for every union in [`tagged_unions`]
+/// that ts-rs exported to `types`.
+pub fn generate_type_guards(types: &[TsExportedType]) -> String {
+    let mut imports = String::new();
+    let mut guards = String::new();
+    for union in tagged_unions() {
+        if union.tags.is_empty() || !types.iter().any(|ty| ty.name == union.name) {
+            continue;
+        }
+        let name = union.name;
+        imports.push_str(&format!("import type {{ {name} }} from \"./{name}\";\n"));
+        for tag in &union.tags {
+            let variant = pascal_case(tag);
+            guards.push_str(&format!(
+                "\nexport function is{variant}(msg: {name}): msg is Extract<{name}, {{ type: \"{tag}\" }}> {{\n  return msg.type === \"{tag}\";\n}}\n"
+            ));
+        }
+    }
+
+    let mut content = String::with_capacity(HEADER.len() + imports.len() + guards.len());
+    content.push_str(HEADER);
+    content.push_str(&imports);
+    content.push_str(&guards);
+    content
+}
+
+/// The companion module for `union`: a constant array of its `type` tags and
+/// the string union of those tags. When ts-rs exported the union itself
+/// (`is_exported`), each tag is also mapped to its variant.
+pub fn generate_union_module(union: &TaggedUnion, is_exported: bool) -> String {
+    let name = union.name;
+    let tags_const = format!("{}_TYPES", union.module.to_uppercase());
+
+    let mut content = String::from(HEADER);
+    if is_exported {
+        content.push_str(&format!("import type {{ {name} }} from \"./{name}\";\n\n"));
+    }
+    content.push_str(&format!(
+        "/** Every `type` tag of `{name}`, in declaration order. */\nexport const {tags_const} = [\n"
+    ));
+    for tag in &union.tags {
+        content.push_str(&format!("  \"{tag}\",\n"));
+    }
+    content.push_str("] as const;\n\n");
+    content.push_str(&format!(
+        "export type {name}Type = (typeof {tags_const})[number];\n"
+    ));
+    if is_exported {
+        content.push_str(&format!(
+            "\n/** Each `{name}` variant, keyed by its `type` tag. */\nexport type {name}ByType = {{\n  [T in {name}Type]: Extract<{name}, {{ type: T }}>;\n}};\n"
+        ));
+    }
+    content
+}
+
+/// Build a Draft 7 JSON Schema for the exported protocol types. Every named
+/// type lives under `$defs` and is referenced via `$ref`; the top-level
+/// messages in `types` become the schema roots.
+#[cfg(feature = "json-schema")]
+pub fn generate_json_schema(types: &[TsExportedType]) -> Result<serde_json::Value> {
+    use codex_app_server_protocol::add_client_response_schemas;
+    use codex_app_server_protocol::add_server_response_schemas;
+    use schemars::r#gen::SchemaGenerator;
+    use schemars::r#gen::SchemaSettings;
+    use schemars::schema::Schema;
+
+    type AddRoot = fn(&mut SchemaGenerator) -> Schema;
+
+    /// Absolute identifier for the schema. A URN rather than a URL, since the
+    /// file is written next to the bindings instead of being hosted.
+    const SCHEMA_ID: &str = "urn:openai:codex:protocol-schema";
+    const SCHEMA_ROOTS: &[(&str, AddRoot)] = &[
+        ("ClientRequest", |g| g.subschema_for::<ClientRequest>()),
+        ("ClientNotification", |g| {
+            g.subschema_for::<ClientNotification>()
+        }),
+        ("ServerRequest", |g| g.subschema_for::<ServerRequest>()),
+        ("ServerNotification", |g| {
+            g.subschema_for::<ServerNotification>()
+        }),
+    ];
+
+    let mut generator = SchemaSettings::draft07()
+        .with(|s| s.definitions_path = "#/$defs/".to_string())
+        .into_generator();
+    for (name, add_root) in SCHEMA_ROOTS {
+        if types.iter().any(|ty| ty.name == *name) {
+            add_root(&mut generator);
+        }
+    }
+    add_client_response_schemas(&mut generator);
+    add_server_response_schemas(&mut generator);
+
+    let meta_schema = generator.settings().meta_schema.clone();
+    let mut root = serde_json::Map::new();
+    if let Some(meta_schema) = meta_schema {
+        root.insert("$schema".to_string(), meta_schema.into());
+    }
+    root.insert("$id".to_string(), SCHEMA_ID.into());
+    root.insert("title".to_string(), "CodexProtocol".into());
+    let defs = serde_json::to_value(generator.take_definitions())
+        .context("Failed to serialize protocol schema definitions")?;
+    root.insert("$defs".to_string(), defs);
+    Ok(serde_json::Value::Object(root))
+}
+
+fn pascal_case(tag: &str) -> String {
+    tag.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn generates_event_msg_type_guards() -> Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        generate_ts(out_dir.path(), None, Some(&workspace_root()))?;
+
+        let guards = fs::read_to_string(out_dir.path().join(GUARDS_FILE))?;
+        assert!(guards.starts_with(HEADER));
+        assert!(guards.contains("import type { EventMsg } from \"./EventMsg\";"));
+        assert!(guards.contains(
+            "export function isAgentMessage(msg: EventMsg): msg is Extract<EventMsg, { type: \"agent_message\" }> {"
+        ));
+
+        let index = fs::read_to_string(out_dir.path().join("index.ts"))?;
+        assert!(!index.contains(".guards"));
+        Ok(())
+    }
+
+    #[cfg(feature = "json-schema")]
+    #[test]
+    fn generates_draft7_json_schema_with_defs() -> Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        generate_ts(out_dir.path(), None, Some(&workspace_root()))?;
+
+        let contents = fs::read_to_string(out_dir.path().join(JSON_SCHEMA_FILE))?;
+        let schema: serde_json::Value = serde_json::from_str(&contents)?;
+        assert_eq!(
+            schema["$schema"],
+            serde_json::json!("http://json-schema.org/draft-07/schema#")
+        );
+        assert_eq!(
+            schema["$id"],
+            serde_json::json!("urn:openai:codex:protocol-schema")
+        );
+
+        let defs = schema["$defs"]
+            .as_object()
+            .ok_or_else(|| anyhow!("$defs is not an object"))?;
+        assert!(defs.contains_key("EventMsg"));
+        assert!(defs.contains_key("ClientRequest"));
+
+        // Every `$ref` must point at an entry in `$defs`.
+        let mut pending = vec![&schema];
+        while let Some(value) = pending.pop() {
+            match value {
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(reference)) = map.get("$ref") {
+                        let name = reference
+                            .strip_prefix("#/$defs/")
+                            .ok_or_else(|| anyhow!("unexpected $ref {reference}"))?;
+                        assert!(defs.contains_key(name), "dangling $ref {reference}");
+                    }
+                    pending.extend(map.values());
+                }
+                serde_json::Value::Array(items) => pending.extend(items),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generated_types_carry_rust_doc_comments() -> Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        generate_ts(out_dir.path(), None, Some(&workspace_root()))?;
+
+        let ask_for_approval = fs::read_to_string(out_dir.path().join("AskForApproval.ts"))?;
+        assert!(ask_for_approval.contains(
+            "/**\n * Determines the conditions under which the user is consulted to approve\n * running the command proposed by Codex.\n */\nexport type AskForApproval ="
+        ));
+        Ok(())
+    }
+
+    /// Extract the `type` tag values from the `export type <name> = ...;` declaration.
+    fn union_tags(source: &str, name: &str) -> Vec<String> {
+        const TAG_PREFIX: &str = "\"type\": \"";
+
+        let decl_start = format!("export type {name} =");
+        let Some(start) = source.find(&decl_start) else {
+            return Vec::new();
+        };
+        let decl = &source[start + decl_start.len()..];
+        let decl = decl.split_once(';').map_or(decl, |(decl, _)| decl);
+
+        let mut tags: Vec<String> = Vec::new();
+        let mut rest = decl;
+        while let Some(idx) = rest.find(TAG_PREFIX) {
+            rest = &rest[idx + TAG_PREFIX.len()..];
+            let Some(end) = rest.find('"') else {
+                break;
+            };
+            let tag = &rest[..end];
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+            rest = &rest[end..];
+        }
+        tags
+    }
+
+    fn workspace_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+    }
+
+    #[test]
+    fn source_root_is_found_from_output_dir() -> Result<()> {
+        let repo = tempfile::tempdir()?;
+        for dir in DOC_SOURCE_DIRS {
+            fs::create_dir_all(repo.path().join("codex-rs").join(dir))?;
+        }
+        let out_dir = repo.path().join("sdk/typescript/src/generated");
+        fs::create_dir_all(&out_dir)?;
+
+        assert_eq!(
+            find_source_root(&out_dir),
+            Some(repo.path().canonicalize()?.join("codex-rs"))
+        );
+        let elsewhere = tempfile::tempdir()?;
+        assert_eq!(find_source_root(elsewhere.path()), None);
+        Ok(())
+    }
+
+    #[test]
+    fn injects_field_doc_comments() -> Result<()> {
+        let source_dir = tempfile::tempdir()?;
+        fs::write(
+            source_dir.path().join("types.rs"),
+            "/// A request.\n#[derive(Serialize, TS)]\n#[serde(rename_all = \"camelCase\")]\npub struct Request {\n    /// Unique id used to correlate responses.\n    pub request_id: String,\n    pub undocumented: bool,\n    /// Renamed on the wire.\n    #[serde(rename = \"kind\")]\n    pub r#type: String,\n}\n",
+        )?;
+        assert_eq!(
+            extract_field_doc_comments(source_dir.path(), "Request"),
+            vec![
+                (
+                    "requestId".to_string(),
+                    vec!["Unique id used to correlate responses.".to_string()]
+                ),
+                ("kind".to_string(), vec!["Renamed on the wire.".to_string()]),
+            ]
+        );
+
+        let out_dir = tempfile::tempdir()?;
+        let ts_path = out_dir.path().join("Request.ts");
+        fs::write(
+            &ts_path,
+            "export type Request = { requestId: string, undocumented: boolean, kind: string, };\n",
+        )?;
+        inject_doc_comments(&ts_path, &[source_dir.path().to_path_buf()])?;
+        assert_eq!(
+            fs::read_to_string(&ts_path)?,
+            "/**\n * A request.\n */\nexport type Request = {\n/**\n * Unique id used to correlate responses.\n */\nrequestId: string, undocumented: boolean,\n/**\n * Renamed on the wire.\n */\nkind: string, };\n"
+        );
+
+        // Running again leaves an already documented file untouched.
+        let before = fs::read_to_string(&ts_path)?;
+        inject_doc_comments(&ts_path, &[source_dir.path().to_path_buf()])?;
+        assert_eq!(fs::read_to_string(&ts_path)?, before);
+        Ok(())
+    }
+
+    #[test]
+    fn extract_doc_comments_skips_attributes() -> Result<()> {
+        let source_dir = tempfile::tempdir()?;
+        fs::write(
+            source_dir.path().join("types.rs"),
+            "/// Not this one.\npub struct Other;\n\n/// First line.\n///\n/// Second paragraph.\n#[derive(\n    Debug, Clone,\n)]\n#[serde(rename_all = \"camelCase\")]\npub struct Documented {\n    field: u8,\n}\n\npub enum Undocumented {}\n",
+        )?;
+
+        assert_eq!(
+            extract_doc_comments(source_dir.path(), "Documented"),
+            vec![
+                "First line.".to_string(),
+                String::new(),
+                "Second paragraph.".to_string(),
+            ]
+        );
+        assert_eq!(
+            extract_doc_comments(source_dir.path(), "Undocumented"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            extract_doc_comments(source_dir.path(), "Missing"),
+            Vec::<String>::new()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn index_barrel_matches_golden_file() -> Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        for stem in [
+            "EventMsg",
+            "AgentMessageEvent",
+            "InputItem",
+            "event_msg",
+            "op",
+            "index.guards",
+            "index",
+        ] {
+            fs::write(out_dir.path().join(format!("{stem}.ts")), "")?;
+        }
+        fs::write(out_dir.path().join("notes.md"), "")?;
+
+        let index_path = generate_index_ts(out_dir.path())?;
+        assert_eq!(
+            fs::read_to_string(index_path)?,
+            include_str!("../tests/fixtures/index.ts.golden")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn union_module_lists_tags_and_maps_variants() {
+        let union = TaggedUnion {
+            name: "EventMsg",
+            module: "event_msg",
+            tags: vec!["error", "task_complete"],
+        };
+        assert_eq!(
+            generate_union_module(&union, true),
+            format!(
+                "{HEADER}import type {{ EventMsg }} from \"./EventMsg\";\n\n/** Every `type` tag of `EventMsg`, in declaration order. */\nexport const EVENT_MSG_TYPES = [\n  \"error\",\n  \"task_complete\",\n] as const;\n\nexport type EventMsgType = (typeof EVENT_MSG_TYPES)[number];\n\n/** Each `EventMsg` variant, keyed by its `type` tag. */\nexport type EventMsgByType = {{\n  [T in EventMsgType]: Extract<EventMsg, {{ type: T }}>;\n}};\n"
+            )
+        );
+
+        // Without an exported union type there is nothing to import or map.
+        let module = generate_union_module(&union, false);
+        assert!(!module.contains("import"));
+        assert!(!module.contains("EventMsgByType"));
+        assert!(module.ends_with("export type EventMsgType = (typeof EVENT_MSG_TYPES)[number];\n"));
+    }
+
+    #[test]
+    fn registry_tags_match_generated_event_msg() -> Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        generate_ts(out_dir.path(), None, Some(&workspace_root()))?;
+
+        let event_msg = tagged_unions()
+            .into_iter()
+            .find(|union| union.name == "EventMsg")
+            .ok_or_else(|| anyhow!("EventMsg is not registered"))?;
+        let source = fs::read_to_string(out_dir.path().join("EventMsg.ts"))?;
+        assert_eq!(union_tags(&source, "EventMsg"), event_msg.tags);
+
+        let module = fs::read_to_string(out_dir.path().join("event_msg.ts"))?;
+        assert!(module.contains("  \"agent_message\",\n"));
+        let op_module = fs::read_to_string(out_dir.path().join("op.ts"))?;
+        assert!(op_module.contains("export type OpType = (typeof OP_TYPES)[number];"));
+        assert!(!op_module.contains("import"));
+
+        let index = fs::read_to_string(out_dir.path().join("index.ts"))?;
+        assert!(index.contains("export * from \"./event_msg\";"));
+        assert!(!index.contains("export type { event_msg }"));
+        Ok(())
+    }
+
+    #[test]
+    fn union_tags_reads_serde_tags_in_order() {
+        let source = "export type Msg = { \"type\": \"error\" } & ErrorEvent | { \"type\": \"task_complete\" };\n";
+        assert_eq!(
+            union_tags(source, "Msg"),
+            vec!["error".to_string(), "task_complete".to_string()]
+        );
+    }
+}
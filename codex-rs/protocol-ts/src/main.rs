@@ -12,9 +12,18 @@ struct Args {
     /// Optional path to the Prettier executable to format generated files
     #[arg(short = 'p', long = "prettier", value_name = "PRETTIER_BIN")]
     prettier: Option<PathBuf>,
+
+    /// Path to the `codex-rs` workspace whose Rust doc comments are copied
+    /// into the bindings. Defaults to the workspace containing `--out`.
+    #[arg(long = "source-root", value_name = "DIR")]
+    source_root: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    codex_protocol_ts::generate_ts(&args.out_dir, args.prettier.as_deref())
+    codex_protocol_ts::generate_ts(
+        &args.out_dir,
+        args.prettier.as_deref(),
+        args.source_root.as_deref(),
+    )
 }
@@ -6,6 +6,8 @@ use ts_rs::TS;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, TS, Hash)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "json-schema", schemars(transparent))]
 #[ts(type = "string")]
 pub struct ConversationId {
     uuid: Uuid,
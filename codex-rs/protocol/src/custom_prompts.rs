@@ -10,6 +10,7 @@ use ts_rs::TS;
 pub const PROMPTS_CMD_PREFIX: &str = "prompts";
 
 #[derive(Serialize, Deserialize, Debug, Clone, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CustomPrompt {
     pub name: String,
     pub path: PathBuf,
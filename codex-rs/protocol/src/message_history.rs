@@ -3,6 +3,7 @@ use serde::Serialize;
 use ts_rs::TS;
 
 #[derive(Serialize, Deserialize, Debug, Clone, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct HistoryEntry {
     pub conversation_id: String,
     pub ts: u64,
@@ -27,6 +27,7 @@ use serde::Serialize;
 use serde_json::Value;
 use serde_with::serde_as;
 use strum_macros::Display;
+use strum_macros::EnumDiscriminants;
 use ts_rs::TS;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
@@ -47,8 +48,15 @@ pub struct Submission {
 }
 
 /// Submission operation
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+///
+/// [`OpType`] lists the serde `type` tag of every variant.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, EnumDiscriminants)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[strum_discriminants(
+    name(OpType),
+    derive(strum_macros::EnumIter, strum_macros::IntoStaticStr),
+    strum(serialize_all = "snake_case")
+)]
 #[allow(clippy::large_enum_variant)]
 #[non_exhaustive]
 pub enum Op {
@@ -56,6 +64,14 @@ pub enum Op {
     /// This server sends [`EventMsg::TurnAborted`] in response.
     Interrupt,
 
+    /// Abort only the task started by the submission with the given `id`.
+    /// The server sends [`EventMsg::TurnAborted`] tagged with that `id` in
+    /// response; ids that are unknown or already finished are ignored.
+    CancelSubmission {
+        /// The id of the submission to cancel
+        id: String,
+    },
+
     /// Input from the user
     UserInput {
         /// User input items, see `InputItem`
@@ -125,6 +141,11 @@ pub enum Op {
         /// Updated reasoning summary preference (honored only for reasoning-capable models).
         #[serde(skip_serializing_if = "Option::is_none")]
         summary: Option<ReasoningSummaryConfig>,
+
+        /// Updated restrictions on which tools the model may use. Replaces any
+        /// previous restrictions; send an empty [`ToolOverrides`] to lift them.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tools: Option<ToolOverrides>,
     },
 
     /// Approve a command execution
@@ -155,10 +176,23 @@ pub enum Op {
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
 
-    /// Request the full in-memory conversation transcript for the current session.
-    /// Reply is delivered via `EventMsg::ConversationHistory`.
+    /// Request the path of the current session's rollout file, flushing
+    /// pending writes first. Reply is delivered via `EventMsg::ConversationPath`.
     GetPath,
 
+    /// Request the agent's current plan. Reply is delivered via
+    /// `EventMsg::PlanUpdate`, with an empty plan when none has been recorded.
+    GetPlan,
+
+    /// Request the combined diff of every file changed by `apply_patch` since
+    /// the session started. Reply is delivered via
+    /// `EventMsg::SessionDiffResponse`.
+    GetSessionDiff,
+
+    /// Write the conversation so far to `path` in the given format.
+    /// Reply is delivered via `EventMsg::ConversationExported`.
+    ExportConversation { path: PathBuf, format: ExportFormat },
+
     /// Request the list of MCP tools available across all configured servers.
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
@@ -178,9 +212,33 @@ pub enum Op {
     Shutdown,
 }
 
+/// Restricts the tools advertised to (and callable by) the model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ToolOverrides {
+    /// When non-empty, only these tools are available.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enable: Vec<String>,
+
+    /// Tools that are never available, even if listed in `enable`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+}
+
+impl ToolOverrides {
+    /// Whether the tool named `name` may be advertised and invoked.
+    pub fn allows(&self, name: &str) -> bool {
+        if self.disable.iter().any(|tool| tool == name) {
+            return false;
+        }
+        self.enable.is_empty() || self.enable.iter().any(|tool| tool == name)
+    }
+}
+
 /// Determines the conditions under which the user is consulted to approve
 /// running the command proposed by Codex.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Display, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[strum(serialize_all = "kebab-case")]
 pub enum AskForApproval {
@@ -208,6 +266,7 @@ pub enum AskForApproval {
 
 /// Determines execution restrictions for model shell commands.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[strum(serialize_all = "kebab-case")]
 #[serde(tag = "mode", rename_all = "kebab-case")]
 pub enum SandboxPolicy {
@@ -233,6 +292,16 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
 
+        /// When set to `true`, connections to loopback addresses and local
+        /// (unix domain) sockets are allowed even if `network_access` is
+        /// `false`. `false` by default.
+        ///
+        /// Enforced by Seatbelt on macOS. The Linux seccomp filter cannot
+        /// inspect destination addresses, so local network access remains
+        /// blocked there unless `network_access` is also set.
+        #[serde(default)]
+        allow_local_network: bool,
+
         /// When set to `true`, will NOT include the per-user `TMPDIR`
         /// environment variable among the default writable roots. Defaults to
         /// `false`.
@@ -243,14 +312,99 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// When set to `true`, each submodule checkout passed to
+        /// [`SandboxPolicy::get_writable_roots_with_submodules`] becomes its
+        /// own writable root, and its `.git` is kept read-only under every
+        /// writable root that contains it.
+        /// Defaults to `true`.
+        #[serde(default = "default_include_submodule_roots")]
+        include_submodule_roots: bool,
+
+        /// Which inherited environment variables reach sandboxed commands.
+        /// Defaults to dropping well-known credentials such as
+        /// `OPENAI_API_KEY` and `AWS_*`.
+        #[serde(default, skip_serializing_if = "is_default_env_filter")]
+        env_filter: EnvFilter,
     },
 }
 
+fn default_include_submodule_roots() -> bool {
+    true
+}
+
+fn is_default_env_filter(env_filter: &EnvFilter) -> bool {
+    *env_filter == EnvFilter::default()
+}
+
+/// Variables removed from the environment of sandboxed commands unless the
+/// user configures a different [`EnvFilter`].
+const DEFAULT_ENV_DENY_LIST: &[&str] = &[
+    "OPENAI_API_KEY",
+    "ANTHROPIC_API_KEY",
+    "AZURE_OPENAI_API_KEY",
+    "GEMINI_API_KEY",
+    "GOOGLE_API_KEY",
+    "AWS_*",
+    "GITHUB_TOKEN",
+    "GH_TOKEN",
+    "GITLAB_TOKEN",
+    "HF_TOKEN",
+    "NPM_TOKEN",
+];
+
+/// Filter applied to the environment a sandboxed command inherits. Names
+/// match exactly, or by prefix when they end in `*` (e.g. `AWS_*`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "mode", content = "names", rename_all = "kebab-case")]
+pub enum EnvFilter {
+    /// Pass every variable through unchanged.
+    Passthrough,
+    /// Keep only the variables whose names match.
+    AllowList(Vec<String>),
+    /// Drop the variables whose names match.
+    DenyList(Vec<String>),
+}
+
+impl Default for EnvFilter {
+    fn default() -> Self {
+        EnvFilter::DenyList(
+            DEFAULT_ENV_DENY_LIST
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+        )
+    }
+}
+
+impl EnvFilter {
+    /// Whether a variable called `name` survives the filter.
+    pub fn allows(&self, name: &str) -> bool {
+        let matches_any = |names: &[String]| {
+            names.iter().any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == pattern,
+            })
+        };
+        match self {
+            EnvFilter::Passthrough => true,
+            EnvFilter::AllowList(names) => matches_any(names),
+            EnvFilter::DenyList(names) => !matches_any(names),
+        }
+    }
+
+    /// Remove the variables this filter does not allow from `env`.
+    pub fn apply(&self, env: &mut HashMap<String, String>) {
+        env.retain(|name, _| self.allows(name));
+    }
+}
+
 /// A writable root path accompanied by a list of subpaths that should remain
 /// read‑only even when the root is writable. This is primarily used to ensure
 /// top‑level VCS metadata directories (e.g. `.git`) under a writable root are
 /// not modified by the agent.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct WritableRoot {
     /// Absolute path, by construction.
     pub root: PathBuf,
@@ -298,8 +452,11 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allow_local_network: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
         }
     }
 
@@ -324,10 +481,47 @@ impl SandboxPolicy {
         }
     }
 
+    /// Whether loopback and local socket connections are allowed. Always
+    /// `true` when [`Self::has_full_network_access`] is.
+    pub fn has_local_network_access(&self) -> bool {
+        match self {
+            SandboxPolicy::DangerFullAccess => true,
+            SandboxPolicy::ReadOnly => false,
+            SandboxPolicy::WorkspaceWrite {
+                network_access,
+                allow_local_network,
+                ..
+            } => *network_access || *allow_local_network,
+        }
+    }
+
+    /// The environment filter for commands run under this policy, sandboxed
+    /// or not, if it has one.
+    pub fn env_filter(&self) -> Option<&EnvFilter> {
+        match self {
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ReadOnly => None,
+            SandboxPolicy::WorkspaceWrite { env_filter, .. } => Some(env_filter),
+        }
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
+    ///
+    /// Submodules are not discovered here; see
+    /// [`Self::get_writable_roots_with_submodules`].
     pub fn get_writable_roots_with_cwd(&self, cwd: &Path) -> Vec<WritableRoot> {
+        self.get_writable_roots_with_submodules(cwd, &[])
+    }
+
+    /// Like [`Self::get_writable_roots_with_cwd`], but also makes each of
+    /// `submodule_paths` (absolute submodule checkouts under `cwd`) a writable
+    /// root when `include_submodule_roots` is set.
+    pub fn get_writable_roots_with_submodules(
+        &self,
+        cwd: &Path,
+        submodule_paths: &[PathBuf],
+    ) -> Vec<WritableRoot> {
         match self {
             SandboxPolicy::DangerFullAccess => Vec::new(),
             SandboxPolicy::ReadOnly => Vec::new(),
@@ -335,7 +529,10 @@ impl SandboxPolicy {
                 writable_roots,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                include_submodule_roots,
                 network_access: _,
+                allow_local_network: _,
+                env_filter: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
@@ -368,7 +565,9 @@ impl SandboxPolicy {
                 }
 
                 // For each root, compute subpaths that should remain read-only.
-                roots
+                // A `.git` file (rather than directory) is a worktree pointer,
+                // so only a `.git` directory is protected here.
+                let mut writable: Vec<WritableRoot> = roots
                     .into_iter()
                     .map(|writable_root| {
                         let mut subpaths = Vec::new();
@@ -381,12 +580,67 @@ impl SandboxPolicy {
                             read_only_subpaths: subpaths,
                         }
                     })
-                    .collect()
+                    .collect();
+
+                // Each submodule is a writable root of its own. Its `.git`
+                // pointer is protected there and under every enclosing root,
+                // since write access is granted if any containing root allows
+                // it.
+                if *include_submodule_roots {
+                    for submodule in submodule_paths {
+                        let submodule_git = submodule.join(".git");
+                        if !writable.iter().any(|w| &w.root == submodule) {
+                            writable.push(WritableRoot {
+                                root: submodule.clone(),
+                                read_only_subpaths: vec![submodule_git.clone()],
+                            });
+                        }
+                        protect_subpath(&mut writable, submodule_git);
+                    }
+                }
+
+                // Protect a worktree's metadata directory when it lives
+                // inside one of the writable roots.
+                let worktree_dirs: Vec<PathBuf> = writable
+                    .iter()
+                    .filter_map(|w| worktree_git_dir(&w.root))
+                    .collect();
+                for git_dir in worktree_dirs {
+                    protect_subpath(&mut writable, git_dir);
+                }
+                writable
             }
         }
     }
 }
 
+/// Mark `path` read-only under every writable root that contains it, unless an
+/// existing read-only subpath already covers it. Roots are granted
+/// independently, so a path left writable by any containing root is writable.
+fn protect_subpath(roots: &mut [WritableRoot], path: PathBuf) {
+    for root in roots.iter_mut().filter(|w| path.starts_with(&w.root)) {
+        if !root.read_only_subpaths.iter().any(|p| path.starts_with(p)) {
+            root.read_only_subpaths.push(path.clone());
+        }
+    }
+}
+
+/// Resolve the `gitdir:` pointer of a worktree's `.git` file, typically
+/// `<repo>/.git/worktrees/<name>`. Returns None when `root/.git` is not a file.
+fn worktree_git_dir(root: &Path) -> Option<PathBuf> {
+    let dot_git = root.join(".git");
+    if !dot_git.is_file() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(&dot_git).ok()?;
+    let pointer = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?
+        .trim();
+    let git_dir = root.join(pointer);
+    git_dir.is_dir().then_some(git_dir)
+}
+
 /// User input
 #[non_exhaustive]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -418,9 +672,17 @@ pub struct Event {
 
 /// Response event from the agent
 /// NOTE: Make sure none of these values have optional types, as it will mess up the extension code-gen.
-#[derive(Debug, Clone, Deserialize, Serialize, Display, TS)]
+///
+/// [`EventMsgType`] lists the serde `type` tag of every variant.
+#[derive(Debug, Clone, Deserialize, Serialize, Display, TS, EnumDiscriminants)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
+#[strum_discriminants(
+    name(EventMsgType),
+    derive(strum_macros::EnumIter, strum_macros::IntoStaticStr),
+    strum(serialize_all = "snake_case")
+)]
 pub enum EventMsg {
     /// Error while executing a submission
     Error(ErrorEvent),
@@ -517,6 +779,12 @@ pub enum EventMsg {
 
     ConversationPath(ConversationPathResponseEvent),
 
+    /// Response to `Op::GetSessionDiff`.
+    SessionDiffResponse(SessionDiffResponseEvent),
+
+    /// Acknowledges `Op::ExportConversation` once the file is written.
+    ConversationExported(ConversationExportedEvent),
+
     /// Entered review mode.
     EnteredReviewMode(ReviewRequest),
 
@@ -525,6 +793,7 @@ pub enum EventMsg {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExitedReviewModeEvent {
     pub review_output: Option<ReviewOutputEvent>,
 }
@@ -532,21 +801,25 @@ pub struct ExitedReviewModeEvent {
 // Individual event payload types matching each `EventMsg` variant.
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ErrorEvent {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TaskCompleteEvent {
     pub last_agent_message: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TaskStartedEvent {
     pub model_context_window: Option<u64>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default, TS)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenUsage {
     #[ts(type = "number")]
     pub input_tokens: u64,
@@ -561,6 +834,7 @@ pub struct TokenUsage {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenUsageInfo {
     pub total_token_usage: TokenUsage,
     pub last_token_usage: TokenUsage,
@@ -624,18 +898,21 @@ impl TokenUsageInfo {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RateLimitSnapshot {
     pub primary: Option<RateLimitWindow>,
     pub secondary: Option<RateLimitWindow>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RateLimitWindow {
     /// Percentage (0-100) of the window that has been consumed.
     pub used_percent: f64,
@@ -700,6 +977,19 @@ impl TokenUsage {
         ((remaining as f32 / effective_window as f32) * 100.0).clamp(0.0, 100.0) as u8
     }
 
+    /// Estimate the used user-controllable percentage of the model's context window.
+    ///
+    /// Usage at or below the baseline is 0%. Otherwise this is the complement
+    /// of [`TokenUsage::percent_of_context_window_remaining`], so the two sum
+    /// to 100 and a full or overflowing window reports exactly 100%. A window
+    /// no larger than the baseline is full as soon as anything is added to it.
+    pub fn percent_of_context_window_used(&self, context_window: u64) -> u8 {
+        if self.tokens_in_context_window() <= BASELINE_TOKENS {
+            return 0;
+        }
+        100 - self.percent_of_context_window_remaining(context_window)
+    }
+
     /// In-place element-wise sum of token counts.
     pub fn add_assign(&mut self, other: &TokenUsage) {
         self.input_tokens += other.input_tokens;
@@ -708,13 +998,77 @@ impl TokenUsage {
         self.reasoning_output_tokens += other.reasoning_output_tokens;
         self.total_tokens += other.total_tokens;
     }
+
+    /// Element-wise difference between this cumulative snapshot and an
+    /// `earlier` one, clamped at zero.
+    ///
+    /// ```
+    /// use codex_protocol::protocol::TokenUsage;
+    ///
+    /// let earlier = TokenUsage {
+    ///     input_tokens: 100,
+    ///     total_tokens: 150,
+    ///     ..TokenUsage::default()
+    /// };
+    /// let now = TokenUsage {
+    ///     input_tokens: 160,
+    ///     total_tokens: 240,
+    ///     ..TokenUsage::default()
+    /// };
+    /// let turn = now.delta(&earlier);
+    /// assert_eq!(turn.input_tokens, 60);
+    /// assert_eq!(turn.total_tokens, 90);
+    /// ```
+    pub fn delta(&self, earlier: &TokenUsage) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens.saturating_sub(earlier.input_tokens),
+            cached_input_tokens: self
+                .cached_input_tokens
+                .saturating_sub(earlier.cached_input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(earlier.output_tokens),
+            reasoning_output_tokens: self
+                .reasoning_output_tokens
+                .saturating_sub(earlier.reasoning_output_tokens),
+            total_tokens: self.total_tokens.saturating_sub(earlier.total_tokens),
+        }
+    }
+
+    /// The same numbers [`FinalOutput`] displays, in machine-readable form.
+    pub fn summary(&self) -> TokenUsageSummary {
+        TokenUsageSummary {
+            blended_total: self.blended_total(),
+            non_cached_input: self.non_cached_input(),
+            cached_input: self.cached_input(),
+            output: self.output_tokens,
+            reasoning_output: self.reasoning_output_tokens,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Structured breakdown of a [`TokenUsage`], see [`TokenUsage::summary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenUsageSummary {
+    pub blended_total: u64,
+    pub non_cached_input: u64,
+    pub cached_input: u64,
+    pub output: u64,
+    pub reasoning_output: u64,
+}
+
+#[derive(Debug, Clone)]
 pub struct FinalOutput {
     pub token_usage: TokenUsage,
 }
 
+impl Serialize for FinalOutput {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.token_usage.summary().serialize(serializer)
+    }
+}
+
 impl From<TokenUsage> for FinalOutput {
     fn from(token_usage: TokenUsage) -> Self {
         Self { token_usage }
@@ -752,11 +1106,13 @@ impl fmt::Display for FinalOutput {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentMessageEvent {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum InputMessageKind {
     /// Plain user text (default)
@@ -768,6 +1124,7 @@ pub enum InputMessageKind {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UserMessageEvent {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -820,34 +1177,41 @@ fn ends_with_ignore_ascii_case(text: &str, suffix: &str) -> bool {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentMessageDeltaEvent {
     pub delta: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentReasoningEvent {
     pub text: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentReasoningRawContentEvent {
     pub text: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentReasoningRawContentDeltaEvent {
     pub delta: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentReasoningSectionBreakEvent {}
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AgentReasoningDeltaEvent {
     pub delta: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct McpInvocation {
     /// Name of the MCP server as defined in the config.
     pub server: String,
@@ -858,6 +1222,7 @@ pub struct McpInvocation {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct McpToolCallBeginEvent {
     /// Identifier so this can be paired with the McpToolCallEnd event.
     pub call_id: String,
@@ -865,6 +1230,7 @@ pub struct McpToolCallBeginEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct McpToolCallEndEvent {
     /// Identifier for the corresponding McpToolCallBegin that finished.
     pub call_id: String,
@@ -885,25 +1251,55 @@ impl McpToolCallEndEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct WebSearchBeginEvent {
     pub call_id: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct WebSearchEndEvent {
     pub call_id: String,
     pub query: String,
 }
 
-/// Response payload for `Op::GetHistory` containing the current session's
-/// in-memory transcript.
+/// Response payload for `Op::GetPath`.
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ConversationPathResponseEvent {
     pub conversation_id: ConversationId,
+    /// Rollout file for the session; `None` when rollout recording is not active.
+    pub path: Option<PathBuf>,
+}
+
+/// Response payload for `Op::GetSessionDiff`.
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct SessionDiffResponseEvent {
+    /// Unified diff from each file's state when the session first touched it
+    /// to its current contents; empty when nothing has changed.
+    pub unified_diff: String,
+}
+
+/// File format for `Op::ExportConversation`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One `## Turn N` section per turn.
+    Markdown,
+    /// The same turns as a JSON array.
+    Json,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ConversationExportedEvent {
     pub path: PathBuf,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ResumedHistory {
     pub conversation_id: ConversationId,
     pub history: Vec<RolloutItem>,
@@ -911,6 +1307,7 @@ pub struct ResumedHistory {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum InitialHistory {
     New,
     Resumed(ResumedHistory),
@@ -953,6 +1350,7 @@ impl InitialHistory {
 }
 
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, TS, Default)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[ts(rename_all = "lowercase")]
 pub enum SessionSource {
@@ -966,6 +1364,7 @@ pub enum SessionSource {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SessionMeta {
     pub id: ConversationId,
     pub timestamp: String,
@@ -992,6 +1391,7 @@ impl Default for SessionMeta {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SessionMetaLine {
     #[serde(flatten)]
     pub meta: SessionMeta,
@@ -1000,6 +1400,7 @@ pub struct SessionMetaLine {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "payload", rename_all = "snake_case")]
 pub enum RolloutItem {
     SessionMeta(SessionMetaLine),
@@ -1010,6 +1411,7 @@ pub enum RolloutItem {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CompactedItem {
     pub message: String,
 }
@@ -1027,6 +1429,7 @@ impl From<CompactedItem> for ResponseItem {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TurnContextItem {
     pub cwd: PathBuf,
     pub approval_policy: AskForApproval,
@@ -1045,6 +1448,7 @@ pub struct RolloutLine {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct GitInfo {
     /// Current commit hash (SHA)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1055,10 +1459,14 @@ pub struct GitInfo {
     /// Repository URL (if available from remote)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repository_url: Option<String>,
+    /// Distinct recent authors for each of the most recently modified files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_authors: Option<HashMap<String, u32>>,
 }
 
 /// Review request sent to the review session.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReviewRequest {
     pub prompt: String,
     pub user_facing_hint: String,
@@ -1066,6 +1474,7 @@ pub struct ReviewRequest {
 
 /// Structured review result produced by a child review session.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReviewOutputEvent {
     pub findings: Vec<ReviewFinding>,
     pub overall_correctness: String,
@@ -1086,6 +1495,7 @@ impl Default for ReviewOutputEvent {
 
 /// A single review finding describing an observed issue or recommendation.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReviewFinding {
     pub title: String,
     pub body: String,
@@ -1096,6 +1506,7 @@ pub struct ReviewFinding {
 
 /// Location of the code related to a review finding.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReviewCodeLocation {
     pub absolute_file_path: PathBuf,
     pub line_range: ReviewLineRange,
@@ -1103,12 +1514,14 @@ pub struct ReviewCodeLocation {
 
 /// Inclusive line range in a file associated with the finding.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReviewLineRange {
     pub start: u32,
     pub end: u32,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExecCommandBeginEvent {
     /// Identifier so this can be paired with the ExecCommandEnd event.
     pub call_id: String,
@@ -1117,9 +1530,70 @@ pub struct ExecCommandBeginEvent {
     /// The command's working directory if not the default cwd for the agent.
     pub cwd: PathBuf,
     pub parsed_cmd: Vec<ParsedCommand>,
+    /// Why the command was allowed to run and how it was sandboxed. Only set
+    /// when that was decided without asking the user: the begin event is sent
+    /// before any approval request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_trace: Option<DecisionTrace>,
+}
+
+/// Record of how the executor decided to run a command: which rule or
+/// approval cleared it, and which sandbox it was placed in.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct DecisionTrace {
+    /// Approval policy in effect for the turn.
+    pub approval_policy: AskForApproval,
+    /// The command matched the built-in list of known-safe, read-only commands.
+    pub matched_safe_command: bool,
+    /// The command was approved for the session earlier ("always allow").
+    pub from_approval_cache: bool,
+    /// The user approved this invocation when prompted.
+    pub user_approved: bool,
+    /// Sandbox the command was launched in.
+    pub sandbox: ExecSandbox,
+    /// A sandbox denial will be escalated to the user for an unsandboxed retry.
+    pub escalate_on_failure: bool,
+}
+
+impl DecisionTrace {
+    /// One-line explanation suitable for display next to the command.
+    pub fn explanation(&self) -> String {
+        let policy = self.approval_policy;
+        if self.user_approved {
+            return "approved by you".to_string();
+        }
+        if self.from_approval_cache {
+            return "auto-approved: you chose to always allow this command".to_string();
+        }
+        if self.matched_safe_command {
+            return format!("auto-approved: read-only command under {policy} policy");
+        }
+        match self.sandbox {
+            ExecSandbox::None => format!("auto-approved: no sandbox under {policy} policy"),
+            sandbox if self.escalate_on_failure => format!(
+                "auto-approved: {sandbox} sandbox under {policy} policy, asks before retrying unsandboxed"
+            ),
+            sandbox => format!("auto-approved: {sandbox} sandbox under {policy} policy"),
+        }
+    }
+}
+
+/// Sandbox a command was launched in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Display, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ExecSandbox {
+    None,
+    #[strum(serialize = "seatbelt")]
+    MacosSeatbelt,
+    #[strum(serialize = "seccomp")]
+    LinuxSeccomp,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExecCommandEndEvent {
     /// Identifier for the ExecCommandBegin that finished.
     pub call_id: String,
@@ -1140,6 +1614,7 @@ pub struct ExecCommandEndEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ViewImageToolCallEvent {
     /// Identifier for the originating tool call.
     pub call_id: String,
@@ -1148,6 +1623,7 @@ pub struct ViewImageToolCallEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ExecOutputStream {
     Stdout,
@@ -1156,6 +1632,7 @@ pub enum ExecOutputStream {
 
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExecCommandOutputDeltaEvent {
     /// Identifier for the ExecCommandBegin that produced this chunk.
     pub call_id: String,
@@ -1164,10 +1641,12 @@ pub struct ExecCommandOutputDeltaEvent {
     /// Raw bytes from the stream (may not be valid UTF-8).
     #[serde_as(as = "serde_with::base64::Base64")]
     #[ts(type = "string")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub chunk: Vec<u8>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
     pub call_id: String,
@@ -1181,6 +1660,7 @@ pub struct ExecApprovalRequestEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ApplyPatchApprovalRequestEvent {
     /// Responses API call id for the associated patch apply call, if available.
     pub call_id: String,
@@ -1194,21 +1674,25 @@ pub struct ApplyPatchApprovalRequestEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BackgroundEventEvent {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StreamErrorEvent {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StreamInfoEvent {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PatchApplyBeginEvent {
     /// Identifier so this can be paired with the PatchApplyEnd event.
     pub call_id: String,
@@ -1219,6 +1703,7 @@ pub struct PatchApplyBeginEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PatchApplyEndEvent {
     /// Identifier for the PatchApplyBegin that finished.
     pub call_id: String,
@@ -1230,12 +1715,22 @@ pub struct PatchApplyEndEvent {
     pub success: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TurnDiffEvent {
     pub unified_diff: String,
+    /// Files with changes in `unified_diff`, relative to their git root when
+    /// inside a repository.
+    #[serde(default)]
+    pub files_modified: Vec<String>,
+    #[serde(default)]
+    pub total_lines_added: usize,
+    #[serde(default)]
+    pub total_lines_removed: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct GetHistoryEntryResponseEvent {
     pub offset: usize,
     pub log_id: u64,
@@ -1245,6 +1740,7 @@ pub struct GetHistoryEntryResponseEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
     pub tools: std::collections::HashMap<String, McpTool>,
@@ -1253,6 +1749,7 @@ pub struct McpListToolsResponseEvent {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
 pub enum McpAuthStatus {
@@ -1276,11 +1773,13 @@ impl fmt::Display for McpAuthStatus {
 
 /// Response payload for `Op::ListCustomPrompts`.
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SessionConfiguredEvent {
     /// Name left as session_id instead of conversation_id for backwards compatibility.
     pub session_id: ConversationId,
@@ -1308,6 +1807,7 @@ pub struct SessionConfiguredEvent {
 
 /// User's decision in response to an ExecApprovalRequest.
 #[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewDecision {
     /// User has approved this command and the agent should execute it.
@@ -1329,6 +1829,7 @@ pub enum ReviewDecision {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum FileChange {
     Add {
@@ -1344,6 +1845,7 @@ pub enum FileChange {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
     pub orig_index: u32,
@@ -1352,11 +1854,13 @@ pub struct Chunk {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TurnAbortedEvent {
     pub reason: TurnAbortReason,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, TS)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TurnAbortReason {
     Interrupted,
@@ -1371,6 +1875,135 @@ mod tests {
     use serde_json::json;
     use tempfile::NamedTempFile;
 
+    fn workspace_write_with_roots(writable_roots: Vec<PathBuf>) -> SandboxPolicy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        }
+    }
+
+    #[test]
+    fn env_filter_defaults_to_deny_list_and_matches_prefixes() {
+        let policy: SandboxPolicy =
+            serde_json::from_value(json!({ "mode": "workspace-write" })).expect("policy");
+        let filter = policy.env_filter().expect("workspace-write has a filter");
+        assert_eq!(filter, &EnvFilter::default());
+        assert!(!filter.allows("OPENAI_API_KEY"));
+        assert!(!filter.allows("AWS_SECRET_ACCESS_KEY"));
+        assert!(filter.allows("PATH"));
+
+        let allow: EnvFilter =
+            serde_json::from_value(json!({ "mode": "allow-list", "names": ["PATH", "LC_*"] }))
+                .expect("filter");
+        let mut env = HashMap::from([
+            ("PATH".to_string(), "/bin".to_string()),
+            ("LC_ALL".to_string(), "C".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ]);
+        allow.apply(&mut env);
+        let mut names: Vec<&str> = env.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["LC_ALL", "PATH"]);
+    }
+
+    #[test]
+    fn worktree_git_file_is_not_read_only() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let worktree = tmp.path().join("wt");
+        std::fs::create_dir(&worktree)?;
+        std::fs::write(
+            worktree.join(".git"),
+            "gitdir: /nonexistent/.git/worktrees/wt\n",
+        )?;
+
+        let roots = workspace_write_with_roots(vec![]).get_writable_roots_with_cwd(&worktree);
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].read_only_subpaths, Vec::<PathBuf>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn worktree_metadata_in_writable_root_is_read_only() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let main = tmp.path().join("main");
+        let metadata = main.join(".git").join("worktrees").join("wt");
+        std::fs::create_dir_all(&metadata)?;
+        let worktree = tmp.path().join("wt");
+        std::fs::create_dir(&worktree)?;
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", metadata.display()),
+        )?;
+        let shared = tmp.path().to_path_buf();
+
+        let roots =
+            workspace_write_with_roots(vec![shared.clone()]).get_writable_roots_with_cwd(&worktree);
+        let shared_root = roots
+            .iter()
+            .find(|r| r.root == shared)
+            .expect("shared root");
+        assert_eq!(shared_root.read_only_subpaths, vec![metadata]);
+        let worktree_root = roots
+            .iter()
+            .find(|r| r.root == worktree)
+            .expect("worktree root");
+        assert_eq!(worktree_root.read_only_subpaths, Vec::<PathBuf>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn submodules_become_roots_with_read_only_git() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let cwd = tmp.path().to_path_buf();
+        let submodule = cwd.join("libs/foo");
+        let submodules = vec![submodule.clone()];
+
+        // An extra root above cwd must protect the pointer as well, since
+        // write access is granted if any containing root allows it.
+        let parent = tmp.path().parent().expect("tempdir parent").to_path_buf();
+        let roots = workspace_write_with_roots(vec![parent.clone()])
+            .get_writable_roots_with_submodules(&cwd, &submodules);
+        let submodule_git = submodule.join(".git");
+        for root in [&cwd, &parent, &submodule] {
+            let writable = roots
+                .iter()
+                .find(|r| &r.root == root)
+                .expect("writable root");
+            assert_eq!(writable.read_only_subpaths, vec![submodule_git.clone()]);
+        }
+
+        let without_submodules = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            include_submodule_roots: false,
+            env_filter: EnvFilter::default(),
+        };
+        let roots = without_submodules.get_writable_roots_with_submodules(&cwd, &submodules);
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].read_only_subpaths, Vec::<PathBuf>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn tool_overrides_disable_wins_over_enable() {
+        let overrides = ToolOverrides {
+            enable: vec!["shell".to_string(), "web_search".to_string()],
+            disable: vec!["web_search".to_string()],
+        };
+        assert!(overrides.allows("shell"));
+        assert!(!overrides.allows("web_search"));
+        assert!(!overrides.allows("apply_patch"));
+        assert!(ToolOverrides::default().allows("apply_patch"));
+    }
+
     /// Serialize Event to verify that its JSON representation has the expected
     /// amount of nesting.
     #[test]
@@ -1423,4 +2056,249 @@ mod tests {
         assert_eq!(deserialized, event);
         Ok(())
     }
+
+    #[test]
+    fn token_usage_delta_subtracts_each_field() {
+        let earlier = TokenUsage {
+            input_tokens: 100,
+            cached_input_tokens: 40,
+            output_tokens: 20,
+            reasoning_output_tokens: 5,
+            total_tokens: 120,
+        };
+        let now = TokenUsage {
+            input_tokens: 250,
+            cached_input_tokens: 90,
+            output_tokens: 70,
+            reasoning_output_tokens: 15,
+            total_tokens: 320,
+        };
+
+        let expected = TokenUsage {
+            input_tokens: 150,
+            cached_input_tokens: 50,
+            output_tokens: 50,
+            reasoning_output_tokens: 10,
+            total_tokens: 200,
+        };
+        assert_eq!(expected, now.delta(&earlier));
+    }
+
+    #[test]
+    fn token_usage_delta_clamps_to_zero() {
+        let earlier = TokenUsage {
+            input_tokens: 500,
+            cached_input_tokens: 10,
+            output_tokens: 300,
+            reasoning_output_tokens: 0,
+            total_tokens: 800,
+        };
+        let now = TokenUsage {
+            input_tokens: 100,
+            cached_input_tokens: 20,
+            output_tokens: 50,
+            reasoning_output_tokens: 5,
+            total_tokens: 150,
+        };
+
+        let expected = TokenUsage {
+            cached_input_tokens: 10,
+            reasoning_output_tokens: 5,
+            ..TokenUsage::default()
+        };
+        assert_eq!(expected, now.delta(&earlier));
+    }
+
+    #[test]
+    fn final_output_serializes_token_usage_summary() -> Result<()> {
+        let output = FinalOutput::from(TokenUsage {
+            input_tokens: 1_200,
+            cached_input_tokens: 200,
+            output_tokens: 300,
+            reasoning_output_tokens: 50,
+            total_tokens: 1_500,
+        });
+
+        let expected = json!({
+            "blended_total": 1_300,
+            "non_cached_input": 1_000,
+            "cached_input": 200,
+            "output": 300,
+            "reasoning_output": 50,
+        });
+        assert_eq!(expected, serde_json::to_value(&output)?);
+        Ok(())
+    }
+
+    fn usage_with_total(total_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            total_tokens,
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn percent_of_context_window_used_empty_usage() {
+        let usage = TokenUsage::default();
+        assert_eq!(0, usage.percent_of_context_window_used(272_000));
+        assert_eq!(100, usage.percent_of_context_window_remaining(272_000));
+    }
+
+    #[test]
+    fn percent_of_context_window_used_full_window() {
+        let usage = usage_with_total(272_000);
+        assert_eq!(100, usage.percent_of_context_window_used(272_000));
+        assert_eq!(0, usage.percent_of_context_window_remaining(272_000));
+
+        let overflow = usage_with_total(400_000);
+        assert_eq!(100, overflow.percent_of_context_window_used(272_000));
+        assert_eq!(0, overflow.percent_of_context_window_remaining(272_000));
+    }
+
+    #[test]
+    fn percent_of_context_window_used_is_zero_at_or_below_baseline() {
+        for total_tokens in [0, BASELINE_TOKENS / 2, BASELINE_TOKENS] {
+            let usage = usage_with_total(total_tokens);
+            assert_eq!(0, usage.percent_of_context_window_used(272_000));
+            assert_eq!(100, usage.percent_of_context_window_remaining(272_000));
+            // Even a window that only fits the baseline is not in use yet.
+            assert_eq!(0, usage.percent_of_context_window_used(BASELINE_TOKENS));
+        }
+
+        let one_over = usage_with_total(BASELINE_TOKENS + 1);
+        assert_eq!(
+            100,
+            one_over.percent_of_context_window_used(BASELINE_TOKENS)
+        );
+    }
+
+    #[test]
+    fn percent_of_context_window_used_stops_at_full_usage() {
+        let effective_window = 100_000;
+        let context_window = BASELINE_TOKENS + effective_window;
+        for total_tokens in [
+            context_window - 1,
+            context_window,
+            context_window + 1,
+            u64::MAX,
+        ] {
+            let used =
+                usage_with_total(total_tokens).percent_of_context_window_used(context_window);
+            assert!(used <= 100, "total_tokens={total_tokens} used={used}");
+        }
+        assert_eq!(
+            100,
+            usage_with_total(context_window).percent_of_context_window_used(context_window)
+        );
+        assert_eq!(
+            100,
+            usage_with_total(u64::MAX).percent_of_context_window_used(context_window)
+        );
+    }
+
+    #[test]
+    fn percent_of_context_window_used_mid_value() {
+        // Half of the 100k effective window, on top of the baseline.
+        let usage = usage_with_total(BASELINE_TOKENS + 50_000);
+        assert_eq!(
+            50,
+            usage.percent_of_context_window_used(BASELINE_TOKENS + 100_000)
+        );
+    }
+
+    #[test]
+    fn percent_of_context_window_used_complements_remaining() {
+        let context_window = 272_000;
+        for total_tokens in (0..=context_window).step_by(997) {
+            let usage = usage_with_total(total_tokens);
+            let used = usage.percent_of_context_window_used(context_window);
+            let remaining = usage.percent_of_context_window_remaining(context_window);
+            assert_eq!(100, used + remaining, "total_tokens={total_tokens}");
+        }
+
+        // Inputs where rounding `used` up and truncating `remaining`
+        // independently would sum to 101.
+        for (context_window, total_tokens) in [(128_000, 29_400), (200_000, 40_200)] {
+            let usage = usage_with_total(total_tokens);
+            assert_eq!(
+                (85, 15),
+                (
+                    usage.percent_of_context_window_remaining(context_window),
+                    usage.percent_of_context_window_used(context_window)
+                ),
+                "context_window={context_window} total_tokens={total_tokens}"
+            );
+        }
+    }
+
+    #[test]
+    fn cancel_submission_op_round_trips() -> Result<()> {
+        let op = Op::CancelSubmission {
+            id: "sub-7".to_string(),
+        };
+
+        let value = serde_json::to_value(&op)?;
+        assert_eq!(json!({"type": "cancel_submission", "id": "sub-7"}), value);
+        assert_eq!(op, serde_json::from_value::<Op>(value)?);
+        Ok(())
+    }
+
+    #[test]
+    fn workspace_write_allow_local_network_round_trips() -> Result<()> {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allow_local_network: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            include_submodule_roots: true,
+            env_filter: EnvFilter::default(),
+        };
+
+        let value = serde_json::to_value(&policy)?;
+        assert_eq!(
+            json!({
+                "mode": "workspace-write",
+                "network_access": false,
+                "allow_local_network": true,
+                "exclude_tmpdir_env_var": false,
+                "exclude_slash_tmp": false,
+                "include_submodule_roots": true,
+            }),
+            value
+        );
+        assert_eq!(policy, serde_json::from_value::<SandboxPolicy>(value)?);
+        assert!(!policy.has_full_network_access());
+        assert!(policy.has_local_network_access());
+
+        // Policies serialized before the field existed default to `false`.
+        let legacy: SandboxPolicy = serde_json::from_value(json!({"mode": "workspace-write"}))?;
+        assert_eq!(SandboxPolicy::new_workspace_write_policy(), legacy);
+        assert!(!legacy.has_local_network_access());
+        Ok(())
+    }
+
+    /// Every discriminant names a variant serde knows under the same `type`
+    /// tag. Payload fields are omitted, so only the variant lookup is checked.
+    #[test]
+    fn discriminant_tags_match_serde_tags() {
+        use strum::IntoEnumIterator;
+
+        for kind in EventMsgType::iter() {
+            let tag: &'static str = kind.into();
+            if let Err(err) = serde_json::from_value::<EventMsg>(json!({ "type": tag })) {
+                assert!(!err.to_string().contains("unknown variant"), "{tag}: {err}");
+            }
+        }
+        for kind in OpType::iter() {
+            let tag: &'static str = kind.into();
+            if let Err(err) = serde_json::from_value::<Op>(json!({ "type": tag })) {
+                assert!(!err.to_string().contains("unknown variant"), "{tag}: {err}");
+            }
+        }
+        assert_eq!(
+            <&'static str>::from(EventMsgType::from(&EventMsg::ShutdownComplete)),
+            "shutdown_complete"
+        );
+    }
 }
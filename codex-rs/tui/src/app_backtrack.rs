@@ -294,11 +294,13 @@ impl App {
         nth_user_message: usize,
         prefill: String,
     ) {
+        let Some(path) = ev.path else {
+            tracing::error!("cannot fork conversation: no rollout file is being recorded");
+            return;
+        };
         let cfg = self.chat_widget.config_ref().clone();
         // Perform the fork via a thin wrapper for clarity/testability.
-        let result = self
-            .perform_fork(ev.path.clone(), nth_user_message, cfg.clone())
-            .await;
+        let result = self.perform_fork(path, nth_user_message, cfg.clone()).await;
         match result {
             Ok(new_conv) => {
                 self.install_forked_conversation(tui, cfg, new_conv, nth_user_message, &prefill)
@@ -21,6 +21,7 @@ use super::command_popup::CommandPopup;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::FooterMode;
 use super::footer::FooterProps;
+use super::footer::TurnDiffStats;
 use super::footer::esc_hint_mode;
 use super::footer::footer_height;
 use super::footer::render_footer;
@@ -109,6 +110,7 @@ pub(crate) struct ChatComposer {
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<u8>,
+    turn_diff_stats: Option<TurnDiffStats>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -152,6 +154,7 @@ impl ChatComposer {
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
+            turn_diff_stats: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -321,6 +324,13 @@ impl ChatComposer {
         self.textarea.text().to_string()
     }
 
+    /// True when no newline precedes the cursor.
+    pub(crate) fn cursor_on_first_line(&self) -> bool {
+        let text = self.textarea.text();
+        let cursor = self.textarea.cursor().min(text.len());
+        !text[..cursor].contains('\n')
+    }
+
     /// Attempt to start a burst by retro-capturing recent chars before the cursor.
     pub fn attach_image(&mut self, path: PathBuf, width: u32, height: u32, _format_label: &str) {
         let file_label = path
@@ -1374,6 +1384,7 @@ impl ChatComposer {
             use_shift_enter_hint: self.use_shift_enter_hint,
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
+            turn_diff_stats: self.turn_diff_stats,
         }
     }
 
@@ -1510,6 +1521,10 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_turn_diff_stats(&mut self, stats: Option<TurnDiffStats>) {
+        self.turn_diff_stats = stats;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -18,6 +18,16 @@ pub(crate) struct FooterProps {
     pub(crate) use_shift_enter_hint: bool,
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<u8>,
+    pub(crate) turn_diff_stats: Option<TurnDiffStats>,
+}
+
+/// Line counts from the latest turn's diff, shown next to the context
+/// indicator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct TurnDiffStats {
+    pub(crate) files: usize,
+    pub(crate) lines_added: usize,
+    pub(crate) lines_removed: usize,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -81,7 +91,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             is_task_running: props.is_task_running,
         })],
         FooterMode::ShortcutSummary => {
-            let mut line = context_window_line(props.context_window_percent);
+            let mut line = context_window_line(props.context_window_percent, props.turn_diff_stats);
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
@@ -94,7 +104,10 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             esc_backtrack_hint: props.esc_backtrack_hint,
         }),
         FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint)],
-        FooterMode::ContextOnly => vec![context_window_line(props.context_window_percent)],
+        FooterMode::ContextOnly => vec![context_window_line(
+            props.context_window_percent,
+            props.turn_diff_stats,
+        )],
     }
 }
 
@@ -221,9 +234,20 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-fn context_window_line(percent: Option<u8>) -> Line<'static> {
+fn context_window_line(percent: Option<u8>, turn_diff: Option<TurnDiffStats>) -> Line<'static> {
     let percent = percent.unwrap_or(100);
-    Line::from(vec![Span::from(format!("{percent}% context left")).dim()])
+    let mut line = Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
+    if let Some(stats) = turn_diff {
+        let files = if stats.files == 1 { "file" } else { "files" };
+        line.extend(vec![
+            " · ".dim(),
+            format!("+{}", stats.lines_added).green(),
+            " ".into(),
+            format!("-{}", stats.lines_removed).red(),
+            format!(" lines across {} {files}", stats.files).dim(),
+        ]);
+    }
+    line
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -400,6 +424,7 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: false,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -411,6 +436,7 @@ mod tests {
                 use_shift_enter_hint: true,
                 is_task_running: false,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -422,6 +448,7 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: false,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -433,6 +460,7 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: true,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -444,6 +472,7 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: false,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -455,6 +484,7 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: false,
                 context_window_percent: None,
+                turn_diff_stats: None,
             },
         );
 
@@ -466,6 +496,23 @@ mod tests {
                 use_shift_enter_hint: false,
                 is_task_running: true,
                 context_window_percent: Some(72),
+                turn_diff_stats: None,
+            },
+        );
+
+        snapshot_footer(
+            "footer_shortcuts_turn_diff",
+            FooterProps {
+                mode: FooterMode::ShortcutSummary,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                context_window_percent: Some(64),
+                turn_diff_stats: Some(TurnDiffStats {
+                    files: 2,
+                    lines_added: 12,
+                    lines_removed: 3,
+                }),
             },
         );
     }
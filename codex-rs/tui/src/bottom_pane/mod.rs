@@ -24,6 +24,7 @@ mod command_popup;
 pub mod custom_prompt_view;
 mod file_search_popup;
 mod footer;
+pub(crate) use footer::TurnDiffStats;
 mod list_selection_view;
 mod prompt_args;
 pub(crate) use list_selection_view::SelectionViewParams;
@@ -360,6 +361,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Show (or clear) the latest turn's diff line counts in the footer.
+    pub(crate) fn set_turn_diff_stats(&mut self, stats: Option<TurnDiffStats>) {
+        self.composer.set_turn_diff_stats(stats);
+        self.request_redraw();
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -15,6 +15,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::DecisionTrace;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -68,6 +69,7 @@ use crate::bottom_pane::InputResult;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::TurnDiffStats;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
@@ -317,6 +319,12 @@ impl ChatWidget {
 
     // --- Small event handlers ---
     fn on_session_configured(&mut self, event: codex_core::protocol::SessionConfiguredEvent) {
+        if self.conversation_id == Some(event.session_id) {
+            // Re-sent after a model switch; only the model has changed.
+            self.session_header.set_model(&event.model);
+            self.request_redraw();
+            return;
+        }
         self.bottom_pane
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.conversation_id = Some(event.session_id);
@@ -398,6 +406,7 @@ impl ChatWidget {
     fn on_task_started(&mut self) {
         self.bottom_pane.clear_ctrl_c_quit_hint();
         self.bottom_pane.set_task_running(true);
+        self.bottom_pane.set_turn_diff_stats(None);
         self.retry_status_header = None;
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
@@ -626,8 +635,13 @@ impl ChatWidget {
         self.app_event_tx.send(AppEvent::ExitRequest);
     }
 
-    fn on_turn_diff(&mut self, unified_diff: String) {
-        debug!("TurnDiffEvent: {unified_diff}");
+    fn on_turn_diff(&mut self, event: TurnDiffEvent) {
+        debug!("TurnDiffEvent: {}", event.unified_diff);
+        self.bottom_pane.set_turn_diff_stats(Some(TurnDiffStats {
+            files: event.files_modified.len(),
+            lines_added: event.total_lines_added,
+            lines_removed: event.total_lines_removed,
+        }));
     }
 
     fn on_background_event(&mut self, message: String) {
@@ -832,6 +846,16 @@ impl ChatWidget {
                 ev.parsed_cmd,
             )));
         }
+        if let Some(cell) = self
+            .active_cell
+            .as_mut()
+            .and_then(|c| c.as_any_mut().downcast_mut::<ExecCell>())
+        {
+            cell.set_decision(
+                &ev.call_id,
+                ev.decision_trace.as_ref().map(DecisionTrace::explanation),
+            );
+        }
 
         self.request_redraw();
     }
@@ -1172,12 +1196,19 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::SessionDiff => {
+                self.add_diff_in_progress();
+                self.submit_op(Op::GetSessionDiff);
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Plan => {
+                self.submit_op(Op::GetPlan);
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
@@ -1448,7 +1479,7 @@ impl ChatWidget {
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::TurnDiff(event) => self.on_turn_diff(event),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
@@ -1462,6 +1493,14 @@ impl ChatWidget {
                 self.app_event_tx
                     .send(crate::app_event::AppEvent::ConversationHistory(ev));
             }
+            EventMsg::SessionDiffResponse(ev) => {
+                self.app_event_tx
+                    .send(AppEvent::DiffResult(ev.unified_diff));
+            }
+            EventMsg::ConversationExported(ev) => self.add_info_message(
+                format!("Conversation exported to {}", ev.path.display()),
+                None,
+            ),
             EventMsg::EnteredReviewMode(review_request) => {
                 self.on_entered_review_mode(review_request)
             }
@@ -1751,6 +1790,7 @@ impl ChatWidget {
                     model: Some(model_for_action.clone()),
                     effort: Some(effort_for_action),
                     summary: None,
+                    tools: None,
                 }));
                 tx.send(AppEvent::UpdateModel(model_for_action.clone()));
                 tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
@@ -1807,6 +1847,7 @@ impl ChatWidget {
                     model: None,
                     effort: None,
                     summary: None,
+                    tools: None,
                 }));
                 tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
                 tx.send(AppEvent::UpdateSandboxPolicy(sandbox.clone()));
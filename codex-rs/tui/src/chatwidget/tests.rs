@@ -516,6 +516,7 @@ fn begin_exec(chat: &mut ChatWidget, call_id: &str, raw_cmd: &str) {
             command,
             cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
             parsed_cmd,
+            decision_trace: None,
         }),
     });
 }
@@ -1209,6 +1210,7 @@ async fn binary_size_transcript_snapshot() {
                                         .into_iter()
                                         .map(std::convert::Into::into)
                                         .collect(),
+                                    decision_trace: e.decision_trace,
                                 }),
                             }
                         }
@@ -2253,6 +2255,7 @@ fn chatwidget_exec_and_status_layout_vt100_snapshot() {
                 }
                 .into(),
             ],
+            decision_trace: None,
         }),
     });
     chat.handle_codex_event(Event {
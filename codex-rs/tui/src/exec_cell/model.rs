@@ -19,6 +19,8 @@ pub(crate) struct ExecCall {
     pub(crate) output: Option<CommandOutput>,
     pub(crate) start_time: Option<Instant>,
     pub(crate) duration: Option<Duration>,
+    /// Why the command was allowed to run, e.g. "auto-approved: read-only command".
+    pub(crate) decision: Option<String>,
 }
 
 #[derive(Debug)]
@@ -44,6 +46,7 @@ impl ExecCell {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         };
         if self.is_exploring_cell() && Self::is_exploring_call(&call) {
             Some(Self {
@@ -67,6 +70,12 @@ impl ExecCell {
         }
     }
 
+    pub(crate) fn set_decision(&mut self, call_id: &str, decision: Option<String>) {
+        if let Some(call) = self.calls.iter_mut().rev().find(|c| c.call_id == call_id) {
+            call.decision = decision;
+        }
+    }
+
     pub(crate) fn should_flush(&self) -> bool {
         !self.is_exploring_cell() && self.calls.iter().all(|c| c.output.is_some())
     }
@@ -45,6 +45,7 @@ pub(crate) fn new_active_exec_command(
         output: None,
         start_time: Some(Instant::now()),
         duration: None,
+        decision: None,
     })
 }
 
@@ -394,6 +395,13 @@ impl ExecCell {
             ));
         }
 
+        if let Some(decision) = call.decision.as_ref() {
+            lines.push(Line::from(vec![
+                Span::from(layout.command_continuation.initial_prefix).dim(),
+                decision.clone().dim().italic(),
+            ]));
+        }
+
         if let Some(output) = call.output.as_ref() {
             let raw_output = output_lines(
                 Some(output),
@@ -1600,6 +1600,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         // Mark call complete so markers are ✓
         cell.complete_call(
@@ -1631,6 +1632,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         // Call 1: Search only
         cell.complete_call(
@@ -1713,6 +1715,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         cell.complete_call(
             "c1",
@@ -1741,6 +1744,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         // Mark call complete so it renders as "Ran"
         cell.complete_call(
@@ -1771,6 +1775,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         cell.complete_call(
             &call_id,
@@ -1799,6 +1804,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         cell.complete_call(
             &call_id,
@@ -1826,6 +1832,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         cell.complete_call(
             &call_id,
@@ -1854,6 +1861,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         cell.complete_call(
             &call_id,
@@ -1882,6 +1890,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
         let stderr: String = (1..=10)
             .map(|n| n.to_string())
@@ -1928,6 +1937,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            decision: None,
         });
 
         let stderr = "error: first line on stderr\nerror: second line on stderr".to_string();
@@ -23,12 +23,23 @@ pub enum ComposerAction {
     None,
 }
 
+/// Result of feeding a bracketed paste into the ComposerInput.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteOutcome {
+    /// The pasted text was inserted verbatim.
+    Inserted,
+    /// The paste exceeded the configured cap; only the first `kept` of
+    /// `total` characters were inserted.
+    Truncated { kept: usize, total: usize },
+}
+
 /// A minimal, public wrapper for the internal `ChatComposer` that behaves as a
 /// reusable text input field with submit semantics.
 pub struct ComposerInput {
     inner: ChatComposer,
     _tx: tokio::sync::mpsc::UnboundedSender<AppEvent>,
     rx: tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    max_paste_chars: Option<usize>,
 }
 
 impl ComposerInput {
@@ -38,7 +49,12 @@ impl ComposerInput {
         let sender = AppEventSender::new(tx.clone());
         // `enhanced_keys_supported=true` enables Shift+Enter newline hint/behavior.
         let inner = ChatComposer::new(true, sender, true, "Compose new task".to_string(), false);
-        Self { inner, _tx: tx, rx }
+        Self {
+            inner,
+            _tx: tx,
+            rx,
+            max_paste_chars: None,
+        }
     }
 
     /// Returns true if the input is empty.
@@ -51,6 +67,21 @@ impl ComposerInput {
         self.inner.set_text_content(String::new());
     }
 
+    /// Current input text.
+    pub fn text(&self) -> String {
+        self.inner.current_text()
+    }
+
+    /// Replace the input text and move the cursor to the start.
+    pub fn set_text(&mut self, text: String) {
+        self.inner.set_text_content(text);
+    }
+
+    /// True when the cursor is on the first line of the input.
+    pub fn cursor_on_first_line(&self) -> bool {
+        self.inner.cursor_on_first_line()
+    }
+
     /// Feed a key event into the composer and return a high-level action.
     pub fn input(&mut self, key: KeyEvent) -> ComposerAction {
         let action = match self.inner.handle_key_event(key).0 {
@@ -61,10 +92,32 @@ impl ComposerInput {
         action
     }
 
-    pub fn handle_paste(&mut self, pasted: String) -> bool {
-        let handled = self.inner.handle_paste(pasted);
+    /// Cap the number of characters a single paste may insert. `None` (the
+    /// default) leaves pastes unbounded.
+    pub fn set_max_paste_chars(&mut self, max: Option<usize>) {
+        self.max_paste_chars = max;
+    }
+
+    /// Insert a bracketed paste verbatim: newlines become line breaks and never
+    /// submit. Receiving one proves the terminal supports bracketed paste, so
+    /// the key-timing paste-burst heuristic is turned off from then on.
+    pub fn handle_paste(&mut self, pasted: String) -> PasteOutcome {
+        // Many terminals send CR for newlines inside a paste; the textarea expects LF.
+        let mut pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
+        let total = pasted.chars().count();
+        let outcome = match self.max_paste_chars {
+            Some(max) if total > max => {
+                if let Some((idx, _)) = pasted.char_indices().nth(max) {
+                    pasted.truncate(idx);
+                }
+                PasteOutcome::Truncated { kept: max, total }
+            }
+            _ => PasteOutcome::Inserted,
+        };
+        self.inner.set_disable_paste_burst(true);
+        self.inner.handle_paste(pasted);
         self.drain_app_events();
-        handled
+        outcome
     }
 
     /// Override the footer hint items displayed under the composer.
@@ -126,3 +179,42 @@ impl Default for ComposerInput {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn paste_keeps_newlines_and_does_not_submit() {
+        let mut composer = ComposerInput::new();
+        let outcome = composer.handle_paste("first paragraph\r\n\r\nsecond\rthird".to_string());
+        assert_eq!(outcome, PasteOutcome::Inserted);
+        assert_eq!(composer.text(), "first paragraph\n\nsecond\nthird");
+
+        match composer.input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) {
+            ComposerAction::Submitted(text) => {
+                assert_eq!(text, "first paragraph\n\nsecond\nthird");
+            }
+            ComposerAction::None => panic!("Enter after a paste should submit"),
+        }
+    }
+
+    #[test]
+    fn paste_over_the_cap_is_truncated_on_a_char_boundary() {
+        let mut composer = ComposerInput::new();
+        composer.set_max_paste_chars(Some(3));
+        let outcome = composer.handle_paste("héllo".to_string());
+        assert_eq!(outcome, PasteOutcome::Truncated { kept: 3, total: 5 });
+        assert_eq!(composer.text(), "hél");
+
+        composer.set_max_paste_chars(None);
+        assert_eq!(
+            composer.handle_paste("lo".to_string()),
+            PasteOutcome::Inserted
+        );
+        assert_eq!(composer.text(), "héllo");
+    }
+}
@@ -20,8 +20,10 @@ pub enum SlashCommand {
     Compact,
     Undo,
     Diff,
+    SessionDiff,
     Mention,
     Status,
+    Plan,
     Mcp,
     Logout,
     Quit,
@@ -40,10 +42,12 @@ impl SlashCommand {
             SlashCommand::Undo => "restore the workspace to the last Codex snapshot",
             SlashCommand::Quit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::SessionDiff => "show every change Codex made this session",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
+            SlashCommand::Plan => "show the agent's current plan",
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Logout => "log out of Codex",
             #[cfg(debug_assertions)]
@@ -69,8 +73,10 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::SessionDiff
             | SlashCommand::Mention
             | SlashCommand::Status
+            | SlashCommand::Plan
             | SlashCommand::Mcp
             | SlashCommand::Quit => true,
 
@@ -5,6 +5,7 @@ use chrono::TimeZone;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
+use codex_core::protocol::EnvFilter;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
@@ -71,8 +72,11 @@ fn status_snapshot_includes_reasoning_details() {
     config.sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: Vec::new(),
         network_access: false,
+        allow_local_network: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        include_submodule_roots: true,
+        env_filter: EnvFilter::default(),
     };
 
     config.cwd = PathBuf::from("/workspace/tests");